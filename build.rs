@@ -0,0 +1,42 @@
+//! With the "build-info" feature, embeds the git commit and the compiler the contract is
+//! built with, reported by the ContractInfo query. Left out by default, so the same source
+//! always builds the same wasm. Setting CW20_PLUS_GIT_HASH or CW20_PLUS_RUSTC_VERSION
+//! embeds the given values instead, with or without the feature.
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=CW20_PLUS_GIT_HASH");
+    println!("cargo:rerun-if-env-changed=CW20_PLUS_RUSTC_VERSION");
+    if env::var_os("CARGO_FEATURE_BUILD_INFO").is_none() {
+        return;
+    }
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+
+    if env::var_os("CW20_PLUS_GIT_HASH").is_none() {
+        if let Some(hash) = output("git", &["rev-parse", "HEAD"]) {
+            println!("cargo:rustc-env=CW20_PLUS_GIT_HASH={hash}");
+        }
+    }
+    if env::var_os("CW20_PLUS_RUSTC_VERSION").is_none() {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        if let Some(version) = output(&rustc, &["--version"]) {
+            println!("cargo:rustc-env=CW20_PLUS_RUSTC_VERSION={version}");
+        }
+    }
+}
+
+/// Trimmed stdout of a command that succeeded
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string())
+}
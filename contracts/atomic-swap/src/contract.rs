@@ -0,0 +1,221 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, HexBinary,
+    MessageInfo, Order, Response, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Balance, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
+use cw_storage_plus::Bound;
+use cw_utils::nonpayable;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    BalanceHuman, CreateMsg, DetailsResponse, ExecuteMsg, InstantiateMsg, ListResponse, QueryMsg,
+    ReceiveMsg,
+};
+use crate::state::{AtomicSwap, SWAPS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-atomic-swap";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Create(msg) => {
+            let balance = Balance::from(info.funds);
+            execute_create(deps, env, info.sender, msg, balance)
+        }
+        ExecuteMsg::Release { id, preimage } => execute_release(deps, env, info, id, preimage),
+        ExecuteMsg::Refund { id } => execute_refund(deps, env, info, id),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let balance = Balance::Cw20(Cw20CoinVerified {
+        address: info.sender,
+        amount: wrapper.amount,
+    });
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Create(msg) => execute_create(deps, env, sender, msg, balance),
+    }
+}
+
+pub fn execute_create(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msg: CreateMsg,
+    balance: Balance,
+) -> Result<Response, ContractError> {
+    if msg.id.is_empty() {
+        return Err(ContractError::InvalidId {});
+    }
+    if msg.hash.len() != 32 {
+        return Err(ContractError::InvalidHash {});
+    }
+    // a swap that never expires could lock the funds for good
+    if matches!(msg.expires, Expiration::Never {}) || msg.expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+    if balance.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    let hash = msg.hash.to_hex();
+    let swap = AtomicSwap {
+        hash: msg.hash,
+        recipient: deps.api.addr_validate(&msg.recipient)?,
+        source: sender,
+        expires: msg.expires,
+        balance,
+    };
+    SWAPS.update(deps.storage, &msg.id, |existing| match existing {
+        None => Ok(swap),
+        Some(_) => Err(ContractError::AlreadyExists {}),
+    })?;
+
+    let res = Response::new()
+        .add_attribute("action", "create")
+        .add_attribute("id", msg.id)
+        .add_attribute("hash", hash);
+    Ok(res)
+}
+
+pub fn execute_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+    preimage: HexBinary,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let swap = SWAPS.load(deps.storage, &id)?;
+    if swap.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    if Sha256::digest(preimage.as_slice()).as_slice() != swap.hash.as_slice() {
+        return Err(ContractError::InvalidPreimage {});
+    }
+    SWAPS.remove(deps.storage, &id);
+
+    let res = Response::new()
+        .add_message(send_tokens(&swap.recipient, swap.balance)?)
+        .add_attribute("action", "release")
+        .add_attribute("id", id)
+        .add_attribute("preimage", preimage.to_hex());
+    Ok(res)
+}
+
+pub fn execute_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let swap = SWAPS.load(deps.storage, &id)?;
+    if !swap.expires.is_expired(&env.block) {
+        return Err(ContractError::NotExpired {});
+    }
+    SWAPS.remove(deps.storage, &id);
+
+    let res = Response::new()
+        .add_message(send_tokens(&swap.source, swap.balance)?)
+        .add_attribute("action", "refund")
+        .add_attribute("id", id);
+    Ok(res)
+}
+
+fn send_tokens(to: &Addr, balance: Balance) -> StdResult<CosmosMsg> {
+    let msg = match balance {
+        Balance::Native(coins) => BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins.into_vec(),
+        }
+        .into(),
+        Balance::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount: token.amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+    Ok(msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Details { id } => to_json_binary(&query_details(deps, id)?),
+        QueryMsg::List { start_after, limit } => {
+            to_json_binary(&query_list(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_details(deps: Deps, id: String) -> StdResult<DetailsResponse> {
+    let swap = SWAPS.load(deps.storage, &id)?;
+    let balance = match swap.balance {
+        Balance::Native(coins) => BalanceHuman::Native(coins.into_vec()),
+        Balance::Cw20(token) => BalanceHuman::Cw20(Cw20Coin {
+            address: token.address.into(),
+            amount: token.amount,
+        }),
+    };
+    Ok(DetailsResponse {
+        id,
+        hash: swap.hash,
+        recipient: swap.recipient.into(),
+        source: swap.source.into(),
+        expires: swap.expires,
+        balance,
+    })
+}
+
+pub fn query_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let swaps = SWAPS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(ListResponse { swaps })
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Swap id must not be empty")]
+    InvalidId {},
+
+    #[error("A swap with this id already exists")]
+    AlreadyExists {},
+
+    #[error("Send some coins to create a swap")]
+    EmptyBalance {},
+
+    #[error("Hash must be 32 bytes")]
+    InvalidHash {},
+
+    #[error("Preimage doesn't match the hash")]
+    InvalidPreimage {},
+
+    #[error("Expiration must be set and in the future")]
+    InvalidExpiration {},
+
+    #[error("Swap expired")]
+    Expired {},
+
+    #[error("Swap not yet expired")]
+    NotExpired {},
+}
@@ -0,0 +1,14 @@
+/*!
+Hash-timelocked atomic swaps of native coins and cw20 tokens.
+
+A swap locks funds for a recipient behind the sha256 hash of a secret. Revealing the
+secret with `Release` pays the recipient. Swaps not released before they expire can be
+refunded to their sender.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,72 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, HexBinary};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, Expiration};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Locks the native coins sent along in a new swap
+    Create(CreateMsg),
+    /// Pays the swap out to its recipient, given the preimage of its hash. Only before
+    /// expiration.
+    Release { id: String, preimage: HexBinary },
+    /// Pays an expired swap back to its creator
+    Refund { id: String },
+    /// Entry point for swaps of cw20 tokens
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Locks the received tokens in a new swap
+    Create(CreateMsg),
+}
+
+#[cw_serde]
+pub struct CreateMsg {
+    /// Unique id, chosen by the creator
+    pub id: String,
+    /// sha256 hash of the secret preimage
+    pub hash: HexBinary,
+    pub recipient: String,
+    /// Until when the swap can be released. After that, it can only be refunded.
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns a single open swap by id
+    #[returns(DetailsResponse)]
+    Details { id: String },
+    /// Returns the ids of all open swaps. Supports pagination.
+    #[returns(ListResponse)]
+    List {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub enum BalanceHuman {
+    Native(Vec<Coin>),
+    Cw20(Cw20Coin),
+}
+
+#[cw_serde]
+pub struct DetailsResponse {
+    pub id: String,
+    pub hash: HexBinary,
+    pub recipient: String,
+    pub source: String,
+    pub expires: Expiration,
+    pub balance: BalanceHuman,
+}
+
+#[cw_serde]
+pub struct ListResponse {
+    pub swaps: Vec<String>,
+}
@@ -0,0 +1,17 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, HexBinary};
+use cw20::{Balance, Expiration};
+use cw_storage_plus::Map;
+
+#[cw_serde]
+pub struct AtomicSwap {
+    /// sha256 hash of the preimage releasing the swap
+    pub hash: HexBinary,
+    pub recipient: Addr,
+    /// Creator of the swap, receiving the funds on refund
+    pub source: Addr,
+    pub expires: Expiration,
+    pub balance: Balance,
+}
+
+pub const SWAPS: Map<&str, AtomicSwap> = Map::new("atomic_swap");
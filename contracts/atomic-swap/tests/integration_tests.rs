@@ -0,0 +1,293 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, to_json_binary, Addr, Empty, HexBinary, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use sha2::{Digest, Sha256};
+
+    use cw20_atomic_swap::msg::{
+        BalanceHuman, CreateMsg, DetailsResponse, ExecuteMsg, InstantiateMsg, ListResponse,
+        QueryMsg, ReceiveMsg,
+    };
+    use cw20_atomic_swap::ContractError;
+
+    const DENOM: &str = "uatom";
+    const PREIMAGE: &[u8] = b"a secret only the sender knows at first";
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn swap_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_atomic_swap::contract::execute,
+            cw20_atomic_swap::contract::instantiate,
+            cw20_atomic_swap::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        swap: Addr,
+        token: Addr,
+        sender: Addr,
+        recipient: Addr,
+    }
+
+    impl Suite {
+        fn new() -> Self {
+            let api = *App::default().api();
+            let sender = api.addr_make("sender");
+            let recipient = api.addr_make("recipient");
+            let mut app = AppBuilder::new().build(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &sender, coins(1_000, DENOM))
+                    .unwrap();
+            });
+            let cw20_id = app.store_code(cw20_contract());
+            let swap_id = app.store_code(swap_contract());
+
+            let token = app
+                .instantiate_contract(
+                    cw20_id,
+                    sender.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: sender.to_string(),
+                            amount: Uint128::new(1_000),
+                        }],
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "token",
+                    None,
+                )
+                .unwrap();
+            let swap = app
+                .instantiate_contract(
+                    swap_id,
+                    sender.clone(),
+                    &InstantiateMsg {},
+                    &[],
+                    "swap",
+                    None,
+                )
+                .unwrap();
+
+            Suite {
+                app,
+                swap,
+                token,
+                sender,
+                recipient,
+            }
+        }
+
+        fn create_msg(&self, id: &str) -> CreateMsg {
+            CreateMsg {
+                id: id.to_string(),
+                hash: HexBinary::from(Sha256::digest(PREIMAGE).as_slice()),
+                recipient: self.recipient.to_string(),
+                expires: Expiration::AtHeight(self.app.block_info().height + 10),
+            }
+        }
+
+        fn create_native(&mut self, msg: CreateMsg, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                self.sender.clone(),
+                self.swap.clone(),
+                &ExecuteMsg::Create(msg),
+                &coins(amount, DENOM),
+            )?;
+            Ok(())
+        }
+
+        fn create_cw20(&mut self, msg: CreateMsg, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                self.sender.clone(),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.swap.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&ReceiveMsg::Create(msg)).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn execute(&mut self, msg: &ExecuteMsg) -> anyhow::Result<()> {
+            // anyone may release or refund
+            let caller = self.app.api().addr_make("anyone");
+            self.app
+                .execute_contract(caller, self.swap.clone(), msg, &[])?;
+            Ok(())
+        }
+
+        fn token_balance(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn native_balance(&self, addr: &Addr) -> Uint128 {
+            self.app.wrap().query_balance(addr, DENOM).unwrap().amount
+        }
+    }
+
+    #[test]
+    fn release_with_preimage() {
+        let mut suite = Suite::new();
+        let msg = suite.create_msg("native");
+        suite.create_native(msg, 100).unwrap();
+        let msg = suite.create_msg("cw20");
+        suite.create_cw20(msg, 400).unwrap();
+
+        let details: DetailsResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.swap,
+                &QueryMsg::Details {
+                    id: "cw20".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(details.source, suite.sender.to_string());
+        assert_eq!(
+            details.balance,
+            BalanceHuman::Cw20(Cw20Coin {
+                address: suite.token.to_string(),
+                amount: Uint128::new(400),
+            })
+        );
+
+        let err = suite
+            .execute(&ExecuteMsg::Release {
+                id: "cw20".to_string(),
+                preimage: HexBinary::from(b"wrong guess".as_slice()),
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPreimage {}
+        );
+        for id in ["native", "cw20"] {
+            suite
+                .execute(&ExecuteMsg::Release {
+                    id: id.to_string(),
+                    preimage: HexBinary::from(PREIMAGE),
+                })
+                .unwrap();
+        }
+        assert_eq!(suite.token_balance(&suite.recipient), Uint128::new(400));
+        assert_eq!(suite.native_balance(&suite.recipient), Uint128::new(100));
+
+        let res: ListResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.swap,
+                &QueryMsg::List {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(res.swaps.is_empty());
+    }
+
+    #[test]
+    fn refund_after_expiration() {
+        let mut suite = Suite::new();
+        let msg = suite.create_msg("cw20");
+        suite.create_cw20(msg.clone(), 400).unwrap();
+        let err = suite.create_cw20(msg, 1).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AlreadyExists {}
+        );
+
+        let refund = ExecuteMsg::Refund {
+            id: "cw20".to_string(),
+        };
+        let err = suite.execute(&refund).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotExpired {}
+        );
+
+        suite.app.update_block(|block| block.height += 10);
+        let err = suite
+            .execute(&ExecuteMsg::Release {
+                id: "cw20".to_string(),
+                preimage: HexBinary::from(PREIMAGE),
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Expired {}
+        );
+        suite.execute(&refund).unwrap();
+        assert_eq!(suite.token_balance(&suite.sender), Uint128::new(1_000));
+    }
+
+    #[test]
+    fn create_validation() {
+        let mut suite = Suite::new();
+
+        let mut msg = suite.create_msg("swap");
+        msg.hash = HexBinary::from(b"too short".as_slice());
+        let err = suite.create_native(msg, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidHash {}
+        );
+
+        let mut msg = suite.create_msg("swap");
+        msg.expires = Expiration::Never {};
+        let err = suite.create_native(msg, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidExpiration {}
+        );
+
+        let mut msg = suite.create_msg("swap");
+        msg.expires = Expiration::AtHeight(suite.app.block_info().height);
+        let err = suite.create_native(msg, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidExpiration {}
+        );
+
+        let msg = suite.create_msg("swap");
+        let err = suite.create_cw20(msg, 0).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::EmptyBalance {}
+        );
+
+        let msg = suite.create_msg("");
+        let err = suite.create_native(msg, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidId {}
+        );
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw_utils::{must_pay, nonpayable};
+
+use crate::curves::{Curve, DecimalPlaces};
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, CurveInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg,
+};
+use crate::state::{Config, CurveState, CONFIG, CURVE_STATE};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-bonding";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if !msg.curve_type.is_valid() {
+        return Err(ContractError::InvalidCurve {});
+    }
+    let token = deps.api.addr_validate(&msg.token)?;
+    let token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})?;
+    if msg.reserve_decimals > 18 || token_info.decimals > 18 {
+        return Err(ContractError::InvalidDecimals {});
+    }
+    let config = Config {
+        token,
+        reserve_denom: msg.reserve_denom,
+        curve_type: msg.curve_type,
+        decimals: DecimalPlaces {
+            supply: token_info.decimals.into(),
+            reserve: msg.reserve_decimals.into(),
+        },
+        admin: msg
+            .admin
+            .map(|admin| deps.api.addr_validate(&admin))
+            .transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    CURVE_STATE.save(deps.storage, &CurveState::default())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Buy {} => execute_buy(deps, info),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::Kill {} => execute_kill(deps, info),
+    }
+}
+
+pub fn execute_buy(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = CURVE_STATE.load(deps.storage)?;
+    if state.killed {
+        return Err(ContractError::Killed {});
+    }
+    let payment = must_pay(&info, &config.reserve_denom)?;
+
+    let curve = Curve::new(config.curve_type, config.decimals);
+    state.reserve = state.reserve.checked_add(payment).map_err(StdError::from)?;
+    let new_supply = curve.supply(state.reserve)?;
+    let minted = new_supply.saturating_sub(state.supply);
+    if minted.is_zero() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+    state.supply = new_supply;
+    CURVE_STATE.save(deps.storage, &state)?;
+
+    let mint = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: info.sender.to_string(),
+            amount: minted,
+        })?,
+        funds: vec![],
+    };
+    let res = Response::new()
+        .add_message(mint)
+        .add_attribute("action", "buy")
+        .add_attribute("buyer", info.sender)
+        .add_attribute("reserve", payment)
+        .add_attribute("minted", minted);
+    Ok(res)
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(ContractError::WrongToken {});
+    }
+    let seller = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Sell {} => sell(deps, config, seller, wrapper.amount),
+    }
+}
+
+fn sell(
+    deps: DepsMut,
+    config: Config,
+    seller: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut state = CURVE_STATE.load(deps.storage)?;
+    let curve = Curve::new(config.curve_type, config.decimals);
+    // only tokens minted by the curve can be sold back to it
+    state.supply = state.supply.checked_sub(amount).map_err(StdError::from)?;
+    let new_reserve = curve.reserve(state.supply)?.min(state.reserve);
+    let payout = state.reserve - new_reserve;
+    if payout.is_zero() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+    state.reserve = new_reserve;
+    CURVE_STATE.save(deps.storage, &state)?;
+
+    let burn = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    };
+    let pay = BankMsg::Send {
+        to_address: seller.to_string(),
+        amount: coins(payout.u128(), config.reserve_denom),
+    };
+    let res = Response::new()
+        .add_message(burn)
+        .add_message(pay)
+        .add_attribute("action", "sell")
+        .add_attribute("seller", seller)
+        .add_attribute("burned", amount)
+        .add_attribute("reserve", payout);
+    Ok(res)
+}
+
+pub fn execute_kill(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    CURVE_STATE.update(deps.storage, |mut state| -> StdResult<_> {
+        state.killed = true;
+        Ok(state)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "kill"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::CurveInfo {} => to_json_binary(&query_curve_info(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token: config.token.into(),
+        reserve_denom: config.reserve_denom,
+        curve_type: config.curve_type,
+        decimals: config.decimals,
+        admin: config.admin.map(Into::into),
+    })
+}
+
+pub fn query_curve_info(deps: Deps) -> StdResult<CurveInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = CURVE_STATE.load(deps.storage)?;
+    let curve = Curve::new(config.curve_type, config.decimals);
+    Ok(CurveInfoResponse {
+        reserve: state.reserve,
+        supply: state.supply,
+        spot_price: curve.spot_price(state.supply)?,
+        reserve_denom: config.reserve_denom,
+        killed: state.killed,
+    })
+}
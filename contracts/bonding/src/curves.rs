@@ -0,0 +1,139 @@
+//! Bonding curves, in whole units of the token and of the reserve.
+//!
+//! A curve gives the spot price of the token at a supply `s`, and the reserve backing
+//! that supply, i.e. the price integrated from 0 to `s`. Selling pays out the difference
+//! in reserve between the old and the new supply, so the reserve always covers the
+//! tokens in circulation.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, StdError, StdResult, Uint128, Uint256, Uint512};
+
+#[cw_serde]
+pub enum CurveType {
+    /// Every token costs `value` reserve coins
+    Constant { value: Decimal256 },
+    /// The price grows by `slope` reserve coins for every token in supply
+    Linear { slope: Decimal256 },
+    /// The price is `slope` times the square root of the supply
+    SquareRoot { slope: Decimal256 },
+}
+
+impl CurveType {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            CurveType::Constant { value } => !value.is_zero(),
+            CurveType::Linear { slope } | CurveType::SquareRoot { slope } => !slope.is_zero(),
+        }
+    }
+}
+
+/// Decimals of the token and of the reserve denom
+#[cw_serde]
+#[derive(Copy)]
+pub struct DecimalPlaces {
+    pub supply: u32,
+    pub reserve: u32,
+}
+
+pub struct Curve {
+    pub curve_type: CurveType,
+    pub decimals: DecimalPlaces,
+}
+
+impl Curve {
+    pub fn new(curve_type: CurveType, decimals: DecimalPlaces) -> Self {
+        Curve {
+            curve_type,
+            decimals,
+        }
+    }
+
+    /// Price of one whole token at `supply`, in whole reserve coins
+    pub fn spot_price(&self, supply: Uint128) -> StdResult<Decimal256> {
+        let s = to_decimal(supply, self.decimals.supply)?;
+        match self.curve_type {
+            CurveType::Constant { value } => Ok(value),
+            CurveType::Linear { slope } => Ok(slope.checked_mul(s)?),
+            CurveType::SquareRoot { slope } => Ok(slope.checked_mul(s.sqrt())?),
+        }
+    }
+
+    /// Reserve backing `supply`, rounded up
+    pub fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
+        let s = to_decimal(supply, self.decimals.supply)?;
+        let reserve = match self.curve_type {
+            CurveType::Constant { value } => value.checked_mul(s)?,
+            CurveType::Linear { slope } => div(slope.checked_mul(s)?.checked_mul(s)?, two())?,
+            CurveType::SquareRoot { slope } => slope
+                .checked_mul(s)?
+                .checked_mul(s.sqrt())?
+                .checked_mul(two_thirds())?,
+        };
+        from_decimal(reserve, self.decimals.reserve, true)
+    }
+
+    /// Supply backed by `reserve`, rounded down
+    pub fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
+        let r = to_decimal(reserve, self.decimals.reserve)?;
+        let supply = match self.curve_type {
+            CurveType::Constant { value } => div(r, value)?,
+            CurveType::Linear { slope } => div(r.checked_mul(two())?, slope)?.sqrt(),
+            CurveType::SquareRoot { slope } => {
+                // s = (r / (slope * 2/3))^(2/3)
+                let root = cbrt(div(r, slope.checked_mul(two_thirds())?)?)?;
+                root.checked_mul(root)?
+            }
+        };
+        from_decimal(supply, self.decimals.supply, false)
+    }
+}
+
+fn two() -> Decimal256 {
+    Decimal256::percent(200)
+}
+
+fn two_thirds() -> Decimal256 {
+    Decimal256::from_ratio(2u8, 3u8)
+}
+
+fn div(a: Decimal256, b: Decimal256) -> StdResult<Decimal256> {
+    a.checked_div(b)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+fn to_decimal(amount: Uint128, decimals: u32) -> StdResult<Decimal256> {
+    Decimal256::from_atomics(amount, decimals).map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+fn from_decimal(value: Decimal256, decimals: u32, round_up: bool) -> StdResult<Uint128> {
+    let unit = Uint256::from(10u8).pow(Decimal256::DECIMAL_PLACES - decimals);
+    let atomics = value.atomics();
+    let amount = if round_up {
+        atomics.checked_add(unit - Uint256::one())? / unit
+    } else {
+        atomics / unit
+    };
+    Ok(amount.try_into()?)
+}
+
+/// Cube root, rounded down
+fn cbrt(value: Decimal256) -> StdResult<Decimal256> {
+    // with x = atomics / 10^18, cbrt(x) * 10^18 = cbrt(atomics * 10^36)
+    let n = Uint512::from(value.atomics()) * Uint512::from(10u8).pow(36);
+    if n.is_zero() {
+        return Ok(Decimal256::zero());
+    }
+    let mut bits = 0;
+    while !(n >> bits).is_zero() {
+        bits += 1;
+    }
+    // Newton's method, starting above the root
+    let mut x = Uint512::one() << bits.div_ceil(3);
+    loop {
+        let next = (x * Uint512::from(2u8) + n / (x * x)) / Uint512::from(3u8);
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    Ok(Decimal256::new(x.try_into()?))
+}
@@ -0,0 +1,30 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Curve parameters must not be zero")]
+    InvalidCurve {},
+
+    #[error("Reserve decimals must not exceed 18")]
+    InvalidDecimals {},
+
+    #[error("This token is not accepted for this operation")]
+    WrongToken {},
+
+    #[error("The curve has been killed")]
+    Killed {},
+
+    #[error("Amount too small to trade on the curve")]
+    AmountTooSmall {},
+}
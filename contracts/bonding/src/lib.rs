@@ -0,0 +1,15 @@
+/*!
+A bonding curve selling a cw20-base token against a native reserve denom.
+
+The contract is the token's minter. Buying mints the tokens the paid reserve buys on the
+curve, selling burns tokens and pays out the reserve freed up by the lower supply. See
+[`curves`] for the supported curves.
+*/
+
+pub mod contract;
+pub mod curves;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,63 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal256, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::curves::{CurveType, DecimalPlaces};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// cw20-base token sold on the curve. The contract must be set as its minter.
+    pub token: String,
+    /// Native denom the token is bought with
+    pub reserve_denom: String,
+    pub reserve_decimals: u8,
+    pub curve_type: CurveType,
+    /// May kill the curve. Without one, the curve can't be killed.
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Mints tokens to the sender for the reserve coins sent along
+    Buy {},
+    /// Entry point for selling tokens back to the curve
+    Receive(Cw20ReceiveMsg),
+    /// Only for the admin. Stops buying for good, selling stays open.
+    Kill {},
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Burns the received tokens and pays the sender the reserve they free up
+    Sell {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the reserve, the supply and the spot price of the curve
+    #[returns(CurveInfoResponse)]
+    CurveInfo {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub token: String,
+    pub reserve_denom: String,
+    pub curve_type: CurveType,
+    pub decimals: DecimalPlaces,
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct CurveInfoResponse {
+    pub reserve: Uint128,
+    pub supply: Uint128,
+    /// Price of one whole token in whole reserve coins
+    pub spot_price: Decimal256,
+    pub reserve_denom: String,
+    pub killed: bool,
+}
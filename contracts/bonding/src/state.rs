@@ -0,0 +1,29 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Item;
+
+use crate::curves::{CurveType, DecimalPlaces};
+
+#[cw_serde]
+pub struct Config {
+    pub token: Addr,
+    pub reserve_denom: String,
+    pub curve_type: CurveType,
+    pub decimals: DecimalPlaces,
+    /// May kill the curve
+    pub admin: Option<Addr>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct CurveState {
+    /// Reserve coins held for the tokens sold through the curve
+    pub reserve: Uint128,
+    /// Tokens minted by the curve and not sold back yet
+    pub supply: Uint128,
+    /// Set by Kill, stopping all buys
+    pub killed: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CURVE_STATE: Item<CurveState> = Item::new("curve_state");
@@ -0,0 +1,307 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, to_json_binary, Addr, Decimal256, Empty, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use cw20_bonding::curves::{Curve, CurveType, DecimalPlaces};
+    use cw20_bonding::msg::{CurveInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg};
+    use cw20_bonding::ContractError;
+
+    const DENOM: &str = "ustake";
+    const DECIMALS: DecimalPlaces = DecimalPlaces {
+        supply: 6,
+        reserve: 6,
+    };
+
+    fn dec(value: &str) -> Decimal256 {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn curves_invert() {
+        let constant = Curve::new(CurveType::Constant { value: dec("1.5") }, DECIMALS);
+        assert_eq!(
+            constant.reserve(Uint128::new(2_000_000)).unwrap(),
+            Uint128::new(3_000_000)
+        );
+        assert_eq!(
+            constant.supply(Uint128::new(3_000_000)).unwrap(),
+            Uint128::new(2_000_000)
+        );
+
+        // reserve = 0.5 * s^2 / 2
+        let linear = Curve::new(CurveType::Linear { slope: dec("0.5") }, DECIMALS);
+        assert_eq!(
+            linear.reserve(Uint128::new(4_000_000)).unwrap(),
+            Uint128::new(4_000_000)
+        );
+        assert_eq!(
+            linear.supply(Uint128::new(4_000_000)).unwrap(),
+            Uint128::new(4_000_000)
+        );
+        assert_eq!(
+            linear.spot_price(Uint128::new(4_000_000)).unwrap(),
+            dec("2")
+        );
+
+        // reserve = 3 * s^1.5 * 2/3
+        let sqrt = Curve::new(CurveType::SquareRoot { slope: dec("3") }, DECIMALS);
+        assert_eq!(
+            sqrt.reserve(Uint128::new(9_000_000)).unwrap(),
+            Uint128::new(54_000_000)
+        );
+        assert_eq!(
+            sqrt.supply(Uint128::new(54_000_000)).unwrap(),
+            Uint128::new(9_000_000)
+        );
+        assert_eq!(sqrt.spot_price(Uint128::new(9_000_000)).unwrap(), dec("9"));
+
+        // rounding never lets the supply outgrow its reserve
+        for reserve in [1u128, 7, 1_234_567, 98_765_432_123] {
+            let reserve = Uint128::new(reserve);
+            for curve in [&constant, &linear, &sqrt] {
+                let supply = curve.supply(reserve).unwrap();
+                assert!(curve.reserve(supply).unwrap() <= reserve);
+            }
+        }
+    }
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn bonding_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_bonding::contract::execute,
+            cw20_bonding::contract::instantiate,
+            cw20_bonding::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        bonding: Addr,
+        token: Addr,
+        admin: Addr,
+    }
+
+    impl Suite {
+        fn new(curve_type: CurveType, users: &[&Addr]) -> Self {
+            let mut app = AppBuilder::new().build(|router, _, storage| {
+                for user in users {
+                    router
+                        .bank
+                        .init_balance(storage, user, coins(100_000_000, DENOM))
+                        .unwrap();
+                }
+            });
+            let admin = app.api().addr_make("admin");
+            let cw20_id = app.store_code(cw20_contract());
+            let bonding_id = app.store_code(bonding_contract());
+
+            let token = app
+                .instantiate_contract(
+                    cw20_id,
+                    admin.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Curve token".to_string(),
+                        symbol: "CURVE".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: admin.to_string(),
+                            amount: Uint128::new(1_000),
+                        }],
+                        mint: Some(MinterResponse {
+                            minter: admin.to_string(),
+                            cap: None,
+                        }),
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "token",
+                    None,
+                )
+                .unwrap();
+            let bonding = app
+                .instantiate_contract(
+                    bonding_id,
+                    admin.clone(),
+                    &InstantiateMsg {
+                        token: token.to_string(),
+                        reserve_denom: DENOM.to_string(),
+                        reserve_decimals: 6,
+                        curve_type,
+                        admin: Some(admin.to_string()),
+                    },
+                    &[],
+                    "bonding",
+                    None,
+                )
+                .unwrap();
+
+            // hand the minter role over to the curve
+            app.execute_contract(
+                admin.clone(),
+                token.clone(),
+                &Cw20ExecuteMsg::UpdateMinter {
+                    new_minter: Some(bonding.to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+
+            Suite {
+                app,
+                bonding,
+                token,
+                admin,
+            }
+        }
+
+        fn buy(&mut self, buyer: &Addr, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                buyer.clone(),
+                self.bonding.clone(),
+                &ExecuteMsg::Buy {},
+                &coins(amount, DENOM),
+            )?;
+            Ok(())
+        }
+
+        fn sell(&mut self, seller: &Addr, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                seller.clone(),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.bonding.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&ReceiveMsg::Sell {}).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn token_balance(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn native_balance(&self, addr: &Addr) -> Uint128 {
+            self.app.wrap().query_balance(addr, DENOM).unwrap().amount
+        }
+
+        fn curve_info(&self) -> CurveInfoResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(&self.bonding, &QueryMsg::CurveInfo {})
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn buy_and_sell_on_linear_curve() {
+        let api = *App::default().api();
+        let alice = api.addr_make("alice");
+        let bob = api.addr_make("bob");
+        let mut suite = Suite::new(CurveType::Linear { slope: dec("0.5") }, &[&alice, &bob]);
+
+        // 4 reserve buys 4 tokens from an empty curve, the next 4 tokens cost 12
+        suite.buy(&alice, 4_000_000).unwrap();
+        assert_eq!(suite.token_balance(&alice), Uint128::new(4_000_000));
+        suite.buy(&bob, 12_000_000).unwrap();
+        assert_eq!(suite.token_balance(&bob), Uint128::new(4_000_000));
+
+        let info = suite.curve_info();
+        assert_eq!(info.reserve, Uint128::new(16_000_000));
+        assert_eq!(info.supply, Uint128::new(8_000_000));
+        assert_eq!(info.spot_price, dec("4"));
+
+        // selling from the top pays the higher price
+        suite.sell(&alice, 4_000_000).unwrap();
+        assert_eq!(suite.token_balance(&alice), Uint128::zero());
+        assert_eq!(suite.native_balance(&alice), Uint128::new(108_000_000));
+        suite.sell(&bob, 4_000_000).unwrap();
+        assert_eq!(suite.native_balance(&bob), Uint128::new(92_000_000));
+        assert_eq!(suite.native_balance(&suite.bonding), Uint128::zero());
+
+        let info = suite.curve_info();
+        assert_eq!(info.reserve, Uint128::zero());
+        assert_eq!(info.supply, Uint128::zero());
+    }
+
+    #[test]
+    fn only_curve_supply_can_be_sold() {
+        let api = *App::default().api();
+        let alice = api.addr_make("alice");
+        let mut suite = Suite::new(CurveType::Constant { value: dec("2") }, &[&alice]);
+        suite.buy(&alice, 10_000_000).unwrap();
+        assert_eq!(suite.token_balance(&alice), Uint128::new(5_000_000));
+
+        // the admin's initial balance wasn't minted by the curve
+        let admin = suite.admin.clone();
+        suite.sell(&alice, 5_000_000).unwrap();
+        suite.sell(&admin, 1_000).unwrap_err();
+
+        let err = suite.buy(&alice, 1).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AmountTooSmall {}
+        );
+    }
+
+    #[test]
+    fn kill_stops_buying() {
+        let api = *App::default().api();
+        let alice = api.addr_make("alice");
+        let mut suite = Suite::new(CurveType::SquareRoot { slope: dec("3") }, &[&alice]);
+        suite.buy(&alice, 54_000_000).unwrap();
+        assert_eq!(suite.token_balance(&alice), Uint128::new(9_000_000));
+
+        let err = suite
+            .app
+            .execute_contract(
+                alice.clone(),
+                suite.bonding.clone(),
+                &ExecuteMsg::Kill {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+        suite
+            .app
+            .execute_contract(
+                suite.admin.clone(),
+                suite.bonding.clone(),
+                &ExecuteMsg::Kill {},
+                &[],
+            )
+            .unwrap();
+        assert!(suite.curve_info().killed);
+
+        let err = suite.buy(&alice, 1_000_000).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Killed {}
+        );
+        suite.sell(&alice, 9_000_000).unwrap();
+        assert_eq!(suite.native_balance(&alice), Uint128::new(100_000_000));
+    }
+}
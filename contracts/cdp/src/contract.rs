@@ -0,0 +1,334 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_utils::{must_pay, nonpayable};
+
+use crate::error::ContractError;
+use crate::msg::{
+    Collateral, ConfigResponse, ExecuteMsg, InstantiateMsg, OracleQueryMsg, PositionResponse,
+    PriceResponse, QueryMsg, ReceiveMsg,
+};
+use crate::state::{CollateralInfo, Config, CONFIG, POSITIONS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-cdp";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.min_collateral_ratio < Decimal::one() {
+        return Err(ContractError::InvalidCollateralRatio {});
+    }
+    // a position is liquidated once its collateral is worth less than the ratio, so with a
+    // larger bonus there would not be enough of it left to pay the liquidator
+    if !msg.liquidation_bonus.is_zero()
+        && msg.liquidation_bonus >= msg.min_collateral_ratio - Decimal::one()
+    {
+        return Err(ContractError::InvalidLiquidationBonus {});
+    }
+    let collateral = match msg.collateral {
+        Collateral::Native { denom } => CollateralInfo::Native { denom },
+        Collateral::Cw20 { address } => CollateralInfo::Cw20 {
+            address: deps.api.addr_validate(&address)?,
+        },
+    };
+    let config = Config {
+        debt_token: deps.api.addr_validate(&msg.debt_token)?,
+        collateral,
+        oracle: deps.api.addr_validate(&msg.oracle)?,
+        min_collateral_ratio: msg.min_collateral_ratio,
+        liquidation_bonus: msg.liquidation_bonus,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Deposit {} => execute_deposit_native(deps, info),
+        ExecuteMsg::Withdraw { amount } => execute_withdraw(deps, info, amount),
+        ExecuteMsg::Borrow { amount } => execute_borrow(deps, info, amount),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+    }
+}
+
+pub fn execute_deposit_native(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = match config.collateral {
+        CollateralInfo::Native { denom } => denom,
+        CollateralInfo::Cw20 { .. } => return Err(ContractError::WrongToken {}),
+    };
+    let amount = must_pay(&info, &denom)?;
+    deposit(deps, info.sender, amount)
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Deposit {} => match config.collateral {
+            CollateralInfo::Cw20 { address } if address == info.sender => {
+                deposit(deps, sender, wrapper.amount)
+            }
+            _ => Err(ContractError::WrongToken {}),
+        },
+        ReceiveMsg::Repay {} => {
+            if info.sender != config.debt_token {
+                return Err(ContractError::WrongToken {});
+            }
+            repay(deps, config, sender, wrapper.amount)
+        }
+        ReceiveMsg::Liquidate { owner } => {
+            if info.sender != config.debt_token {
+                return Err(ContractError::WrongToken {});
+            }
+            let owner = deps.api.addr_validate(&owner)?;
+            liquidate(deps, config, sender, owner, wrapper.amount)
+        }
+    }
+}
+
+fn deposit(deps: DepsMut, owner: Addr, amount: Uint128) -> Result<Response, ContractError> {
+    let mut position = POSITIONS
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    position.collateral = position
+        .collateral
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    POSITIONS.save(deps.storage, &owner, &position)?;
+
+    let res = Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("owner", owner)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut position = POSITIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    position.collateral = position
+        .collateral
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientCollateral {})?;
+    if !position.is_healthy(
+        query_price(deps.as_ref(), &config)?,
+        config.min_collateral_ratio,
+    ) {
+        return Err(ContractError::Undercollateralized {});
+    }
+    POSITIONS.save(deps.storage, &info.sender, &position)?;
+
+    let res = Response::new()
+        .add_message(send_collateral(&config, &info.sender, amount)?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("owner", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+pub fn execute_borrow(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut position = POSITIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    position.debt = position.debt.checked_add(amount).map_err(StdError::from)?;
+    if !position.is_healthy(
+        query_price(deps.as_ref(), &config)?,
+        config.min_collateral_ratio,
+    ) {
+        return Err(ContractError::Undercollateralized {});
+    }
+    POSITIONS.save(deps.storage, &info.sender, &position)?;
+
+    let mint = WasmMsg::Execute {
+        contract_addr: config.debt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+    let res = Response::new()
+        .add_message(mint)
+        .add_attribute("action", "borrow")
+        .add_attribute("owner", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+fn repay(
+    deps: DepsMut,
+    config: Config,
+    owner: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut position = POSITIONS
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    position.debt = position
+        .debt
+        .checked_sub(amount)
+        .map_err(|_| ContractError::RepayExceedsDebt {})?;
+    POSITIONS.save(deps.storage, &owner, &position)?;
+
+    let res = Response::new()
+        .add_message(burn_debt(&config, amount)?)
+        .add_attribute("action", "repay")
+        .add_attribute("owner", owner)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+fn liquidate(
+    deps: DepsMut,
+    config: Config,
+    liquidator: Addr,
+    owner: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut position = POSITIONS
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    let price = query_price(deps.as_ref(), &config)?;
+    if position.is_healthy(price, config.min_collateral_ratio) {
+        return Err(ContractError::NotLiquidatable {});
+    }
+
+    position.debt = position
+        .debt
+        .checked_sub(amount)
+        .map_err(|_| ContractError::RepayExceedsDebt {})?;
+    // the collateral worth the repaid debt plus the bonus, as far as the position covers it
+    let seized = amount
+        .checked_mul_floor(Decimal::one() + config.liquidation_bonus)?
+        .checked_div_floor(price)?
+        .min(position.collateral);
+    position.collateral -= seized;
+    POSITIONS.save(deps.storage, &owner, &position)?;
+
+    let res = Response::new()
+        .add_message(burn_debt(&config, amount)?)
+        .add_message(send_collateral(&config, &liquidator, seized)?)
+        .add_attribute("action", "liquidate")
+        .add_attribute("owner", owner)
+        .add_attribute("liquidator", liquidator)
+        .add_attribute("repaid", amount)
+        .add_attribute("seized", seized);
+    Ok(res)
+}
+
+/// Price of the collateral in debt tokens. A zero price is rejected, as nothing could be
+/// valued with it.
+fn query_price(deps: Deps, config: &Config) -> Result<Decimal, ContractError> {
+    let res: PriceResponse = deps
+        .querier
+        .query_wasm_smart(&config.oracle, &OracleQueryMsg::Price {})?;
+    if res.price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    Ok(res.price)
+}
+
+fn burn_debt(config: &Config, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.debt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn send_collateral(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match &config.collateral {
+        CollateralInfo::Native { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount, denom)],
+        }
+        .into(),
+        CollateralInfo::Cw20 { address } => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+    Ok(msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Position { owner } => to_json_binary(&query_position(deps, owner)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        debt_token: config.debt_token.into(),
+        collateral: config.collateral,
+        oracle: config.oracle.into(),
+        min_collateral_ratio: config.min_collateral_ratio,
+        liquidation_bonus: config.liquidation_bonus,
+    })
+}
+
+pub fn query_position(deps: Deps, owner: String) -> StdResult<PositionResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let config = CONFIG.load(deps.storage)?;
+    let position = POSITIONS
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    let price = query_price(deps, &config).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let healthy = position.is_healthy(price, config.min_collateral_ratio);
+    Ok(PositionResponse {
+        collateral: position.collateral,
+        debt: position.debt,
+        healthy,
+    })
+}
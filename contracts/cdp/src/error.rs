@@ -0,0 +1,42 @@
+use cosmwasm_std::{CheckedMultiplyFractionError, StdError};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Fraction(#[from] CheckedMultiplyFractionError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Collateral ratio must be at least 1")]
+    InvalidCollateralRatio {},
+
+    #[error("Liquidation bonus must be below the collateral ratio minus 1")]
+    InvalidLiquidationBonus {},
+
+    #[error("The oracle reported a zero price")]
+    InvalidPrice {},
+
+    #[error("This token is not accepted for this operation")]
+    WrongToken {},
+
+    #[error("Position would fall below the minimum collateral ratio")]
+    Undercollateralized {},
+
+    #[error("Position is sufficiently collateralized")]
+    NotLiquidatable {},
+
+    #[error("Repayment exceeds the outstanding debt")]
+    RepayExceedsDebt {},
+
+    #[error("Not enough collateral in the position")]
+    InsufficientCollateral {},
+}
@@ -0,0 +1,19 @@
+/*!
+A reference collateralized debt position (CDP) contract.
+
+Users lock collateral (a native coin or a cw20 token) and borrow a cw20-base debt token
+against it. The CDP must be the debt token's minter: it mints on `Borrow` and burns the
+tokens it receives on repayment or liquidation. Collateral is valued through a pluggable
+oracle contract answering [`msg::OracleQueryMsg::Price`].
+
+A position whose debt times `min_collateral_ratio` exceeds its collateral value can be
+liquidated by anyone repaying (part of) its debt, in exchange for collateral worth the
+repaid amount plus `liquidation_bonus`.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,90 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::CollateralInfo;
+
+#[cw_serde]
+pub enum Collateral {
+    Native { denom: String },
+    Cw20 { address: String },
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// cw20-base token this contract mints as debt. The contract must be set as its minter.
+    pub debt_token: String,
+    pub collateral: Collateral,
+    /// Contract answering OracleQueryMsg::Price
+    pub oracle: String,
+    /// Collateral value required per unit of debt, e.g. 1.5
+    pub min_collateral_ratio: Decimal,
+    /// Extra collateral paid to liquidators on top of the repaid value, e.g. 0.05. Has to
+    /// be below `min_collateral_ratio - 1`.
+    pub liquidation_bonus: Decimal,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Adds native collateral sent along with the message
+    Deposit {},
+    /// Takes collateral out of the sender's position, keeping it healthy
+    Withdraw { amount: Uint128 },
+    /// Mints debt tokens to the sender against its collateral
+    Borrow { amount: Uint128 },
+    /// Entry point for cw20 collateral deposits and debt token repayments
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Adds the received cw20 collateral to the sender's position
+    Deposit {},
+    /// Burns the received debt tokens against the sender's debt
+    Repay {},
+    /// Burns the received debt tokens against an unhealthy position's debt and
+    /// pays out collateral plus the liquidation bonus
+    Liquidate { owner: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the position of the given address, empty if it has none
+    #[returns(PositionResponse)]
+    Position { owner: String },
+}
+
+/// Interface the price oracle has to implement
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    /// Price of one unit of collateral, denominated in debt tokens
+    #[returns(PriceResponse)]
+    Price {},
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub debt_token: String,
+    pub collateral: CollateralInfo,
+    pub oracle: String,
+    pub min_collateral_ratio: Decimal,
+    pub liquidation_bonus: Decimal,
+}
+
+#[cw_serde]
+pub struct PositionResponse {
+    pub collateral: Uint128,
+    pub debt: Uint128,
+    /// Whether the position holds the minimum ratio at the current oracle price
+    pub healthy: bool,
+}
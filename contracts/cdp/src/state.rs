@@ -0,0 +1,36 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub enum CollateralInfo {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+#[cw_serde]
+pub struct Config {
+    pub debt_token: Addr,
+    pub collateral: CollateralInfo,
+    pub oracle: Addr,
+    pub min_collateral_ratio: Decimal,
+    pub liquidation_bonus: Decimal,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Position {
+    pub collateral: Uint128,
+    pub debt: Uint128,
+}
+
+impl Position {
+    /// Whether the collateral, at `price` debt tokens per unit, covers the debt
+    /// times the minimum ratio
+    pub fn is_healthy(&self, price: Decimal, min_ratio: Decimal) -> bool {
+        self.debt.mul_ceil(min_ratio) <= self.collateral.mul_floor(price)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const POSITIONS: Map<&Addr, Position> = Map::new("position");
@@ -0,0 +1,380 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+        Response, StdResult, Uint128,
+    };
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+
+    use cw20_cdp::msg::{
+        Collateral, ExecuteMsg, InstantiateMsg, OracleQueryMsg, PositionResponse, PriceResponse,
+        QueryMsg, ReceiveMsg,
+    };
+    use cw20_cdp::ContractError;
+
+    const DENOM: &str = "uatom";
+
+    // a stub oracle whose price is set on instantiate and on every execute
+    const PRICE: Item<Decimal> = Item::new("price");
+
+    fn set_price(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        price: Decimal,
+    ) -> StdResult<Response> {
+        PRICE.save(deps.storage, &price)?;
+        Ok(Response::default())
+    }
+
+    fn oracle_query(deps: Deps, _env: Env, _msg: OracleQueryMsg) -> StdResult<Binary> {
+        to_json_binary(&PriceResponse {
+            price: PRICE.load(deps.storage)?,
+        })
+    }
+
+    fn oracle_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(set_price, set_price, oracle_query))
+    }
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn cdp_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_cdp::contract::execute,
+            cw20_cdp::contract::instantiate,
+            cw20_cdp::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        cdp: Addr,
+        debt: Addr,
+        oracle: Addr,
+    }
+
+    impl Suite {
+        fn new(users: &[&Addr]) -> Self {
+            let mut app = AppBuilder::new().build(|router, _, storage| {
+                for user in users {
+                    router
+                        .bank
+                        .init_balance(storage, user, coins(1_000, DENOM))
+                        .unwrap();
+                }
+            });
+            let admin = app.api().addr_make("admin");
+            let oracle_id = app.store_code(oracle_contract());
+            let cw20_id = app.store_code(cw20_contract());
+            let cdp_id = app.store_code(cdp_contract());
+
+            let oracle = app
+                .instantiate_contract(
+                    oracle_id,
+                    admin.clone(),
+                    &Decimal::percent(1_000),
+                    &[],
+                    "oracle",
+                    None,
+                )
+                .unwrap();
+            let debt = app
+                .instantiate_contract(
+                    cw20_id,
+                    admin.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Stable".to_string(),
+                        symbol: "STBL".to_string(),
+                        decimals: 6,
+                        mint: Some(MinterResponse {
+                            minter: admin.to_string(),
+                            cap: None,
+                        }),
+                        initial_balances: vec![Cw20Coin {
+                            address: users[0].to_string(),
+                            amount: Uint128::new(1_000),
+                        }],
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "debt",
+                    None,
+                )
+                .unwrap();
+            let cdp = app
+                .instantiate_contract(
+                    cdp_id,
+                    admin.clone(),
+                    &InstantiateMsg {
+                        debt_token: debt.to_string(),
+                        collateral: Collateral::Native {
+                            denom: DENOM.to_string(),
+                        },
+                        oracle: oracle.to_string(),
+                        min_collateral_ratio: Decimal::percent(150),
+                        liquidation_bonus: Decimal::percent(10),
+                    },
+                    &[],
+                    "cdp",
+                    None,
+                )
+                .unwrap();
+
+            // hand the minter role over to the CDP
+            app.execute_contract(
+                admin,
+                debt.clone(),
+                &Cw20ExecuteMsg::UpdateMinter {
+                    new_minter: Some(cdp.to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+
+            Suite {
+                app,
+                cdp,
+                debt,
+                oracle,
+            }
+        }
+
+        fn debt_balance(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.debt,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn position(&self, owner: &Addr) -> PositionResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(
+                    &self.cdp,
+                    &QueryMsg::Position {
+                        owner: owner.to_string(),
+                    },
+                )
+                .unwrap()
+        }
+
+        fn send_debt(&mut self, from: &Addr, amount: u128, msg: ReceiveMsg) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                from.clone(),
+                self.debt.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.cdp.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&msg).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn borrow_and_repay() {
+        let borrower = App::default().api().addr_make("borrower");
+        let mut suite = Suite::new(&[&borrower]);
+
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Deposit {},
+                &coins(100, DENOM),
+            )
+            .unwrap();
+
+        // 100 collateral at price 10 is worth 1000, so at most 666 can be borrowed
+        let err = suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Borrow {
+                    amount: Uint128::new(667),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Undercollateralized {}
+        );
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Borrow {
+                    amount: Uint128::new(600),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(suite.debt_balance(&borrower), Uint128::new(1_600));
+
+        suite
+            .send_debt(&borrower, 600, ReceiveMsg::Repay {})
+            .unwrap();
+        assert_eq!(suite.debt_balance(&borrower), Uint128::new(1_000));
+        assert_eq!(suite.debt_balance(&suite.cdp), Uint128::zero());
+
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Withdraw {
+                    amount: Uint128::new(100),
+                },
+                &[],
+            )
+            .unwrap();
+        let balance = suite.app.wrap().query_balance(&borrower, DENOM).unwrap();
+        assert_eq!(balance.amount, Uint128::new(1_000));
+    }
+
+    #[test]
+    fn unhealthy_positions_can_be_liquidated() {
+        let api = *App::default().api();
+        let liquidator = api.addr_make("liquidator");
+        let borrower = api.addr_make("borrower");
+        let mut suite = Suite::new(&[&liquidator, &borrower]);
+
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Deposit {},
+                &coins(100, DENOM),
+            )
+            .unwrap();
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Borrow {
+                    amount: Uint128::new(500),
+                },
+                &[],
+            )
+            .unwrap();
+
+        let liquidate = || ReceiveMsg::Liquidate {
+            owner: borrower.to_string(),
+        };
+        let err = suite.send_debt(&liquidator, 100, liquidate()).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotLiquidatable {}
+        );
+
+        // collateral drops to 700 in value, below 1.5 * 500
+        let oracle = suite.oracle.clone();
+        suite
+            .app
+            .execute_contract(liquidator.clone(), oracle, &Decimal::percent(700), &[])
+            .unwrap();
+        assert!(!suite.position(&borrower).healthy);
+
+        suite.send_debt(&liquidator, 350, liquidate()).unwrap();
+        // 350 debt plus 10% is worth 55 collateral at price 7
+        let balance = suite.app.wrap().query_balance(&liquidator, DENOM).unwrap();
+        assert_eq!(balance.amount, Uint128::new(1_055));
+        let position = suite.position(&borrower);
+        assert_eq!(position.debt, Uint128::new(150));
+        assert_eq!(position.collateral, Uint128::new(45));
+        assert!(position.healthy);
+    }
+
+    #[test]
+    fn zero_prices_are_rejected() {
+        let api = *App::default().api();
+        let liquidator = api.addr_make("liquidator");
+        let borrower = api.addr_make("borrower");
+        let mut suite = Suite::new(&[&liquidator, &borrower]);
+
+        suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Deposit {},
+                &coins(100, DENOM),
+            )
+            .unwrap();
+        let oracle = suite.oracle.clone();
+        suite
+            .app
+            .execute_contract(liquidator.clone(), oracle, &Decimal::zero(), &[])
+            .unwrap();
+
+        let err = suite
+            .app
+            .execute_contract(
+                borrower.clone(),
+                suite.cdp.clone(),
+                &ExecuteMsg::Borrow {
+                    amount: Uint128::new(1),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPrice {}
+        );
+        let liquidate = ReceiveMsg::Liquidate {
+            owner: borrower.to_string(),
+        };
+        let err = suite.send_debt(&liquidator, 100, liquidate).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPrice {}
+        );
+    }
+
+    #[test]
+    fn liquidation_bonus_must_fit_the_collateral_ratio() {
+        use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+        use cw20_cdp::contract::instantiate;
+
+        let mut deps = mock_dependencies();
+        let msg = |liquidation_bonus| InstantiateMsg {
+            debt_token: deps.api.addr_make("debt").to_string(),
+            collateral: Collateral::Native {
+                denom: DENOM.to_string(),
+            },
+            oracle: deps.api.addr_make("oracle").to_string(),
+            min_collateral_ratio: Decimal::percent(150),
+            liquidation_bonus,
+        };
+        let (bad, good) = (msg(Decimal::percent(50)), msg(Decimal::percent(49)));
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), bad).unwrap_err();
+        assert_eq!(err, ContractError::InvalidLiquidationBonus {});
+        instantiate(deps.as_mut(), mock_env(), info, good).unwrap();
+    }
+}
@@ -0,0 +1,311 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Balance, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
+use cw_storage_plus::Bound;
+use cw_utils::{nonpayable, NativeBalance};
+
+use crate::error::ContractError;
+use crate::msg::{
+    CreateMsg, DetailsResponse, ExecuteMsg, InstantiateMsg, ListResponse, QueryMsg, ReceiveMsg,
+};
+use crate::state::{escrows, Escrow, GenericBalance};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-escrow";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Create(msg) => {
+            let balance = Balance::from(info.funds);
+            execute_create(deps, env, info.sender, msg, balance)
+        }
+        ExecuteMsg::TopUp { id } => {
+            let balance = Balance::from(info.funds);
+            execute_top_up(deps, env, id, balance)
+        }
+        ExecuteMsg::Approve { id } => execute_approve(deps, env, info, id),
+        ExecuteMsg::Refund { id } => execute_refund(deps, env, info, id),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let balance = Balance::Cw20(Cw20CoinVerified {
+        address: info.sender,
+        amount: wrapper.amount,
+    });
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Create(msg) => {
+            let sender = deps.api.addr_validate(&wrapper.sender)?;
+            execute_create(deps, env, sender, msg, balance)
+        }
+        ReceiveMsg::TopUp { id } => execute_top_up(deps, env, id, balance),
+    }
+}
+
+pub fn execute_create(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msg: CreateMsg,
+    balance: Balance,
+) -> Result<Response, ContractError> {
+    if msg.id.is_empty() {
+        return Err(ContractError::InvalidId {});
+    }
+    if balance.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+    let expires = msg.expires.unwrap_or(Expiration::Never {});
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let mut cw20_whitelist = msg
+        .cw20_whitelist
+        .unwrap_or_default()
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<Vec<_>>>()?;
+    // the token the escrow is created with can always top it up
+    if let Balance::Cw20(token) = &balance {
+        if !cw20_whitelist.contains(&token.address) {
+            cw20_whitelist.push(token.address.clone());
+        }
+    }
+    let mut escrow_balance = GenericBalance::default();
+    escrow_balance.add_tokens(balance);
+
+    let escrow = Escrow {
+        arbiter: deps.api.addr_validate(&msg.arbiter)?,
+        recipient: deps.api.addr_validate(&msg.recipient)?,
+        source: sender,
+        title: msg.title,
+        description: msg.description,
+        expires,
+        balance: escrow_balance,
+        cw20_whitelist,
+    };
+    escrows().update(deps.storage, &msg.id, |existing| match existing {
+        None => Ok(escrow),
+        Some(_) => Err(ContractError::AlreadyInUse {}),
+    })?;
+
+    let res = Response::new()
+        .add_attribute("action", "create")
+        .add_attribute("id", msg.id);
+    Ok(res)
+}
+
+pub fn execute_top_up(
+    deps: DepsMut,
+    env: Env,
+    id: String,
+    balance: Balance,
+) -> Result<Response, ContractError> {
+    if balance.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+    let mut escrow = escrows().load(deps.storage, &id)?;
+    if escrow.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    if let Balance::Cw20(token) = &balance {
+        if !escrow.cw20_whitelist.contains(&token.address) {
+            return Err(ContractError::NotInWhitelist {});
+        }
+    }
+    escrow.balance.add_tokens(balance);
+    escrows().save(deps.storage, &id, &escrow)?;
+
+    let res = Response::new()
+        .add_attribute("action", "top_up")
+        .add_attribute("id", id);
+    Ok(res)
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let escrow = escrows().load(deps.storage, &id)?;
+    if info.sender != escrow.arbiter {
+        return Err(ContractError::Unauthorized {});
+    }
+    if escrow.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    escrows().remove(deps.storage, &id)?;
+
+    let res = Response::new()
+        .add_messages(send_tokens(&escrow.recipient, escrow.balance)?)
+        .add_attribute("action", "approve")
+        .add_attribute("id", id)
+        .add_attribute("to", escrow.recipient);
+    Ok(res)
+}
+
+pub fn execute_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let escrow = escrows().load(deps.storage, &id)?;
+    if info.sender != escrow.arbiter && !escrow.is_expired(&env.block) {
+        return Err(ContractError::Unauthorized {});
+    }
+    escrows().remove(deps.storage, &id)?;
+
+    let res = Response::new()
+        .add_messages(send_tokens(&escrow.source, escrow.balance)?)
+        .add_attribute("action", "refund")
+        .add_attribute("id", id)
+        .add_attribute("to", escrow.source);
+    Ok(res)
+}
+
+fn send_tokens(to: &Addr, balance: GenericBalance) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut native = NativeBalance(balance.native);
+    native.normalize();
+    let native = native.into_vec();
+    if !native.is_empty() {
+        msgs.push(
+            BankMsg::Send {
+                to_address: to.to_string(),
+                amount: native,
+            }
+            .into(),
+        );
+    }
+    for token in balance.cw20.into_iter().filter(|c| !c.amount.is_zero()) {
+        msgs.push(
+            WasmMsg::Execute {
+                contract_addr: token.address.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: to.to_string(),
+                    amount: token.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+    Ok(msgs)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Details { id } => to_json_binary(&query_details(deps, id)?),
+        QueryMsg::List { start_after, limit } => {
+            to_json_binary(&query_list(deps, start_after, limit)?)
+        }
+        QueryMsg::ListByArbiter {
+            arbiter,
+            start_after,
+            limit,
+        } => to_json_binary(&query_list_by_arbiter(deps, arbiter, start_after, limit)?),
+    }
+}
+
+pub fn query_details(deps: Deps, id: String) -> StdResult<DetailsResponse> {
+    let escrow = escrows().load(deps.storage, &id)?;
+    let cw20_balance = escrow
+        .balance
+        .cw20
+        .into_iter()
+        .map(|c| Cw20Coin {
+            address: c.address.into(),
+            amount: c.amount,
+        })
+        .collect();
+    Ok(DetailsResponse {
+        id,
+        arbiter: escrow.arbiter.into(),
+        recipient: escrow.recipient.into(),
+        source: escrow.source.into(),
+        title: escrow.title,
+        description: escrow.description,
+        expires: escrow.expires,
+        native_balance: escrow.balance.native,
+        cw20_balance,
+        cw20_whitelist: escrow
+            .cw20_whitelist
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    })
+}
+
+pub fn query_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let escrows = escrows()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(ListResponse { escrows })
+}
+
+pub fn query_list_by_arbiter(
+    deps: Deps,
+    arbiter: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListResponse> {
+    let arbiter = deps.api.addr_validate(&arbiter)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let escrows = escrows()
+        .idx
+        .arbiter
+        .prefix(arbiter)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(ListResponse { escrows })
+}
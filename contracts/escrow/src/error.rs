@@ -0,0 +1,33 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Escrow id must not be empty")]
+    InvalidId {},
+
+    #[error("An escrow with this id already exists")]
+    AlreadyInUse {},
+
+    #[error("Send some coins to create an escrow")]
+    EmptyBalance {},
+
+    #[error("Expiration is already in the past")]
+    InvalidExpiration {},
+
+    #[error("Escrow expired")]
+    Expired {},
+
+    #[error("This cw20 token is not accepted by the escrow")]
+    NotInWhitelist {},
+}
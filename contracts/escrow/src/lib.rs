@@ -0,0 +1,15 @@
+/*!
+An escrow contract for native coins and cw20 tokens.
+
+Escrows are created and topped up with native coins or through a cw20 `Send`, and hold
+any mix of both. Their arbiter either approves them, paying the recipient, or refunds
+them to the creator. After an optional expiration, approval is no longer possible and
+anyone may trigger the refund.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,84 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Coin;
+use cw20::{Cw20Coin, Cw20ReceiveMsg, Expiration};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates an escrow funded by the native coins sent along
+    Create(CreateMsg),
+    /// Adds the native coins sent along to an escrow
+    TopUp { id: String },
+    /// Only for the arbiter, before expiration. Pays the whole escrow out to the recipient.
+    Approve { id: String },
+    /// Pays the whole escrow back to its creator. The arbiter may refund at any time,
+    /// anyone else only once the escrow expired.
+    Refund { id: String },
+    /// Entry point for escrows created or topped up with cw20 tokens
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Creates an escrow funded by the received tokens
+    Create(CreateMsg),
+    /// Adds the received tokens to an escrow accepting them
+    TopUp { id: String },
+}
+
+#[cw_serde]
+pub struct CreateMsg {
+    /// Unique id, chosen by the creator
+    pub id: String,
+    pub arbiter: String,
+    pub recipient: String,
+    pub title: String,
+    pub description: String,
+    /// Never expires if unset
+    pub expires: Option<Expiration>,
+    /// cw20 tokens the escrow can be topped up with, besides the one it was created with
+    pub cw20_whitelist: Option<Vec<String>>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns a single escrow by id
+    #[returns(DetailsResponse)]
+    Details { id: String },
+    /// Returns the ids of all open escrows. Supports pagination.
+    #[returns(ListResponse)]
+    List {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the ids of the open escrows with the given arbiter. Supports pagination.
+    #[returns(ListResponse)]
+    ListByArbiter {
+        arbiter: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DetailsResponse {
+    pub id: String,
+    pub arbiter: String,
+    pub recipient: String,
+    pub source: String,
+    pub title: String,
+    pub description: String,
+    pub expires: Expiration,
+    pub native_balance: Vec<Coin>,
+    pub cw20_balance: Vec<Cw20Coin>,
+    pub cw20_whitelist: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ListResponse {
+    pub escrows: Vec<String>,
+}
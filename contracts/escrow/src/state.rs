@@ -0,0 +1,71 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Coin};
+use cw20::{Balance, Cw20CoinVerified, Expiration};
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+
+#[cw_serde]
+#[derive(Default)]
+pub struct GenericBalance {
+    pub native: Vec<Coin>,
+    pub cw20: Vec<Cw20CoinVerified>,
+}
+
+impl GenericBalance {
+    pub fn add_tokens(&mut self, add: Balance) {
+        match add {
+            Balance::Native(balance) => {
+                for token in balance.0 {
+                    match self.native.iter_mut().find(|c| c.denom == token.denom) {
+                        Some(existing) => existing.amount += token.amount,
+                        None => self.native.push(token),
+                    }
+                }
+            }
+            Balance::Cw20(token) => {
+                match self.cw20.iter_mut().find(|c| c.address == token.address) {
+                    Some(existing) => existing.amount += token.amount,
+                    None => self.cw20.push(token),
+                }
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Escrow {
+    /// Releases the escrow to the recipient or refunds it
+    pub arbiter: Addr,
+    pub recipient: Addr,
+    /// Creator of the escrow, receiving it on refund
+    pub source: Addr,
+    pub title: String,
+    pub description: String,
+    pub expires: Expiration,
+    pub balance: GenericBalance,
+    /// cw20 tokens the escrow can be topped up with
+    pub cw20_whitelist: Vec<Addr>,
+}
+
+impl Escrow {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+pub struct EscrowIndexes<'a> {
+    pub arbiter: MultiIndex<'a, Addr, Escrow, String>,
+}
+
+impl<'a> IndexList<Escrow> for EscrowIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Escrow>> + '_> {
+        let v: Vec<&dyn Index<Escrow>> = vec![&self.arbiter];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn escrows<'a>() -> IndexedMap<&'a str, Escrow, EscrowIndexes<'a>> {
+    let indexes = EscrowIndexes {
+        arbiter: MultiIndex::new(|_pk, e| e.arbiter.clone(), "escrow", "escrow__arbiter"),
+    };
+    IndexedMap::new("escrow", indexes)
+}
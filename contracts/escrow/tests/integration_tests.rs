@@ -0,0 +1,339 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, coins, to_json_binary, Addr, Empty, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use cw20_escrow::msg::{
+        CreateMsg, DetailsResponse, ExecuteMsg, InstantiateMsg, ListResponse, QueryMsg, ReceiveMsg,
+    };
+    use cw20_escrow::ContractError;
+
+    const DENOM: &str = "uatom";
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn escrow_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_escrow::contract::execute,
+            cw20_escrow::contract::instantiate,
+            cw20_escrow::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        escrow: Addr,
+        token: Addr,
+        source: Addr,
+        arbiter: Addr,
+        recipient: Addr,
+    }
+
+    impl Suite {
+        fn new() -> Self {
+            let api = *App::default().api();
+            let source = api.addr_make("source");
+            let arbiter = api.addr_make("arbiter");
+            let recipient = api.addr_make("recipient");
+            let mut app = AppBuilder::new().build(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &source, coins(1_000, DENOM))
+                    .unwrap();
+            });
+            let cw20_id = app.store_code(cw20_contract());
+            let escrow_id = app.store_code(escrow_contract());
+
+            let token = app
+                .instantiate_contract(
+                    cw20_id,
+                    source.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: source.to_string(),
+                            amount: Uint128::new(1_000),
+                        }],
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "token",
+                    None,
+                )
+                .unwrap();
+            let escrow = app
+                .instantiate_contract(
+                    escrow_id,
+                    source.clone(),
+                    &InstantiateMsg {},
+                    &[],
+                    "escrow",
+                    None,
+                )
+                .unwrap();
+
+            Suite {
+                app,
+                escrow,
+                token,
+                source,
+                arbiter,
+                recipient,
+            }
+        }
+
+        fn create_msg(&self, id: &str, expires: Option<Expiration>) -> CreateMsg {
+            CreateMsg {
+                id: id.to_string(),
+                arbiter: self.arbiter.to_string(),
+                recipient: self.recipient.to_string(),
+                title: "Deal".to_string(),
+                description: "Payment on delivery".to_string(),
+                expires,
+                cw20_whitelist: None,
+            }
+        }
+
+        fn send_token(&mut self, amount: u128, msg: ReceiveMsg) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                self.source.clone(),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.escrow.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&msg).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg) -> anyhow::Result<()> {
+            self.app
+                .execute_contract(sender.clone(), self.escrow.clone(), msg, &[])?;
+            Ok(())
+        }
+
+        fn token_balance(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn native_balance(&self, addr: &Addr) -> Uint128 {
+            self.app.wrap().query_balance(addr, DENOM).unwrap().amount
+        }
+
+        fn list(&self, start_after: Option<&str>, limit: Option<u32>) -> Vec<String> {
+            let res: ListResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.escrow,
+                    &QueryMsg::List {
+                        start_after: start_after.map(String::from),
+                        limit,
+                    },
+                )
+                .unwrap();
+            res.escrows
+        }
+    }
+
+    #[test]
+    fn approve_pays_native_and_cw20_to_recipient() {
+        let mut suite = Suite::new();
+        let source = suite.source.clone();
+        let msg = ExecuteMsg::Create(suite.create_msg("deal", None));
+        suite
+            .app
+            .execute_contract(source, suite.escrow.clone(), &msg, &coins(100, DENOM))
+            .unwrap();
+        // the token isn't whitelisted for this escrow
+        let err = suite
+            .send_token(
+                250,
+                ReceiveMsg::TopUp {
+                    id: "deal".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotInWhitelist {}
+        );
+
+        let mut create = suite.create_msg("tokens", None);
+        create.cw20_whitelist = Some(vec![suite.token.to_string()]);
+        suite.send_token(250, ReceiveMsg::Create(create)).unwrap();
+        suite
+            .send_token(
+                50,
+                ReceiveMsg::TopUp {
+                    id: "tokens".to_string(),
+                },
+            )
+            .unwrap();
+        let source = suite.source.clone();
+        let msg = ExecuteMsg::TopUp {
+            id: "tokens".to_string(),
+        };
+        suite
+            .app
+            .execute_contract(source, suite.escrow.clone(), &msg, &coins(10, DENOM))
+            .unwrap();
+
+        let details: DetailsResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.escrow,
+                &QueryMsg::Details {
+                    id: "tokens".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(details.source, suite.source.to_string());
+        assert_eq!(details.native_balance, vec![coin(10, DENOM)]);
+        assert_eq!(
+            details.cw20_balance,
+            vec![Cw20Coin {
+                address: suite.token.to_string(),
+                amount: Uint128::new(300),
+            }]
+        );
+        assert_eq!(details.cw20_whitelist, vec![suite.token.to_string()]);
+
+        // only the arbiter approves
+        let approve = ExecuteMsg::Approve {
+            id: "tokens".to_string(),
+        };
+        let err = suite
+            .execute(&suite.recipient.clone(), &approve)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+        suite.execute(&suite.arbiter.clone(), &approve).unwrap();
+        assert_eq!(suite.token_balance(&suite.recipient), Uint128::new(300));
+        assert_eq!(suite.native_balance(&suite.recipient), Uint128::new(10));
+        assert_eq!(suite.list(None, None), vec!["deal".to_string()]);
+    }
+
+    #[test]
+    fn refund_after_expiration() {
+        let mut suite = Suite::new();
+        let expires = Expiration::AtHeight(suite.app.block_info().height + 10);
+        let msg = ExecuteMsg::Create(suite.create_msg("deal", Some(expires)));
+        let source = suite.source.clone();
+        suite
+            .app
+            .execute_contract(source, suite.escrow.clone(), &msg, &coins(100, DENOM))
+            .unwrap();
+        let err = suite
+            .app
+            .execute_contract(
+                suite.source.clone(),
+                suite.escrow.clone(),
+                &msg,
+                &coins(1, DENOM),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AlreadyInUse {}
+        );
+
+        let refund = ExecuteMsg::Refund {
+            id: "deal".to_string(),
+        };
+        let err = suite
+            .execute(&suite.recipient.clone(), &refund)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        suite.app.update_block(|block| block.height += 10);
+        let approve = ExecuteMsg::Approve {
+            id: "deal".to_string(),
+        };
+        let err = suite.execute(&suite.arbiter.clone(), &approve).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Expired {}
+        );
+        suite.execute(&suite.recipient.clone(), &refund).unwrap();
+        assert_eq!(suite.native_balance(&suite.source), Uint128::new(1_000));
+        assert!(suite.list(None, None).is_empty());
+    }
+
+    #[test]
+    fn list_escrows() {
+        let mut suite = Suite::new();
+        for id in ["a", "b", "c"] {
+            let msg = ExecuteMsg::Create(suite.create_msg(id, None));
+            let source = suite.source.clone();
+            suite
+                .app
+                .execute_contract(source, suite.escrow.clone(), &msg, &coins(1, DENOM))
+                .unwrap();
+        }
+        let mut other = suite.create_msg("d", None);
+        other.arbiter = suite.source.to_string();
+        let source = suite.source.clone();
+        suite
+            .app
+            .execute_contract(
+                source,
+                suite.escrow.clone(),
+                &ExecuteMsg::Create(other),
+                &coins(1, DENOM),
+            )
+            .unwrap();
+
+        assert_eq!(suite.list(None, Some(2)), vec!["a", "b"]);
+        assert_eq!(suite.list(Some("b"), None), vec!["c", "d"]);
+
+        let res: ListResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.escrow,
+                &QueryMsg::ListByArbiter {
+                    arbiter: suite.arbiter.to_string(),
+                    start_after: Some("a".to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.escrows, vec!["b", "c"]);
+
+        // an escrow needs funds
+        let msg = ExecuteMsg::Create(suite.create_msg("e", None));
+        let err = suite.execute(&suite.source.clone(), &msg).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::EmptyBalance {}
+        );
+    }
+}
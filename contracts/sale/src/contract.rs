@@ -0,0 +1,253 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_utils::{must_pay, nonpayable};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, PaymentAssetMsg, PurchasedResponse, QueryMsg, ReceiveMsg,
+    SaleResponse,
+};
+use crate::state::{Config, PaymentAsset, SaleState, CONFIG, PURCHASES, SALE_STATE};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-sale";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.start >= msg.end {
+        return Err(ContractError::InvalidWindow {});
+    }
+    if !msg.price.is_valid() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    let payment = match msg.payment {
+        PaymentAssetMsg::Native { denom } => PaymentAsset::Native { denom },
+        PaymentAssetMsg::Cw20 { address } => PaymentAsset::Cw20 {
+            address: deps.api.addr_validate(&address)?,
+        },
+    };
+    let config = Config {
+        token: deps.api.addr_validate(&msg.token)?,
+        payment,
+        price: msg.price,
+        start: msg.start,
+        end: msg.end,
+        per_address_cap: msg.per_address_cap,
+        treasury: deps.api.addr_validate(&msg.treasury)?,
+        admin: deps.api.addr_validate(&msg.admin)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    SALE_STATE.save(deps.storage, &SaleState::default())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Buy {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let PaymentAsset::Native { denom } = &config.payment else {
+                return Err(ContractError::WrongToken {});
+            };
+            let payment = must_pay(&info, denom)?;
+            execute_buy(deps, env, config, info.sender, payment)
+        }
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::WithdrawUnsold {} => execute_withdraw_unsold(deps, env, info),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Fund {} => {
+            if info.sender != config.token {
+                return Err(ContractError::WrongToken {});
+            }
+            if sender != config.admin {
+                return Err(ContractError::Unauthorized {});
+            }
+            execute_fund(deps, wrapper.amount)
+        }
+        ReceiveMsg::Buy {} => {
+            if config.payment
+                != (PaymentAsset::Cw20 {
+                    address: info.sender,
+                })
+            {
+                return Err(ContractError::WrongToken {});
+            }
+            execute_buy(deps, env, config, sender, wrapper.amount)
+        }
+    }
+}
+
+pub fn execute_fund(deps: DepsMut, amount: Uint128) -> Result<Response, ContractError> {
+    let state = SALE_STATE.update(deps.storage, |mut state| -> StdResult<_> {
+        state.allocation = state.allocation.checked_add(amount)?;
+        Ok(state)
+    })?;
+
+    let res = Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("amount", amount)
+        .add_attribute("allocation", state.allocation);
+    Ok(res)
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    buyer: Addr,
+    payment: Uint128,
+) -> Result<Response, ContractError> {
+    let now = env.block.time;
+    if now < config.start {
+        return Err(ContractError::NotStarted {});
+    }
+    if now >= config.end {
+        return Err(ContractError::Ended {});
+    }
+
+    let price = config.price_at(now);
+    let amount = payment
+        .checked_div_floor(price)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    if amount.is_zero() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+    let mut state = SALE_STATE.load(deps.storage)?;
+    let available = state.allocation - state.sold;
+    if amount > available {
+        return Err(ContractError::NotEnoughTokens { available });
+    }
+    state.sold += amount;
+    SALE_STATE.save(deps.storage, &state)?;
+
+    let purchased = PURCHASES
+        .may_load(deps.storage, &buyer)?
+        .unwrap_or_default();
+    if let Some(cap) = config.per_address_cap {
+        if purchased + amount > cap {
+            return Err(ContractError::CapExceeded {
+                remaining: cap.saturating_sub(purchased),
+            });
+        }
+    }
+    PURCHASES.save(deps.storage, &buyer, &(purchased + amount))?;
+
+    let res = Response::new()
+        .add_message(transfer_tokens(&config.token, &buyer, amount)?)
+        .add_message(forward_payment(&config, payment)?)
+        .add_attribute("action", "buy")
+        .add_attribute("buyer", buyer)
+        .add_attribute("amount", amount)
+        .add_attribute("paid", payment)
+        .add_attribute("price", price.to_string());
+    Ok(res)
+}
+
+pub fn execute_withdraw_unsold(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if env.block.time < config.end {
+        return Err(ContractError::NotEnded {});
+    }
+    let mut state = SALE_STATE.load(deps.storage)?;
+    let unsold = state.allocation - state.sold - state.withdrawn;
+    state.withdrawn += unsold;
+    SALE_STATE.save(deps.storage, &state)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "withdraw_unsold")
+        .add_attribute("amount", unsold);
+    if !unsold.is_zero() {
+        res = res.add_message(transfer_tokens(&config.token, &config.treasury, unsold)?);
+    }
+    Ok(res)
+}
+
+fn transfer_tokens(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn forward_payment(config: &Config, amount: Uint128) -> StdResult<CosmosMsg> {
+    match &config.payment {
+        PaymentAsset::Native { denom } => Ok(BankMsg::Send {
+            to_address: config.treasury.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into()),
+        PaymentAsset::Cw20 { address } => transfer_tokens(address, &config.treasury, amount),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Sale {} => to_json_binary(&query_sale(deps, env)?),
+        QueryMsg::Purchased { address } => to_json_binary(&query_purchased(deps, address)?),
+    }
+}
+
+pub fn query_sale(deps: Deps, env: Env) -> StdResult<SaleResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = SALE_STATE.load(deps.storage)?;
+    let now = env.block.time;
+    Ok(SaleResponse {
+        allocation: state.allocation,
+        sold: state.sold,
+        price: config.price_at(now),
+        started: now >= config.start,
+        ended: now >= config.end,
+    })
+}
+
+pub fn query_purchased(deps: Deps, address: String) -> StdResult<PurchasedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let amount = PURCHASES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(PurchasedResponse { amount })
+}
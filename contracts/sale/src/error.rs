@@ -0,0 +1,42 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("This token is not accepted for this operation")]
+    WrongToken {},
+
+    #[error("The sale must end after it starts")]
+    InvalidWindow {},
+
+    #[error("Prices must not be zero, and a Dutch auction must get cheaper")]
+    InvalidPrice {},
+
+    #[error("The sale has not started yet")]
+    NotStarted {},
+
+    #[error("The sale is over")]
+    Ended {},
+
+    #[error("The sale is still running")]
+    NotEnded {},
+
+    #[error("Payment too small to buy any tokens")]
+    AmountTooSmall {},
+
+    #[error("Only {available} tokens are left for sale")]
+    NotEnoughTokens { available: Uint128 },
+
+    #[error("Purchase exceeds the per-address cap, {remaining} tokens left")]
+    CapExceeded { remaining: Uint128 },
+}
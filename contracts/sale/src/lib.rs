@@ -0,0 +1,15 @@
+/*!
+A token sale selling a fixed allocation of a cw20 token, such as a cw20-base token.
+
+The admin funds the allocation with a cw20 `Send`. Within the sale window anyone buys at
+the current [`Price`](state::Price), paying in a native denom or a cw20 token, up to the
+per-address cap. The payment goes straight to the treasury, and the unsold tokens can be
+withdrawn to it once the sale is over.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
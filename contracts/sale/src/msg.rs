@@ -0,0 +1,74 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::Price;
+
+#[cw_serde]
+pub enum PaymentAssetMsg {
+    Native { denom: String },
+    Cw20 { address: String },
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// cw20 token on sale
+    pub token: String,
+    pub payment: PaymentAssetMsg,
+    pub price: Price,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Most tokens a single address may buy. Unlimited if unset.
+    pub per_address_cap: Option<Uint128>,
+    /// Receives the payments and the unsold tokens
+    pub treasury: String,
+    /// Funds the allocation
+    pub admin: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Buys tokens with the native payment sent along
+    Buy {},
+    /// Entry point for funding the sale and for buying with a cw20 payment
+    Receive(Cw20ReceiveMsg),
+    /// Sends the unsold tokens to the treasury once the sale is over. Anyone may call this.
+    WithdrawUnsold {},
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Only for the admin, sending the token on sale. Adds to the allocation.
+    Fund {},
+    /// Buys tokens with the received cw20 payment
+    Buy {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Returns the allocation, the tokens sold and the current price
+    #[returns(SaleResponse)]
+    Sale {},
+    /// Returns the tokens `address` bought
+    #[returns(PurchasedResponse)]
+    Purchased { address: String },
+}
+
+#[cw_serde]
+pub struct SaleResponse {
+    pub allocation: Uint128,
+    pub sold: Uint128,
+    /// Payment units per token unit at the current block
+    pub price: Decimal,
+    pub started: bool,
+    pub ended: bool,
+}
+
+#[cw_serde]
+pub struct PurchasedResponse {
+    pub amount: Uint128,
+}
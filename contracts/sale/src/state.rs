@@ -0,0 +1,84 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+
+/// What the tokens are paid with
+#[cw_serde]
+pub enum PaymentAsset {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+/// Payment units per token unit
+#[cw_serde]
+pub enum Price {
+    Fixed {
+        price: Decimal,
+    },
+    /// Falls linearly from `start_price` at the start of the sale to `end_price` at its end
+    DutchAuction {
+        start_price: Decimal,
+        end_price: Decimal,
+    },
+}
+
+impl Price {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Price::Fixed { price } => !price.is_zero(),
+            Price::DutchAuction {
+                start_price,
+                end_price,
+            } => !end_price.is_zero() && start_price > end_price,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Config {
+    /// cw20 token on sale
+    pub token: Addr,
+    pub payment: PaymentAsset,
+    pub price: Price,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Most tokens a single address may buy
+    pub per_address_cap: Option<Uint128>,
+    /// Receives the payments and the unsold tokens
+    pub treasury: Addr,
+    /// Funds the allocation
+    pub admin: Addr,
+}
+
+impl Config {
+    /// Price at `now`, within the sale window
+    pub fn price_at(&self, now: Timestamp) -> Decimal {
+        match self.price {
+            Price::Fixed { price } => price,
+            Price::DutchAuction {
+                start_price,
+                end_price,
+            } => {
+                let elapsed = now.seconds().saturating_sub(self.start.seconds());
+                let duration = self.end.seconds() - self.start.seconds();
+                let drop = (start_price - end_price) * Decimal::from_ratio(elapsed, duration);
+                start_price - drop.min(start_price - end_price)
+            }
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct SaleState {
+    /// Tokens funded for the sale
+    pub allocation: Uint128,
+    pub sold: Uint128,
+    /// Unsold tokens withdrawn to the treasury after the sale
+    pub withdrawn: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const SALE_STATE: Item<SaleState> = Item::new("sale_state");
+/// Tokens bought per address
+pub const PURCHASES: Map<&Addr, Uint128> = Map::new("purchases");
@@ -0,0 +1,342 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Empty, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use cw20_sale::msg::{
+        ExecuteMsg, InstantiateMsg, PaymentAssetMsg, PurchasedResponse, QueryMsg, ReceiveMsg,
+        SaleResponse,
+    };
+    use cw20_sale::state::Price;
+    use cw20_sale::ContractError;
+
+    const DENOM: &str = "uusd";
+    const DURATION: u64 = 100;
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn sale_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_sale::contract::execute,
+            cw20_sale::contract::instantiate,
+            cw20_sale::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        sale: Addr,
+        token: Addr,
+        payment_token: Addr,
+        admin: Addr,
+        treasury: Addr,
+        alice: Addr,
+        bob: Addr,
+    }
+
+    impl Suite {
+        /// A sale of 1_000 tokens starting in 10 seconds
+        fn new(price: Price, cw20_payment: bool, per_address_cap: Option<u128>) -> Self {
+            let api = *App::default().api();
+            let admin = api.addr_make("admin");
+            let treasury = api.addr_make("treasury");
+            let alice = api.addr_make("alice");
+            let bob = api.addr_make("bob");
+            let mut app = AppBuilder::new().build(|router, _, storage| {
+                for user in [&alice, &bob] {
+                    router
+                        .bank
+                        .init_balance(storage, user, coins(10_000, DENOM))
+                        .unwrap();
+                }
+            });
+            let cw20_id = app.store_code(cw20_contract());
+            let sale_id = app.store_code(sale_contract());
+
+            let mut new_token = |symbol: &str, holders: &[&Addr]| {
+                let initial_balances = holders
+                    .iter()
+                    .map(|holder| Cw20Coin {
+                        address: holder.to_string(),
+                        amount: Uint128::new(10_000),
+                    })
+                    .collect();
+                app.instantiate_contract(
+                    cw20_id,
+                    admin.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: format!("{symbol} token"),
+                        symbol: symbol.to_string(),
+                        decimals: 6,
+                        initial_balances,
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    symbol,
+                    None,
+                )
+                .unwrap()
+            };
+            let token = new_token("SALE", &[&admin]);
+            let payment_token = new_token("PAY", &[&alice, &bob]);
+
+            let payment = if cw20_payment {
+                PaymentAssetMsg::Cw20 {
+                    address: payment_token.to_string(),
+                }
+            } else {
+                PaymentAssetMsg::Native {
+                    denom: DENOM.to_string(),
+                }
+            };
+            let start = app.block_info().time.plus_seconds(10);
+            let sale = app
+                .instantiate_contract(
+                    sale_id,
+                    admin.clone(),
+                    &InstantiateMsg {
+                        token: token.to_string(),
+                        payment,
+                        price,
+                        start,
+                        end: start.plus_seconds(DURATION),
+                        per_address_cap: per_address_cap.map(Uint128::new),
+                        treasury: treasury.to_string(),
+                        admin: admin.to_string(),
+                    },
+                    &[],
+                    "sale",
+                    None,
+                )
+                .unwrap();
+
+            let mut suite = Suite {
+                app,
+                sale,
+                token,
+                payment_token,
+                admin,
+                treasury,
+                alice,
+                bob,
+            };
+            let admin = suite.admin.clone();
+            suite.fund(&admin, 1_000).unwrap();
+            suite
+        }
+
+        fn advance(&mut self, seconds: u64) {
+            self.app
+                .update_block(|block| block.time = block.time.plus_seconds(seconds));
+        }
+
+        fn fund(&mut self, sender: &Addr, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                sender.clone(),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.sale.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&ReceiveMsg::Fund {}).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn buy_native(&mut self, buyer: &Addr, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                buyer.clone(),
+                self.sale.clone(),
+                &ExecuteMsg::Buy {},
+                &coins(amount, DENOM),
+            )?;
+            Ok(())
+        }
+
+        fn buy_cw20(&mut self, buyer: &Addr, amount: u128) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                buyer.clone(),
+                self.payment_token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.sale.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&ReceiveMsg::Buy {}).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn balance(&self, token: &Addr, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn sale_info(&self) -> SaleResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(&self.sale, &QueryMsg::Sale {})
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn fixed_price_native_sale() {
+        let price = Price::Fixed {
+            price: Decimal::percent(200),
+        };
+        let mut suite = Suite::new(price, false, Some(300));
+        let (alice, bob) = (suite.alice.clone(), suite.bob.clone());
+
+        let err = suite.buy_native(&alice, 400).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotStarted {}
+        );
+
+        suite.advance(10);
+        suite.buy_native(&alice, 400).unwrap();
+        assert_eq!(suite.balance(&suite.token, &alice), Uint128::new(200));
+        assert_eq!(
+            suite
+                .app
+                .wrap()
+                .query_balance(&suite.treasury, DENOM)
+                .unwrap()
+                .amount,
+            Uint128::new(400)
+        );
+        let err = suite.buy_native(&alice, 400).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::CapExceeded {
+                remaining: Uint128::new(100)
+            }
+        );
+        let purchased: PurchasedResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.sale,
+                &QueryMsg::Purchased {
+                    address: alice.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(purchased.amount, Uint128::new(200));
+
+        suite.buy_native(&bob, 600).unwrap();
+        assert_eq!(suite.sale_info().sold, Uint128::new(500));
+
+        let withdraw = ExecuteMsg::WithdrawUnsold {};
+        let err = suite
+            .app
+            .execute_contract(bob.clone(), suite.sale.clone(), &withdraw, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotEnded {}
+        );
+        suite.advance(DURATION);
+        let err = suite.buy_native(&bob, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Ended {}
+        );
+        suite
+            .app
+            .execute_contract(bob, suite.sale.clone(), &withdraw, &[])
+            .unwrap();
+        assert_eq!(
+            suite.balance(&suite.token, &suite.treasury),
+            Uint128::new(500)
+        );
+    }
+
+    #[test]
+    fn dutch_auction_cw20_sale() {
+        let price = Price::DutchAuction {
+            start_price: Decimal::percent(400),
+            end_price: Decimal::percent(200),
+        };
+        let mut suite = Suite::new(price, true, None);
+        let alice = suite.alice.clone();
+
+        // halfway through the price has dropped from 4 to 3
+        suite.advance(10 + DURATION / 2);
+        assert_eq!(suite.sale_info().price, Decimal::percent(300));
+        suite.buy_cw20(&alice, 300).unwrap();
+        assert_eq!(suite.balance(&suite.token, &alice), Uint128::new(100));
+        assert_eq!(
+            suite.balance(&suite.payment_token, &suite.treasury),
+            Uint128::new(300)
+        );
+
+        let err = suite.buy_native(&alice, 300).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::WrongToken {}
+        );
+        let err = suite.buy_cw20(&alice, 3_000).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotEnoughTokens {
+                available: Uint128::new(900)
+            }
+        );
+        let err = suite.buy_cw20(&alice, 2).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AmountTooSmall {}
+        );
+    }
+
+    #[test]
+    fn only_admin_funds() {
+        let price = Price::Fixed {
+            price: Decimal::one(),
+        };
+        let mut suite = Suite::new(price, true, None);
+        let admin = suite.admin.clone();
+
+        // the admin transfers some sale tokens away, the new holder can't fund
+        let alice = suite.alice.clone();
+        suite
+            .app
+            .execute_contract(
+                admin.clone(),
+                suite.token.clone(),
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: alice.to_string(),
+                    amount: Uint128::new(100),
+                },
+                &[],
+            )
+            .unwrap();
+        let err = suite.fund(&alice, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        suite.fund(&admin, 500).unwrap();
+        assert_eq!(suite.sale_info().allocation, Uint128::new(1_500));
+    }
+}
@@ -0,0 +1,342 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coin, coins, to_json_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, DistributionMsg,
+    Env, MessageInfo, QuerierWrapper, Response, StakingMsg, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{AllowanceResponse, BalanceResponse, TokenInfoResponse};
+use cw20_base::allowances::{
+    execute_decrease_allowance, execute_increase_allowance, execute_send_from,
+    execute_transfer_from,
+};
+use cw20_base::contract::{execute_send, execute_transfer};
+use cw20_base::msg::{validate_name, validate_symbol};
+use cw20_base::state::TokenMetadata;
+use cw20_base::storage::Cw20Storage;
+use cw_utils::{must_pay, nonpayable};
+
+use crate::error::ContractError;
+use crate::msg::{ClaimsResponse, ExecuteMsg, InstantiateMsg, InvestmentResponse, QueryMsg};
+use crate::state::{Claim, Config, CLAIMS, CONFIG, TOTAL_CLAIMS};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-staking";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_name(&msg.name)?;
+    validate_symbol(&msg.symbol, Default::default())?;
+    if msg.decimals > 18 {
+        return Err(ContractError::InvalidDecimals {});
+    }
+    if deps.querier.query_validator(&msg.validator)?.is_none() {
+        return Err(ContractError::NotInValidatorSet {
+            validator: msg.validator,
+        });
+    }
+
+    let metadata = TokenMetadata {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+    };
+    Cw20Storage::DEFAULT.instantiate(deps.storage, &metadata, env.block.height)?;
+    let config = Config {
+        validator: msg.validator,
+        bond_denom: deps.querier.query_bonded_denom()?,
+        unbonding_period: msg.unbonding_period,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL_CLAIMS.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Bond {} => execute_bond(deps, env, info),
+        ExecuteMsg::Unbond { amount } => execute_unbond(deps, env, info, amount),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Reinvest {} => execute_reinvest(deps, env, info),
+        ExecuteMsg::BondAllTokens {} => execute_bond_all_tokens(deps, env, info),
+
+        // the shares are plain cw20-base tokens
+        ExecuteMsg::Transfer { recipient, amount } => {
+            Ok(execute_transfer(deps, env, info, recipient, amount, None)?)
+        }
+        ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => Ok(execute_send(
+            deps, env, info, contract, amount, msg, None, false,
+        )?),
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => Ok(execute_increase_allowance(
+            deps, env, info, spender, amount, expires,
+        )?),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => Ok(execute_decrease_allowance(
+            deps, env, info, spender, amount, expires,
+        )?),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => Ok(execute_transfer_from(
+            deps, env, info, owner, recipient, amount, None,
+        )?),
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => Ok(execute_send_from(
+            deps, env, info, owner, contract, amount, msg, None, false,
+        )?),
+    }
+}
+
+/// Tokens the contract currently has delegated to its validator
+fn bonded(querier: &QuerierWrapper, contract: &Addr, config: &Config) -> StdResult<Uint128> {
+    let delegation = querier.query_delegation(contract, &config.validator)?;
+    Ok(delegation
+        .map(|delegation| delegation.amount.amount)
+        .unwrap_or_default())
+}
+
+pub fn execute_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let payment = must_pay(&info, &config.bond_denom)?;
+
+    // shares keep the exchange rate, the first bonder sets it at 1
+    let bonded = bonded(&deps.querier, &env.contract.address, &config)?;
+    let supply = Cw20Storage::DEFAULT.token_info(deps.storage)?.total_supply;
+    let shares = if supply.is_zero() || bonded.is_zero() {
+        payment
+    } else {
+        payment.multiply_ratio(supply, bonded)
+    };
+    if shares.is_zero() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+    Cw20Storage::DEFAULT.mint(deps.storage, &info.sender, shares, env.block.height)?;
+
+    let res = Response::new()
+        .add_message(StakingMsg::Delegate {
+            validator: config.validator,
+            amount: coin(payment.u128(), config.bond_denom),
+        })
+        .add_attribute("action", "bond")
+        .add_attribute("from", info.sender)
+        .add_attribute("bonded", payment)
+        .add_attribute("minted", shares);
+    Ok(res)
+}
+
+pub fn execute_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let bonded = bonded(&deps.querier, &env.contract.address, &config)?;
+    let supply = Cw20Storage::DEFAULT.token_info(deps.storage)?.total_supply;
+    Cw20Storage::DEFAULT.burn(deps.storage, &info.sender, amount, env.block.height)?;
+    let unbonded = amount.multiply_ratio(bonded, supply);
+    if unbonded.is_zero() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+
+    let claim = Claim {
+        amount: unbonded,
+        release_at: config.unbonding_period.after(&env.block),
+    };
+    CLAIMS.update(deps.storage, &info.sender, |claims| -> StdResult<_> {
+        let mut claims = claims.unwrap_or_default();
+        claims.push(claim);
+        Ok(claims)
+    })?;
+    TOTAL_CLAIMS.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total.checked_add(unbonded)?)
+    })?;
+
+    let res = Response::new()
+        .add_message(StakingMsg::Undelegate {
+            validator: config.validator,
+            amount: coin(unbonded.u128(), config.bond_denom),
+        })
+        .add_attribute("action", "unbond")
+        .add_attribute("from", info.sender)
+        .add_attribute("burned", amount)
+        .add_attribute("unbonded", unbonded);
+    Ok(res)
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let claims = CLAIMS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let (released, pending): (Vec<_>, Vec<_>) = claims
+        .into_iter()
+        .partition(|claim| claim.release_at.is_expired(&env.block));
+    let amount: Uint128 = released.iter().map(|claim| claim.amount).sum();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    if pending.is_empty() {
+        CLAIMS.remove(deps.storage, &info.sender);
+    } else {
+        CLAIMS.save(deps.storage, &info.sender, &pending)?;
+    }
+    TOTAL_CLAIMS.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total.checked_sub(amount)?)
+    })?;
+
+    let res = Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), config.bond_denom),
+        })
+        .add_attribute("action", "claim")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+pub fn execute_reinvest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    // the rewards land in the contract's balance before the second step bonds them
+    let res = Response::new()
+        .add_message(DistributionMsg::WithdrawDelegatorReward {
+            validator: config.validator,
+        })
+        .add_message(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::BondAllTokens {})?,
+            funds: vec![],
+        })
+        .add_attribute("action", "reinvest");
+    Ok(res)
+}
+
+pub fn execute_bond_all_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+
+    // tokens held for claims stay liquid, and an empty withdrawal is no error
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.bond_denom)?;
+    let amount = balance
+        .amount
+        .saturating_sub(TOTAL_CLAIMS.load(deps.storage)?);
+    let mut res = Response::new()
+        .add_attribute("action", "bond_all_tokens")
+        .add_attribute("bonded", amount);
+    if !amount.is_zero() {
+        res = res.add_message(StakingMsg::Delegate {
+            validator: config.validator,
+            amount: coin(amount.u128(), config.bond_denom),
+        });
+    }
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Investment {} => to_json_binary(&query_investment(deps, env)?),
+        QueryMsg::Claims { address } => to_json_binary(&query_claims(deps, address)?),
+        QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
+        QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_json_binary(&query_allowance(deps, owner, spender)?)
+        }
+    }
+}
+
+pub fn query_investment(deps: Deps, env: Env) -> StdResult<InvestmentResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let bonded = bonded(&deps.querier, &env.contract.address, &config)?;
+    let supply = Cw20Storage::DEFAULT.token_info(deps.storage)?.total_supply;
+    let exchange_rate = if supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::checked_from_ratio(bonded, supply)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+    };
+    Ok(InvestmentResponse {
+        validator: config.validator,
+        unbonding_period: config.unbonding_period,
+        token_supply: supply,
+        staked_tokens: coin(bonded.u128(), config.bond_denom),
+        exchange_rate,
+        pending_claims: TOTAL_CLAIMS.load(deps.storage)?,
+    })
+}
+
+pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claims = CLAIMS.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(ClaimsResponse { claims })
+}
+
+pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+    Cw20Storage::DEFAULT.token_info(deps.storage)
+}
+
+pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = Cw20Storage::DEFAULT.balance(deps.storage, &address)?;
+    Ok(BalanceResponse { balance })
+}
+
+pub fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let spender = deps.api.addr_validate(&spender)?;
+    Cw20Storage::DEFAULT.allowance(deps.storage, &owner, &spender)
+}
@@ -0,0 +1,30 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Base(#[from] cw20_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{validator} is not in the current validator set")]
+    NotInValidatorSet { validator: String },
+
+    #[error("Decimals must not exceed 18")]
+    InvalidDecimals {},
+
+    #[error("Amount too small to bond or unbond")]
+    AmountTooSmall {},
+
+    #[error("No claims that can be released yet")]
+    NothingToClaim {},
+}
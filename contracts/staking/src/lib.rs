@@ -0,0 +1,16 @@
+/*!
+A liquid staking derivative. Native tokens bonded to the contract are delegated to a single
+validator, and the bonder gets cw20 shares of the delegation in return.
+
+The contract is itself the share token: transfers, sends and allowances are the cw20-base
+ones, imported with its `library` feature. Shares are worth a growing amount of the staked
+token as `Reinvest {}` delegates the staking rewards, and are redeemed by `Unbond {}` for a
+claim that can be withdrawn once the unbonding period has passed.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
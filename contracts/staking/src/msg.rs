@@ -0,0 +1,103 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Coin, Decimal, Uint128};
+use cw20::Expiration;
+use cw_utils::Duration;
+
+use crate::state::Claim;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Name of the share token
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Validator to delegate to
+    pub validator: String,
+    /// Should be at least the unbonding time of the chain, or claims could be released
+    /// before the tokens are back
+    pub unbonding_period: Duration,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Delegates the staking tokens sent along and mints shares to the sender at the
+    /// current exchange rate
+    Bond {},
+    /// Burns `amount` shares of the sender and undelegates what they are worth, as a claim
+    /// the sender can release after the unbonding period
+    Unbond { amount: Uint128 },
+    /// Releases the matured claims of the sender
+    Claim {},
+    /// Withdraws the staking rewards and delegates them, raising the exchange rate.
+    /// Anyone may call this.
+    Reinvest {},
+    /// Only callable by the contract itself, as the second step of Reinvest. Delegates the
+    /// whole balance not held for claims.
+    BondAllTokens {},
+
+    /// The cw20-base messages for the shares
+    Transfer { recipient: String, amount: Uint128 },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the delegation, the share supply and the exchange rate between the two
+    #[returns(InvestmentResponse)]
+    Investment {},
+    /// Returns the claims of `address`, matured or not
+    #[returns(ClaimsResponse)]
+    Claims { address: String },
+
+    #[returns(cw20::TokenInfoResponse)]
+    TokenInfo {},
+    #[returns(cw20::BalanceResponse)]
+    Balance { address: String },
+    #[returns(cw20::AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+}
+
+#[cw_serde]
+pub struct InvestmentResponse {
+    pub validator: String,
+    pub unbonding_period: Duration,
+    /// Shares in circulation
+    pub token_supply: Uint128,
+    /// Tokens delegated by the contract
+    pub staked_tokens: Coin,
+    /// Staked tokens one share is worth
+    pub exchange_rate: Decimal,
+    /// Tokens held back for claims
+    pub pending_claims: Uint128,
+}
+
+#[cw_serde]
+pub struct ClaimsResponse {
+    pub claims: Vec<Claim>,
+}
@@ -0,0 +1,28 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+#[cw_serde]
+pub struct Config {
+    /// Validator all bonded tokens are delegated to
+    pub validator: String,
+    /// Staking denom of the chain
+    pub bond_denom: String,
+    /// Time from unbonding until the claim can be released. Should be at least the
+    /// unbonding time of the chain.
+    pub unbonding_period: Duration,
+}
+
+/// Native tokens owed to an unbonder
+#[cw_serde]
+pub struct Claim {
+    pub amount: Uint128,
+    pub release_at: Expiration,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CLAIMS: Map<&Addr, Vec<Claim>> = Map::new("claims");
+/// Sum of all claims not released yet. These tokens are never reinvested.
+pub const TOTAL_CLAIMS: Item<Uint128> = Item::new("total_claims");
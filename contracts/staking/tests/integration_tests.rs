@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coins, Addr, Decimal, Empty, Uint128, Validator};
+    use cw20::BalanceResponse;
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor, StakingInfo};
+    use cw_utils::{Duration, PaymentError};
+
+    use cw20_staking::msg::{
+        ClaimsResponse, ExecuteMsg, InstantiateMsg, InvestmentResponse, QueryMsg,
+    };
+    use cw20_staking::ContractError;
+
+    const DENOM: &str = "ustake";
+    const UNBONDING_TIME: u64 = 60;
+    const YEAR: u64 = 60 * 60 * 24 * 365;
+
+    fn staking_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_staking::contract::execute,
+            cw20_staking::contract::instantiate,
+            cw20_staking::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        staking: Addr,
+        code_id: u64,
+        validator: String,
+        alice: Addr,
+        bob: Addr,
+    }
+
+    impl Suite {
+        fn new() -> Self {
+            let api = *App::default().api();
+            let alice = api.addr_make("alice");
+            let bob = api.addr_make("bob");
+            let validator = api.addr_make("validator").to_string();
+            let mut app = AppBuilder::new().build(|router, api, storage| {
+                for user in [&alice, &bob] {
+                    router
+                        .bank
+                        .init_balance(storage, user, coins(10_000, DENOM))
+                        .unwrap();
+                }
+                router
+                    .staking
+                    .setup(
+                        storage,
+                        StakingInfo {
+                            bonded_denom: DENOM.to_string(),
+                            unbonding_time: UNBONDING_TIME,
+                            apr: Decimal::percent(10),
+                        },
+                    )
+                    .unwrap();
+                router
+                    .staking
+                    .add_validator(
+                        api,
+                        storage,
+                        &mock_env().block,
+                        Validator::create(
+                            validator.clone(),
+                            Decimal::zero(),
+                            Decimal::one(),
+                            Decimal::one(),
+                        ),
+                    )
+                    .unwrap();
+            });
+            let code_id = app.store_code(staking_contract());
+            let mut suite = Suite {
+                app,
+                staking: Addr::unchecked(""),
+                code_id,
+                validator,
+                alice,
+                bob,
+            };
+            suite.staking = suite.instantiate(&suite.validator.clone()).unwrap();
+            suite
+        }
+
+        fn instantiate(&mut self, validator: &str) -> anyhow::Result<Addr> {
+            self.app.instantiate_contract(
+                self.code_id,
+                self.alice.clone(),
+                &InstantiateMsg {
+                    name: "Staked token".to_string(),
+                    symbol: "STAKED".to_string(),
+                    decimals: 6,
+                    validator: validator.to_string(),
+                    unbonding_period: Duration::Time(UNBONDING_TIME),
+                },
+                &[],
+                "staking",
+                None,
+            )
+        }
+
+        fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg, funds: u128) -> anyhow::Result<()> {
+            let funds = if funds == 0 {
+                vec![]
+            } else {
+                coins(funds, DENOM)
+            };
+            self.app
+                .execute_contract(sender.clone(), self.staking.clone(), msg, &funds)?;
+            Ok(())
+        }
+
+        fn shares(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.staking,
+                    &QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn native_balance(&self, addr: &Addr) -> Uint128 {
+            self.app.wrap().query_balance(addr, DENOM).unwrap().amount
+        }
+
+        fn investment(&self) -> InvestmentResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(&self.staking, &QueryMsg::Investment {})
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn bond_unbond_and_claim() {
+        let mut suite = Suite::new();
+        let (alice, bob) = (suite.alice.clone(), suite.bob.clone());
+        suite.execute(&alice, &ExecuteMsg::Bond {}, 1_000).unwrap();
+        assert_eq!(suite.shares(&alice), Uint128::new(1_000));
+
+        let investment = suite.investment();
+        assert_eq!(investment.staked_tokens.amount, Uint128::new(1_000));
+        assert_eq!(investment.token_supply, Uint128::new(1_000));
+        assert_eq!(investment.exchange_rate, Decimal::one());
+
+        // shares move like any cw20 token
+        let transfer = ExecuteMsg::Transfer {
+            recipient: bob.to_string(),
+            amount: Uint128::new(300),
+        };
+        suite.execute(&alice, &transfer, 0).unwrap();
+        let unbond = ExecuteMsg::Unbond {
+            amount: Uint128::new(300),
+        };
+        suite.execute(&bob, &unbond, 0).unwrap();
+        assert_eq!(suite.shares(&bob), Uint128::zero());
+        assert_eq!(suite.investment().pending_claims, Uint128::new(300));
+
+        let claims: ClaimsResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &suite.staking,
+                &QueryMsg::Claims {
+                    address: bob.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(claims.claims.len(), 1);
+        assert_eq!(claims.claims[0].amount, Uint128::new(300));
+
+        let err = suite.execute(&bob, &ExecuteMsg::Claim {}, 0).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+        suite
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(UNBONDING_TIME));
+        suite.execute(&bob, &ExecuteMsg::Claim {}, 0).unwrap();
+        assert_eq!(suite.native_balance(&bob), Uint128::new(10_300));
+        assert_eq!(suite.investment().pending_claims, Uint128::zero());
+        assert_eq!(suite.investment().staked_tokens.amount, Uint128::new(700));
+    }
+
+    #[test]
+    fn reinvest_raises_exchange_rate() {
+        let mut suite = Suite::new();
+        let (alice, bob) = (suite.alice.clone(), suite.bob.clone());
+        suite.execute(&alice, &ExecuteMsg::Bond {}, 1_000).unwrap();
+
+        // 10% a year on the delegation
+        suite
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(YEAR));
+        suite.execute(&bob, &ExecuteMsg::Reinvest {}, 0).unwrap();
+        let investment = suite.investment();
+        assert_eq!(investment.staked_tokens.amount, Uint128::new(1_100));
+        assert_eq!(investment.exchange_rate, Decimal::percent(110));
+
+        suite.execute(&bob, &ExecuteMsg::Bond {}, 550).unwrap();
+        assert_eq!(suite.shares(&bob), Uint128::new(500));
+
+        // alice's shares now unbond for more than she bonded
+        let unbond = ExecuteMsg::Unbond {
+            amount: Uint128::new(1_000),
+        };
+        suite.execute(&alice, &unbond, 0).unwrap();
+        assert_eq!(suite.investment().pending_claims, Uint128::new(1_100));
+
+        let err = suite
+            .execute(&alice, &ExecuteMsg::BondAllTokens {}, 0)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let mut suite = Suite::new();
+        let alice = suite.alice.clone();
+
+        let err = suite.instantiate("unknown").unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotInValidatorSet {
+                validator: "unknown".to_string()
+            }
+        );
+
+        let err = suite.execute(&alice, &ExecuteMsg::Bond {}, 0).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Payment(PaymentError::NoFunds {})
+        );
+
+        suite.execute(&alice, &ExecuteMsg::Bond {}, 100).unwrap();
+        let unbond = ExecuteMsg::Unbond {
+            amount: Uint128::new(101),
+        };
+        suite.execute(&alice, &unbond, 0).unwrap_err();
+    }
+}
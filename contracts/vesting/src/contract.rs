@@ -0,0 +1,240 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+use cw_utils::nonpayable;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, VestingInfo, VestingsResponse,
+};
+use crate::state::{Config, Schedule, Vesting, CONFIG, VESTINGS, VESTING_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw20-vesting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let config = Config {
+        token: deps.api.addr_validate(&msg.token)?,
+        admin: deps.api.addr_validate(&msg.admin)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    VESTING_COUNT.save(deps.storage, &0)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Revoke { beneficiary, id } => execute_revoke(deps, env, info, beneficiary, id),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(ContractError::WrongToken {});
+    }
+    if deps.api.addr_validate(&wrapper.sender)? != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::CreateVesting {
+            beneficiary,
+            schedule,
+        } => execute_create_vesting(deps, env, beneficiary, wrapper.amount, schedule),
+    }
+}
+
+pub fn execute_create_vesting(
+    deps: DepsMut,
+    _env: Env,
+    beneficiary: String,
+    amount: Uint128,
+    schedule: Schedule,
+) -> Result<Response, ContractError> {
+    if !schedule.is_valid() {
+        return Err(ContractError::InvalidSchedule {});
+    }
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let id = VESTING_COUNT.update(deps.storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+    let vesting = Vesting {
+        amount,
+        claimed: Uint128::zero(),
+        schedule,
+        revoked: false,
+    };
+    VESTINGS.save(deps.storage, (&beneficiary, id), &vesting)?;
+
+    let res = Response::new()
+        .add_attribute("action", "create_vesting")
+        .add_attribute("id", id.to_string())
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let vestings = VESTINGS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut amount = Uint128::zero();
+    for (id, mut vesting) in vestings {
+        let vested = vesting.vested(env.block.time);
+        amount += vested - vesting.claimed;
+        vesting.claimed = vested;
+        // fully paid out vestings are dropped
+        if vesting.claimed == vesting.amount {
+            VESTINGS.remove(deps.storage, (&info.sender, id));
+        } else {
+            VESTINGS.save(deps.storage, (&info.sender, id), &vesting)?;
+        }
+    }
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let res = Response::new()
+        .add_message(transfer(&config.token, &info.sender, amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("beneficiary", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    beneficiary: String,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let mut vesting = VESTINGS
+        .may_load(deps.storage, (&beneficiary, id))?
+        .ok_or(ContractError::NothingToRevoke { id })?;
+
+    let vested = vesting.vested(env.block.time);
+    let unvested = vesting.amount - vested;
+    if unvested.is_zero() {
+        return Err(ContractError::NothingToRevoke { id });
+    }
+    vesting.amount = vested;
+    vesting.revoked = true;
+    if vesting.claimed == vesting.amount {
+        VESTINGS.remove(deps.storage, (&beneficiary, id));
+    } else {
+        VESTINGS.save(deps.storage, (&beneficiary, id), &vesting)?;
+    }
+
+    let res = Response::new()
+        .add_message(transfer(&config.token, &config.admin, unvested)?)
+        .add_attribute("action", "revoke")
+        .add_attribute("id", id.to_string())
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("unvested", unvested);
+    Ok(res)
+}
+
+fn transfer(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Vestings {
+            beneficiary,
+            start_after,
+            limit,
+        } => to_json_binary(&query_vestings(deps, env, beneficiary, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token: config.token.into(),
+        admin: config.admin.into(),
+    })
+}
+
+pub fn query_vestings(
+    deps: Deps,
+    env: Env,
+    beneficiary: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VestingsResponse> {
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let vestings = VESTINGS
+        .prefix(&beneficiary)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, vesting) = item?;
+            Ok(VestingInfo {
+                id,
+                amount: vesting.amount,
+                vested: vesting.vested(env.block.time),
+                claimed: vesting.claimed,
+                schedule: vesting.schedule,
+                revoked: vesting.revoked,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(VestingsResponse { vestings })
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("This token is not accepted for this operation")]
+    WrongToken {},
+
+    #[error("Invalid vesting schedule")]
+    InvalidSchedule {},
+
+    #[error("Nothing has vested yet")]
+    NothingToClaim {},
+
+    #[error("Vesting {id} has already been revoked or fully vested")]
+    NothingToRevoke { id: u64 },
+}
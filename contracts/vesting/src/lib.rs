@@ -0,0 +1,14 @@
+/*!
+Vests cw20 tokens, such as cw20-base tokens, to beneficiaries.
+
+The admin funds every vesting with a cw20 `Send` naming the beneficiary and a
+[`Schedule`](state::Schedule). Beneficiaries claim what has vested so far, and the admin
+may revoke a vesting, taking back the part that has not vested yet.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
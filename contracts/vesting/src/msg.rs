@@ -0,0 +1,70 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+
+use crate::state::Schedule;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// cw20 token to vest
+    pub token: String,
+    /// Creates and revokes vestings, and receives revoked tokens
+    pub admin: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Entry point for the admin funding new vestings
+    Receive(Cw20ReceiveMsg),
+    /// Sends the sender everything vested and not claimed yet, over all their vestings
+    Claim {},
+    /// Only for the admin. Ends a vesting, sending the admin the part that has not vested
+    /// yet. The beneficiary can still claim the vested part.
+    Revoke { beneficiary: String, id: u64 },
+}
+
+/// Messages carried in the Cw20ReceiveMsg payload
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Vests the received tokens to `beneficiary` on `schedule`
+    CreateVesting {
+        beneficiary: String,
+        schedule: Schedule,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the vestings of `beneficiary`. Supports pagination.
+    #[returns(VestingsResponse)]
+    Vestings {
+        beneficiary: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub token: String,
+    pub admin: String,
+}
+
+#[cw_serde]
+pub struct VestingInfo {
+    pub id: u64,
+    pub amount: Uint128,
+    /// Vested at the current block, claimed or not
+    pub vested: Uint128,
+    pub claimed: Uint128,
+    pub schedule: Schedule,
+    pub revoked: bool,
+}
+
+#[cw_serde]
+pub struct VestingsResponse {
+    pub vestings: Vec<VestingInfo>,
+}
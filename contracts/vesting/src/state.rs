@@ -0,0 +1,102 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    /// The only cw20 token vested by this contract
+    pub token: Addr,
+    /// Creates and revokes vestings
+    pub admin: Addr,
+}
+
+#[cw_serde]
+pub enum Schedule {
+    /// Everything vests at once at `at`
+    Cliff { at: Timestamp },
+    /// Vests linearly from `start` to `end`, but nothing is released before `cliff`
+    Linear {
+        start: Timestamp,
+        cliff: Timestamp,
+        end: Timestamp,
+    },
+    /// Vests in `count` equal parts, one every `interval` seconds after `start`
+    Periodic {
+        start: Timestamp,
+        interval: u64,
+        count: u32,
+    },
+}
+
+impl Schedule {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Schedule::Cliff { .. } => true,
+            Schedule::Linear { start, cliff, end } => start < end && start <= cliff && cliff <= end,
+            Schedule::Periodic {
+                interval, count, ..
+            } => *interval > 0 && *count > 0,
+        }
+    }
+
+    /// Part of `amount` vested at `now`
+    pub fn vested(&self, amount: Uint128, now: Timestamp) -> Uint128 {
+        match self {
+            Schedule::Cliff { at } => {
+                if now < *at {
+                    Uint128::zero()
+                } else {
+                    amount
+                }
+            }
+            Schedule::Linear { start, cliff, end } => {
+                if now < *cliff {
+                    Uint128::zero()
+                } else if now >= *end {
+                    amount
+                } else {
+                    let elapsed = now.seconds() - start.seconds();
+                    let duration = end.seconds() - start.seconds();
+                    amount.multiply_ratio(elapsed, duration)
+                }
+            }
+            Schedule::Periodic {
+                start,
+                interval,
+                count,
+            } => {
+                if now < *start {
+                    return Uint128::zero();
+                }
+                let periods = ((now.seconds() - start.seconds()) / interval).min(*count as u64);
+                amount.multiply_ratio(periods, *count as u64)
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Vesting {
+    /// Tokens vesting in total. Cut down to the vested part on revocation.
+    pub amount: Uint128,
+    /// Tokens the beneficiary has claimed so far
+    pub claimed: Uint128,
+    pub schedule: Schedule,
+    pub revoked: bool,
+}
+
+impl Vesting {
+    pub fn vested(&self, now: Timestamp) -> Uint128 {
+        if self.revoked {
+            self.amount
+        } else {
+            self.schedule.vested(self.amount, now)
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Vestings by beneficiary and id
+pub const VESTINGS: Map<(&Addr, u64), Vesting> = Map::new("vestings");
+/// Id of the last vesting created
+pub const VESTING_COUNT: Item<u64> = Item::new("vesting_count");
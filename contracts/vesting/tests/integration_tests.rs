@@ -0,0 +1,323 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{to_json_binary, Addr, Empty, Timestamp, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+    use cw20_vesting::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, VestingsResponse};
+    use cw20_vesting::state::Schedule;
+    use cw20_vesting::ContractError;
+
+    #[test]
+    fn schedules_vest() {
+        let at = |seconds| Timestamp::from_seconds(seconds);
+        let amount = Uint128::new(1_000);
+
+        let cliff = Schedule::Cliff { at: at(100) };
+        assert_eq!(cliff.vested(amount, at(99)), Uint128::zero());
+        assert_eq!(cliff.vested(amount, at(100)), amount);
+
+        let linear = Schedule::Linear {
+            start: at(100),
+            cliff: at(200),
+            end: at(1_100),
+        };
+        assert_eq!(linear.vested(amount, at(199)), Uint128::zero());
+        assert_eq!(linear.vested(amount, at(200)), Uint128::new(100));
+        assert_eq!(linear.vested(amount, at(600)), Uint128::new(500));
+        assert_eq!(linear.vested(amount, at(2_000)), amount);
+
+        let periodic = Schedule::Periodic {
+            start: at(100),
+            interval: 10,
+            count: 4,
+        };
+        assert_eq!(periodic.vested(amount, at(109)), Uint128::zero());
+        assert_eq!(periodic.vested(amount, at(125)), Uint128::new(500));
+        assert_eq!(periodic.vested(amount, at(1_000)), amount);
+
+        assert!(!Schedule::Linear {
+            start: at(100),
+            cliff: at(50),
+            end: at(200),
+        }
+        .is_valid());
+        assert!(!Schedule::Periodic {
+            start: at(100),
+            interval: 0,
+            count: 4,
+        }
+        .is_valid());
+    }
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn vesting_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_vesting::contract::execute,
+            cw20_vesting::contract::instantiate,
+            cw20_vesting::contract::query,
+        ))
+    }
+
+    struct Suite {
+        app: App,
+        vesting: Addr,
+        token: Addr,
+        admin: Addr,
+        beneficiary: Addr,
+    }
+
+    impl Suite {
+        fn new() -> Self {
+            let mut app = App::default();
+            let admin = app.api().addr_make("admin");
+            let beneficiary = app.api().addr_make("beneficiary");
+            let cw20_id = app.store_code(cw20_contract());
+            let vesting_id = app.store_code(vesting_contract());
+
+            let token = app
+                .instantiate_contract(
+                    cw20_id,
+                    admin.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![
+                            Cw20Coin {
+                                address: admin.to_string(),
+                                amount: Uint128::new(10_000),
+                            },
+                            Cw20Coin {
+                                address: beneficiary.to_string(),
+                                amount: Uint128::new(10_000),
+                            },
+                        ],
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "token",
+                    None,
+                )
+                .unwrap();
+            let vesting = app
+                .instantiate_contract(
+                    vesting_id,
+                    admin.clone(),
+                    &InstantiateMsg {
+                        token: token.to_string(),
+                        admin: admin.to_string(),
+                    },
+                    &[],
+                    "vesting",
+                    None,
+                )
+                .unwrap();
+
+            Suite {
+                app,
+                vesting,
+                token,
+                admin,
+                beneficiary,
+            }
+        }
+
+        fn now(&self) -> Timestamp {
+            self.app.block_info().time
+        }
+
+        fn advance(&mut self, seconds: u64) {
+            self.app
+                .update_block(|block| block.time = block.time.plus_seconds(seconds));
+        }
+
+        fn create_vesting(
+            &mut self,
+            sender: &Addr,
+            amount: u128,
+            schedule: Schedule,
+        ) -> anyhow::Result<()> {
+            let msg = ReceiveMsg::CreateVesting {
+                beneficiary: self.beneficiary.to_string(),
+                schedule,
+            };
+            self.app.execute_contract(
+                sender.clone(),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.vesting.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_json_binary(&msg).unwrap(),
+                },
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn claim(&mut self) -> anyhow::Result<()> {
+            self.app.execute_contract(
+                self.beneficiary.clone(),
+                self.vesting.clone(),
+                &ExecuteMsg::Claim {},
+                &[],
+            )?;
+            Ok(())
+        }
+
+        fn revoke(&mut self, sender: &Addr, id: u64) -> anyhow::Result<()> {
+            let msg = ExecuteMsg::Revoke {
+                beneficiary: self.beneficiary.to_string(),
+                id,
+            };
+            self.app
+                .execute_contract(sender.clone(), self.vesting.clone(), &msg, &[])?;
+            Ok(())
+        }
+
+        fn token_balance(&self, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    &self.token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn vestings(&self) -> VestingsResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(
+                    &self.vesting,
+                    &QueryMsg::Vestings {
+                        beneficiary: self.beneficiary.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn claim_across_vestings() {
+        let mut suite = Suite::new();
+        let admin = suite.admin.clone();
+        let now = suite.now();
+        let linear = Schedule::Linear {
+            start: now,
+            cliff: now.plus_seconds(100),
+            end: now.plus_seconds(1_000),
+        };
+        suite.create_vesting(&admin, 1_000, linear).unwrap();
+        let periodic = Schedule::Periodic {
+            start: now,
+            interval: 250,
+            count: 4,
+        };
+        suite.create_vesting(&admin, 2_000, periodic).unwrap();
+
+        suite.advance(50);
+        let err = suite.claim().unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+
+        // half of the linear one, two of four periods
+        suite.advance(450);
+        suite.claim().unwrap();
+        assert_eq!(
+            suite.token_balance(&suite.beneficiary),
+            Uint128::new(11_500)
+        );
+        let vestings = suite.vestings().vestings;
+        assert_eq!(vestings.len(), 2);
+        assert_eq!(vestings[0].claimed, Uint128::new(500));
+        assert_eq!(vestings[1].claimed, Uint128::new(1_000));
+
+        // paid out vestings are dropped
+        suite.advance(500);
+        suite.claim().unwrap();
+        assert_eq!(
+            suite.token_balance(&suite.beneficiary),
+            Uint128::new(13_000)
+        );
+        assert!(suite.vestings().vestings.is_empty());
+    }
+
+    #[test]
+    fn revoke_unvested() {
+        let mut suite = Suite::new();
+        let (admin, beneficiary) = (suite.admin.clone(), suite.beneficiary.clone());
+        let now = suite.now();
+        let linear = Schedule::Linear {
+            start: now,
+            cliff: now,
+            end: now.plus_seconds(1_000),
+        };
+        suite.create_vesting(&admin, 1_000, linear).unwrap();
+        suite.advance(400);
+
+        let err = suite.revoke(&beneficiary, 1).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+        suite.revoke(&admin, 1).unwrap();
+        assert_eq!(suite.token_balance(&admin), Uint128::new(9_600));
+        let err = suite.revoke(&admin, 1).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToRevoke { id: 1 }
+        );
+
+        // the vested part stays claimable
+        suite.advance(400);
+        suite.claim().unwrap();
+        assert_eq!(suite.token_balance(&beneficiary), Uint128::new(10_400));
+        assert!(suite.vestings().vestings.is_empty());
+    }
+
+    #[test]
+    fn only_admin_creates_vestings() {
+        let mut suite = Suite::new();
+        let (admin, beneficiary) = (suite.admin.clone(), suite.beneficiary.clone());
+        let schedule = Schedule::Cliff { at: suite.now() };
+
+        let err = suite
+            .create_vesting(&beneficiary, 100, schedule.clone())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+
+        let invalid = Schedule::Periodic {
+            start: suite.now(),
+            interval: 10,
+            count: 0,
+        };
+        let err = suite.create_vesting(&admin, 100, invalid).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidSchedule {}
+        );
+
+        suite.create_vesting(&admin, 100, schedule).unwrap();
+        suite.claim().unwrap();
+        assert_eq!(suite.token_balance(&beneficiary), Uint128::new(10_100));
+    }
+}
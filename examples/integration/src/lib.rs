@@ -0,0 +1,8 @@
+/*!
+End-to-end tests of cw20-base tokens moving between the contracts of this workspace.
+
+The suite in `tests/` runs cw20-base, cw20-escrow, cw20-staking and cw20-vesting together
+in cw-multi-test: tokens reach the other contracts through `Send` hooks and allowances,
+vest, sit in escrow and are unbonded, as a contract integrating them would do. The crate
+itself is empty.
+*/
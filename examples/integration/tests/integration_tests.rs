@@ -0,0 +1,468 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Empty, Uint128, Validator};
+    use cw20::{
+        AllowanceResponse, BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg,
+        TokenInfoResponse,
+    };
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor, StakingInfo};
+    use cw_utils::Duration;
+
+    use cw20_escrow::msg::{CreateMsg, DetailsResponse, ReceiveMsg as EscrowReceiveMsg};
+    use cw20_staking::msg::{ExecuteMsg as StakingExecuteMsg, QueryMsg as StakingQueryMsg};
+    use cw20_vesting::msg::{ReceiveMsg as VestingReceiveMsg, VestingsResponse};
+    use cw20_vesting::state::Schedule;
+
+    const DENOM: &str = "ustake";
+    const UNBONDING_TIME: u64 = 60;
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+
+    fn escrow_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_escrow::contract::execute,
+            cw20_escrow::contract::instantiate,
+            cw20_escrow::contract::query,
+        ))
+    }
+
+    fn staking_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_staking::contract::execute,
+            cw20_staking::contract::instantiate,
+            cw20_staking::contract::query,
+        ))
+    }
+
+    fn vesting_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            cw20_vesting::contract::execute,
+            cw20_vesting::contract::instantiate,
+            cw20_vesting::contract::query,
+        ))
+    }
+
+    /// An app where `admin` holds staking coins, with a validator to delegate them to,
+    /// and an escrow contract
+    struct Suite {
+        app: App,
+        escrow: Addr,
+        admin: Addr,
+        beneficiary: Addr,
+        spender: Addr,
+        arbiter: Addr,
+        recipient: Addr,
+    }
+
+    impl Suite {
+        fn new() -> Self {
+            let api = *App::default().api();
+            let admin = api.addr_make("admin");
+            let validator = api.addr_make("validator").to_string();
+            let mut app = AppBuilder::new().build(|router, api, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &admin, coins(10_000, DENOM))
+                    .unwrap();
+                router
+                    .staking
+                    .setup(
+                        storage,
+                        StakingInfo {
+                            bonded_denom: DENOM.to_string(),
+                            unbonding_time: UNBONDING_TIME,
+                            apr: Decimal::percent(10),
+                        },
+                    )
+                    .unwrap();
+                router
+                    .staking
+                    .add_validator(
+                        api,
+                        storage,
+                        &mock_env().block,
+                        Validator::create(
+                            validator,
+                            Decimal::zero(),
+                            Decimal::one(),
+                            Decimal::one(),
+                        ),
+                    )
+                    .unwrap();
+            });
+            let escrow_id = app.store_code(escrow_contract());
+            let escrow = app
+                .instantiate_contract(
+                    escrow_id,
+                    admin.clone(),
+                    &cw20_escrow::msg::InstantiateMsg {},
+                    &[],
+                    "escrow",
+                    None,
+                )
+                .unwrap();
+
+            Suite {
+                app,
+                escrow,
+                admin,
+                beneficiary: api.addr_make("beneficiary"),
+                spender: api.addr_make("spender"),
+                arbiter: api.addr_make("arbiter"),
+                recipient: api.addr_make("recipient"),
+            }
+        }
+
+        fn instantiate_staking(&mut self) -> Addr {
+            let code_id = self.app.store_code(staking_contract());
+            let validator = self.app.api().addr_make("validator");
+            self.app
+                .instantiate_contract(
+                    code_id,
+                    self.admin.clone(),
+                    &cw20_staking::msg::InstantiateMsg {
+                        name: "Staked token".to_string(),
+                        symbol: "STAKED".to_string(),
+                        decimals: 6,
+                        validator: validator.to_string(),
+                        unbonding_period: Duration::Time(UNBONDING_TIME),
+                    },
+                    &[],
+                    "staking",
+                    None,
+                )
+                .unwrap()
+        }
+
+        fn instantiate_token(&mut self, amount: u128) -> Addr {
+            let code_id = self.app.store_code(cw20_contract());
+            self.app
+                .instantiate_contract(
+                    code_id,
+                    self.admin.clone(),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: self.admin.to_string(),
+                            amount: Uint128::new(amount),
+                        }],
+                        ..cw20_base::msg::InstantiateMsg::default()
+                    },
+                    &[],
+                    "token",
+                    None,
+                )
+                .unwrap()
+        }
+
+        /// A vesting contract for `token`, administered by `admin`
+        fn instantiate_vesting(&mut self, token: &Addr) -> Addr {
+            let code_id = self.app.store_code(vesting_contract());
+            self.app
+                .instantiate_contract(
+                    code_id,
+                    self.admin.clone(),
+                    &cw20_vesting::msg::InstantiateMsg {
+                        token: token.to_string(),
+                        admin: self.admin.to_string(),
+                    },
+                    &[],
+                    "vesting",
+                    None,
+                )
+                .unwrap()
+        }
+
+        fn advance(&mut self, seconds: u64) {
+            self.app
+                .update_block(|block| block.time = block.time.plus_seconds(seconds));
+        }
+
+        fn escrow_msg(&self, id: &str, expires: Option<cw20::Expiration>) -> EscrowReceiveMsg {
+            EscrowReceiveMsg::Create(CreateMsg {
+                id: id.to_string(),
+                arbiter: self.arbiter.to_string(),
+                recipient: self.recipient.to_string(),
+                title: "Deal".to_string(),
+                description: "Payment on delivery".to_string(),
+                expires,
+                cw20_whitelist: None,
+            })
+        }
+
+        fn escrow_details(&self, id: &str) -> DetailsResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(
+                    &self.escrow,
+                    &cw20_escrow::msg::QueryMsg::Details { id: id.to_string() },
+                )
+                .unwrap()
+        }
+
+        fn vestings(&self, vesting: &Addr) -> VestingsResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(
+                    vesting,
+                    &cw20_vesting::msg::QueryMsg::Vestings {
+                        beneficiary: self.beneficiary.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        }
+
+        /// Works for the staking shares too, which answer the cw20 queries
+        fn balance(&self, token: &Addr, addr: &Addr) -> Uint128 {
+            let res: BalanceResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        fn total_supply(&self, token: &Addr) -> Uint128 {
+            let res: TokenInfoResponse = self
+                .app
+                .wrap()
+                .query_wasm_smart(token, &Cw20QueryMsg::TokenInfo {})
+                .unwrap();
+            res.total_supply
+        }
+    }
+
+    #[test]
+    fn staking_shares_vest_and_settle_through_escrow() {
+        let mut suite = Suite::new();
+        let (admin, beneficiary, spender) = (
+            suite.admin.clone(),
+            suite.beneficiary.clone(),
+            suite.spender.clone(),
+        );
+        let staking = suite.instantiate_staking();
+        let vesting = suite.instantiate_vesting(&staking);
+        suite
+            .app
+            .execute_contract(
+                admin.clone(),
+                staking.clone(),
+                &StakingExecuteMsg::Bond {},
+                &coins(1_000, DENOM),
+            )
+            .unwrap();
+        assert_eq!(suite.balance(&staking, &admin), Uint128::new(1_000));
+
+        // the shares reach the vesting contract through its Send hook
+        let now = suite.app.block_info().time;
+        let msg = VestingReceiveMsg::CreateVesting {
+            beneficiary: beneficiary.to_string(),
+            schedule: Schedule::Linear {
+                start: now,
+                cliff: now.plus_seconds(100),
+                end: now.plus_seconds(1_000),
+            },
+        };
+        let send = StakingExecuteMsg::Send {
+            contract: vesting.to_string(),
+            amount: Uint128::new(600),
+            msg: to_json_binary(&msg).unwrap(),
+        };
+        suite
+            .app
+            .execute_contract(admin.clone(), staking.clone(), &send, &[])
+            .unwrap();
+        assert_eq!(suite.balance(&staking, &vesting), Uint128::new(600));
+
+        suite.advance(500);
+        suite
+            .app
+            .execute_contract(
+                beneficiary.clone(),
+                vesting.clone(),
+                &cw20_vesting::msg::ExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap();
+        assert_eq!(suite.balance(&staking, &beneficiary), Uint128::new(300));
+        assert_eq!(
+            suite.vestings(&vesting).vestings[0].claimed,
+            Uint128::new(300)
+        );
+
+        // a spender hands part of the vested shares to an escrow on the beneficiary's behalf
+        let approve = StakingExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(200),
+            expires: None,
+        };
+        suite
+            .app
+            .execute_contract(beneficiary.clone(), staking.clone(), &approve, &[])
+            .unwrap();
+        let send_from = StakingExecuteMsg::SendFrom {
+            owner: beneficiary.to_string(),
+            contract: suite.escrow.to_string(),
+            amount: Uint128::new(200),
+            msg: to_json_binary(&suite.escrow_msg("deal", None)).unwrap(),
+        };
+        suite
+            .app
+            .execute_contract(spender.clone(), staking.clone(), &send_from, &[])
+            .unwrap();
+        assert_eq!(suite.balance(&staking, &beneficiary), Uint128::new(100));
+        let details = suite.escrow_details("deal");
+        assert_eq!(details.source, spender.to_string());
+        assert_eq!(
+            details.cw20_balance,
+            vec![Cw20Coin {
+                address: staking.to_string(),
+                amount: Uint128::new(200),
+            }]
+        );
+        // the allowance is used up
+        let allowance: AllowanceResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &staking,
+                &StakingQueryMsg::Allowance {
+                    owner: beneficiary.to_string(),
+                    spender: spender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(allowance.allowance, Uint128::zero());
+
+        let recipient = suite.recipient.clone();
+        suite
+            .app
+            .execute_contract(
+                suite.arbiter.clone(),
+                suite.escrow.clone(),
+                &cw20_escrow::msg::ExecuteMsg::Approve {
+                    id: "deal".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(suite.balance(&staking, &recipient), Uint128::new(200));
+
+        // and the recipient unbonds them for the staked coins
+        let unbond = StakingExecuteMsg::Unbond {
+            amount: Uint128::new(200),
+        };
+        suite
+            .app
+            .execute_contract(recipient.clone(), staking.clone(), &unbond, &[])
+            .unwrap();
+        suite.advance(UNBONDING_TIME);
+        suite
+            .app
+            .execute_contract(
+                recipient.clone(),
+                staking.clone(),
+                &StakingExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap();
+        let coins = suite.app.wrap().query_balance(&recipient, DENOM).unwrap();
+        assert_eq!(coins.amount, Uint128::new(200));
+        assert_eq!(suite.total_supply(&staking), Uint128::new(800));
+    }
+
+    #[test]
+    fn revoked_vesting_and_expired_escrow_return_tokens() {
+        let mut suite = Suite::new();
+        let (admin, beneficiary) = (suite.admin.clone(), suite.beneficiary.clone());
+        let token = suite.instantiate_token(1_000);
+        let vesting = suite.instantiate_vesting(&token);
+
+        let now = suite.app.block_info().time;
+        let msg = VestingReceiveMsg::CreateVesting {
+            beneficiary: beneficiary.to_string(),
+            schedule: Schedule::Periodic {
+                start: now,
+                interval: 100,
+                count: 4,
+            },
+        };
+        let send = Cw20ExecuteMsg::Send {
+            contract: vesting.to_string(),
+            amount: Uint128::new(400),
+            msg: to_json_binary(&msg).unwrap(),
+        };
+        suite
+            .app
+            .execute_contract(admin.clone(), token.clone(), &send, &[])
+            .unwrap();
+
+        // one period in, the admin takes back the three that have not vested
+        suite.advance(150);
+        let revoke = cw20_vesting::msg::ExecuteMsg::Revoke {
+            beneficiary: beneficiary.to_string(),
+            id: 1,
+        };
+        suite
+            .app
+            .execute_contract(admin.clone(), vesting.clone(), &revoke, &[])
+            .unwrap();
+        assert_eq!(suite.balance(&token, &admin), Uint128::new(900));
+        suite
+            .app
+            .execute_contract(
+                beneficiary.clone(),
+                vesting.clone(),
+                &cw20_vesting::msg::ExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap();
+        assert_eq!(suite.balance(&token, &beneficiary), Uint128::new(100));
+        assert_eq!(suite.balance(&token, &vesting), Uint128::zero());
+
+        // an escrow nobody approved in time goes back to its creator, whoever refunds it
+        let expires = cw20::Expiration::AtTime(suite.app.block_info().time.plus_seconds(50));
+        let send = Cw20ExecuteMsg::Send {
+            contract: suite.escrow.to_string(),
+            amount: Uint128::new(500),
+            msg: to_json_binary(&suite.escrow_msg("late", Some(expires))).unwrap(),
+        };
+        suite
+            .app
+            .execute_contract(admin.clone(), token.clone(), &send, &[])
+            .unwrap();
+        let refund = cw20_escrow::msg::ExecuteMsg::Refund {
+            id: "late".to_string(),
+        };
+        suite
+            .app
+            .execute_contract(beneficiary.clone(), suite.escrow.clone(), &refund, &[])
+            .unwrap_err();
+        suite.advance(50);
+        suite
+            .app
+            .execute_contract(beneficiary.clone(), suite.escrow.clone(), &refund, &[])
+            .unwrap();
+        assert_eq!(suite.balance(&token, &admin), Uint128::new(900));
+        assert_eq!(suite.balance(&token, &suite.escrow), Uint128::zero());
+
+        let held = suite.balance(&token, &admin) + suite.balance(&token, &beneficiary);
+        assert_eq!(held, suite.total_supply(&token));
+    }
+}
@@ -0,0 +1,59 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Response, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::msg::AdminResponse;
+use crate::state::{ADMIN, PENDING_ADMIN};
+
+/// Fails unless `sender` is the token admin. Tokens without an admin reject everyone.
+pub fn assert_admin(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    match ADMIN.may_load(storage)? {
+        Some(admin) if admin == sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Nominates `new_admin`, who only takes over once it accepts, so a mistyped address
+/// can't lock the admin role. None gives up the role for good, along with any nomination.
+pub fn execute_update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_admin: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+
+    let res = Response::new().add_attribute("action", "update_admin");
+    match new_admin {
+        Some(new_admin) => {
+            let nominee = deps.api.addr_validate(&new_admin)?;
+            PENDING_ADMIN.save(deps.storage, &nominee)?;
+            Ok(res.add_attribute("pending_admin", nominee))
+        }
+        None => {
+            ADMIN.remove(deps.storage);
+            PENDING_ADMIN.remove(deps.storage);
+            Ok(res.add_attribute("admin", "none"))
+        }
+    }
+}
+
+pub fn execute_accept_admin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    match PENDING_ADMIN.may_load(deps.storage)? {
+        Some(nominee) if nominee == info.sender => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+
+    PENDING_ADMIN.remove(deps.storage);
+    ADMIN.save(deps.storage, &info.sender)?;
+
+    let res = Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender);
+    Ok(res)
+}
+
+pub fn query_admin(deps: Deps) -> StdResult<AdminResponse> {
+    Ok(AdminResponse {
+        admin: ADMIN.may_load(deps.storage)?,
+        pending: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
@@ -0,0 +1,143 @@
+use cosmwasm_std::{
+    attr, Addr, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult, Storage,
+    SubMsg, SubMsgResult, Uint128,
+};
+use cw20::AllowanceResponse;
+
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::msg::{AllowanceChange, AllowanceNotificationsResponse, Cw20AllowanceChangedMsg};
+use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, ALLOWANCE_NOTIFICATIONS};
+
+pub const ALLOWANCE_NOTIFICATION_REPLY_ID: u64 = 2;
+
+/// Opts the calling spender contract in to, or out of, a `Cw20AllowanceChangedMsg` on
+/// every change of an allowance it holds.
+pub fn execute_set_allowance_notifications(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    if enabled {
+        deps.querier
+            .query_wasm_contract_info(&info.sender)
+            .map_err(|_| ContractError::NotAContract {})?;
+        ALLOWANCE_NOTIFICATIONS.save(deps.storage, &info.sender, &())?;
+    } else {
+        ALLOWANCE_NOTIFICATIONS.remove(deps.storage, &info.sender);
+    }
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "set_allowance_notifications"),
+        attr("spender", info.sender),
+        attr("enabled", enabled.to_string()),
+    ]);
+    Ok(res)
+}
+
+/// Removes up to `limit` expired allowances granted by `owner`. Anyone can prune, as
+/// expired allowances can't be spent anyway.
+pub fn execute_prune_expired_allowances(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    owner: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let expired = ALLOWANCES
+        .prefix(&owner_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, allowance)| {
+                allowance.expires.is_expired(&env.block)
+            })
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "prune_expired_allowances"),
+        attr("owner", owner),
+        attr("pruned", expired.len().to_string()),
+    ]);
+    for (spender, allowance) in expired {
+        ALLOWANCES.remove(deps.storage, (&owner_addr, &spender));
+        ALLOWANCES_SPENDER.remove(deps.storage, (&spender, &owner_addr));
+        #[cfg(feature = "events")]
+        {
+            res = res.add_event(events::allowance(
+                &owner_addr,
+                &spender,
+                &AllowanceResponse::default(),
+            ));
+        }
+        res = res.add_submessages(notification(
+            deps.storage,
+            &owner_addr,
+            &spender,
+            AllowanceChange::Pruned,
+            allowance.allowance,
+            &AllowanceResponse::default(),
+        )?);
+    }
+    Ok(res)
+}
+
+/// The notification of a change by `amount` to the allowance of `spender`, if it opted in.
+/// A failing spender doesn't fail the change, so it can't keep an owner from lowering
+/// its allowance.
+pub fn notification(
+    storage: &dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    change: AllowanceChange,
+    amount: Uint128,
+    allowance: &AllowanceResponse,
+) -> StdResult<Option<SubMsg>> {
+    if !ALLOWANCE_NOTIFICATIONS.has(storage, spender) {
+        return Ok(None);
+    }
+    let msg = Cw20AllowanceChangedMsg {
+        owner: owner.to_string(),
+        change,
+        amount,
+        allowance: allowance.allowance,
+        expires: allowance.expires,
+    }
+    .into_cosmos_msg(spender)?;
+    Ok(Some(SubMsg::reply_on_error(
+        msg,
+        ALLOWANCE_NOTIFICATION_REPLY_ID,
+    )))
+}
+
+/// Records a spender that failed to take its notification, leaving the change in place.
+pub fn reply_allowance_notification(
+    _deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+    };
+    let res = Response::new()
+        .add_attribute("action", "allowance_notification_failed")
+        .add_attribute("error", error);
+    Ok(res)
+}
+
+pub fn query_allowance_notifications(
+    deps: Deps,
+    spender: String,
+) -> StdResult<AllowanceNotificationsResponse> {
+    let spender = deps.api.addr_validate(&spender)?;
+    Ok(AllowanceNotificationsResponse {
+        enabled: ALLOWANCE_NOTIFICATIONS.has(deps.storage, &spender),
+    })
+}
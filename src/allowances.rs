@@ -2,10 +2,44 @@ use cosmwasm_std::{
     attr, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
     Storage, Uint128,
 };
-use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
+use cw20::{AllowanceResponse, Cw20Coin, Cw20ReceiveMsg, Expiration};
 
+#[cfg(feature = "allowance-hooks")]
+use crate::allowance_hooks::notification;
+use crate::balances::{spend_balance, transfer_balance};
+#[cfg(feature = "burn-stats")]
+use crate::burn_stats::record_burn;
 use crate::error::ContractError;
-use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_INFO};
+#[cfg(feature = "events")]
+use crate::events;
+use crate::execute::execute_transfer::memo_attributes;
+#[cfg(feature = "allowance-hooks")]
+use crate::msg::AllowanceChange;
+#[cfg(feature = "allowance-limits")]
+use crate::msg::AllowanceLimitResponse;
+#[cfg(feature = "allowance-locks")]
+use crate::msg::AllowanceLockResponse;
+#[cfg(feature = "supply-log")]
+use crate::msg::SupplyAction;
+use crate::msg::{Cw20ApprovalMsg, Cw20ReceiveFromMsg};
+#[cfg(feature = "send-allowlist")]
+use crate::send_allowlist::assert_send_allowed;
+#[cfg(feature = "allowance-locks")]
+use crate::state::ALLOWANCE_LOCKS;
+#[cfg(feature = "allowance-limits")]
+use crate::state::{AllowanceLimit, AllowanceWindow, ALLOWANCE_LIMITS, ALLOWANCE_WINDOWS};
+use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER};
+use crate::storage::Cw20Storage;
+use crate::supply::decrease_supply;
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+#[cfg(feature = "supply-log")]
+use crate::supply_log;
+use crate::tokenfactory;
+
+/// Time an owner has to wait between asking to lift an allowance lock and the lock ending
+#[cfg(feature = "allowance-locks")]
+pub const ALLOWANCE_UNLOCK_DELAY: u64 = 24 * 60 * 60;
 
 pub fn execute_increase_allowance(
     deps: DepsMut,
@@ -31,18 +65,48 @@ pub fn execute_increase_allowance(
         val.allowance += amount;
         Ok(val)
     };
-    ALLOWANCES.update(deps.storage, (&info.sender, &spender_addr), update_fn)?;
-    ALLOWANCES_SPENDER.update(deps.storage, (&spender_addr, &info.sender), update_fn)?;
+    let allowance = ALLOWANCES.update(deps.storage, (&info.sender, &spender_addr), update_fn)?;
+    // copied from the owner map, the one complete while a migration indexes spenders
+    ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &info.sender), &allowance)?;
 
     let res = Response::new().add_attributes(vec![
         attr("action", "increase_allowance"),
-        attr("owner", info.sender),
+        attr("owner", &info.sender),
         attr("spender", spender),
         attr("amount", amount),
     ]);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::allowance(&info.sender, &spender_addr, &allowance));
+    #[cfg(feature = "allowance-hooks")]
+    let res = res.add_submessages(notification(
+        deps.storage,
+        &info.sender,
+        &spender_addr,
+        AllowanceChange::Increased,
+        amount,
+        &allowance,
+    )?);
     Ok(res)
 }
 
+/// Increases the allowance like IncreaseAllowance, then hands `msg` to the spender
+/// contract so it can pull the tokens in the same transaction.
+pub fn execute_increase_allowance_and_send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender_contract: String,
+    amount: Uint128,
+    msg: Binary,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let owner = info.sender.to_string();
+    let res =
+        execute_increase_allowance(deps, env, info, spender_contract.clone(), amount, expires)?;
+    let hook = Cw20ApprovalMsg { owner, amount, msg }.into_cosmos_msg(spender_contract)?;
+    Ok(res.add_message(hook))
+}
+
 pub fn execute_decrease_allowance(
     deps: DepsMut,
     env: Env,
@@ -64,7 +128,9 @@ pub fn execute_decrease_allowance(
 
     // load value and delete if it hits 0, or update otherwise
     let mut allowance = ALLOWANCES.load(deps.storage, key)?;
-    if amount < allowance.allowance {
+    #[cfg(feature = "allowance-hooks")]
+    let decreased = amount.min(allowance.allowance);
+    let allowance = if amount < allowance.allowance {
         // update the new amount
         allowance.allowance = allowance
             .allowance
@@ -78,20 +144,169 @@ pub fn execute_decrease_allowance(
         }
         ALLOWANCES.save(deps.storage, key, &allowance)?;
         ALLOWANCES_SPENDER.save(deps.storage, reverse(key), &allowance)?;
+        allowance
     } else {
         ALLOWANCES.remove(deps.storage, key);
         ALLOWANCES_SPENDER.remove(deps.storage, reverse(key));
-    }
+        AllowanceResponse::default()
+    };
 
     let res = Response::new().add_attributes(vec![
         attr("action", "decrease_allowance"),
-        attr("owner", info.sender),
+        attr("owner", &info.sender),
         attr("spender", spender),
         attr("amount", amount),
     ]);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::allowance(&info.sender, &spender_addr, &allowance));
+    #[cfg(feature = "allowance-hooks")]
+    let res = res.add_submessages(notification(
+        deps.storage,
+        &info.sender,
+        &spender_addr,
+        AllowanceChange::Decreased,
+        decreased,
+        &allowance,
+    )?);
+    #[cfg(not(any(feature = "events", feature = "allowance-hooks")))]
+    let _ = allowance;
+    Ok(res)
+}
+
+#[cfg(feature = "allowance-locks")]
+pub fn execute_lock_allowances(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // locking again also cancels a pending unlock
+    ALLOWANCE_LOCKS.save(deps.storage, &info.sender, &None)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "lock_allowances"),
+        attr("owner", info.sender),
+    ]);
+    Ok(res)
+}
+
+#[cfg(feature = "allowance-locks")]
+pub fn execute_unlock_allowances(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let unlocks_at = match ALLOWANCE_LOCKS.may_load(deps.storage, &info.sender)? {
+        Some(None) => env.block.time.plus_seconds(ALLOWANCE_UNLOCK_DELAY),
+        Some(Some(unlocks_at)) => unlocks_at,
+        None => return Err(ContractError::AllowancesNotLocked {}),
+    };
+    ALLOWANCE_LOCKS.save(deps.storage, &info.sender, &Some(unlocks_at))?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "unlock_allowances"),
+        attr("owner", info.sender),
+        attr("unlocks_at", unlocks_at.to_string()),
+    ]);
+    Ok(res)
+}
+
+#[cfg(feature = "allowance-locks")]
+/// Fails while `owner` has its allowances locked
+pub fn assert_allowances_unlocked(
+    storage: &dyn Storage,
+    owner: &Addr,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    match ALLOWANCE_LOCKS.may_load(storage, owner)? {
+        Some(None) => Err(ContractError::AllowancesLocked {}),
+        Some(Some(unlocks_at)) if block.time < unlocks_at => {
+            Err(ContractError::AllowancesLocked {})
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "allowance-limits")]
+pub fn execute_set_allowance_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    spender: String,
+    limit: Option<AllowanceLimit>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+    let key = (&info.sender, &spender_addr);
+
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "set_allowance_limit"),
+        attr("owner", &info.sender),
+        attr("spender", spender),
+    ]);
+    match limit {
+        Some(limit) => {
+            if limit.amount.is_zero() || limit.period == 0 {
+                return Err(ContractError::InvalidAllowanceLimit {});
+            }
+            ALLOWANCE_LIMITS.save(deps.storage, key, &limit)?;
+            res = res.add_attributes(vec![
+                attr("amount", limit.amount),
+                attr("period", limit.period.to_string()),
+            ]);
+        }
+        None => {
+            ALLOWANCE_LIMITS.remove(deps.storage, key);
+            ALLOWANCE_WINDOWS.remove(deps.storage, key);
+            res = res.add_attribute("amount", "None");
+        }
+    }
     Ok(res)
 }
 
+/// Window the spender is in at the current time. A new one opens with the first spend
+/// after the previous one has closed.
+#[cfg(feature = "allowance-limits")]
+fn current_allowance_window(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    key: (&Addr, &Addr),
+    limit: &AllowanceLimit,
+) -> StdResult<AllowanceWindow> {
+    match ALLOWANCE_WINDOWS.may_load(storage, key)? {
+        Some(window) if block.time < window.start.plus_seconds(limit.period) => Ok(window),
+        _ => Ok(AllowanceWindow {
+            start: block.time,
+            spent: Uint128::zero(),
+        }),
+    }
+}
+
+/// Counts `amount` against the window of the owner's cap on the spender, if any, and
+/// fails past the cap
+#[cfg(feature = "allowance-limits")]
+pub fn record_allowance_spend(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let key = (owner, spender);
+    let limit = match ALLOWANCE_LIMITS.may_load(storage, key)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let mut window = current_allowance_window(storage, block, key, &limit)?;
+    window.spent = window.spent.checked_add(amount).map_err(StdError::from)?;
+    if window.spent > limit.amount {
+        return Err(ContractError::AllowanceLimitExceeded {});
+    }
+    ALLOWANCE_WINDOWS.save(storage, key, &window)?;
+    Ok(())
+}
+
 // this can be used to update a lower allowance - call bucket.update with proper keys
 pub fn deduct_allowance(
     storage: &mut dyn Storage,
@@ -100,128 +315,200 @@ pub fn deduct_allowance(
     block: &BlockInfo,
     amount: Uint128,
 ) -> Result<AllowanceResponse, ContractError> {
-    let update_fn = |current: Option<AllowanceResponse>| -> _ {
-        match current {
-            Some(mut a) => {
-                if a.expires.is_expired(block) {
-                    Err(ContractError::Expired {})
-                } else {
-                    // deduct the allowance if enough
-                    a.allowance = a
-                        .allowance
-                        .checked_sub(amount)
-                        .map_err(StdError::overflow)?;
-                    Ok(a)
-                }
-            }
-            None => Err(ContractError::NoAllowance {}),
-        }
-    };
-    ALLOWANCES.update(storage, (owner, spender), update_fn)?;
-    ALLOWANCES_SPENDER.update(storage, (spender, owner), update_fn)
+    #[cfg(feature = "allowance-locks")]
+    assert_allowances_unlocked(storage, owner, block)?;
+    #[cfg(feature = "allowance-limits")]
+    record_allowance_spend(storage, owner, spender, block, amount)?;
+    Cw20Storage::DEFAULT.deduct_allowance(storage, block, owner, spender, amount)
 }
 
 pub fn execute_transfer_from(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     owner: String,
     recipient: String,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
-    BALANCES.update(
-        deps.storage,
-        &owner_addr,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
-    )?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
+    let effects = transfer_balance(&mut deps, &env, &owner_addr, &rcpt_addr, amount)?;
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "transfer_from"),
-        attr("from", owner),
-        attr("to", recipient),
-        attr("by", info.sender),
-        attr("amount", amount),
-    ]);
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "transfer_from"),
+            attr("from", owner),
+            attr("to", recipient),
+            attr("by", &info.sender),
+            attr("amount", amount),
+        ])
+        .add_attributes(memo)
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::allowance(&owner_addr, &info.sender, &allowance));
+    #[cfg(not(feature = "events"))]
+    let _ = allowance;
     Ok(res)
 }
 
-pub fn execute_burn_from(
+/// Transfers the spender's whole remaining allowance from owner, as it stands when the
+/// message executes, so settlements don't fail over dust
+pub fn execute_transfer_from_all(
     deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let allowance = Cw20Storage::DEFAULT.allowance(deps.storage, &owner_addr, &info.sender)?;
+    execute_transfer_from(deps, env, info, owner, recipient, allowance.allowance, None)
+}
+
+pub fn execute_burn_from(
+    mut deps: DepsMut,
 
     env: Env,
     info: MessageInfo,
     owner: String,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
     // lower balance
-    BALANCES.update(
+    spend_balance(&mut deps, &env, &owner_addr, amount)?;
+    // reduce total_supply
+    decrease_supply(deps.storage, &owner_addr, amount, env.block.height)?;
+    #[cfg(feature = "supply-log")]
+    supply_log::record(
         deps.storage,
+        &env.block,
+        SupplyAction::Burn,
+        &info.sender,
         &owner_addr,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
+        amount,
     )?;
-    // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
-        meta.total_supply = meta.total_supply.checked_sub(amount)?;
-        Ok(meta)
-    })?;
+    #[cfg(feature = "burn-stats")]
+    record_burn(deps.storage, &owner_addr, amount)?;
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "burn_from"),
-        attr("from", owner),
-        attr("by", info.sender),
-        attr("amount", amount),
-    ]);
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "burn_from"),
+            attr("from", owner),
+            attr("by", &info.sender),
+            attr("amount", amount),
+        ])
+        .add_attributes(memo)
+        .add_messages(tokenfactory::burn_msgs(deps.storage, &env, amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::burn_msgs(deps.storage, &owner_addr, amount)?);
+    #[cfg(feature = "events")]
+    let res = res
+        .add_event(events::burn(deps.storage, &owner_addr, amount)?)
+        .add_event(events::allowance(&owner_addr, &info.sender, &allowance));
+    #[cfg(not(feature = "events"))]
+    let _ = allowance;
     Ok(res)
 }
 
+pub fn execute_burn_from_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owners: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "burn_from_batch"),
+        attr("by", info.sender.clone()),
+    ]);
+
+    let mut total = Uint128::zero();
+    for owner in owners {
+        let owner_addr = deps.api.addr_validate(&owner.address)?;
+        let allowance = deduct_allowance(
+            deps.storage,
+            &owner_addr,
+            &info.sender,
+            &env.block,
+            owner.amount,
+        )?;
+        spend_balance(&mut deps, &env, &owner_addr, owner.amount)?;
+        decrease_supply(deps.storage, &owner_addr, owner.amount, env.block.height)?;
+        #[cfg(feature = "supply-log")]
+        supply_log::record(
+            deps.storage,
+            &env.block,
+            SupplyAction::Burn,
+            &info.sender,
+            &owner_addr,
+            owner.amount,
+        )?;
+        #[cfg(feature = "burn-stats")]
+        record_burn(deps.storage, &owner_addr, owner.amount)?;
+        #[cfg(feature = "events")]
+        {
+            res = res
+                .add_event(events::burn(deps.storage, &owner_addr, owner.amount)?)
+                .add_event(events::allowance(&owner_addr, &info.sender, &allowance));
+        }
+        #[cfg(not(feature = "events"))]
+        let _ = allowance;
+        #[cfg(feature = "supply-hooks")]
+        {
+            res = res.add_submessages(supply_hooks::burn_msgs(
+                deps.storage,
+                &owner_addr,
+                owner.amount,
+            )?);
+        }
+        total = total
+            .checked_add(owner.amount)
+            .map_err(StdError::overflow)?;
+        res = res.add_attributes(vec![
+            attr("from", owner.address),
+            attr("amount", owner.amount),
+        ]);
+    }
+
+    Ok(res.add_messages(tokenfactory::burn_msgs(deps.storage, &env, total)?))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_send_from(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     owner: String,
     contract: String,
     amount: Uint128,
     msg: Binary,
+    memo: Option<String>,
+    with_owner: bool,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     let rcpt_addr = deps.api.addr_validate(&contract)?;
+    #[cfg(feature = "send-allowlist")]
+    assert_send_allowed(deps.storage, &rcpt_addr)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
     // move the tokens to the contract
-    BALANCES.update(
-        deps.storage,
-        &owner_addr,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
-    )?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
+    let effects = transfer_balance(&mut deps, &env, &owner_addr, &rcpt_addr, amount)?;
 
     let attrs = vec![
         attr("action", "send_from"),
@@ -232,22 +519,91 @@ pub fn execute_send_from(
     ];
 
     // create a send message
-    let msg = Cw20ReceiveMsg {
-        sender: info.sender.into(),
-        amount,
-        msg,
-    }
-    .into_cosmos_msg(contract)?;
+    let msg = if with_owner {
+        Cw20ReceiveFromMsg {
+            sender: info.sender.to_string(),
+            owner,
+            amount: effects.received,
+            msg,
+        }
+        .into_cosmos_msg(contract)?
+    } else {
+        Cw20ReceiveMsg {
+            sender: info.sender.to_string(),
+            amount: effects.received,
+            msg,
+        }
+        .into_cosmos_msg(contract)?
+    };
 
-    let res = Response::new().add_message(msg).add_attributes(attrs);
+    let res = Response::new()
+        .add_submessages(effects.messages)
+        .add_message(msg)
+        .add_attributes(attrs)
+        .add_attributes(memo)
+        .add_attributes(effects.attributes)
+        .add_events(effects.events);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::allowance(&owner_addr, &info.sender, &allowance));
+    #[cfg(not(feature = "events"))]
+    let _ = allowance;
     Ok(res)
 }
 
 pub fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
     let owner_addr = deps.api.addr_validate(&owner)?;
     let spender_addr = deps.api.addr_validate(&spender)?;
-    let allowance = ALLOWANCES
-        .may_load(deps.storage, (&owner_addr, &spender_addr))?
-        .unwrap_or_default();
-    Ok(allowance)
-}
\ No newline at end of file
+    Cw20Storage::DEFAULT.allowance(deps.storage, &owner_addr, &spender_addr)
+}
+
+#[cfg(feature = "allowance-locks")]
+pub fn query_allowance_lock(
+    deps: Deps,
+    env: Env,
+    owner: String,
+) -> StdResult<AllowanceLockResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let res = match ALLOWANCE_LOCKS.may_load(deps.storage, &owner_addr)? {
+        Some(None) => AllowanceLockResponse {
+            locked: true,
+            unlocks_at: None,
+        },
+        Some(Some(unlocks_at)) if env.block.time < unlocks_at => AllowanceLockResponse {
+            locked: true,
+            unlocks_at: Some(unlocks_at),
+        },
+        _ => AllowanceLockResponse {
+            locked: false,
+            unlocks_at: None,
+        },
+    };
+    Ok(res)
+}
+
+#[cfg(feature = "allowance-limits")]
+pub fn query_allowance_limit(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    spender: String,
+) -> StdResult<AllowanceLimitResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let key = (&owner_addr, &spender_addr);
+    let limit = match ALLOWANCE_LIMITS.may_load(deps.storage, key)? {
+        Some(limit) => limit,
+        None => {
+            return Ok(AllowanceLimitResponse {
+                limit: None,
+                remaining: None,
+                window_end: None,
+            })
+        }
+    };
+    let window = current_allowance_window(deps.storage, &env.block, key, &limit)?;
+    Ok(AllowanceLimitResponse {
+        remaining: Some(limit.amount.saturating_sub(window.spent)),
+        window_end: Some(window.start.plus_seconds(limit.period)),
+        limit: Some(limit),
+    })
+}
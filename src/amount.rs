@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{StdError, StdResult, Uint128};
+
+/// What to do with the digits past the precision kept
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Drops them
+    Down,
+    /// Rounds up if any of them isn't zero
+    Up,
+    /// Rounds up from a half
+    HalfUp,
+}
+
+/// An amount in base units shown in whole tokens of `decimals` decimals, without
+/// trailing zeros: 12_500_000 with 6 decimals is "12.5"
+pub fn format_amount(amount: Uint128, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, frac) = digits.split_at(digits.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{frac}")
+    }
+}
+
+/// Like [`format_amount`], but with at most `precision` decimals shown
+pub fn format_amount_rounded(
+    amount: Uint128,
+    decimals: u8,
+    precision: u8,
+    rounding: Rounding,
+) -> StdResult<String> {
+    if precision >= decimals {
+        return Ok(format_amount(amount, decimals));
+    }
+    let divisor = Uint128::new(10).checked_pow((decimals - precision).into())?;
+    let rest = amount % divisor;
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => !rest.is_zero(),
+        Rounding::HalfUp => rest >= divisor - rest,
+    };
+    let mut kept = amount / divisor;
+    if round_up {
+        kept = kept.checked_add(Uint128::one())?;
+    }
+    Ok(format_amount(kept, precision))
+}
+
+/// An amount in whole tokens, such as "12.5", in base units of a token of `decimals`
+/// decimals. Fails if it has more decimals than the token.
+pub fn parse_amount(amount: &str, decimals: u8) -> StdResult<Uint128> {
+    parse(amount, decimals, None)
+}
+
+/// Like [`parse_amount`], but rounds off the decimals the token doesn't have
+pub fn parse_amount_rounded(amount: &str, decimals: u8, rounding: Rounding) -> StdResult<Uint128> {
+    parse(amount, decimals, Some(rounding))
+}
+
+fn parse(amount: &str, decimals: u8, rounding: Option<Rounding>) -> StdResult<Uint128> {
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(frac) || amount.ends_with('.') {
+        return Err(StdError::generic_err(format!("Invalid amount: {amount}")));
+    }
+
+    let decimals = decimals as usize;
+    let (kept, rest) = frac.split_at(frac.len().min(decimals));
+    let round_up = match rounding {
+        _ if rest.bytes().all(|b| b == b'0') => false,
+        None => {
+            let msg = format!("Amount {amount} has more than {decimals} decimals");
+            return Err(StdError::generic_err(msg));
+        }
+        Some(Rounding::Down) => false,
+        Some(Rounding::Up) => true,
+        Some(Rounding::HalfUp) => rest.as_bytes()[0] >= b'5',
+    };
+
+    let scale = Uint128::new(10).checked_pow(decimals as u32)?;
+    let kept = format!("{kept:0<decimals$}");
+    let frac = if kept.is_empty() {
+        Uint128::zero()
+    } else {
+        Uint128::from_str(&kept)?
+    };
+    let mut total = Uint128::from_str(whole)?
+        .checked_mul(scale)?
+        .checked_add(frac)?;
+    if round_up {
+        total = total.checked_add(Uint128::one())?;
+    }
+    Ok(total)
+}
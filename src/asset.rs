@@ -0,0 +1,105 @@
+use std::fmt;
+
+use cosmwasm_std::{
+    coins, Addr, Api, BankMsg, CosmosMsg, CustomQuery, QuerierWrapper, StdError, StdResult, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Contract, Cw20ExecuteMsg, Cw20QueryMsg};
+
+use crate::msg::{Asset, AssetInfo};
+
+impl AssetInfo {
+    pub fn native(denom: impl Into<String>) -> Self {
+        AssetInfo::Native {
+            denom: denom.into(),
+        }
+    }
+
+    pub fn cw20(contract_addr: &Addr) -> Self {
+        AssetInfo::Cw20 {
+            contract_addr: contract_addr.to_string(),
+        }
+    }
+
+    /// The same asset, failing on an empty denom or an invalid token address
+    pub fn validate(&self, api: &dyn Api) -> StdResult<AssetInfo> {
+        match self {
+            AssetInfo::Native { denom } if denom.is_empty() => {
+                Err(StdError::generic_err("Denom must not be empty"))
+            }
+            AssetInfo::Native { denom } => Ok(AssetInfo::native(denom)),
+            AssetInfo::Cw20 { contract_addr } => {
+                Ok(AssetInfo::cw20(&api.addr_validate(contract_addr)?))
+            }
+        }
+    }
+
+    /// How much `addr` holds of the asset
+    pub fn query_balance<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        addr: &Addr,
+    ) -> StdResult<Uint128> {
+        match self {
+            AssetInfo::Native { denom } => Ok(querier.query_balance(addr, denom)?.amount),
+            AssetInfo::Cw20 { contract_addr } => {
+                let msg = Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                };
+                let res: BalanceResponse = querier.query_wasm_smart(contract_addr, &msg)?;
+                Ok(res.balance)
+            }
+        }
+    }
+
+    /// Message sending `amount` of the asset from this contract to `recipient`
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        match self {
+            AssetInfo::Native { denom } => Ok(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), denom),
+            }
+            .into()),
+            AssetInfo::Cw20 { contract_addr } => {
+                Cw20Contract(Addr::unchecked(contract_addr)).call(Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })
+            }
+        }
+    }
+}
+
+/// The denom or the token address
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfo::Native { denom } => write!(f, "{denom}"),
+            AssetInfo::Cw20 { contract_addr } => write!(f, "{contract_addr}"),
+        }
+    }
+}
+
+impl Asset {
+    pub fn new(info: AssetInfo, amount: impl Into<Uint128>) -> Self {
+        Asset {
+            info,
+            amount: amount.into(),
+        }
+    }
+
+    pub fn validate(&self, api: &dyn Api) -> StdResult<Asset> {
+        Ok(Asset::new(self.info.validate(api)?, self.amount))
+    }
+
+    /// Message sending the asset from this contract to `recipient`
+    pub fn transfer_msg(&self, recipient: &Addr) -> StdResult<CosmosMsg> {
+        self.info.transfer_msg(recipient, self.amount)
+    }
+}
+
+/// The amount followed by the denom or the token address, like native coins
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
@@ -0,0 +1,112 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::{AutoBurnInfoResponse, InstantiateAutoBurn};
+use crate::state::{AutoBurnConfig, AUTO_BURN, AUTO_BURNED, AUTO_BURN_EXEMPT};
+use crate::supply::{decrease_supply, increase_supply};
+
+const MAX_BASIS_POINTS: u16 = 10_000;
+
+pub fn setup_auto_burn(
+    deps: &mut DepsMut,
+    config: InstantiateAutoBurn,
+) -> Result<(), ContractError> {
+    if config.basis_points > MAX_BASIS_POINTS {
+        return Err(ContractError::InvalidBasisPoints {});
+    }
+    AUTO_BURN.save(
+        deps.storage,
+        &AutoBurnConfig {
+            basis_points: config.basis_points,
+        },
+    )?;
+    AUTO_BURNED.save(deps.storage, &Uint128::zero())?;
+    for addr in config.exempt {
+        let addr = deps.api.addr_validate(&addr)?;
+        AUTO_BURN_EXEMPT.save(deps.storage, &addr, &())?;
+    }
+    Ok(())
+}
+
+pub fn execute_update_auto_burn_exempt(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if !AUTO_BURN.exists(deps.storage) {
+        return Err(ContractError::AutoBurnDisabled {});
+    }
+
+    for addr in &add {
+        let addr = deps.api.addr_validate(addr)?;
+        AUTO_BURN_EXEMPT.save(deps.storage, &addr, &())?;
+    }
+    for addr in &remove {
+        let addr = deps.api.addr_validate(addr)?;
+        AUTO_BURN_EXEMPT.remove(deps.storage, &addr);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_auto_burn_exempt")
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(","));
+    Ok(res)
+}
+
+/// Burns the configured slice of a transfer out of the total supply and returns the burned amount.
+/// Transfers from or to an exempt address are not taxed.
+pub fn apply(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<Uint128> {
+    let config = match AUTO_BURN.may_load(storage)? {
+        Some(config) => config,
+        None => return Ok(Uint128::zero()),
+    };
+    if AUTO_BURN_EXEMPT.has(storage, from) || AUTO_BURN_EXEMPT.has(storage, to) {
+        return Ok(Uint128::zero());
+    }
+
+    let burned = amount.multiply_ratio(config.basis_points, MAX_BASIS_POINTS);
+    if burned.is_zero() {
+        return Ok(burned);
+    }
+    decrease_supply(storage, from, burned, height)?;
+    AUTO_BURNED.update(storage, |total| -> StdResult<_> {
+        Ok(total.checked_add(burned)?)
+    })?;
+    Ok(burned)
+}
+
+/// Puts back `burned` tokens `apply` burned from a transfer by `from`
+pub fn revert(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    burned: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    increase_supply(storage, from, burned, height)?;
+    AUTO_BURNED.update(storage, |total| -> StdResult<_> {
+        Ok(total.checked_sub(burned)?)
+    })?;
+    Ok(())
+}
+
+pub fn query_auto_burn_info(deps: Deps) -> StdResult<AutoBurnInfoResponse> {
+    let basis_points = AUTO_BURN
+        .may_load(deps.storage)?
+        .map(|config| config.basis_points)
+        .unwrap_or_default();
+    let total_burned = AUTO_BURNED.may_load(deps.storage)?.unwrap_or_default();
+    Ok(AutoBurnInfoResponse {
+        basis_points,
+        total_burned,
+    })
+}
@@ -0,0 +1,45 @@
+use cosmwasm_std::{from_json, Addr, Binary, Deps, Env, StdResult, Uint128};
+use cw_storage_plus::Map;
+
+use crate::msg::BalanceProofDataResponse;
+use crate::state::BALANCES_KEY;
+
+/// Store of the wasm module, the one light clients prove contract storage against
+pub const WASM_STORE_NAME: &str = "wasm";
+/// Prefix wasmd puts before the canonical contract address on every key of a contract
+pub const CONTRACT_STORE_PREFIX: u8 = 0x03;
+
+/// What a bridge needs to check an ICS-23 proof of the balance of `address`: the
+/// stored key and value, and where they sit in the wasm store. The proof has to be
+/// queried at the returned height, and checked against the app hash of the block after.
+pub fn query_balance_proof_data(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<BalanceProofDataResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let key = Map::<&Addr, Uint128>::new(BALANCES_KEY).key(&addr).to_vec();
+    let value = deps.storage.get(&key);
+    let balance = value
+        .as_deref()
+        .map(from_json)
+        .transpose()?
+        .unwrap_or_default();
+
+    let contract = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let mut store_key = vec![CONTRACT_STORE_PREFIX];
+    store_key.extend_from_slice(contract.as_slice());
+    store_key.extend_from_slice(&key);
+
+    Ok(BalanceProofDataResponse {
+        address,
+        balance,
+        contract: env.contract.address.into_string(),
+        chain_id: env.block.chain_id,
+        height: env.block.height,
+        store_name: WASM_STORE_NAME.to_string(),
+        key: key.into(),
+        store_key: store_key.into(),
+        value: value.map(Binary::from),
+    })
+}
@@ -0,0 +1,328 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    attr, Addr, Attribute, DepsMut, Env, Event, StdResult, Storage, SubMsg, Uint128,
+};
+
+#[cfg(feature = "auto-burn")]
+use crate::auto_burn;
+#[cfg(feature = "burn-sink")]
+use crate::burn_sink;
+#[cfg(feature = "changelog")]
+use crate::changelog;
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+#[cfg(feature = "supply-log")]
+use crate::msg::SupplyAction;
+use crate::restrictions;
+#[cfg(feature = "rewards")]
+use crate::rewards;
+#[cfg(feature = "sampling")]
+use crate::sampling;
+use crate::state::BALANCES;
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+#[cfg(feature = "supply-log")]
+use crate::supply_log;
+use crate::tokenfactory;
+#[cfg(feature = "transfer-limits")]
+use crate::transfer_limits;
+#[cfg(feature = "votes")]
+use crate::votes;
+
+/// Side effects of a transfer that the calling handler has to add to its response
+#[derive(Debug, Default)]
+pub struct TransferEffects {
+    /// Amount actually credited to the recipient
+    pub received: Uint128,
+    /// Amount the burn sink burned
+    pub sunk: Uint128,
+    pub auto_burned: Uint128,
+    pub attributes: Vec<Attribute>,
+    pub messages: Vec<SubMsg>,
+    /// Typed events, only with the "events" feature
+    pub events: Vec<Event>,
+}
+
+impl TransferEffects {
+    /// What `revert_transfer` needs to undo the transfer of `amount` from `from` to `to`
+    /// that had these effects
+    pub fn reversal(&self, from: &Addr, to: &Addr, amount: Uint128) -> Reversal {
+        Reversal {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            received: self.received,
+            sunk: self.sunk,
+            auto_burned: self.auto_burned,
+        }
+    }
+}
+
+/// A transfer to undo, and where its amount went
+#[cw_serde]
+pub struct Reversal {
+    pub from: Addr,
+    pub to: Addr,
+    pub amount: Uint128,
+    pub received: Uint128,
+    pub sunk: Uint128,
+    pub auto_burned: Uint128,
+}
+
+pub fn add_balance(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<Uint128> {
+    #[cfg(feature = "rewards")]
+    rewards::settle(storage, addr)?;
+    let balance = BALANCES.update(
+        storage,
+        addr,
+        height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_add(amount)?)
+        },
+    )?;
+    #[cfg(feature = "sampling")]
+    sampling::increase_weight(storage, addr, amount)?;
+    #[cfg(feature = "votes")]
+    votes::increase_power(storage, addr, amount, height)?;
+    #[cfg(feature = "changelog")]
+    changelog::record_change(storage, addr, height)?;
+    Ok(balance)
+}
+
+pub fn sub_balance(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<Uint128> {
+    #[cfg(feature = "rewards")]
+    rewards::settle(storage, addr)?;
+    let balance = BALANCES.update(
+        storage,
+        addr,
+        height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    #[cfg(feature = "sampling")]
+    sampling::decrease_weight(storage, addr, amount)?;
+    #[cfg(feature = "votes")]
+    votes::decrease_power(storage, addr, amount, height)?;
+    #[cfg(feature = "changelog")]
+    changelog::record_change(storage, addr, height)?;
+    Ok(balance)
+}
+
+/// Debits tokens an account is spending, enforcing every restriction on outgoing funds.
+pub fn spend_balance(
+    deps: &mut DepsMut,
+    env: &Env,
+    addr: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    restrictions::assert_spendable(deps.as_ref(), env, addr, amount)?;
+    Ok(sub_balance(deps.storage, addr, amount, env.block.height)?)
+}
+
+/// Moves tokens between two holders, applying every transfer-time rule of the token.
+pub fn transfer_balance(
+    deps: &mut DepsMut,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<TransferEffects, ContractError> {
+    restrictions::assert_transferable(deps.as_ref(), env, from, to, amount)?;
+    #[cfg(feature = "transfer-limits")]
+    transfer_limits::record_transfer(deps.storage, env, from, amount)?;
+    move_balance(deps, env, from, to, amount)
+}
+
+/// Puts tokens `from` sends to `to` in escrow on the contract's own account, checking the
+/// transfer as if it went to `to` right away. `release_escrow` pays them out later.
+pub fn escrow_balance(
+    deps: &mut DepsMut,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    restrictions::assert_transferable(deps.as_ref(), env, from, to, amount)?;
+    #[cfg(feature = "transfer-limits")]
+    transfer_limits::record_transfer(deps.storage, env, from, amount)?;
+    sub_balance(deps.storage, from, amount, env.block.height)?;
+    add_balance(
+        deps.storage,
+        &env.contract.address,
+        amount,
+        env.block.height,
+    )?;
+    Ok(())
+}
+
+/// Pays out tokens `from` escrowed for `to`. The receiving side of the transfer is checked
+/// again, as it may have changed since, and the transfer-time burns apply.
+pub fn release_escrow(
+    deps: &mut DepsMut,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<TransferEffects, ContractError> {
+    restrictions::assert_receivable(deps.as_ref(), from, to, amount)?;
+    pay_out_escrow(deps, env, to, amount)
+}
+
+/// Pays out escrowed tokens to `to`, once the receiving side is checked
+pub fn pay_out_escrow(
+    deps: &mut DepsMut,
+    env: &Env,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<TransferEffects, ContractError> {
+    move_balance(deps, env, &env.contract.address, to, amount)
+}
+
+/// The balance updates and burns of a transfer whose checks have passed
+fn move_balance(
+    deps: &mut DepsMut,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<TransferEffects, ContractError> {
+    sub_balance(deps.storage, from, amount, env.block.height)?;
+
+    // a transfer to the burn sink burns all of it, so there is nothing left to auto-burn
+    #[cfg(feature = "burn-sink")]
+    let sunk = burn_sink::apply(deps.storage, from, to, amount, env.block.height)?;
+    #[cfg(not(feature = "burn-sink"))]
+    let sunk = Uint128::zero();
+    #[cfg(feature = "auto-burn")]
+    let auto_burned = auto_burn::apply(deps.storage, from, to, amount - sunk, env.block.height)?;
+    #[cfg(not(feature = "auto-burn"))]
+    let auto_burned = Uint128::zero();
+    let received = amount - sunk - auto_burned;
+
+    let mut effects = TransferEffects {
+        received,
+        sunk,
+        auto_burned,
+        ..TransferEffects::default()
+    };
+    if sunk.is_zero() {
+        add_balance(deps.storage, to, received, env.block.height)?;
+        #[cfg(feature = "events")]
+        effects
+            .events
+            .push(events::transfer(deps.storage, from, to, received)?);
+    }
+    for (key, burned) in [("burned", sunk), ("auto_burned", auto_burned)] {
+        if burned.is_zero() {
+            continue;
+        }
+        effects.attributes.push(attr(key, burned));
+        #[cfg(feature = "events")]
+        effects
+            .events
+            .push(events::burn(deps.storage, from, burned)?);
+        #[cfg(feature = "supply-log")]
+        supply_log::record(
+            deps.storage,
+            &env.block,
+            SupplyAction::Burn,
+            from,
+            from,
+            burned,
+        )?;
+        let burn_msgs = tokenfactory::burn_msgs(deps.storage, env, burned)?;
+        effects
+            .messages
+            .extend(burn_msgs.into_iter().map(SubMsg::new));
+        #[cfg(feature = "supply-hooks")]
+        effects
+            .messages
+            .extend(supply_hooks::burn_msgs(deps.storage, from, burned)?);
+    }
+    Ok(effects)
+}
+
+/// Undoes a transfer, giving the sender back all of it: what the recipient got, and what
+/// the transfer burned, minted anew. It no longer counts against the sender's transfer
+/// limit either. The rules on transfers don't apply, as this is no transfer of its own.
+pub fn revert_transfer(
+    deps: &mut DepsMut,
+    env: &Env,
+    reversal: &Reversal,
+) -> StdResult<TransferEffects> {
+    let Reversal {
+        from,
+        to,
+        amount,
+        received,
+        sunk,
+        auto_burned,
+    } = reversal;
+    let height = env.block.height;
+    sub_balance(deps.storage, to, *received, height)?;
+    add_balance(deps.storage, from, *amount, height)?;
+    #[cfg(feature = "transfer-limits")]
+    transfer_limits::release_transfer(deps.storage, env, from, *amount)?;
+
+    let mut effects = TransferEffects {
+        received: *amount,
+        ..TransferEffects::default()
+    };
+    #[cfg(feature = "events")]
+    if !received.is_zero() {
+        effects
+            .events
+            .push(events::transfer(deps.storage, to, from, *received)?);
+    }
+    #[cfg(feature = "burn-sink")]
+    if !sunk.is_zero() {
+        burn_sink::revert(deps.storage, from, *sunk, height)?;
+    }
+    #[cfg(feature = "auto-burn")]
+    if !auto_burned.is_zero() {
+        auto_burn::revert(deps.storage, from, *auto_burned, height)?;
+    }
+    for (key, burned) in [("reminted", *sunk), ("auto_reminted", *auto_burned)] {
+        if burned.is_zero() {
+            continue;
+        }
+        effects.attributes.push(attr(key, burned));
+        #[cfg(feature = "events")]
+        effects
+            .events
+            .push(events::mint(deps.storage, from, burned)?);
+        #[cfg(feature = "supply-log")]
+        supply_log::record(
+            deps.storage,
+            &env.block,
+            SupplyAction::Mint,
+            &env.contract.address,
+            from,
+            burned,
+        )?;
+        let mint_msgs = tokenfactory::mint_msgs(deps.storage, env, burned)?;
+        effects
+            .messages
+            .extend(mint_msgs.into_iter().map(SubMsg::new));
+        #[cfg(feature = "supply-hooks")]
+        effects.messages.extend(supply_hooks::mint_msgs(
+            deps.storage,
+            &env.contract.address,
+            from,
+            burned,
+        )?);
+    }
+    Ok(effects)
+}
@@ -0,0 +1,70 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::admin::assert_admin;
+#[cfg(feature = "burn-stats")]
+use crate::burn_stats::{record_burn, revert_burn};
+use crate::error::ContractError;
+use crate::msg::BurnSinkResponse;
+use crate::state::BURN_SINK;
+use crate::supply::{decrease_supply, increase_supply};
+
+/// Makes transfers to `address` burn the tokens from now on
+pub fn setup_burn_sink(deps: &mut DepsMut, address: &str) -> StdResult<()> {
+    let address = deps.api.addr_validate(address)?;
+    BURN_SINK.save(deps.storage, &address)
+}
+
+/// Burns a transfer out of the total supply if it goes to the burn sink, and returns the
+/// burned amount. Transfers to any other address are left alone.
+pub fn apply(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<Uint128> {
+    if BURN_SINK.may_load(storage)?.as_ref() != Some(to) {
+        return Ok(Uint128::zero());
+    }
+    decrease_supply(storage, from, amount, height)?;
+    #[cfg(feature = "burn-stats")]
+    record_burn(storage, from, amount)?;
+    Ok(amount)
+}
+
+/// Puts back `amount` tokens `apply` burned from a transfer by `from`
+pub fn revert(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    increase_supply(storage, from, amount, height)?;
+    #[cfg(feature = "burn-stats")]
+    revert_burn(storage, from, amount)?;
+    Ok(())
+}
+
+pub fn execute_update_burn_sink(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    match &address {
+        Some(address) => setup_burn_sink(&mut deps, address)?,
+        None => BURN_SINK.remove(deps.storage),
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_burn_sink")
+        .add_attribute("address", address.unwrap_or_else(|| "None".to_string()));
+    Ok(res)
+}
+
+pub fn query_burn_sink(deps: Deps) -> StdResult<BurnSinkResponse> {
+    Ok(BurnSinkResponse {
+        address: BURN_SINK.may_load(deps.storage)?,
+    })
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Storage, Uint128};
+
+use crate::msg::BurnedByResponse;
+use crate::state::BURNED_BY;
+
+/// Adds `amount` to the tokens `addr` burned of its own so far
+pub fn record_burn(storage: &mut dyn Storage, addr: &Addr, amount: Uint128) -> StdResult<()> {
+    BURNED_BY.update(storage, addr, |burned| -> StdResult<_> {
+        Ok(burned.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+/// Takes back `amount` of the tokens `addr` burned, for a burn that was undone
+pub fn revert_burn(storage: &mut dyn Storage, addr: &Addr, amount: Uint128) -> StdResult<()> {
+    BURNED_BY.update(storage, addr, |burned| -> StdResult<_> {
+        Ok(burned.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    Ok(())
+}
+
+pub fn query_burned_by(deps: Deps, address: String) -> StdResult<BurnedByResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let burned = BURNED_BY.may_load(deps.storage, &addr)?.unwrap_or_default();
+    Ok(BurnedByResponse { burned })
+}
@@ -0,0 +1,52 @@
+use cosmwasm_std::{Addr, Deps, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::msg::{BalanceChange, ChangedBalancesResponse};
+use crate::state::{BALANCE_CHANGES, LAST_BALANCE_CHANGE};
+
+/// Records that the balance of `addr` changed at `height`. Only its latest change is
+/// kept, so the log never holds more entries than there are accounts.
+pub fn record_change(storage: &mut dyn Storage, addr: &Addr, height: u64) -> StdResult<()> {
+    if let Some(previous) = LAST_BALANCE_CHANGE.may_load(storage, addr)? {
+        if previous == height {
+            return Ok(());
+        }
+        BALANCE_CHANGES.remove(storage, (previous, addr));
+    }
+    BALANCE_CHANGES.save(storage, (height, addr), &())?;
+    LAST_BALANCE_CHANGE.save(storage, addr, &height)
+}
+
+/// Accounts whose balance last changed at `height` or later, oldest change first
+pub fn query_changed_balances_since(
+    deps: Deps,
+    height: u64,
+    start_after: Option<BalanceChange>,
+    limit: Option<u32>,
+) -> StdResult<ChangedBalancesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|change| -> StdResult<_> {
+            Ok((change.height, deps.api.addr_validate(&change.address)?))
+        })
+        .transpose()?;
+    // sorts before every address changed at `height`
+    let lowest = Addr::unchecked("");
+    let start = match &start_after {
+        Some((height, addr)) => Bound::exclusive((*height, addr)),
+        None => Bound::inclusive((height, &lowest)),
+    };
+
+    let changes = BALANCE_CHANGES
+        .keys(deps.storage, Some(start), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(height, address)| BalanceChange {
+                height,
+                address: address.into(),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(ChangedBalancesResponse { changes })
+}
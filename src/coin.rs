@@ -0,0 +1,60 @@
+use cosmwasm_std::{Api, StdError, StdResult};
+use cw20::{Cw20Coin, Cw20CoinVerified};
+
+use crate::amount::format_amount;
+
+/// What cw20 leaves out of its coin types. The traits stand in for `Add`, `Sub` and
+/// `TryFrom`, which can't be implemented here for types of another crate.
+pub trait Cw20CoinExt {
+    /// The coin with its token address validated
+    fn verify(&self, api: &dyn Api) -> StdResult<Cw20CoinVerified>;
+}
+
+impl Cw20CoinExt for Cw20Coin {
+    fn verify(&self, api: &dyn Api) -> StdResult<Cw20CoinVerified> {
+        Ok(Cw20CoinVerified {
+            address: api.addr_validate(&self.address)?,
+            amount: self.amount,
+        })
+    }
+}
+
+pub trait Cw20CoinVerifiedExt: Sized {
+    /// Sum of two amounts of the same token, failing for different tokens or on overflow
+    fn checked_add(&self, other: &Self) -> StdResult<Self>;
+    /// Difference of two amounts of the same token, failing for different tokens or on
+    /// underflow
+    fn checked_sub(&self, other: &Self) -> StdResult<Self>;
+    /// The amount in whole tokens of `decimals` decimals, followed by the token address
+    fn to_string_with_decimals(&self, decimals: u8) -> String;
+}
+
+impl Cw20CoinVerifiedExt for Cw20CoinVerified {
+    fn checked_add(&self, other: &Self) -> StdResult<Self> {
+        assert_same_token(self, other)?;
+        Ok(Cw20CoinVerified {
+            address: self.address.clone(),
+            amount: self.amount.checked_add(other.amount)?,
+        })
+    }
+
+    fn checked_sub(&self, other: &Self) -> StdResult<Self> {
+        assert_same_token(self, other)?;
+        Ok(Cw20CoinVerified {
+            address: self.address.clone(),
+            amount: self.amount.checked_sub(other.amount)?,
+        })
+    }
+
+    fn to_string_with_decimals(&self, decimals: u8) -> String {
+        format!("{} {}", format_amount(self.amount, decimals), self.address)
+    }
+}
+
+fn assert_same_token(a: &Cw20CoinVerified, b: &Cw20CoinVerified) -> StdResult<()> {
+    if a.address != b.address {
+        let msg = format!("Cannot combine {} and {} tokens", a.address, b.address);
+        return Err(StdError::generic_err(msg));
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::{CanTransferResponse, ComplianceQueryMsg};
+use crate::state::TRANSFER_RESTRICTION_CONTRACT;
+
+/// Asks the configured compliance contract whether the transfer may happen, and fails
+/// with its reason if not. Passes when no contract is configured.
+pub fn assert_can_transfer(
+    deps: Deps,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let contract = match TRANSFER_RESTRICTION_CONTRACT.may_load(deps.storage)? {
+        Some(contract) => contract,
+        None => return Ok(()),
+    };
+    let res: CanTransferResponse = deps.querier.query_wasm_smart(
+        contract,
+        &ComplianceQueryMsg::CanTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+        },
+    )?;
+    if !res.allowed {
+        return Err(ContractError::TransferRestricted {
+            reason: res.reason.unwrap_or_default(),
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_update_transfer_restriction_contract(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    match &contract {
+        Some(contract) => {
+            let contract = deps.api.addr_validate(contract)?;
+            TRANSFER_RESTRICTION_CONTRACT.save(deps.storage, &contract)?;
+        }
+        None => TRANSFER_RESTRICTION_CONTRACT.remove(deps.storage),
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_transfer_restriction_contract")
+        .add_attribute("contract", contract.unwrap_or_else(|| "None".to_string()));
+    Ok(res)
+}
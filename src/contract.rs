@@ -1,34 +1,204 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    Uint128,
 };
 
 use cw2::{ensure_from_older_version, set_contract_version};
-use cw20::{
-    BalanceResponse, Cw20Coin, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
-    MarketingInfoResponse, MinterResponse, TokenInfoResponse,
-};
+use cw20::{BalanceResponse, Cw20Coin, MarketingInfoResponse, MinterResponse, TokenInfoResponse};
+#[cfg(feature = "marketing")]
+use cw20::{DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo};
 
+use crate::admin::{execute_accept_admin, execute_update_admin, query_admin};
 use crate::allowances::{
-    execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance,
+    execute_burn_from, execute_burn_from_batch, execute_decrease_allowance,
+    execute_increase_allowance, execute_increase_allowance_and_send, execute_send_from,
+    execute_transfer_from, execute_transfer_from_all, query_allowance,
+};
+#[cfg(feature = "allowance-hooks")]
+use crate::allowance_hooks::{
+    execute_prune_expired_allowances, execute_set_allowance_notifications,
+    query_allowance_notifications, reply_allowance_notification, ALLOWANCE_NOTIFICATION_REPLY_ID,
+};
+#[cfg(feature = "allowance-limits")]
+use crate::allowances::{execute_set_allowance_limit, query_allowance_limit};
+#[cfg(feature = "allowance-locks")]
+use crate::allowances::{execute_lock_allowances, execute_unlock_allowances, query_allowance_lock};
+#[cfg(feature = "auto-burn")]
+use crate::auto_burn::{
+    execute_update_auto_burn_exempt, query_auto_burn_info, setup_auto_burn,
+};
+#[cfg(feature = "balance-proofs")]
+use crate::balance_proofs::query_balance_proof_data;
+use crate::balances::add_balance;
+#[cfg(feature = "burn-sink")]
+use crate::burn_sink::{execute_update_burn_sink, query_burn_sink, setup_burn_sink};
+#[cfg(feature = "burn-stats")]
+use crate::burn_stats::query_burned_by;
+#[cfg(feature = "changelog")]
+use crate::changelog::query_changed_balances_since;
+#[cfg(feature = "compliance")]
+use crate::compliance::execute_update_transfer_restriction_contract;
+#[cfg(feature = "cranks")]
+use crate::cranks::{
+    crank_of, execute_update_crank_params, query_crank_status, query_keeper_runs, record_crank,
+};
+#[cfg(feature = "enumerable")]
+use crate::enumerable::{
+    query_account_count, query_accounts_page, query_all_accounts, query_allowance_count,
+    query_allowances_expiring_before, query_enumeration_limit, query_owner_allowances,
+    query_owner_allowances_page, query_spender_allowance_count, query_spender_allowances,
+    query_spender_allowances_page, query_verify_invariants, set_enumeration_limit,
 };
-use crate::enumerable::{query_all_accounts, query_owner_allowances, query_spender_allowances};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+#[cfg(feature = "events")]
+use crate::events::{events_only, strip_legacy_attributes};
+#[cfg(feature = "exchange-rate")]
+use crate::exchange_rate::{
+    execute_update_exchange_rate, query_exchange_rate, query_shares, setup_exchange_rate,
+    to_underlying,
+};
+#[cfg(feature = "extended-metadata")]
+use crate::extended_metadata::{execute_remove_metadata, execute_set_metadata, query_metadata};
+#[cfg(feature = "faucet")]
+use crate::faucet::{execute_faucet_mint, execute_update_faucet, query_faucet_quota, setup_faucet};
+use crate::interfaces::{
+    query_contract_info, query_features, query_supported_interfaces, register_interfaces,
+};
+#[cfg(feature = "launch-guard")]
+use crate::launch_guard::{execute_enable_trading, query_trading_status, setup_launch_guard};
+#[cfg(feature = "logo-info")]
+use crate::logo_info::query_logo_info;
+#[cfg(feature = "marketing-admins")]
+use crate::marketing_admins::{
+    execute_accept_marketing_admin, execute_update_marketing_admins, query_marketing_admins,
+};
+#[cfg(feature = "max-wallet")]
+use crate::max_wallet::{execute_update_max_wallet, query_max_wallet, setup_max_wallet};
+#[cfg(feature = "meta-tx")]
+use crate::meta_tx::{
+    execute_cancel_authorization, execute_set_signing_key, execute_signed,
+    execute_transfer_with_authorization, query_authorization_state, query_signing_info,
+};
+use crate::migrations::{
+    execute_process_migration, pending_steps, query_migration_status, run_steps, stored_version,
+};
+#[cfg(feature = "contract-guard")]
+use crate::contract_guard::execute_update_reject_contract_transfers;
+#[cfg(feature = "min-transfer")]
+use crate::min_transfer::{execute_update_min_transfer_amount, query_min_transfer_amount};
+#[cfg(feature = "mint-timelock")]
+use crate::mint_timelock::{
+    execute_cancel_mint, execute_execute_mint, execute_propose_mint, query_pending_mints,
+};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, MintingRenouncedResponse, MirrorDenomResponse,
+    QueryMsg, SymbolValidation,
+};
+#[cfg(feature = "multi-token")]
+use crate::multi_token::{
+    execute_create_token, execute_mint_multi, execute_transfer_multi, query_multi_balance,
+    query_multi_token_info, query_multi_tokens,
+};
+#[cfg(feature = "rebrand")]
+use crate::rebrand::execute_update_token_info;
+#[cfg(feature = "rescale-decimals")]
+use crate::rescale::{
+    assert_not_rescaling, execute_continue_migration, query_rescale_status, start_rescale,
+};
+use crate::restrictions::{query_detect_transfer_restriction, query_spendable_balance};
+#[cfg(feature = "rewards")]
+use crate::rewards::{
+    execute_distribute_rewards, execute_receive, execute_withdraw_rewards, query_pending_rewards,
+    setup_rewards,
+};
+#[cfg(feature = "sampling")]
+use crate::sampling::{enable_sampling, query_sample_holders};
+#[cfg(feature = "scheduled")]
+use crate::scheduled::{execute_matured, execute_schedule_transfer, query_scheduled_transfers};
+#[cfg(feature = "send-allowlist")]
+use crate::send_allowlist::{
+    execute_update_send_allowlist, query_send_allowlist, setup_send_allowlist,
+};
+#[cfg(feature = "snapshots")]
+use crate::snapshots::{execute_record_snapshot, query_balance_at_snapshot, query_snapshots};
+#[cfg(feature = "mintable")]
+use crate::state::MinterData;
+#[cfg(feature = "events")]
+use crate::state::EVENTS_ONLY;
+#[cfg(feature = "marketing")]
+use crate::state::LOGO;
+#[cfg(feature = "mint-timelock")]
+use crate::state::MINT_TIMELOCK;
+#[cfg(feature = "min-transfer")]
+use crate::state::MIN_TRANSFER_AMOUNT;
+#[cfg(feature = "contract-guard")]
+use crate::state::REJECT_CONTRACT_TRANSFERS;
+#[cfg(feature = "rebrand")]
+use crate::state::TOKEN_INFO_FROZEN;
+#[cfg(feature = "compliance")]
+use crate::state::TRANSFER_RESTRICTION_CONTRACT;
 use crate::state::{
-    MinterData, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO, MARKETING_INFO,
-    TOKEN_INFO,
+    TokenMetadata, ADMIN, MARKETING_INFO, MINTER, MINTING_RENOUNCED, MIRROR_DENOM,
+    SYMBOL_VALIDATION, TOKEN_METADATA, TOTAL_SUPPLY,
+};
+#[cfg(feature = "state-export")]
+use crate::state_export::{
+    execute_finish_import, execute_import_state, query_export_state, setup_import,
+};
+use crate::storage::Cw20Storage;
+#[cfg(feature = "streams")]
+use crate::streams::{
+    execute_create_stream, execute_withdraw_from_stream, query_stream, query_streams_by_recipient,
+    query_streams_by_sender,
+};
+#[cfg(feature = "subscriptions")]
+use crate::subscriptions::{
+    execute_cancel_subscription, execute_charge, execute_subscribe, query_subscription,
+};
+#[cfg(feature = "supply-shards")]
+use crate::supply::enable_shards;
+use crate::supply::query_total_supply;
+#[cfg(feature = "snapshots")]
+use crate::supply::query_total_supply_at;
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks::{
+    execute_update_supply_hooks, query_supply_hooks, reply_supply_hook, SUPPLY_HOOK_REPLY_ID,
+};
+#[cfg(feature = "supply-log")]
+use crate::supply_log::{query_supply_change_log, setup_supply_log};
+use crate::tokenfactory;
+#[cfg(feature = "tokenfactory")]
+use crate::tokenfactory::{execute_unwrap, execute_wrap};
+#[cfg(feature = "transfer-limits")]
+use crate::transfer_limits::{
+    execute_update_transfer_limit, query_transfer_quota, setup_transfer_limit,
+};
+#[cfg(feature = "vesting")]
+use crate::vesting::execute_mint_locked;
+#[cfg(feature = "votes")]
+use crate::votes::{
+    execute_delegate, query_total_power_at, query_total_power_at_height, query_voting_power_at,
+    query_voting_power_at_height,
 };
 
-pub use crate::execute::execute_transfer::execute_transfer;
-pub use crate::execute::execute_burn::execute_burn;
-pub use crate::execute::execute_mint::execute_mint;
-pub use crate::execute::execute_send::execute_send;
+pub use crate::execute::execute_transfer::{execute_transfer, execute_transfer_all};
+pub use crate::execute::execute_burn::{execute_burn, execute_burn_all};
+#[cfg(feature = "mintable")]
+pub use crate::execute::execute_mint::{execute_mint, execute_mint_batch};
+#[cfg(feature = "mintable")]
+pub use crate::execute::execute_renounce_minting::execute_renounce_minting;
+#[cfg(feature = "rescue")]
+pub use crate::execute::execute_rescue::execute_rescue;
+pub use crate::execute::execute_send::{execute_send, reply_safe_send, SAFE_SEND_REPLY_ID};
+#[cfg(feature = "mintable")]
+pub use crate::execute::execute_update_cap::execute_update_cap;
+#[cfg(feature = "mintable")]
 pub use crate::execute::execute_update_minter::execute_update_minter;
+#[cfg(feature = "marketing")]
 pub use crate::execute::execute_update_marketing::{execute_update_marketing};
+#[cfg(feature = "marketing")]
 pub use crate::execute::execute_upload_logo::{execute_upload_logo, verify_logo};
 
 // version info for migration info
@@ -38,15 +208,24 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // check valid token info
     msg.validate()?;
+    #[cfg(feature = "sampling")]
+    if msg.holder_sampling {
+        enable_sampling(deps.storage)?;
+    }
     // create initial accounts
-    let total_supply = create_accounts(&mut deps, &msg.initial_balances)?;
+    let total_supply = create_accounts(
+        &mut deps,
+        &env,
+        &msg.initial_balances,
+        msg.merge_initial_balances,
+    )?;
 
     if let Some(limit) = msg.get_cap() {
         if total_supply > limit {
@@ -54,24 +233,57 @@ pub fn instantiate(
         }
     }
 
-    let mint = match msg.mint {
-        Some(m) => Some(MinterData {
+    #[cfg(feature = "mintable")]
+    if let Some(m) = msg.mint {
+        let minter = MinterData {
             minter: deps.api.addr_validate(&m.minter)?,
             cap: m.cap,
-        }),
-        None => None,
-    };
+            cap_can_only_decrease: msg.cap_can_only_decrease,
+        };
+        MINTER.save(deps.storage, &minter)?;
+    }
+    #[cfg(not(feature = "mintable"))]
+    if msg.mint.is_some() {
+        return Err(ContractError::MintableDisabled {});
+    }
 
     // store token info
-    let data = TokenInfo {
+    let data = TokenMetadata {
         name: msg.name,
         symbol: msg.symbol,
         decimals: msg.decimals,
-        total_supply,
-        mint,
     };
-    TOKEN_INFO.save(deps.storage, &data)?;
+    TOKEN_METADATA.save(deps.storage, &data)?;
+    TOTAL_SUPPLY.save(deps.storage, &total_supply, env.block.height)?;
+    if msg.symbol_validation != SymbolValidation::Strict {
+        SYMBOL_VALIDATION.save(deps.storage, &msg.symbol_validation)?;
+    }
+
+    if let Some(admin) = msg.admin {
+        ADMIN.save(deps.storage, &deps.api.addr_validate(&admin)?)?;
+    }
+    #[cfg(feature = "rebrand")]
+    if msg.freeze_token_info {
+        TOKEN_INFO_FROZEN.save(deps.storage, &true)?;
+    }
+    #[cfg(feature = "mint-timelock")]
+    if let Some(timelock) = msg.mint_timelock {
+        MINT_TIMELOCK.save(deps.storage, &timelock)?;
+    }
+    #[cfg(feature = "supply-shards")]
+    if let Some(count) = msg.supply_shards {
+        enable_shards(deps.storage, count)?;
+    }
+    #[cfg(feature = "supply-log")]
+    if let Some(capacity) = msg.supply_log_capacity {
+        setup_supply_log(deps.storage, capacity)?;
+    }
+    #[cfg(feature = "enumerable")]
+    if let Some(max_limit) = msg.enumeration_limit {
+        set_enumeration_limit(deps.storage, max_limit)?;
+    }
 
+    #[cfg(feature = "marketing")]
     if let Some(marketing) = msg.marketing {
         let logo = if let Some(logo) = marketing.logo {
             verify_logo(&logo)?;
@@ -96,20 +308,101 @@ pub fn instantiate(
         };
         MARKETING_INFO.save(deps.storage, &data)?;
     }
+    #[cfg(not(feature = "marketing"))]
+    if msg.marketing.is_some() {
+        return Err(ContractError::MarketingDisabled {});
+    }
+
+    #[cfg(feature = "auto-burn")]
+    if let Some(auto_burn) = msg.auto_burn {
+        setup_auto_burn(&mut deps, auto_burn)?;
+    }
+    #[cfg(feature = "burn-sink")]
+    if let Some(burn_sink) = msg.burn_sink {
+        setup_burn_sink(&mut deps, &burn_sink)?;
+    }
+    #[cfg(feature = "send-allowlist")]
+    if let Some(contracts) = msg.send_allowlist {
+        setup_send_allowlist(&mut deps, contracts)?;
+    }
+    #[cfg(feature = "compliance")]
+    if let Some(contract) = msg.transfer_restriction_contract {
+        let contract = deps.api.addr_validate(&contract)?;
+        TRANSFER_RESTRICTION_CONTRACT.save(deps.storage, &contract)?;
+    }
+    #[cfg(feature = "transfer-limits")]
+    if let Some(limit) = msg.transfer_limit {
+        setup_transfer_limit(deps.storage, &limit)?;
+    }
+    #[cfg(feature = "min-transfer")]
+    if let Some(amount) = msg.min_transfer_amount {
+        MIN_TRANSFER_AMOUNT.save(deps.storage, &amount)?;
+    }
+    #[cfg(feature = "contract-guard")]
+    if msg.reject_contract_transfers {
+        REJECT_CONTRACT_TRANSFERS.save(deps.storage, &true)?;
+    }
+    #[cfg(feature = "faucet")]
+    if let Some(faucet) = msg.faucet {
+        setup_faucet(deps.storage, &faucet)?;
+    }
+    #[cfg(feature = "max-wallet")]
+    if let Some(max_wallet) = msg.max_wallet {
+        setup_max_wallet(&mut deps, max_wallet)?;
+    }
+    #[cfg(feature = "launch-guard")]
+    if let Some(allowlist) = msg.launch_allowlist {
+        setup_launch_guard(&mut deps, allowlist)?;
+    }
+    #[cfg(feature = "rewards")]
+    if let Some(asset) = msg.rewards {
+        setup_rewards(&mut deps, &env, asset)?;
+    }
+    #[cfg(feature = "exchange-rate")]
+    if let Some(exchange_rate) = msg.exchange_rate {
+        setup_exchange_rate(&mut deps, &env, exchange_rate)?;
+    }
+    #[cfg(feature = "events")]
+    if msg.events_only {
+        EVENTS_ONLY.save(deps.storage, &true)?;
+    }
+    #[cfg(feature = "state-export")]
+    if msg.import_state {
+        setup_import(&mut deps, &info.sender)?;
+    }
+    #[cfg(not(feature = "state-export"))]
+    let _ = info;
+
+    register_interfaces(deps.storage)?;
+
+    let mut res = Response::default();
+    if let Some(mirror) = msg.mirror {
+        res = res.add_messages(tokenfactory::setup_mirror(
+            deps.storage,
+            &env,
+            &mirror.subdenom,
+            total_supply,
+        )?);
+    }
 
-    Ok(Response::default())
+    Ok(res)
 }
 
 pub fn create_accounts(
     deps: &mut DepsMut,
+    env: &Env,
     accounts: &[Cw20Coin],
+    merge_duplicates: bool,
 ) -> Result<Uint128, ContractError> {
-    validate_accounts(accounts)?;
+    // balances are added up, so repeated addresses are credited every amount
+    if !merge_duplicates {
+        validate_accounts(accounts)?;
+    }
 
     let mut total_supply = Uint128::zero();
     for row in accounts {
         let address = deps.api.addr_validate(&row.address)?;
-        BALANCES.save(deps.storage, &address, &row.amount)?;
+        add_balance(deps.storage, &address, row.amount, env.block.height)?;
         total_supply += row.amount;
     }
 
@@ -135,22 +428,57 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    match msg {
-        ExecuteMsg::Transfer { recipient, amount } => {
-            execute_transfer(deps, env, info, recipient, amount)
-        }
-        ExecuteMsg::Burn { amount } => execute_burn(deps, env, info, amount),
+    #[cfg(feature = "events")]
+    let events_only = events_only(deps.storage)?;
+    #[cfg(feature = "rescale-decimals")]
+    if !matches!(
+        msg,
+        ExecuteMsg::ContinueMigration { .. } | ExecuteMsg::ProcessMigration { .. }
+    ) {
+        assert_not_rescaling(deps.storage)?;
+    }
+    #[cfg(feature = "cranks")]
+    if let Some((crank, keeper)) = crank_of(&info, &msg) {
+        let keeper = deps.api.addr_validate(&keeper)?;
+        record_crank(deps.storage, &env, crank, &keeper)?;
+    }
+    let res = match msg {
+        ExecuteMsg::Transfer {
+            recipient,
+            amount,
+            memo,
+        } => execute_transfer(deps, env, info, recipient, amount, memo),
+        ExecuteMsg::TransferAll { recipient } => execute_transfer_all(deps, env, info, recipient),
+        ExecuteMsg::Burn { amount, memo } => execute_burn(deps, env, info, amount, memo),
+        ExecuteMsg::BurnAll {} => execute_burn_all(deps, env, info),
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => execute_send(deps, env, info, contract, amount, msg),
+            memo,
+            safe,
+        } => execute_send(deps, env, info, contract, amount, msg, memo, safe),
+        #[cfg(feature = "mintable")]
         ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
             expires,
         } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::IncreaseAllowanceAndSend {
+            spender_contract,
+            amount,
+            msg,
+            expires,
+        } => execute_increase_allowance_and_send(
+            deps,
+            env,
+            info,
+            spender_contract,
+            amount,
+            msg,
+            expires,
+        ),
         ExecuteMsg::DecreaseAllowance {
             spender,
             amount,
@@ -160,40 +488,266 @@ pub fn execute(
             owner,
             recipient,
             amount,
-        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
-        ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
+            memo,
+        } => execute_transfer_from(deps, env, info, owner, recipient, amount, memo),
+        ExecuteMsg::TransferFromAll { owner, recipient } => {
+            execute_transfer_from_all(deps, env, info, owner, recipient)
+        }
+        ExecuteMsg::BurnFrom {
+            owner,
+            amount,
+            memo,
+        } => execute_burn_from(deps, env, info, owner, amount, memo),
         ExecuteMsg::SendFrom {
             owner,
             contract,
             amount,
             msg,
-        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+            memo,
+            with_owner,
+        } => execute_send_from(
+            deps, env, info, owner, contract, amount, msg, memo, with_owner,
+        ),
+        #[cfg(feature = "marketing")]
         ExecuteMsg::UpdateMarketing {
             project,
             description,
             marketing,
         } => execute_update_marketing(deps, env, info, project, description, marketing),
+        #[cfg(feature = "marketing")]
         ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
+        #[cfg(feature = "marketing-admins")]
+        ExecuteMsg::AcceptMarketingAdmin {} => execute_accept_marketing_admin(deps, info),
+        #[cfg(feature = "marketing-admins")]
+        ExecuteMsg::UpdateMarketingAdmins { add, remove } => {
+            execute_update_marketing_admins(deps, info, add, remove)
+        }
+        #[cfg(feature = "rebrand")]
+        ExecuteMsg::UpdateTokenInfo { name, symbol } => {
+            execute_update_token_info(deps, info, name, symbol)
+        }
+        #[cfg(feature = "extended-metadata")]
+        ExecuteMsg::SetMetadata { key, value } => execute_set_metadata(deps, info, key, value),
+        #[cfg(feature = "extended-metadata")]
+        ExecuteMsg::RemoveMetadata { key } => execute_remove_metadata(deps, info, key),
+        #[cfg(feature = "multi-token")]
+        ExecuteMsg::CreateToken {
+            token_id,
+            name,
+            symbol,
+            decimals,
+        } => execute_create_token(deps, env, info, token_id, name, symbol, decimals),
+        #[cfg(feature = "multi-token")]
+        ExecuteMsg::MintMulti { recipient, amounts } => {
+            execute_mint_multi(deps, env, info, recipient, amounts)
+        }
+        #[cfg(feature = "multi-token")]
+        ExecuteMsg::TransferMulti { recipient, amounts } => {
+            execute_transfer_multi(deps, env, info, recipient, amounts)
+        }
+        #[cfg(feature = "mintable")]
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
-    }
+        #[cfg(feature = "supply-hooks")]
+        ExecuteMsg::UpdateSupplyHooks { add, remove } => {
+            execute_update_supply_hooks(deps, env, info, add, remove)
+        }
+        #[cfg(feature = "streams")]
+        ExecuteMsg::CreateStream {
+            recipient,
+            amount,
+            start,
+            end,
+        } => execute_create_stream(deps, env, info, recipient, amount, start, end),
+        #[cfg(feature = "streams")]
+        ExecuteMsg::WithdrawFromStream { id } => execute_withdraw_from_stream(deps, env, info, id),
+        #[cfg(feature = "subscriptions")]
+        ExecuteMsg::Subscribe {
+            spender,
+            amount_per_period,
+            period,
+            expires,
+        } => execute_subscribe(deps, env, info, spender, amount_per_period, period, expires),
+        #[cfg(feature = "subscriptions")]
+        ExecuteMsg::CancelSubscription { spender } => {
+            execute_cancel_subscription(deps, env, info, spender)
+        }
+        #[cfg(feature = "subscriptions")]
+        ExecuteMsg::Charge {
+            owner,
+            amount,
+            recipient,
+        } => execute_charge(deps, env, info, owner, amount, recipient),
+        #[cfg(feature = "scheduled")]
+        ExecuteMsg::ScheduleTransfer {
+            recipient,
+            amount,
+            execute_after,
+        } => execute_schedule_transfer(deps, env, info, recipient, amount, execute_after),
+        #[cfg(feature = "scheduled")]
+        ExecuteMsg::ExecuteMatured { limit } => execute_matured(deps, env, info, limit),
+        ExecuteMsg::BurnFromBatch { owners } => execute_burn_from_batch(deps, env, info, owners),
+        #[cfg(feature = "allowance-hooks")]
+        ExecuteMsg::SetAllowanceNotifications { enabled } => {
+            execute_set_allowance_notifications(deps, env, info, enabled)
+        }
+        #[cfg(feature = "allowance-hooks")]
+        ExecuteMsg::PruneExpiredAllowances { owner, limit } => {
+            execute_prune_expired_allowances(deps, env, info, owner, limit)
+        }
+        #[cfg(feature = "allowance-locks")]
+        ExecuteMsg::LockAllowances {} => execute_lock_allowances(deps, env, info),
+        #[cfg(feature = "allowance-locks")]
+        ExecuteMsg::UnlockAllowances {} => execute_unlock_allowances(deps, env, info),
+        #[cfg(feature = "allowance-limits")]
+        ExecuteMsg::SetAllowanceLimit { spender, limit } => {
+            execute_set_allowance_limit(deps, env, info, spender, limit)
+        }
+        ExecuteMsg::UpdateAdmin { new_admin } => execute_update_admin(deps, info, new_admin),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, info),
+        #[cfg(feature = "snapshots")]
+        ExecuteMsg::RecordSnapshot { label } => execute_record_snapshot(deps, env, info, label),
+        #[cfg(feature = "mintable")]
+        ExecuteMsg::UpdateCap { cap } => execute_update_cap(deps, env, info, cap),
+        #[cfg(feature = "mintable")]
+        ExecuteMsg::RenounceMinting {} => execute_renounce_minting(deps, env, info),
+        #[cfg(feature = "rescue")]
+        ExecuteMsg::Rescue { asset, recipient } => {
+            execute_rescue(deps, env, info, asset, recipient)
+        }
+        #[cfg(feature = "mintable")]
+        ExecuteMsg::MintBatch { recipients } => execute_mint_batch(deps, env, info, recipients),
+        #[cfg(feature = "send-allowlist")]
+        ExecuteMsg::UpdateSendAllowlist { add, remove } => {
+            execute_update_send_allowlist(deps, env, info, add, remove)
+        }
+        #[cfg(feature = "compliance")]
+        ExecuteMsg::UpdateTransferRestrictionContract { contract } => {
+            execute_update_transfer_restriction_contract(deps, env, info, contract)
+        }
+        #[cfg(feature = "transfer-limits")]
+        ExecuteMsg::UpdateTransferLimit { limit } => {
+            execute_update_transfer_limit(deps, env, info, limit)
+        }
+        #[cfg(feature = "cranks")]
+        ExecuteMsg::UpdateCrankParams { crank, params } => {
+            execute_update_crank_params(deps, env, info, crank, params)
+        }
+        #[cfg(feature = "min-transfer")]
+        ExecuteMsg::UpdateMinTransferAmount { amount } => {
+            execute_update_min_transfer_amount(deps, env, info, amount)
+        }
+        #[cfg(feature = "contract-guard")]
+        ExecuteMsg::UpdateRejectContractTransfers { reject } => {
+            execute_update_reject_contract_transfers(deps, env, info, reject)
+        }
+        #[cfg(feature = "faucet")]
+        ExecuteMsg::FaucetMint {} => execute_faucet_mint(deps, env, info),
+        #[cfg(feature = "faucet")]
+        ExecuteMsg::UpdateFaucet { faucet } => execute_update_faucet(deps, env, info, faucet),
+        #[cfg(feature = "burn-sink")]
+        ExecuteMsg::UpdateBurnSink { address } => {
+            execute_update_burn_sink(deps, env, info, address)
+        }
+        #[cfg(feature = "auto-burn")]
+        ExecuteMsg::UpdateAutoBurnExempt { add, remove } => {
+            execute_update_auto_burn_exempt(deps, env, info, add, remove)
+        }
+        #[cfg(feature = "meta-tx")]
+        ExecuteMsg::SetSigningKey { pubkey } => execute_set_signing_key(deps, env, info, pubkey),
+        #[cfg(feature = "meta-tx")]
+        ExecuteMsg::ExecuteSigned {
+            payload,
+            nonce,
+            signature,
+        } => execute_signed(deps, env, info, payload, nonce, signature),
+        #[cfg(feature = "meta-tx")]
+        ExecuteMsg::TransferWithAuthorization {
+            authorization,
+            signature,
+        } => execute_transfer_with_authorization(deps, env, info, authorization, signature),
+        #[cfg(feature = "meta-tx")]
+        ExecuteMsg::CancelAuthorization { nonce } => {
+            execute_cancel_authorization(deps, env, info, nonce)
+        }
+        #[cfg(feature = "exchange-rate")]
+        ExecuteMsg::UpdateExchangeRate { rate } => {
+            execute_update_exchange_rate(deps, env, info, rate)
+        }
+        #[cfg(feature = "launch-guard")]
+        ExecuteMsg::EnableTrading {} => execute_enable_trading(deps, env, info),
+        #[cfg(feature = "max-wallet")]
+        ExecuteMsg::UpdateMaxWallet {
+            amount,
+            add_exempt,
+            remove_exempt,
+        } => execute_update_max_wallet(deps, env, info, amount, add_exempt, remove_exempt),
+        #[cfg(feature = "mint-timelock")]
+        ExecuteMsg::ProposeMint { recipient, amount } => {
+            execute_propose_mint(deps, env, info, recipient, amount)
+        }
+        #[cfg(feature = "mint-timelock")]
+        ExecuteMsg::ExecuteMint { id } => execute_execute_mint(deps, env, info, id),
+        #[cfg(feature = "mint-timelock")]
+        ExecuteMsg::CancelMint { id } => execute_cancel_mint(deps, env, info, id),
+        #[cfg(feature = "vesting")]
+        ExecuteMsg::MintLocked {
+            recipient,
+            amount,
+            schedule,
+        } => execute_mint_locked(deps, env, info, recipient, amount, schedule),
+        #[cfg(feature = "rewards")]
+        ExecuteMsg::DistributeRewards {} => execute_distribute_rewards(deps, env, info),
+        #[cfg(feature = "rewards")]
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+        #[cfg(feature = "rewards")]
+        ExecuteMsg::WithdrawRewards {} => execute_withdraw_rewards(deps, env, info),
+        #[cfg(feature = "votes")]
+        ExecuteMsg::Delegate { delegatee } => execute_delegate(deps, env, info, delegatee),
+        #[cfg(feature = "state-export")]
+        ExecuteMsg::ImportState {
+            balances,
+            allowances,
+        } => execute_import_state(deps, env, info, balances, allowances),
+        #[cfg(feature = "state-export")]
+        ExecuteMsg::FinishImport {} => execute_finish_import(deps, env, info),
+        #[cfg(feature = "rescale-decimals")]
+        ExecuteMsg::ContinueMigration { limit } => {
+            execute_continue_migration(deps, env, info, limit)
+        }
+        ExecuteMsg::ProcessMigration { limit } => execute_process_migration(deps, env, info, limit),
+        #[cfg(feature = "tokenfactory")]
+        ExecuteMsg::Unwrap { amount } => execute_unwrap(deps, env, info, amount),
+        #[cfg(feature = "tokenfactory")]
+        ExecuteMsg::Wrap {} => execute_wrap(deps, env, info),
+    }?;
+    #[cfg(feature = "events")]
+    let res = strip_legacy_attributes(res, events_only);
+    Ok(res)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
+        #[cfg(feature = "balance-proofs")]
+        QueryMsg::BalanceProofData { address } => {
+            to_json_binary(&query_balance_proof_data(deps, env, address)?)
+        }
         QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
+        #[cfg(feature = "mintable")]
         QueryMsg::Minter {} => to_json_binary(&query_minter(deps)?),
         QueryMsg::Allowance { owner, spender } => {
             to_json_binary(&query_allowance(deps, owner, spender)?)
         }
+        #[cfg(feature = "enumerable")]
         QueryMsg::AllAllowances {
             owner,
             start_after,
             limit,
         } => to_json_binary(&query_owner_allowances(deps, owner, start_after, limit)?),
+        #[cfg(feature = "enumerable")]
         QueryMsg::AllSpenderAllowances {
             spender,
             start_after,
@@ -204,49 +758,298 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         )?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::AllowancesExpiringBefore {
+            owner,
+            timestamp_or_height,
+            start_after,
+            limit,
+        } => to_json_binary(&query_allowances_expiring_before(
+            deps,
+            owner,
+            timestamp_or_height,
+            start_after,
+            limit,
+        )?),
+        #[cfg(feature = "enumerable")]
         QueryMsg::AllAccounts { start_after, limit } => {
             to_json_binary(&query_all_accounts(deps, start_after, limit)?)
         }
+        #[cfg(feature = "enumerable")]
+        QueryMsg::AllowancesPage {
+            owner,
+            cursor,
+            limit,
+        } => to_json_binary(&query_owner_allowances_page(deps, owner, cursor, limit)?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::SpenderAllowancesPage {
+            spender,
+            cursor,
+            limit,
+        } => to_json_binary(&query_spender_allowances_page(deps, spender, cursor, limit)?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::AccountsPage { cursor, limit } => {
+            to_json_binary(&query_accounts_page(deps, cursor, limit)?)
+        }
+        #[cfg(feature = "enumerable")]
+        QueryMsg::AllowanceCount { owner } => to_json_binary(&query_allowance_count(deps, owner)?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::SpenderAllowanceCount { spender } => {
+            to_json_binary(&query_spender_allowance_count(deps, spender)?)
+        }
+        #[cfg(feature = "enumerable")]
+        QueryMsg::AccountCount {} => to_json_binary(&query_account_count(deps)?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::EnumerationLimit {} => to_json_binary(&query_enumeration_limit(deps)?),
+        #[cfg(feature = "enumerable")]
+        QueryMsg::VerifyInvariants {
+            start_after,
+            running_total,
+            limit,
+        } => to_json_binary(&query_verify_invariants(
+            deps,
+            start_after,
+            running_total,
+            limit,
+        )?),
+        #[cfg(feature = "marketing")]
         QueryMsg::MarketingInfo {} => to_json_binary(&query_marketing_info(deps)?),
+        #[cfg(feature = "marketing")]
         QueryMsg::DownloadLogo {} => to_json_binary(&query_download_logo(deps)?),
+        #[cfg(feature = "logo-info")]
+        QueryMsg::LogoInfo {} => to_json_binary(&query_logo_info(deps)?),
+        #[cfg(feature = "extended-metadata")]
+        QueryMsg::Metadata { start_after, limit } => {
+            to_json_binary(&query_metadata(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "multi-token")]
+        QueryMsg::MultiTokenInfo { token_id } => {
+            to_json_binary(&query_multi_token_info(deps, token_id)?)
+        }
+        #[cfg(feature = "multi-token")]
+        QueryMsg::MultiBalance { token_id, address } => {
+            to_json_binary(&query_multi_balance(deps, token_id, address)?)
+        }
+        #[cfg(feature = "multi-token")]
+        QueryMsg::MultiTokens { start_after, limit } => {
+            to_json_binary(&query_multi_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::MirrorDenom {} => to_json_binary(&query_mirror_denom(deps)?),
+        #[cfg(feature = "auto-burn")]
+        QueryMsg::AutoBurnInfo {} => to_json_binary(&query_auto_burn_info(deps)?),
+        #[cfg(feature = "burn-stats")]
+        QueryMsg::BurnedBy { address } => to_json_binary(&query_burned_by(deps, address)?),
+        #[cfg(feature = "burn-sink")]
+        QueryMsg::BurnSink {} => to_json_binary(&query_burn_sink(deps)?),
+        #[cfg(feature = "streams")]
+        QueryMsg::Stream { id } => to_json_binary(&query_stream(deps, env, id)?),
+        #[cfg(feature = "streams")]
+        QueryMsg::StreamsBySender {
+            sender,
+            start_after,
+            limit,
+        } => to_json_binary(&query_streams_by_sender(
+            deps,
+            env,
+            sender,
+            start_after,
+            limit,
+        )?),
+        #[cfg(feature = "streams")]
+        QueryMsg::StreamsByRecipient {
+            recipient,
+            start_after,
+            limit,
+        } => to_json_binary(&query_streams_by_recipient(
+            deps,
+            env,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        #[cfg(feature = "sampling")]
+        QueryMsg::SampleHolders { seed, count } => {
+            to_json_binary(&query_sample_holders(deps, env, seed, count)?)
+        }
+        #[cfg(feature = "subscriptions")]
+        QueryMsg::Subscription { owner, spender } => {
+            to_json_binary(&query_subscription(deps, env, owner, spender)?)
+        }
+        QueryMsg::SpendableBalance { address } => {
+            to_json_binary(&query_spendable_balance(deps, env, address)?)
+        }
+        QueryMsg::DetectTransferRestriction {
+            from,
+            to,
+            amount,
+            send,
+        } => to_json_binary(&query_detect_transfer_restriction(
+            deps, env, from, to, amount, send,
+        )?),
+        #[cfg(feature = "snapshots")]
+        QueryMsg::Snapshots { start_after, limit } => {
+            to_json_binary(&query_snapshots(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "snapshots")]
+        QueryMsg::BalanceAtSnapshot { address, label } => {
+            to_json_binary(&query_balance_at_snapshot(deps, address, label)?)
+        }
+        #[cfg(feature = "snapshots")]
+        QueryMsg::TotalSupplyAt { height } => to_json_binary(&query_total_supply_at(deps, height)?),
+        #[cfg(feature = "allowance-hooks")]
+        QueryMsg::AllowanceNotifications { spender } => {
+            to_json_binary(&query_allowance_notifications(deps, spender)?)
+        }
+        #[cfg(feature = "allowance-locks")]
+        QueryMsg::AllowanceLock { owner } => {
+            to_json_binary(&query_allowance_lock(deps, env, owner)?)
+        }
+        #[cfg(feature = "allowance-limits")]
+        QueryMsg::AllowanceLimit { owner, spender } => {
+            to_json_binary(&query_allowance_limit(deps, env, owner, spender)?)
+        }
+        QueryMsg::TotalSupply {} => to_json_binary(&query_total_supply(deps)?),
+        #[cfg(feature = "send-allowlist")]
+        QueryMsg::SendAllowlist { start_after, limit } => {
+            to_json_binary(&query_send_allowlist(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "transfer-limits")]
+        QueryMsg::TransferQuota { address } => {
+            to_json_binary(&query_transfer_quota(deps, env, address)?)
+        }
+        #[cfg(feature = "cranks")]
+        QueryMsg::CrankStatus { crank } => to_json_binary(&query_crank_status(deps, crank)?),
+        #[cfg(feature = "cranks")]
+        QueryMsg::KeeperRuns { keeper, crank } => {
+            to_json_binary(&query_keeper_runs(deps, keeper, crank)?)
+        }
+        #[cfg(feature = "min-transfer")]
+        QueryMsg::MinTransferAmount {} => to_json_binary(&query_min_transfer_amount(deps)?),
+        #[cfg(feature = "faucet")]
+        QueryMsg::FaucetQuota { address } => {
+            to_json_binary(&query_faucet_quota(deps, env, address)?)
+        }
+        #[cfg(feature = "meta-tx")]
+        QueryMsg::SigningInfo { address } => {
+            to_json_binary(&query_signing_info(deps, env, address)?)
+        }
+        #[cfg(feature = "meta-tx")]
+        QueryMsg::AuthorizationState { authorizer, nonce } => {
+            to_json_binary(&query_authorization_state(deps, authorizer, nonce)?)
+        }
+        #[cfg(feature = "changelog")]
+        QueryMsg::ChangedBalancesSince {
+            height,
+            start_after,
+            limit,
+        } => to_json_binary(&query_changed_balances_since(
+            deps,
+            height,
+            start_after,
+            limit,
+        )?),
+        #[cfg(feature = "supply-hooks")]
+        QueryMsg::SupplyHooks {} => to_json_binary(&query_supply_hooks(deps)?),
+        #[cfg(feature = "supply-log")]
+        QueryMsg::SupplyChangeLog { start_after, limit } => {
+            to_json_binary(&query_supply_change_log(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "votes")]
+        QueryMsg::VotingPowerAt { address, height } => {
+            to_json_binary(&query_voting_power_at(deps, address, height)?)
+        }
+        #[cfg(feature = "votes")]
+        QueryMsg::TotalPowerAt { height } => to_json_binary(&query_total_power_at(deps, height)?),
+        #[cfg(feature = "votes")]
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            to_json_binary(&query_voting_power_at_height(deps, env, address, height)?)
+        }
+        #[cfg(feature = "votes")]
+        QueryMsg::TotalPowerAtHeight { height } => {
+            to_json_binary(&query_total_power_at_height(deps, env, height)?)
+        }
+        #[cfg(feature = "exchange-rate")]
+        QueryMsg::ExchangeRate {} => to_json_binary(&query_exchange_rate(deps)?),
+        #[cfg(feature = "exchange-rate")]
+        QueryMsg::Shares { address } => to_json_binary(&query_shares(deps, address)?),
+        #[cfg(feature = "rewards")]
+        QueryMsg::PendingRewards { address } => {
+            to_json_binary(&query_pending_rewards(deps, address)?)
+        }
+        #[cfg(feature = "launch-guard")]
+        QueryMsg::TradingStatus {} => to_json_binary(&query_trading_status(deps)?),
+        #[cfg(feature = "max-wallet")]
+        QueryMsg::MaxWallet { start_after, limit } => {
+            to_json_binary(&query_max_wallet(deps, start_after, limit)?)
+        }
+        QueryMsg::SupportedInterfaces {} => to_json_binary(&query_supported_interfaces(deps)?),
+        QueryMsg::Features {} => to_json_binary(&query_features(deps)?),
+        QueryMsg::Admin {} => to_json_binary(&query_admin(deps)?),
+        QueryMsg::ContractInfo {} => to_json_binary(&query_contract_info(deps)?),
+        QueryMsg::MigrationStatus {} => to_json_binary(&query_migration_status(deps)?),
+        #[cfg(feature = "state-export")]
+        QueryMsg::ExportState {
+            section,
+            start_after,
+            limit,
+        } => to_json_binary(&query_export_state(deps, section, start_after, limit)?),
+        #[cfg(feature = "marketing-admins")]
+        QueryMsg::MarketingAdmins {} => to_json_binary(&query_marketing_admins(deps)?),
+        #[cfg(feature = "mintable")]
+        QueryMsg::MintingRenounced {} => to_json_binary(&query_minting_renounced(deps)?),
+        #[cfg(feature = "mint-timelock")]
+        QueryMsg::PendingMints { start_after, limit } => {
+            to_json_binary(&query_pending_mints(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "scheduled")]
+        QueryMsg::ScheduledTransfers { start_after, limit } => {
+            to_json_binary(&query_scheduled_transfers(deps, start_after, limit)?)
+        }
+        #[cfg(feature = "rescale-decimals")]
+        QueryMsg::RescaleStatus {} => to_json_binary(&query_rescale_status(deps)?),
     }
 }
 
 pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
     let address = deps.api.addr_validate(&address)?;
-    let balance = BALANCES
-        .may_load(deps.storage, &address)?
-        .unwrap_or_default();
+    let balance = Cw20Storage::DEFAULT.balance(deps.storage, &address)?;
+    #[cfg(feature = "exchange-rate")]
+    let balance = to_underlying(deps.storage, balance)?;
     Ok(BalanceResponse { balance })
 }
 
 pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
-    let info = TOKEN_INFO.load(deps.storage)?;
+    let info = TOKEN_METADATA.load(deps.storage)?;
     let res = TokenInfoResponse {
         name: info.name,
         symbol: info.symbol,
         decimals: info.decimals,
-        total_supply: info.total_supply,
+        total_supply: query_total_supply(deps)?.total_supply,
     };
     Ok(res)
 }
 
 pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
-    let meta = TOKEN_INFO.load(deps.storage)?;
-    let minter = match meta.mint {
-        Some(m) => Some(MinterResponse {
-            minter: m.minter.into(),
-            cap: m.cap,
-        }),
-        None => None,
-    };
+    let minter = MINTER.may_load(deps.storage)?.map(|m| MinterResponse {
+        minter: m.minter.into(),
+        cap: m.cap,
+    });
     Ok(minter)
 }
 
+pub fn query_minting_renounced(deps: Deps) -> StdResult<MintingRenouncedResponse> {
+    Ok(MintingRenouncedResponse {
+        renounced: MINTING_RENOUNCED
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
+
 pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
     Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
 }
 
+#[cfg(feature = "marketing")]
 pub fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
     let logo = LOGO.load(deps.storage)?;
     match logo {
@@ -262,19 +1065,69 @@ pub fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
     }
 }
 
+pub fn query_mirror_denom(deps: Deps) -> StdResult<MirrorDenomResponse> {
+    Ok(MirrorDenomResponse {
+        denom: MIRROR_DENOM.may_load(deps.storage)?,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    #[cfg(feature = "events")]
+    let events_only = events_only(deps.storage)?;
+    let res = match msg.id {
+        SAFE_SEND_REPLY_ID => reply_safe_send(deps, env, msg),
+        #[cfg(feature = "allowance-hooks")]
+        ALLOWANCE_NOTIFICATION_REPLY_ID => reply_allowance_notification(deps, env, msg),
+        #[cfg(feature = "supply-hooks")]
+        SUPPLY_HOOK_REPLY_ID => reply_supply_hook(deps, env, msg),
+        id => Err(StdError::generic_err(format!("Unknown reply id {id}")).into()),
+    }?;
+    #[cfg(feature = "events")]
+    let res = strip_legacy_attributes(res, events_only);
+    Ok(res)
+}
+
+/// Moves a token onto this code, from an earlier version of it or from upstream
+/// cw20-base (0.16, 1.x and 2.x share its contract name and storage keys), running the
+/// migration steps newer than the stored version in order. With dry_run, only reports
+/// the steps that would run.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let original_version =
-        ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let original_version = stored_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let steps = pending_steps(&original_version)?;
+    let step_names: Vec<_> = steps.iter().map(|step| step.name).collect();
 
-    if original_version < "0.14.0".parse::<semver::Version>().unwrap() {
-        // Build reverse map of allowances per spender
-        let data = ALLOWANCES
-            .range(deps.storage, None, None, Ascending)
-            .collect::<StdResult<Vec<_>>>()?;
-        for ((owner, spender), allowance) in data {
-            ALLOWANCES_SPENDER.save(deps.storage, (&spender, &owner), &allowance)?;
-        }
+    let res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", original_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("steps", step_names.join(","))
+        .add_attribute("dry_run", msg.dry_run.to_string());
+    #[cfg(feature = "rescale-decimals")]
+    let res = match &msg.rescale_decimals {
+        Some(rescale) => res.add_attribute("rescale_decimals", rescale.new_decimals.to_string()),
+        None => res,
+    };
+    #[cfg(feature = "enumerable")]
+    let res = match msg.enumeration_limit {
+        Some(max_limit) => res.add_attribute("enumeration_limit", max_limit.to_string()),
+        None => res,
+    };
+    if msg.dry_run {
+        return Ok(res);
+    }
+
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    run_steps(deps.storage, &env, &steps)?;
+    register_interfaces(deps.storage)?;
+    #[cfg(feature = "rescale-decimals")]
+    if let Some(rescale) = msg.rescale_decimals {
+        start_rescale(deps.storage, rescale.new_decimals)?;
+    }
+    #[cfg(feature = "enumerable")]
+    if let Some(max_limit) = msg.enumeration_limit {
+        set_enumeration_limit(deps.storage, max_limit)?;
     }
-    Ok(Response::default())
+    Ok(res)
 }
@@ -0,0 +1,33 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::state::REJECT_CONTRACT_TRANSFERS;
+
+/// Fails if the flag is set and `recipient` is a contract, so tokens can only reach
+/// contracts through Send, which they have to handle. Contracts not understanding cw20
+/// would otherwise hold them for good.
+pub fn assert_not_contract(deps: Deps, recipient: &Addr) -> Result<(), ContractError> {
+    let reject = REJECT_CONTRACT_TRANSFERS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if reject && deps.querier.query_wasm_contract_info(recipient).is_ok() {
+        return Err(ContractError::ContractRecipient {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_reject_contract_transfers(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    reject: bool,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    REJECT_CONTRACT_TRANSFERS.save(deps.storage, &reject)?;
+
+    let res = Response::new()
+        .add_attribute("action", "update_reject_contract_transfers")
+        .add_attribute("reject", reject.to_string());
+    Ok(res)
+}
@@ -0,0 +1,125 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::{Crank, CrankParams, CrankStatusResponse, ExecuteMsg, KeeperRunsResponse};
+use crate::state::{CrankConfig, CRANK_CONFIGS, CRANK_LAST_RUN, KEEPER_RUNS};
+
+/// The crank `msg` runs, if any, and the keeper running it. Rewards sent in a cw20 token
+/// arrive through the token, so their keeper is the sender it passes on.
+pub fn crank_of(info: &MessageInfo, msg: &ExecuteMsg) -> Option<(Crank, String)> {
+    let crank = match msg {
+        #[cfg(feature = "scheduled")]
+        ExecuteMsg::ExecuteMatured { .. } => Crank::ExecuteMatured,
+        #[cfg(feature = "rewards")]
+        ExecuteMsg::DistributeRewards {} => Crank::DistributeRewards,
+        #[cfg(feature = "rewards")]
+        ExecuteMsg::Receive(wrapper) => {
+            return Some((Crank::DistributeRewards, wrapper.sender.clone()))
+        }
+        #[cfg(feature = "allowance-hooks")]
+        ExecuteMsg::PruneExpiredAllowances { .. } => Crank::PruneExpiredAllowances,
+        #[cfg(feature = "rescale-decimals")]
+        ExecuteMsg::ContinueMigration { .. } => Crank::ContinueMigration,
+        ExecuteMsg::ProcessMigration { .. } => Crank::ProcessMigration,
+        _ => return None,
+    };
+    Some((crank, info.sender.to_string()))
+}
+
+/// Fails if `keeper` may not run `crank`, or may not yet, and counts the run otherwise
+pub fn record_crank(
+    storage: &mut dyn Storage,
+    env: &Env,
+    crank: Crank,
+    keeper: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(config) = CRANK_CONFIGS.may_load(storage, crank.as_str())? {
+        if let Some(keepers) = &config.keepers {
+            if !keepers.contains(keeper) {
+                return Err(ContractError::NotAKeeper {});
+            }
+        }
+        if let Some(last_run) = CRANK_LAST_RUN.may_load(storage, crank.as_str())? {
+            let next_run = last_run.plus_seconds(config.min_interval);
+            if env.block.time < next_run {
+                return Err(ContractError::CrankRateLimited { next_run });
+            }
+        }
+    }
+    CRANK_LAST_RUN.save(storage, crank.as_str(), &env.block.time)?;
+    KEEPER_RUNS.update(storage, (keeper, crank.as_str()), |runs| -> StdResult<_> {
+        Ok(runs.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
+
+pub fn execute_update_crank_params(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    crank: Crank,
+    params: Option<CrankParams>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    let mut res = Response::new()
+        .add_attribute("action", "update_crank_params")
+        .add_attribute("crank", crank.as_str());
+    match params {
+        Some(params) => {
+            let keepers = params
+                .keepers
+                .map(|keepers| {
+                    keepers
+                        .iter()
+                        .map(|keeper| deps.api.addr_validate(keeper))
+                        .collect::<StdResult<Vec<_>>>()
+                })
+                .transpose()?;
+            res = res
+                .add_attribute("min_interval", params.min_interval.to_string())
+                .add_attribute(
+                    "keepers",
+                    keepers
+                        .as_ref()
+                        .map_or("anyone".to_string(), |keepers| keepers.len().to_string()),
+                );
+            let config = CrankConfig {
+                min_interval: params.min_interval,
+                keepers,
+            };
+            CRANK_CONFIGS.save(deps.storage, crank.as_str(), &config)?;
+        }
+        None => {
+            CRANK_CONFIGS.remove(deps.storage, crank.as_str());
+            res = res.add_attribute("min_interval", "None");
+        }
+    }
+    Ok(res)
+}
+
+pub fn query_crank_status(deps: Deps, crank: Crank) -> StdResult<CrankStatusResponse> {
+    let config = CRANK_CONFIGS.may_load(deps.storage, crank.as_str())?;
+    let last_run = CRANK_LAST_RUN.may_load(deps.storage, crank.as_str())?;
+    let next_run = match (&config, last_run) {
+        (Some(config), Some(last_run)) => Some(last_run.plus_seconds(config.min_interval)),
+        _ => None,
+    };
+    Ok(CrankStatusResponse {
+        config,
+        last_run,
+        next_run,
+    })
+}
+
+pub fn query_keeper_runs(
+    deps: Deps,
+    keeper: String,
+    crank: Crank,
+) -> StdResult<KeeperRunsResponse> {
+    let keeper = deps.api.addr_validate(&keeper)?;
+    let runs = KEEPER_RUNS
+        .may_load(deps.storage, (&keeper, crank.as_str()))?
+        .unwrap_or_default();
+    Ok(KeeperRunsResponse { runs })
+}
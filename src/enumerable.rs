@@ -1,16 +1,63 @@
-use cosmwasm_std::{Deps, Order, StdResult};
+#[cfg(feature = "enumerable")]
+use cosmwasm_std::{Addr, Binary, Deps, Order, Uint128};
+use cosmwasm_std::{StdError, StdResult, Storage};
+#[cfg(feature = "enumerable")]
 use cw20::{
     AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
-    SpenderAllowanceInfo,
+    Expiration, SpenderAllowanceInfo,
 };
 
+#[cfg(feature = "enumerable")]
+use crate::migrations::is_pending;
+#[cfg(feature = "enumerable")]
+use crate::msg::{CountResponse, EnumerationLimitResponse, Page, VerifyInvariantsResponse};
+use crate::state::ENUMERATION_LIMIT;
+#[cfg(feature = "enumerable")]
 use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES};
+#[cfg(feature = "enumerable")]
+use crate::supply::total_supply;
+#[cfg(feature = "enumerable")]
 use cw_storage_plus::Bound;
 
-// settings for pagination
-const MAX_LIMIT: u32 = 30;
-const DEFAULT_LIMIT: u32 = 10;
+// settings for pagination, shared by every paginated query
+pub const MAX_LIMIT: u32 = 30;
+pub const DEFAULT_LIMIT: u32 = 10;
+// VerifyInvariants only loads amounts, so it can sum much larger pages
+pub const MAX_VERIFY_LIMIT: u32 = 500;
+pub const DEFAULT_VERIFY_LIMIT: u32 = 100;
+// indexers walk every allowance and account, so those pages can be made much larger
+pub const DEFAULT_ENUMERATION_LIMIT: u32 = 100;
+pub const MAX_ENUMERATION_LIMIT: u32 = 1_000;
 
+pub fn set_enumeration_limit(storage: &mut dyn Storage, max_limit: u32) -> StdResult<()> {
+    if max_limit == 0 || max_limit > MAX_ENUMERATION_LIMIT {
+        return Err(StdError::generic_err(format!(
+            "Enumeration limit must be between 1 and {MAX_ENUMERATION_LIMIT}"
+        )));
+    }
+    ENUMERATION_LIMIT.save(storage, &max_limit)
+}
+
+#[cfg(feature = "enumerable")]
+fn max_enumeration_limit(storage: &dyn Storage) -> StdResult<u32> {
+    Ok(ENUMERATION_LIMIT
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_ENUMERATION_LIMIT))
+}
+
+#[cfg(feature = "enumerable")]
+fn enumeration_limit(storage: &dyn Storage, limit: Option<u32>) -> StdResult<usize> {
+    let max_limit = max_enumeration_limit(storage)?;
+    Ok(limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize)
+}
+
+#[cfg(feature = "enumerable")]
+pub fn query_enumeration_limit(deps: Deps) -> StdResult<EnumerationLimitResponse> {
+    let max_limit = max_enumeration_limit(deps.storage)?;
+    Ok(EnumerationLimitResponse { max_limit })
+}
+
+#[cfg(feature = "enumerable")]
 pub fn query_owner_allowances(
     deps: Deps,
     owner: String,
@@ -18,12 +65,80 @@ pub fn query_owner_allowances(
     limit: Option<u32>,
 ) -> StdResult<AllAllowancesResponse> {
     let owner_addr = deps.api.addr_validate(&owner)?;
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let limit = enumeration_limit(deps.storage, limit)?;
+    let start = start_after.map(String::into_bytes);
+    let allowances = owner_allowances(deps, &owner_addr, start, limit)?;
+    Ok(AllAllowancesResponse { allowances })
+}
+
+/// Like AllAllowances, resuming after the `cursor` of the previous page
+#[cfg(feature = "enumerable")]
+pub fn query_owner_allowances_page(
+    deps: Deps,
+    owner: String,
+    cursor: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Page<AllowanceInfo>> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = enumeration_limit(deps.storage, limit)?.max(1);
+    let start = cursor.map(Vec::from);
+    let allowances = owner_allowances(deps, &owner_addr, start, limit + 1)?;
+    Ok(into_page(allowances, limit, |allow| &allow.spender))
+}
+
+#[cfg(feature = "enumerable")]
+fn owner_allowances(
+    deps: Deps,
+    owner: &Addr,
+    start_after: Option<Vec<u8>>,
+    limit: usize,
+) -> StdResult<Vec<AllowanceInfo>> {
+    ALLOWANCES
+        .prefix(owner)
+        .range(
+            deps.storage,
+            start_after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            item.map(|(addr, allow)| AllowanceInfo {
+                spender: addr.into(),
+                allowance: allow.allowance,
+                expires: allow.expires,
+            })
+        })
+        .collect()
+}
+
+/// Allowances of `owner` lapsing before `before`, counting lapsed ones. Only expirations of
+/// the same kind compare: an `AtTime` bound finds time expirations, an `AtHeight` bound
+/// height expirations, and `Never` finds the allowances that never expire.
+#[cfg(feature = "enumerable")]
+pub fn query_allowances_expiring_before(
+    deps: Deps,
+    owner: String,
+    before: Expiration,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = enumeration_limit(deps.storage, limit)?;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
 
     let allowances = ALLOWANCES
         .prefix(&owner_addr)
         .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, allow)) => match (allow.expires, before) {
+                (Expiration::AtTime(expires), Expiration::AtTime(before)) => expires < before,
+                (Expiration::AtHeight(expires), Expiration::AtHeight(before)) => expires < before,
+                (Expiration::Never {}, Expiration::Never {}) => true,
+                _ => false,
+            },
+            Err(_) => true,
+        })
         .take(limit)
         .map(|item| {
             item.map(|(addr, allow)| AllowanceInfo {
@@ -36,6 +151,7 @@ pub fn query_owner_allowances(
     Ok(AllAllowancesResponse { allowances })
 }
 
+#[cfg(feature = "enumerable")]
 pub fn query_spender_allowances(
     deps: Deps,
     spender: String,
@@ -43,12 +159,45 @@ pub fn query_spender_allowances(
     limit: Option<u32>,
 ) -> StdResult<AllSpenderAllowancesResponse> {
     let spender_addr = deps.api.addr_validate(&spender)?;
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+    let limit = enumeration_limit(deps.storage, limit)?;
+    let start = start_after.map(String::into_bytes);
+    let allowances = spender_allowances(deps, &spender_addr, start, limit)?;
+    Ok(AllSpenderAllowancesResponse { allowances })
+}
 
-    let allowances = ALLOWANCES_SPENDER
-        .prefix(&spender_addr)
-        .range(deps.storage, start, None, Order::Ascending)
+/// Like AllSpenderAllowances, resuming after the `cursor` of the previous page
+#[cfg(feature = "enumerable")]
+pub fn query_spender_allowances_page(
+    deps: Deps,
+    spender: String,
+    cursor: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Page<SpenderAllowanceInfo>> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let limit = enumeration_limit(deps.storage, limit)?.max(1);
+    let start = cursor.map(Vec::from);
+    let allowances = spender_allowances(deps, &spender_addr, start, limit + 1)?;
+    Ok(into_page(allowances, limit, |allow| &allow.owner))
+}
+
+#[cfg(feature = "enumerable")]
+fn spender_allowances(
+    deps: Deps,
+    spender: &Addr,
+    start_after: Option<Vec<u8>>,
+    limit: usize,
+) -> StdResult<Vec<SpenderAllowanceInfo>> {
+    if is_pending(deps.storage, "index_allowances_by_spender")? {
+        return scan_spender_allowances(deps, spender, start_after, limit);
+    }
+    ALLOWANCES_SPENDER
+        .prefix(spender)
+        .range(
+            deps.storage,
+            start_after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
         .take(limit)
         .map(|item| {
             item.map(|(addr, allow)| SpenderAllowanceInfo {
@@ -57,23 +206,162 @@ pub fn query_spender_allowances(
                 expires: allow.expires,
             })
         })
-        .collect::<StdResult<_>>()?;
-    Ok(AllSpenderAllowancesResponse { allowances })
+        .collect()
+}
+
+/// The same page read from the owner map, while a migration still builds the spender
+/// index. Costs a scan of every allowance, so it's only for the migration window.
+/// The owner map orders owners by length first, so the page is sorted like the index
+/// before it's cut, or `start_after` would skip owners.
+#[cfg(feature = "enumerable")]
+fn scan_spender_allowances(
+    deps: Deps,
+    spender: &Addr,
+    start_after: Option<Vec<u8>>,
+    limit: usize,
+) -> StdResult<Vec<SpenderAllowanceInfo>> {
+    let mut allowances = vec![];
+    for item in ALLOWANCES.range(deps.storage, None, None, Order::Ascending) {
+        let ((owner, owner_spender), allow) = item?;
+        if owner_spender != *spender
+            || start_after
+                .as_deref()
+                .is_some_and(|start| owner.as_bytes() <= start)
+        {
+            continue;
+        }
+        allowances.push(SpenderAllowanceInfo {
+            owner: owner.into(),
+            allowance: allow.allowance,
+            expires: allow.expires,
+        });
+    }
+    allowances.sort_by(|a, b| a.owner.cmp(&b.owner));
+    allowances.truncate(limit);
+    Ok(allowances)
+}
+
+/// Costs a read of every allowance of `owner`, fine for indexers but not for contracts
+#[cfg(feature = "enumerable")]
+pub fn query_allowance_count(deps: Deps, owner: String) -> StdResult<CountResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let count = ALLOWANCES
+        .prefix(&owner_addr)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+    Ok(CountResponse { count })
+}
+
+#[cfg(feature = "enumerable")]
+pub fn query_spender_allowance_count(deps: Deps, spender: String) -> StdResult<CountResponse> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if is_pending(deps.storage, "index_allowances_by_spender")? {
+        let mut count = 0;
+        for item in ALLOWANCES.keys(deps.storage, None, None, Order::Ascending) {
+            if item?.1 == spender_addr {
+                count += 1;
+            }
+        }
+        return Ok(CountResponse { count });
+    }
+    let count = ALLOWANCES_SPENDER
+        .prefix(&spender_addr)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+    Ok(CountResponse { count })
 }
 
+#[cfg(feature = "enumerable")]
+pub fn query_account_count(deps: Deps) -> StdResult<CountResponse> {
+    let count = BALANCES
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+    Ok(CountResponse { count })
+}
+
+#[cfg(feature = "enumerable")]
 pub fn query_all_accounts(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<AllAccountsResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let limit = enumeration_limit(deps.storage, limit)?;
+    let accounts = accounts(deps, start_after.map(String::into_bytes), limit)?;
+    Ok(AllAccountsResponse { accounts })
+}
 
-    let accounts = BALANCES
-        .keys(deps.storage, start, None, Order::Ascending)
+/// Like AllAccounts, resuming after the `cursor` of the previous page
+#[cfg(feature = "enumerable")]
+pub fn query_accounts_page(
+    deps: Deps,
+    cursor: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Page<String>> {
+    let limit = enumeration_limit(deps.storage, limit)?.max(1);
+    let accounts = accounts(deps, cursor.map(Vec::from), limit + 1)?;
+    Ok(into_page(accounts, limit, String::as_str))
+}
+
+#[cfg(feature = "enumerable")]
+fn accounts(deps: Deps, start_after: Option<Vec<u8>>, limit: usize) -> StdResult<Vec<String>> {
+    BALANCES
+        .keys(
+            deps.storage,
+            start_after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
         .take(limit)
         .map(|item| item.map(Into::into))
-        .collect::<StdResult<_>>()?;
+        .collect()
+}
 
-    Ok(AllAccountsResponse { accounts })
-}
\ No newline at end of file
+/// Cuts a page fetched with one entry more than `limit`. That entry tells whether
+/// there's more, and the key of the last one kept is where the next page starts.
+#[cfg(feature = "enumerable")]
+fn into_page<T>(mut items: Vec<T>, limit: usize, key: impl Fn(&T) -> &str) -> Page<T> {
+    let is_truncated = items.len() > limit;
+    items.truncate(limit);
+    let next_cursor = match is_truncated {
+        true => items.last().map(|item| Binary::from(key(item).as_bytes())),
+        false => None,
+    };
+    Page {
+        items,
+        next_cursor,
+        is_truncated,
+    }
+}
+/// Adds a page of balances to `running_total`, the sum of the balances up to
+/// `start_after` returned by the previous page. Once the last balance is added, checks
+/// the sum against the total supply.
+#[cfg(feature = "enumerable")]
+pub fn query_verify_invariants(
+    deps: Deps,
+    start_after: Option<String>,
+    running_total: Option<Uint128>,
+    limit: Option<u32>,
+) -> StdResult<VerifyInvariantsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_VERIFY_LIMIT).min(MAX_VERIFY_LIMIT);
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+    let mut balances = BALANCES.range(deps.storage, start, None, Order::Ascending);
+    let mut running_total = running_total.unwrap_or_default();
+    let mut last_address = None;
+    for item in balances.by_ref().take(limit as usize) {
+        let (addr, balance) = item?;
+        running_total = running_total.checked_add(balance)?;
+        last_address = Some(addr.into_string());
+    }
+    let total_supply = total_supply(deps.storage)?;
+    let passed = match balances.next() {
+        Some(_) => None,
+        None => Some(running_total == total_supply),
+    };
+    Ok(VerifyInvariantsResponse {
+        running_total,
+        last_address,
+        total_supply,
+        passed,
+    })
+}
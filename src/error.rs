@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -35,9 +35,240 @@ pub enum ContractError {
     #[error("Invalid png header")]
     InvalidPngHeader {},
 
+    #[error("Malformed SVG")]
+    InvalidSvg {},
+
+    #[error("SVG logos must not contain <{element}>")]
+    SvgForbiddenElement { element: String },
+
+    #[error("SVG logos must not contain event handlers ({attribute})")]
+    SvgEventHandler { attribute: String },
+
+    #[error("SVG logos must not reference external resources")]
+    SvgExternalReference {},
+
+    #[error("SVG width and height must not exceed {max}")]
+    SvgTooLarge { max: u64 },
+
+    #[error("Metadata keys must be between 1 and {max} bytes")]
+    InvalidMetadataKey { max: usize },
+
+    #[error("Metadata values must not exceed {max} bytes")]
+    MetadataValueTooLong { max: usize },
+
+    #[error("Token ids must be 1 to {max} letters, digits, '-' or '_'")]
+    InvalidMultiTokenId { max: usize },
+
+    #[error("A token with this id already exists")]
+    MultiTokenExists {},
+
+    #[error("No token with id {token_id}")]
+    UnknownMultiToken { token_id: String },
+
     #[error("Invalid expiration value")]
     InvalidExpiration {},
 
     #[error("Duplicate initial balance addresses")]
     DuplicateInitialBalanceAddresses {},
+
+    #[error("Basis points must not exceed 10000")]
+    InvalidBasisPoints {},
+
+    #[error("Auto-burn is not configured")]
+    AutoBurnDisabled {},
+
+    #[error("Stream must end after it starts")]
+    InvalidStreamSchedule {},
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("Subscription period must not be zero")]
+    InvalidSubscriptionPeriod {},
+
+    #[error("No subscription for this account")]
+    NoSubscription {},
+
+    #[error("Charge exceeds the amount left in this subscription period")]
+    SubscriptionLimitExceeded {},
+
+    #[error("Vesting schedule must end after it starts, with the cliff in between")]
+    InvalidVestingSchedule {},
+
+    #[error("Amount exceeds the unlocked balance")]
+    TokensLocked {},
+
+    #[error("A snapshot with this label already exists")]
+    SnapshotExists {},
+
+    #[error("Mints above the timelock threshold within one delay must be proposed first")]
+    MintTimelocked {},
+
+    #[error("The mint timelock has not elapsed yet")]
+    TimelockNotElapsed {},
+
+    #[error("No pending mint with this id")]
+    NoPendingMint {},
+
+    #[error("The owner has locked its allowances")]
+    AllowancesLocked {},
+
+    #[error("Allowances are not locked")]
+    AllowancesNotLocked {},
+
+    #[error("The cap can only be decreased")]
+    CapCanOnlyDecrease {},
+
+    #[error("Cap must not be below the current supply")]
+    CapBelowSupply {},
+
+    #[error("Minting has been renounced")]
+    MintingRenounced {},
+
+    #[error("Token name and symbol can no longer be changed")]
+    TokenInfoFrozen {},
+
+    #[error("This token and its mirrored denom can't be rescued")]
+    CannotRescueOwnToken {},
+
+    #[error("The contract holds none of this asset")]
+    NothingToRescue {},
+
+    #[error(
+        "Memo must not exceed {} bytes",
+        crate::execute::execute_transfer::MAX_MEMO_LENGTH
+    )]
+    MemoTooLong {},
+
+    #[error("Send to this contract is not allowed")]
+    SendTargetNotAllowed {},
+
+    #[error("The send allowlist is not enabled")]
+    SendAllowlistDisabled {},
+
+    #[error("Transfer restricted: {reason}")]
+    TransferRestricted { reason: String },
+
+    #[error("Transfer limit period must not be zero")]
+    InvalidTransferLimit {},
+
+    #[error("Amount exceeds what is left of the transfer limit for this period")]
+    TransferLimitExceeded {},
+
+    #[error("A faucet needs a minter, and its amount and period must not be zero")]
+    InvalidFaucet {},
+
+    #[error("The faucet is not enabled")]
+    FaucetDisabled {},
+
+    #[error("Faucet amount for this period already minted")]
+    FaucetLimitReached {},
+
+    #[error("Signing key must be a compressed or uncompressed secp256k1 public key")]
+    InvalidPubkey {},
+
+    #[error("Owner has no signing key set")]
+    NoSigningKey {},
+
+    #[error("Invalid nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Authorization nonce must be 32 bytes")]
+    InvalidAuthorizationNonce {},
+
+    #[error("Authorization is not valid yet")]
+    AuthorizationNotYetValid {},
+
+    #[error("Authorization expired")]
+    AuthorizationExpired {},
+
+    #[error("Authorization already used or cancelled")]
+    AuthorizationUsed {},
+
+    #[error("Allowance limit amount and period must not be zero")]
+    InvalidAllowanceLimit {},
+
+    #[error("Amount exceeds what is left of the allowance limit for this period")]
+    AllowanceLimitExceeded {},
+
+    #[error("Tokens can only reach a contract through Send")]
+    ContractRecipient {},
+
+    #[error("Transfers must move at least {min} tokens")]
+    TransferBelowMinimum { min: Uint128 },
+
+    #[error("Recipient balance would exceed the max wallet")]
+    MaxWalletExceeded {},
+
+    #[error("Trading is not enabled yet")]
+    TradingNotEnabled {},
+
+    #[error("Trading is already enabled")]
+    TradingAlreadyEnabled {},
+
+    #[error("Rewards are not enabled")]
+    RewardsDisabled {},
+
+    #[error("Rewards must be paid in the reward asset, which can't be this token")]
+    InvalidRewardAsset {},
+
+    #[error("No tokens held to distribute rewards to")]
+    NoRewardHolders {},
+
+    #[error("Rewards owed to holders can't be rescued")]
+    CannotRescueRewards {},
+
+    #[error("Exchange rate must not be zero")]
+    InvalidExchangeRate {},
+
+    #[error("The token is not in share mode")]
+    ExchangeRateDisabled {},
+
+    #[error("The token is not importing state")]
+    ImportClosed {},
+
+    #[error("New decimals must differ from the current ones and not exceed 18")]
+    InvalidRescale {},
+
+    #[error("Decimals are being rescaled, until ContinueMigration finishes")]
+    RescaleInProgress {},
+
+    #[error("No decimal rescale in progress")]
+    NoRescaleInProgress {},
+
+    #[error("The last migration is still being processed")]
+    MigrationInProgress {},
+
+    #[error("No migration in progress")]
+    NoMigrationInProgress {},
+
+    #[error("Only contracts can receive allowance notifications")]
+    NotAContract {},
+
+    #[error("At most {max} supply hooks can be registered")]
+    TooManySupplyHooks { max: usize },
+
+    #[error("Contract was built without the tokenfactory feature")]
+    TokenFactoryDisabled {},
+
+    #[error("This token has no native mirror")]
+    NoMirror {},
+
+    #[error("Wrap takes exactly one nonzero coin of {denom}")]
+    InvalidWrapFunds { denom: String },
+
+    #[error("Contract was built without the mintable feature")]
+    MintableDisabled {},
+
+    #[error("Contract was built without the marketing feature")]
+    MarketingDisabled {},
+
+    #[error("Only the keepers can run this crank")]
+    NotAKeeper {},
+
+    #[error("This crank can run again at {next_run}")]
+    CrankRateLimited { next_run: Timestamp },
 }
@@ -0,0 +1,63 @@
+use cosmwasm_std::{Addr, Event, Response, StdResult, Storage, Uint128};
+use cw20::AllowanceResponse;
+
+use crate::state::{EVENTS_ONLY, SEQUENCE};
+
+/// Prefix of the typed events, so they don't collide with the `wasm` attributes of
+/// other contracts in the same transaction
+const EVENT_PREFIX: &str = "cw20-";
+
+/// Numbers every balance change, so off-chain systems can order and deduplicate them
+/// across the messages of a transaction and across replays
+fn balance_event(storage: &mut dyn Storage, ty: &str) -> StdResult<Event> {
+    let seq = SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    SEQUENCE.save(storage, &seq)?;
+    Ok(Event::new(ty).add_attribute("seq", seq.to_string()))
+}
+
+/// Tokens moved between two accounts, `amount` being what `to` received
+pub fn transfer(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> StdResult<Event> {
+    Ok(balance_event(storage, "cw20-transfer")?
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount))
+}
+
+pub fn mint(storage: &mut dyn Storage, to: &Addr, amount: Uint128) -> StdResult<Event> {
+    Ok(balance_event(storage, "cw20-mint")?
+        .add_attribute("to", to)
+        .add_attribute("amount", amount))
+}
+
+pub fn burn(storage: &mut dyn Storage, from: &Addr, amount: Uint128) -> StdResult<Event> {
+    Ok(balance_event(storage, "cw20-burn")?
+        .add_attribute("from", from)
+        .add_attribute("amount", amount))
+}
+
+/// Allowance of `spender` over the tokens of `owner`, as left by the action
+pub fn allowance(owner: &Addr, spender: &Addr, allowance: &AllowanceResponse) -> Event {
+    Event::new("cw20-allowance")
+        .add_attribute("owner", owner)
+        .add_attribute("spender", spender)
+        .add_attribute("allowance", allowance.allowance)
+        .add_attribute("expires", allowance.expires.to_string())
+}
+
+pub fn events_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(EVENTS_ONLY.may_load(storage)?.unwrap_or_default())
+}
+
+/// Drops the flat attributes of a response described by typed events, if the token
+/// was instantiated with `events_only`
+pub fn strip_legacy_attributes(mut res: Response, events_only: bool) -> Response {
+    if events_only && res.events.iter().any(|e| e.ty.starts_with(EVENT_PREFIX)) {
+        res.attributes.clear();
+    }
+    res
+}
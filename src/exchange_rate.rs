@@ -0,0 +1,88 @@
+use cosmwasm_std::{
+    Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw20::BalanceResponse;
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::{ExchangeRateResponse, InstantiateExchangeRate};
+use crate::state::{BALANCES, EXCHANGE_RATE, EXCHANGE_RATE_UPDATER};
+
+/// Turns the token into shares of an underlying amount growing with the exchange rate
+pub fn setup_exchange_rate(
+    deps: &mut DepsMut,
+    env: &Env,
+    config: InstantiateExchangeRate,
+) -> Result<(), ContractError> {
+    if config.rate.is_zero() {
+        return Err(ContractError::InvalidExchangeRate {});
+    }
+    EXCHANGE_RATE.save(deps.storage, &config.rate, env.block.height)?;
+    if let Some(updater) = config.updater {
+        let updater = deps.api.addr_validate(&updater)?;
+        EXCHANGE_RATE_UPDATER.save(deps.storage, &updater)?;
+    }
+    Ok(())
+}
+
+/// Underlying amount `shares` are worth at the current exchange rate
+pub fn to_underlying(storage: &dyn Storage, shares: Uint128) -> StdResult<Uint128> {
+    apply_rate(EXCHANGE_RATE.may_load(storage)?, shares)
+}
+
+/// Underlying amount `shares` were worth at the start of `height`, at the rate back then
+pub fn to_underlying_at(storage: &dyn Storage, shares: Uint128, height: u64) -> StdResult<Uint128> {
+    apply_rate(EXCHANGE_RATE.may_load_at_height(storage, height)?, shares)
+}
+
+fn apply_rate(rate: Option<Decimal>, shares: Uint128) -> StdResult<Uint128> {
+    match rate {
+        Some(rate) => shares
+            .checked_mul_floor(rate)
+            .map_err(|err| StdError::generic_err(err.to_string())),
+        None => Ok(shares),
+    }
+}
+
+/// Sets the underlying amount per share. Callable by the updater, usually the yield
+/// strategy, or the admin.
+pub fn execute_update_exchange_rate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rate: Decimal,
+) -> Result<Response, ContractError> {
+    if EXCHANGE_RATE_UPDATER.may_load(deps.storage)?.as_ref() != Some(&info.sender) {
+        assert_admin(deps.storage, &info.sender)?;
+    }
+    if EXCHANGE_RATE.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::ExchangeRateDisabled {});
+    }
+    if rate.is_zero() {
+        return Err(ContractError::InvalidExchangeRate {});
+    }
+    EXCHANGE_RATE.save(deps.storage, &rate, env.block.height)?;
+
+    let res = Response::new()
+        .add_attribute("action", "update_exchange_rate")
+        .add_attribute("rate", rate.to_string());
+    Ok(res)
+}
+
+pub fn query_exchange_rate(deps: Deps) -> StdResult<ExchangeRateResponse> {
+    Ok(ExchangeRateResponse {
+        rate: EXCHANGE_RATE
+            .may_load(deps.storage)?
+            .unwrap_or(Decimal::one()),
+        updater: EXCHANGE_RATE_UPDATER.may_load(deps.storage)?,
+    })
+}
+
+/// Balance of `address` in shares, the unit every message amount is in
+pub fn query_shares(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
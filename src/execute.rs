@@ -1,7 +1,17 @@
 pub mod execute_transfer;
 pub mod execute_burn;
+#[cfg(feature = "mintable")]
 pub mod execute_mint;
+#[cfg(feature = "mintable")]
+pub mod execute_renounce_minting;
+#[cfg(feature = "rescue")]
+pub mod execute_rescue;
 pub mod execute_send;
+#[cfg(feature = "mintable")]
+pub mod execute_update_cap;
+#[cfg(feature = "mintable")]
 pub mod execute_update_minter;
+#[cfg(feature = "marketing")]
 pub mod execute_update_marketing;
+#[cfg(feature = "marketing")]
 pub mod execute_upload_logo;
\ No newline at end of file
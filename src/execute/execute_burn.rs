@@ -1,30 +1,63 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+use crate::balances::spend_balance;
+#[cfg(feature = "burn-stats")]
+use crate::burn_stats::record_burn;
 use crate::error::ContractError;
-use crate::state::{BALANCES, TOKEN_INFO};
+#[cfg(feature = "events")]
+use crate::events;
+use crate::execute::execute_transfer::memo_attributes;
+#[cfg(feature = "supply-log")]
+use crate::msg::SupplyAction;
+use crate::restrictions::spendable_balance;
+use crate::supply::decrease_supply;
+#[cfg(feature = "supply-log")]
+use crate::supply_log;
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+use crate::tokenfactory;
 
 pub fn execute_burn(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     // lower balance
-    BALANCES.update(
+    spend_balance(&mut deps, &env, &info.sender, amount)?;
+    // reduce total_supply
+    decrease_supply(deps.storage, &info.sender, amount, env.block.height)?;
+    #[cfg(feature = "supply-log")]
+    supply_log::record(
         deps.storage,
+        &env.block,
+        SupplyAction::Burn,
+        &info.sender,
         &info.sender,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
+        amount,
     )?;
-    // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-        info.total_supply = info.total_supply.checked_sub(amount)?;
-        Ok(info)
-    })?;
+    #[cfg(feature = "burn-stats")]
+    record_burn(deps.storage, &info.sender, amount)?;
 
     let res = Response::new()
         .add_attribute("action", "burn")
-        .add_attribute("from", info.sender)
-        .add_attribute("amount", amount);
+        .add_attribute("from", &info.sender)
+        .add_attribute("amount", amount)
+        .add_attributes(memo)
+        .add_messages(tokenfactory::burn_msgs(deps.storage, &env, amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::burn_msgs(deps.storage, &info.sender, amount)?);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::burn(deps.storage, &info.sender, amount)?);
     Ok(res)
 }
+
+pub fn execute_burn_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let amount = spendable_balance(deps.as_ref(), &env, &info.sender)?.spendable;
+    execute_burn(deps, env, info, amount, None)
+}
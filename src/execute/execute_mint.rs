@@ -1,49 +1,145 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError, Storage, Uint128};
+use cw20::Cw20Coin;
+use crate::balances::add_balance;
 use crate::error::ContractError;
-use crate::state::{BALANCES, TOKEN_INFO};
-use cosmwasm_std::StdResult;
+#[cfg(feature = "events")]
+use crate::events;
+#[cfg(feature = "max-wallet")]
+use crate::max_wallet::assert_within_max_wallet;
+#[cfg(feature = "mint-timelock")]
+use crate::mint_timelock::record_direct_mint;
+#[cfg(feature = "supply-log")]
+use crate::msg::SupplyAction;
+use crate::state::MINTER;
+use crate::supply::{assert_within_cap, increase_supply};
+#[cfg(feature = "supply-log")]
+use crate::supply_log;
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+use crate::tokenfactory;
 
 pub fn execute_mint(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let mut config = TOKEN_INFO
-        .may_load(deps.storage)?
-        .ok_or(ContractError::Unauthorized {})?;
+    #[cfg(feature = "mint-timelock")]
+    record_direct_mint(deps.storage, &env, amount)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    mint(deps.storage, &env, &info.sender, &rcpt_addr, amount)?;
 
-    if config
-        .mint
-        .as_ref()
-        .ok_or(ContractError::Unauthorized {})?
-        .minter
-        != info.sender
-    {
-        return Err(ContractError::Unauthorized {});
-    }
+    let res = Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount)
+        .add_messages(tokenfactory::mint_msgs(deps.storage, &env, amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::mint_msgs(
+        deps.storage,
+        &info.sender,
+        &rcpt_addr,
+        amount,
+    )?);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::mint(deps.storage, &rcpt_addr, amount)?);
+    Ok(res)
+}
+
+pub fn execute_mint_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    let total = recipients
+        .iter()
+        .try_fold(Uint128::zero(), |total, coin| {
+            total.checked_add(coin.amount)
+        })
+        .map_err(StdError::from)?;
+    #[cfg(feature = "mint-timelock")]
+    record_direct_mint(deps.storage, &env, total)?;
+    authorize_mint(deps.storage, &info.sender, total)?;
 
-    // update supply and enforce cap
-    config.total_supply += amount;
-    if let Some(limit) = config.get_cap() {
-        if config.total_supply > limit {
-            return Err(ContractError::CannotExceedCap {});
+    let mut res = Response::new()
+        .add_attribute("action", "mint_batch")
+        .add_attribute("total", total);
+    for coin in recipients {
+        let rcpt_addr = deps.api.addr_validate(&coin.address)?;
+        credit_mint(deps.storage, &env, &info.sender, &rcpt_addr, coin.amount)?;
+        #[cfg(feature = "events")]
+        {
+            res = res.add_event(events::mint(deps.storage, &rcpt_addr, coin.amount)?);
         }
+        #[cfg(feature = "supply-hooks")]
+        {
+            res = res.add_submessages(supply_hooks::mint_msgs(
+                deps.storage,
+                &info.sender,
+                &rcpt_addr,
+                coin.amount,
+            )?);
+        }
+        res = res
+            .add_attribute("to", coin.address)
+            .add_attribute("amount", coin.amount);
     }
-    TOKEN_INFO.save(deps.storage, &config)?;
+    Ok(res.add_messages(tokenfactory::mint_msgs(deps.storage, &env, total)?))
+}
+
+/// Checks the minter and the cap, then credits `amount` new tokens to `recipient`.
+pub(crate) fn mint(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    authorize_mint(storage, sender, amount)?;
+    credit_mint(storage, env, sender, recipient, amount)
+}
+
+/// Credits `amount` new tokens to `recipient`, once the minter and the cap are checked.
+pub(crate) fn credit_mint(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    #[cfg(feature = "max-wallet")]
+    assert_within_max_wallet(storage, recipient, amount)?;
+    #[cfg(not(feature = "supply-log"))]
+    let _ = sender;
 
     // add amount to recipient balance
-    let rcpt_addr = deps.api.addr_validate(&recipient)?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+    increase_supply(storage, recipient, amount, env.block.height)?;
+    #[cfg(feature = "supply-log")]
+    supply_log::record(
+        storage,
+        &env.block,
+        SupplyAction::Mint,
+        sender,
+        recipient,
+        amount,
     )?;
+    add_balance(storage, recipient, amount, env.block.height)?;
+    Ok(())
+}
 
-    let res = Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
-    Ok(res)
+/// Checks the minter and the cap for `amount` new tokens.
+fn authorize_mint(
+    storage: &dyn Storage,
+    sender: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let mint = MINTER
+        .may_load(storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if mint.minter != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    assert_within_cap(storage, amount)
 }
@@ -0,0 +1,32 @@
+use crate::error::ContractError;
+use crate::interfaces::register_interfaces;
+#[cfg(feature = "supply-hooks")]
+use crate::state::SUPPLY_HOOKS;
+use crate::state::{IMPORTER, MINTER, MINTING_RENOUNCED};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+pub fn execute_renounce_minting(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mint = MINTER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if mint.minter != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    MINTER.remove(deps.storage);
+    MINTING_RENOUNCED.save(deps.storage, &true)?;
+    // an import credits new supply, so it can't outlive the minter
+    IMPORTER.remove(deps.storage);
+    // only the minter manages the supply hooks, so they go with it
+    #[cfg(feature = "supply-hooks")]
+    SUPPLY_HOOKS.clear(deps.storage);
+    register_interfaces(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "renounce_minting")
+        .add_attribute("minter", info.sender))
+}
@@ -0,0 +1,45 @@
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::AssetInfo;
+#[cfg(feature = "rewards")]
+use crate::rewards::assert_not_reward_asset;
+use crate::state::MIRROR_DENOM;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+/// Sends everything the contract holds of a foreign asset to `recipient`.
+/// This token and its mirrored denom back the escrows and the native supply,
+/// so they can't be rescued.
+pub fn execute_rescue(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    let asset = asset.validate(deps.api)?;
+    #[cfg(feature = "rewards")]
+    assert_not_reward_asset(deps.storage, &asset)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+
+    let own_token = match &asset {
+        AssetInfo::Native { denom } => MIRROR_DENOM.may_load(deps.storage)?.as_ref() == Some(denom),
+        AssetInfo::Cw20 { .. } => asset == AssetInfo::cw20(&env.contract.address),
+    };
+    if own_token {
+        return Err(ContractError::CannotRescueOwnToken {});
+    }
+    let amount = asset.query_balance(&deps.querier, &env.contract.address)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRescue {});
+    }
+    let msg = asset.transfer_msg(&rcpt_addr, amount)?;
+
+    let res = Response::new()
+        .add_message(msg)
+        .add_attribute("action", "rescue")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
@@ -1,44 +1,83 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Binary};
+use cosmwasm_std::{
+    from_json, to_json_binary, DepsMut, Env, MessageInfo, Reply, Response, SubMsg,
+    SubMsgResult, Uint128, Binary,
+};
 use cw20::Cw20ReceiveMsg;
+use crate::balances::{revert_transfer, transfer_balance, Reversal};
 use crate::error::ContractError;
-use crate::state::BALANCES;
+use crate::execute::execute_transfer::memo_attributes;
+#[cfg(feature = "send-allowlist")]
+use crate::send_allowlist::assert_send_allowed;
 
+/// Reply id of the receive hook of a safe Send
+pub const SAFE_SEND_REPLY_ID: u64 = 1;
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_send(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     contract: String,
     amount: Uint128,
     msg: Binary,
+    memo: Option<String>,
+    safe: bool,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     let rcpt_addr = deps.api.addr_validate(&contract)?;
+    #[cfg(feature = "send-allowlist")]
+    assert_send_allowed(deps.storage, &rcpt_addr)?;
 
     // move the tokens to the contract
-    BALANCES.update(
-        deps.storage,
-        &info.sender,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
-    )?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
+    let effects = transfer_balance(&mut deps, &env, &info.sender, &rcpt_addr, amount)?;
+
+    let receive = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount: effects.received,
+        msg,
+    }
+    .into_cosmos_msg(&contract)?;
+    let receive = if safe {
+        // a failing receiver gets its tokens taken back instead of failing the transaction,
+        // carrying what the reply has to undo as the payload
+        let payload = effects.reversal(&info.sender, &rcpt_addr, amount);
+        SubMsg::reply_on_error(receive, SAFE_SEND_REPLY_ID).with_payload(to_json_binary(&payload)?)
+    } else {
+        SubMsg::new(receive)
+    };
 
     let res = Response::new()
         .add_attribute("action", "send")
         .add_attribute("from", &info.sender)
         .add_attribute("to", &contract)
         .add_attribute("amount", amount)
-        .add_message(
-            Cw20ReceiveMsg {
-                sender: info.sender.into(),
-                amount,
-                msg,
-            }
-            .into_cosmos_msg(contract)?,
-        );
+        .add_attributes(memo)
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events)
+        .add_submessage(receive);
+    Ok(res)
+}
+
+/// Returns the tokens of a safe Send whose receiver failed to the sender, whole, as if
+/// the Send never happened: burns on the way are minted back and the transfer limit
+/// released.
+pub fn reply_safe_send(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+    };
+    let send: Reversal = from_json(&msg.payload)?;
+    let effects = revert_transfer(&mut deps, &env, &send)?;
+
+    let res = Response::new()
+        .add_attribute("action", "send_refund")
+        .add_attribute("from", &send.to)
+        .add_attribute("to", &send.from)
+        .add_attribute("amount", send.amount)
+        .add_attribute("error", error)
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
     Ok(res)
 }
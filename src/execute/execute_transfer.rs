@@ -1,33 +1,55 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+use cosmwasm_std::{attr, Attribute, DepsMut, Env, MessageInfo, Response, Uint128};
+use crate::balances::transfer_balance;
+#[cfg(feature = "contract-guard")]
+use crate::contract_guard::assert_not_contract;
 use crate::error::ContractError;
-use crate::state::BALANCES;
+use crate::restrictions::transferable_balance;
 
 pub fn execute_transfer(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    let memo = memo_attributes(memo)?;
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    #[cfg(feature = "contract-guard")]
+    assert_not_contract(deps.as_ref(), &rcpt_addr)?;
 
-    BALANCES.update(
-        deps.storage,
-        &info.sender,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
-    )?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
+    let effects = transfer_balance(&mut deps, &env, &info.sender, &rcpt_addr, amount)?;
 
     let res = Response::new()
         .add_attribute("action", "transfer")
         .add_attribute("from", info.sender)
         .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
+        .add_attribute("amount", amount)
+        .add_attributes(memo)
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
     Ok(res)
 }
+
+pub fn execute_transfer_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let amount = transferable_balance(deps.as_ref(), &env, &info.sender)?.spendable;
+    execute_transfer(deps, env, info, recipient, amount, None)
+}
+
+/// Longest memo accepted on a transfer, in bytes
+pub const MAX_MEMO_LENGTH: usize = 256;
+
+/// The `memo` attribute of a transfer, if it has one
+pub fn memo_attributes(memo: Option<String>) -> Result<Vec<Attribute>, ContractError> {
+    match memo {
+        Some(memo) if memo.len() > MAX_MEMO_LENGTH => Err(ContractError::MemoTooLong {}),
+        Some(memo) => Ok(vec![attr("memo", memo)]),
+        None => Ok(vec![]),
+    }
+}
@@ -0,0 +1,45 @@
+use crate::error::ContractError;
+use crate::state::MINTER;
+use crate::supply::total_supply;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn execute_update_cap(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut mint = MINTER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if mint.minter != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if mint.cap_can_only_decrease {
+        let decreases = match (mint.cap, cap) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(old), Some(new)) => new <= old,
+        };
+        if !decreases {
+            return Err(ContractError::CapCanOnlyDecrease {});
+        }
+    }
+    if let Some(cap) = cap {
+        if cap < total_supply(deps.storage)? {
+            return Err(ContractError::CapBelowSupply {});
+        }
+    }
+    mint.cap = cap;
+
+    MINTER.save(deps.storage, &mint)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_cap")
+        .add_attribute(
+            "cap",
+            cap.map(|c| c.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
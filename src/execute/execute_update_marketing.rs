@@ -1,43 +1,81 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Storage};
+use cw20::MarketingInfoResponse;
 use crate::error::ContractError;
+use crate::interfaces::register_interfaces;
+#[cfg(feature = "marketing-admins")]
+use crate::marketing_admins;
+use crate::msg::FieldUpdate;
 use crate::state::MARKETING_INFO;
 
-pub fn execute_update_marketing(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    project: Option<String>,
-    description: Option<String>,
-    marketing: Option<String>,
-) -> Result<Response, ContractError> {
-    let mut marketing_info = MARKETING_INFO
-        .may_load(deps.storage)?
-        .ok_or(ContractError::Unauthorized {})?;
+/// Fails unless `sender` may update the marketing info and logo
+pub(crate) fn assert_marketing(
+    storage: &dyn Storage,
+    marketing_info: &MarketingInfoResponse,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    #[cfg(feature = "marketing-admins")]
+    if marketing_admins::is_marketing_admin(storage, sender) {
+        return Ok(());
+    }
+    #[cfg(not(feature = "marketing-admins"))]
+    let _ = storage;
 
     if marketing_info
         .marketing
         .as_ref()
         .ok_or(ContractError::Unauthorized {})?
-        != info.sender
+        != sender
     {
         return Err(ContractError::Unauthorized {});
     }
+    Ok(())
+}
 
-    match project {
-        Some(empty) if empty.trim().is_empty() => marketing_info.project = None,
-        Some(project) => marketing_info.project = Some(project),
-        None => (),
-    }
+pub fn execute_update_marketing(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    project: FieldUpdate,
+    description: FieldUpdate,
+    marketing: FieldUpdate,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
 
-    match description {
-        Some(empty) if empty.trim().is_empty() => marketing_info.description = None,
-        Some(description) => marketing_info.description = Some(description),
-        None => (),
-    }
+    assert_marketing(deps.storage, &marketing_info, &info.sender)?;
+
+    marketing_info.project = project.apply(marketing_info.project);
+    marketing_info.description = description.apply(marketing_info.description);
 
+    // Some(None) clears the marketing admin
+    let marketing = match marketing {
+        FieldUpdate::Unchanged => None,
+        update => Some(update.apply(None)),
+    };
+    #[cfg(feature = "marketing-admins")]
+    let mut nominee = None;
     match marketing {
-        Some(empty) if empty.trim().is_empty() => marketing_info.marketing = None,
-        Some(marketing) => marketing_info.marketing = Some(deps.api.addr_validate(&marketing)?),
+        Some(None) => {
+            #[cfg(feature = "marketing-admins")]
+            marketing_admins::clear(deps.storage, &marketing_info, &info.sender)?;
+            marketing_info.marketing = None
+        }
+        // the new marketing admin takes over once it accepts
+        #[cfg(feature = "marketing-admins")]
+        Some(Some(marketing)) => {
+            nominee = Some(marketing_admins::nominate(
+                deps.api,
+                deps.storage,
+                &marketing_info,
+                &info.sender,
+                &marketing,
+            )?);
+        }
+        #[cfg(not(feature = "marketing-admins"))]
+        Some(Some(marketing)) => {
+            marketing_info.marketing = Some(deps.api.addr_validate(&marketing)?)
+        }
         None => (),
     }
 
@@ -50,7 +88,10 @@ pub fn execute_update_marketing(
     } else {
         MARKETING_INFO.save(deps.storage, &marketing_info)?;
     }
+    register_interfaces(deps.storage)?;
 
     let res = Response::new().add_attribute("action", "update_marketing");
+    #[cfg(feature = "marketing-admins")]
+    let res = res.add_attributes(nominee.map(|nominee| ("pending_marketing", nominee)));
     Ok(res)
 }
@@ -1,6 +1,7 @@
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use crate::error::ContractError;
-use crate::state::{MinterData, TOKEN_INFO};
+use crate::interfaces::register_interfaces;
+use crate::state::{MinterData, MINTER, MINTING_RENOUNCED};
 
 pub fn execute_update_minter(
     deps: DepsMut,
@@ -8,11 +9,15 @@ pub fn execute_update_minter(
     info: MessageInfo,
     new_minter: Option<String>,
 ) -> Result<Response, ContractError> {
-    let mut config = TOKEN_INFO
+    if MINTING_RENOUNCED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::MintingRenounced {});
+    }
+    let mint = MINTER
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
-
-    let mint = config.mint.as_ref().ok_or(ContractError::Unauthorized {})?;
     if mint.minter != info.sender {
         return Err(ContractError::Unauthorized {});
     }
@@ -23,18 +28,21 @@ pub fn execute_update_minter(
         .map(|minter| MinterData {
             minter,
             cap: mint.cap,
+            cap_can_only_decrease: mint.cap_can_only_decrease,
         });
 
-    config.mint = minter_data;
-
-    TOKEN_INFO.save(deps.storage, &config)?;
+    match &minter_data {
+        Some(data) => MINTER.save(deps.storage, data)?,
+        None => MINTER.remove(deps.storage),
+    }
+    // a token nobody can mint with no longer implements cw20-mintable
+    register_interfaces(deps.storage)?;
 
     Ok(Response::default()
         .add_attribute("action", "update_minter")
         .add_attribute(
             "new_minter",
-            config
-                .mint
+            minter_data
                 .map(|m| m.minter.into_string())
                 .unwrap_or_else(|| "None".to_string()),
         ))
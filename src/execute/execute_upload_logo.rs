@@ -1,9 +1,16 @@
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use cw20::{Logo, LogoInfo};
 use crate::error::ContractError;
+use crate::execute::execute_update_marketing::assert_marketing;
 use crate::state::{LOGO, MARKETING_INFO};
 
 const LOGO_SIZE_CAP: usize = 5 * 1024;
+/// Largest width or height an embedded SVG may declare on its `<svg>` elements
+const SVG_DIMENSION_CAP: u64 = 4096;
+/// Elements that run code or pull other documents in when an explorer renders the logo
+const SVG_FORBIDDEN_ELEMENTS: &[&str] = &["script", "foreignobject", "iframe", "object", "embed"];
+/// Elements that set another attribute of their parent, from `values`, `from`, `to` or `by`
+const SVG_ANIMATION_ELEMENTS: &[&str] = &["animate", "set"];
 
 pub fn execute_upload_logo(
     deps: DepsMut,
@@ -17,14 +24,7 @@ pub fn execute_upload_logo(
 
     verify_logo(&logo)?;
 
-    if marketing_info
-        .marketing
-        .as_ref()
-        .ok_or(ContractError::Unauthorized {})?
-        != info.sender
-    {
-        return Err(ContractError::Unauthorized {});
-    }
+    assert_marketing(deps.storage, &marketing_info, &info.sender)?;
 
     LOGO.save(deps.storage, &logo)?;
 
@@ -54,7 +54,7 @@ fn verify_xml_logo(logo: &[u8]) -> Result<(), ContractError> {
     if logo.len() > LOGO_SIZE_CAP {
         Err(ContractError::LogoTooBig {})
     } else {
-        Ok(())
+        verify_svg_content(logo)
     }
 }
 
@@ -84,3 +84,260 @@ pub fn verify_png_logo(logo: &[u8]) -> Result<(), ContractError> {
         Ok(())
     }
 }
+
+/// Walks the markup tag by tag. This is not a full XML parser, but it understands enough of
+/// the syntax (comments, CDATA, quoted attributes) that nothing can hide from the checks.
+fn verify_svg_content(data: &[u8]) -> Result<(), ContractError> {
+    let mut rest = data;
+    let mut has_svg = false;
+    while let Some(start) = rest.iter().position(|c| *c == b'<') {
+        verify_svg_text(&decode_references(&rest[..start])?)?;
+        rest = &rest[start + 1..];
+        rest = if let Some(pi) = rest.strip_prefix(b"?") {
+            skip_past(pi, b"?>")?
+        } else if let Some(comment) = rest.strip_prefix(b"!--") {
+            skip_past(comment, b"-->")?
+        } else if let Some(cdata) = rest.strip_prefix(b"![CDATA[") {
+            let end = find(cdata, b"]]>").ok_or(ContractError::InvalidSvg {})?;
+            verify_svg_text(&cdata[..end])?;
+            &cdata[end + 3..]
+        } else if rest.starts_with(b"!") {
+            // a DTD can declare entities, which is how billion-laughs payloads get in
+            return Err(ContractError::SvgForbiddenElement {
+                element: "!DOCTYPE".to_owned(),
+            });
+        } else if let Some(closing) = rest.strip_prefix(b"/") {
+            skip_past(closing, b">")?
+        } else {
+            let (is_svg, after) = verify_svg_tag(rest)?;
+            has_svg |= is_svg;
+            after
+        };
+    }
+    verify_svg_text(&decode_references(rest)?)?;
+
+    if has_svg {
+        Ok(())
+    } else {
+        Err(ContractError::InvalidSvg {})
+    }
+}
+
+/// Checks a start tag (everything after its `<`), returning whether it is an `<svg>` element
+/// and the data following the tag
+fn verify_svg_tag(tag: &[u8]) -> Result<(bool, &[u8]), ContractError> {
+    let name_len = tag
+        .iter()
+        .position(|c| c.is_ascii_whitespace() || *c == b'/' || *c == b'>')
+        .ok_or(ContractError::InvalidSvg {})?;
+    let name = svg_name(&tag[..name_len])?;
+    let element = local_name(&name);
+    if SVG_FORBIDDEN_ELEMENTS.contains(&element) {
+        return Err(ContractError::SvgForbiddenElement {
+            element: element.to_owned(),
+        });
+    }
+
+    let mut rest = &tag[name_len..];
+    loop {
+        rest = trim_start(rest);
+        match rest {
+            [b'>', after @ ..] | [b'/', b'>', after @ ..] => return Ok((element == "svg", after)),
+            [] | [b'/', ..] => return Err(ContractError::InvalidSvg {}),
+            _ => {}
+        }
+
+        let name_len = rest
+            .iter()
+            .position(|c| c.is_ascii_whitespace() || matches!(c, b'=' | b'/' | b'>'))
+            .ok_or(ContractError::InvalidSvg {})?;
+        let attribute = svg_name(&rest[..name_len])?;
+        rest = trim_start(&rest[name_len..]);
+        rest = rest.strip_prefix(b"=").ok_or(ContractError::InvalidSvg {})?;
+        rest = trim_start(rest);
+        let (quote, quoted) = rest.split_first().ok_or(ContractError::InvalidSvg {})?;
+        if !matches!(quote, b'"' | b'\'') {
+            return Err(ContractError::InvalidSvg {});
+        }
+        let value_len = quoted
+            .iter()
+            .position(|c| c == quote)
+            .ok_or(ContractError::InvalidSvg {})?;
+        let value = decode_references(&quoted[..value_len])?;
+        let value = String::from_utf8(value).map_err(|_| ContractError::InvalidSvg {})?;
+        rest = &quoted[value_len + 1..];
+
+        verify_svg_attribute(element, &attribute, &value)?;
+    }
+}
+
+/// Checks an attribute of `element`, its value with the character references resolved
+fn verify_svg_attribute(element: &str, attribute: &str, value: &str) -> Result<(), ContractError> {
+    if attribute.starts_with("on") {
+        return Err(ContractError::SvgEventHandler {
+            attribute: attribute.to_owned(),
+        });
+    }
+    if local_name(attribute) == "href" && !value.trim_start().starts_with('#') {
+        return Err(ContractError::SvgExternalReference {});
+    }
+    // an animation could set a link to any of its values, which are not checked like one
+    if SVG_ANIMATION_ELEMENTS.contains(&element)
+        && attribute == "attributename"
+        && local_name(&value.trim().to_ascii_lowercase()) == "href"
+    {
+        return Err(ContractError::SvgExternalReference {});
+    }
+    if element == "svg"
+        && matches!(attribute, "width" | "height")
+        && !svg_dimension_within_cap(value)
+    {
+        return Err(ContractError::SvgTooLarge {
+            max: SVG_DIMENSION_CAP,
+        });
+    }
+    verify_svg_text(value.as_bytes())
+}
+
+/// Rejects CSS that loads anything but fragments of the logo itself, in `<style>` content
+/// as well as `style` attributes. Takes the text with its character references resolved.
+fn verify_svg_text(text: &[u8]) -> Result<(), ContractError> {
+    let text = decode_css_escapes(text).to_ascii_lowercase();
+    if find(&text, b"@import").is_some() {
+        return Err(ContractError::SvgExternalReference {});
+    }
+    let mut rest = &text[..];
+    while let Some(start) = find(rest, b"url(") {
+        rest = trim_start(&rest[start + 4..]);
+        let target = match rest {
+            [b'"' | b'\'', quoted @ ..] => quoted,
+            unquoted => unquoted,
+        };
+        if !target.starts_with(b"#") {
+            return Err(ContractError::SvgExternalReference {});
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the character references of text or an attribute value, as the renderer does
+/// before reading the CSS or link in it. Named entities other than the predefined ones
+/// would need a DTD, so they are invalid.
+fn decode_references(data: &[u8]) -> Result<Vec<u8>, ContractError> {
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut rest = data;
+    while let Some(start) = rest.iter().position(|c| *c == b'&') {
+        decoded.extend_from_slice(&rest[..start]);
+        rest = &rest[start + 1..];
+        let end = rest
+            .iter()
+            .position(|c| *c == b';')
+            .ok_or(ContractError::InvalidSvg {})?;
+        let c = match &rest[..end] {
+            b"lt" => Some('<'),
+            b"gt" => Some('>'),
+            b"amp" => Some('&'),
+            b"quot" => Some('"'),
+            b"apos" => Some('\''),
+            [b'#', b'x' | b'X', hex @ ..] => code_point(hex, 16),
+            [b'#', decimal @ ..] => code_point(decimal, 10),
+            _ => None,
+        };
+        let c = c.ok_or(ContractError::InvalidSvg {})?;
+        decoded.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        rest = &rest[end + 1..];
+    }
+    decoded.extend_from_slice(rest);
+    Ok(decoded)
+}
+
+/// Resolves the escapes of CSS, so `u\72l(` is caught as the `url(` it stands for
+fn decode_css_escapes(text: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.iter().position(|c| *c == b'\\') {
+        decoded.extend_from_slice(&rest[..start]);
+        rest = &rest[start + 1..];
+        let digits = rest
+            .iter()
+            .take(6)
+            .take_while(|c| c.is_ascii_hexdigit())
+            .count();
+        if digits == 0 {
+            // any other character stands for itself, but an escaped newline for nothing
+            match rest.split_first() {
+                Some((b'\n', after)) => rest = after,
+                Some((c, after)) => {
+                    decoded.push(*c);
+                    rest = after;
+                }
+                None => {}
+            }
+            continue;
+        }
+        let c = code_point(&rest[..digits], 16).unwrap_or(char::REPLACEMENT_CHARACTER);
+        decoded.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        rest = &rest[digits..];
+        // a single whitespace ends the escape and belongs to it
+        if let Some((c, after)) = rest.split_first() {
+            if c.is_ascii_whitespace() {
+                rest = after;
+            }
+        }
+    }
+    decoded.extend_from_slice(rest);
+    decoded
+}
+
+fn code_point(digits: &[u8], radix: u32) -> Option<char> {
+    let digits = std::str::from_utf8(digits).ok()?;
+    if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(digits, radix).ok()?)
+}
+
+/// Only the integer part is compared, as floats are not available in wasm. Percentages and
+/// values without a leading number (`auto`) scale with the container and always pass.
+fn svg_dimension_within_cap(value: &str) -> bool {
+    let value = value.trim();
+    let digits = value.bytes().take_while(u8::is_ascii_digit).count();
+    if value.ends_with('%') || digits == 0 {
+        return true;
+    }
+    value[..digits]
+        .parse::<u64>()
+        .is_ok_and(|size| size <= SVG_DIMENSION_CAP)
+}
+
+fn svg_name(name: &[u8]) -> Result<String, ContractError> {
+    if name.is_empty() {
+        return Err(ContractError::InvalidSvg {});
+    }
+    std::str::from_utf8(name)
+        .map(str::to_ascii_lowercase)
+        .map_err(|_| ContractError::InvalidSvg {})
+}
+
+/// Drops the namespace prefix, so `svg:script` and `xlink:href` are caught as well
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn skip_past<'a>(data: &'a [u8], terminator: &[u8]) -> Result<&'a [u8], ContractError> {
+    find(data, terminator)
+        .map(|end| &data[end + terminator.len()..])
+        .ok_or(ContractError::InvalidSvg {})
+}
+
+fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+    data.windows(needle.len()).position(|window| window == needle)
+}
+
+fn trim_start(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    &data[start..]
+}
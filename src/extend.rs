@@ -0,0 +1,34 @@
+/// Declares the `ExecuteMsg` and `QueryMsg` of a contract embedding this token, taking
+/// every message of cw20-base besides its own ones.
+///
+/// ```ignore
+/// cw20_base::cw20_extend!(ExecuteMsg(StakingExecuteMsg), QueryMsg(StakingQueryMsg));
+/// ```
+///
+/// declares `ExecuteMsg::{Custom(StakingExecuteMsg), Cw20(cw20_base::msg::ExecuteMsg)}` and
+/// the same for queries. Both are untagged, so they take the JSON of either enum as is,
+/// with the contract's own messages tried first. The contract routes `Cw20` to
+/// `cw20_base::contract::execute` and `query`, built with the `library` feature. Query
+/// names must not clash, or the schema generation panics.
+///
+/// Needs `cosmwasm-schema` among the dependencies of the embedding contract.
+#[macro_export]
+macro_rules! cw20_extend {
+    ($execute:ident($custom_execute:ty), $query:ident($custom_query:ty) $(,)?) => {
+        #[::cosmwasm_schema::cw_serde]
+        #[serde(untagged)]
+        pub enum $execute {
+            Custom($custom_execute),
+            Cw20($crate::msg::ExecuteMsg),
+        }
+
+        #[::cosmwasm_schema::cw_serde]
+        #[derive(::cosmwasm_schema::QueryResponses)]
+        #[query_responses(nested)]
+        #[serde(untagged)]
+        pub enum $query {
+            Custom($custom_query),
+            Cw20($crate::msg::QueryMsg),
+        }
+    };
+}
@@ -0,0 +1,67 @@
+use cosmwasm_std::{Deps, DepsMut, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+use crate::msg::{MetadataEntry, MetadataResponse};
+use crate::state::EXTENDED_METADATA;
+
+pub const MAX_METADATA_KEY_LENGTH: usize = 64;
+pub const MAX_METADATA_VALUE_LENGTH: usize = 1024;
+
+pub fn execute_set_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+    value: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if key.is_empty() || key.len() > MAX_METADATA_KEY_LENGTH {
+        return Err(ContractError::InvalidMetadataKey {
+            max: MAX_METADATA_KEY_LENGTH,
+        });
+    }
+    if value.len() > MAX_METADATA_VALUE_LENGTH {
+        return Err(ContractError::MetadataValueTooLong {
+            max: MAX_METADATA_VALUE_LENGTH,
+        });
+    }
+
+    EXTENDED_METADATA.save(deps.storage, &key, &value)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_metadata")
+        .add_attribute("key", key);
+    Ok(res)
+}
+
+pub fn execute_remove_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    EXTENDED_METADATA.remove(deps.storage, &key);
+
+    let res = Response::new()
+        .add_attribute("action", "remove_metadata")
+        .add_attribute("key", key);
+    Ok(res)
+}
+
+pub fn query_metadata(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MetadataResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let entries = EXTENDED_METADATA
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(key, value)| MetadataEntry { key, value }))
+        .collect::<StdResult<_>>()?;
+    Ok(MetadataResponse { entries })
+}
@@ -0,0 +1,153 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::balances::add_balance;
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+#[cfg(feature = "max-wallet")]
+use crate::max_wallet::assert_within_max_wallet;
+#[cfg(feature = "mint-timelock")]
+use crate::mint_timelock::assert_below_threshold;
+use crate::msg::FaucetQuotaResponse;
+#[cfg(feature = "supply-log")]
+use crate::msg::SupplyAction;
+use crate::state::{FaucetConfig, FaucetWindow, FAUCET, FAUCET_WINDOWS, MINTER, MINTING_RENOUNCED};
+use crate::supply::{assert_within_cap, increase_supply};
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+#[cfg(feature = "supply-log")]
+use crate::supply_log;
+use crate::tokenfactory;
+
+/// Lets every account mint `faucet.amount` per period from now on. It mints in the name of
+/// the minter, so a token without one can't have a faucet.
+pub fn setup_faucet(storage: &mut dyn Storage, faucet: &FaucetConfig) -> Result<(), ContractError> {
+    if !MINTER.exists(storage) || faucet.amount.is_zero() || faucet.period == 0 {
+        return Err(ContractError::InvalidFaucet {});
+    }
+    // no more than the minter could mint without going through the timelock queue
+    #[cfg(feature = "mint-timelock")]
+    assert_below_threshold(storage, faucet.amount)?;
+    FAUCET.save(storage, faucet)?;
+    Ok(())
+}
+
+/// Window `addr` is in at the current time. A new one opens with the first mint after the
+/// previous one has closed.
+fn current_window(
+    storage: &dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    faucet: &FaucetConfig,
+) -> StdResult<FaucetWindow> {
+    let now = env.block.time;
+    match FAUCET_WINDOWS.may_load(storage, addr)? {
+        Some(window) if now < window.start.plus_seconds(faucet.period) => Ok(window),
+        _ => Ok(FaucetWindow {
+            start: now,
+            minted: Uint128::zero(),
+        }),
+    }
+}
+
+/// Mints the sender whatever is left of its faucet amount for the current window. Still
+/// held to the cap and the max wallet, but not to the minter.
+pub fn execute_faucet_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if MINTING_RENOUNCED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::MintingRenounced {});
+    }
+    let faucet = FAUCET
+        .may_load(deps.storage)?
+        .ok_or(ContractError::FaucetDisabled {})?;
+    let mut window = current_window(deps.storage, &env, &info.sender, &faucet)?;
+    let amount = faucet.amount.saturating_sub(window.minted);
+    if amount.is_zero() {
+        return Err(ContractError::FaucetLimitReached {});
+    }
+    assert_within_cap(deps.storage, amount)?;
+    #[cfg(feature = "max-wallet")]
+    assert_within_max_wallet(deps.storage, &info.sender, amount)?;
+
+    window.minted += amount;
+    FAUCET_WINDOWS.save(deps.storage, &info.sender, &window)?;
+    increase_supply(deps.storage, &info.sender, amount, env.block.height)?;
+    #[cfg(feature = "supply-log")]
+    supply_log::record(
+        deps.storage,
+        &env.block,
+        SupplyAction::Mint,
+        &info.sender,
+        &info.sender,
+        amount,
+    )?;
+    add_balance(deps.storage, &info.sender, amount, env.block.height)?;
+
+    let res = Response::new()
+        .add_attribute("action", "faucet_mint")
+        .add_attribute("to", &info.sender)
+        .add_attribute("amount", amount)
+        .add_messages(tokenfactory::mint_msgs(deps.storage, &env, amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::mint_msgs(
+        deps.storage,
+        &info.sender,
+        &info.sender,
+        amount,
+    )?);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::mint(deps.storage, &info.sender, amount)?);
+    Ok(res)
+}
+
+pub fn execute_update_faucet(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    faucet: Option<FaucetConfig>,
+) -> Result<Response, ContractError> {
+    match MINTER.may_load(deps.storage)? {
+        Some(mint) if mint.minter == info.sender => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+    let mut res = Response::new().add_attribute("action", "update_faucet");
+    match faucet {
+        Some(faucet) => {
+            setup_faucet(deps.storage, &faucet)?;
+            res = res
+                .add_attribute("amount", faucet.amount)
+                .add_attribute("period", faucet.period.to_string());
+        }
+        None => {
+            FAUCET.remove(deps.storage);
+            res = res.add_attribute("amount", "None");
+        }
+    }
+    Ok(res)
+}
+
+pub fn query_faucet_quota(deps: Deps, env: Env, address: String) -> StdResult<FaucetQuotaResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let faucet = match FAUCET.may_load(deps.storage)? {
+        Some(faucet) => faucet,
+        None => {
+            return Ok(FaucetQuotaResponse {
+                faucet: None,
+                remaining: None,
+                window_end: None,
+            })
+        }
+    };
+    let window = current_window(deps.storage, &env, &addr, &faucet)?;
+    Ok(FaucetQuotaResponse {
+        remaining: Some(faucet.amount.saturating_sub(window.minted)),
+        window_end: Some(window.start.plus_seconds(faucet.period)),
+        faucet: Some(faucet),
+    })
+}
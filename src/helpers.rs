@@ -0,0 +1,303 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, CustomQuery, QuerierWrapper, StdError,
+    StdResult, Uint128, WasmQuery,
+};
+#[cfg(feature = "enumerable")]
+use cw20::AllSpenderAllowancesResponse;
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, Cw20Contract, Cw20ExecuteMsg,
+    Cw20QueryMsg, DownloadLogoResponse, Expiration, MarketingInfoResponse,
+};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[cfg(feature = "enumerable")]
+use crate::msg::QueryMsg;
+use crate::state::{
+    TokenMetadata, ALLOWANCES_KEY, BALANCES_KEY, TOKEN_METADATA_KEY, TOTAL_SUPPLY_KEY,
+};
+
+/// Typed messages and queries for the rest of the cw20 surface, on top of the transfer
+/// and balance helpers of [`Cw20Contract`]. Works with any cw20 token, but the queries
+/// of an extension fail on tokens built without it.
+pub trait Cw20ContractExt {
+    fn transfer(&self, recipient: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg>;
+    fn send(
+        &self,
+        contract: impl Into<String>,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg>;
+    fn burn(&self, amount: Uint128) -> StdResult<CosmosMsg>;
+    fn increase_allowance(
+        &self,
+        spender: impl Into<String>,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg>;
+    fn decrease_allowance(
+        &self,
+        spender: impl Into<String>,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg>;
+    fn transfer_from(
+        &self,
+        owner: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg>;
+    fn send_from(
+        &self,
+        owner: impl Into<String>,
+        contract: impl Into<String>,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg>;
+    fn burn_from(&self, owner: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg>;
+    fn mint(&self, recipient: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg>;
+
+    fn all_allowances<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        owner: impl Into<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAllowancesResponse>;
+    #[cfg(feature = "enumerable")]
+    fn all_spender_allowances<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        spender: impl Into<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllSpenderAllowancesResponse>;
+    fn all_accounts<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse>;
+    fn marketing_info<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<MarketingInfoResponse>;
+    fn download_logo<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<DownloadLogoResponse>;
+}
+
+impl Cw20ContractExt for Cw20Contract {
+    fn transfer(&self, recipient: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::Transfer {
+            recipient: recipient.into(),
+            amount,
+        })
+    }
+
+    fn send(
+        &self,
+        contract: impl Into<String>,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::Send {
+            contract: contract.into(),
+            amount,
+            msg,
+        })
+    }
+
+    fn burn(&self, amount: Uint128) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::Burn { amount })
+    }
+
+    fn increase_allowance(
+        &self,
+        spender: impl Into<String>,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::IncreaseAllowance {
+            spender: spender.into(),
+            amount,
+            expires,
+        })
+    }
+
+    fn decrease_allowance(
+        &self,
+        spender: impl Into<String>,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::DecreaseAllowance {
+            spender: spender.into(),
+            amount,
+            expires,
+        })
+    }
+
+    fn transfer_from(
+        &self,
+        owner: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::TransferFrom {
+            owner: owner.into(),
+            recipient: recipient.into(),
+            amount,
+        })
+    }
+
+    fn send_from(
+        &self,
+        owner: impl Into<String>,
+        contract: impl Into<String>,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::SendFrom {
+            owner: owner.into(),
+            contract: contract.into(),
+            amount,
+            msg,
+        })
+    }
+
+    fn burn_from(&self, owner: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::BurnFrom {
+            owner: owner.into(),
+            amount,
+        })
+    }
+
+    fn mint(&self, recipient: impl Into<String>, amount: Uint128) -> StdResult<CosmosMsg> {
+        self.call(Cw20ExecuteMsg::Mint {
+            recipient: recipient.into(),
+            amount,
+        })
+    }
+
+    fn all_allowances<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        owner: impl Into<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAllowancesResponse> {
+        let msg = Cw20QueryMsg::AllAllowances {
+            owner: owner.into(),
+            start_after,
+            limit,
+        };
+        smart_query(self, querier, &msg)
+    }
+
+    #[cfg(feature = "enumerable")]
+    fn all_spender_allowances<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        spender: impl Into<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllSpenderAllowancesResponse> {
+        // not part of the cw20 query message
+        let msg = QueryMsg::AllSpenderAllowances {
+            spender: spender.into(),
+            start_after,
+            limit,
+        };
+        smart_query(self, querier, &msg)
+    }
+
+    fn all_accounts<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        let msg = Cw20QueryMsg::AllAccounts { start_after, limit };
+        smart_query(self, querier, &msg)
+    }
+
+    fn marketing_info<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<MarketingInfoResponse> {
+        smart_query(self, querier, &Cw20QueryMsg::MarketingInfo {})
+    }
+
+    fn download_logo<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<DownloadLogoResponse> {
+        smart_query(self, querier, &Cw20QueryMsg::DownloadLogo {})
+    }
+}
+
+fn smart_query<CQ: CustomQuery, T: DeserializeOwned>(
+    contract: &Cw20Contract,
+    querier: &QuerierWrapper<CQ>,
+    msg: &(impl Serialize + ?Sized),
+) -> StdResult<T> {
+    querier.query(
+        &WasmQuery::Smart {
+            contract_addr: contract.addr().into(),
+            msg: to_json_binary(msg)?,
+        }
+        .into(),
+    )
+}
+
+/// Balance of `addr` read straight from the storage of `token`, far cheaper than a smart
+/// query. In share mode it's the balance in shares.
+pub fn query_balance_raw<CQ: CustomQuery>(
+    querier: &QuerierWrapper<CQ>,
+    token: &Addr,
+    addr: &Addr,
+) -> StdResult<Uint128> {
+    let key = Map::<&Addr, Uint128>::new(BALANCES_KEY).key(addr);
+    Ok(raw_query(querier, token, &key)?.unwrap_or_default())
+}
+
+pub fn query_allowance_raw<CQ: CustomQuery>(
+    querier: &QuerierWrapper<CQ>,
+    token: &Addr,
+    owner: &Addr,
+    spender: &Addr,
+) -> StdResult<AllowanceResponse> {
+    let key = Map::<(&Addr, &Addr), AllowanceResponse>::new(ALLOWANCES_KEY).key((owner, spender));
+    Ok(raw_query(querier, token, &key)?.unwrap_or_default())
+}
+
+pub fn query_token_metadata_raw<CQ: CustomQuery>(
+    querier: &QuerierWrapper<CQ>,
+    token: &Addr,
+) -> StdResult<TokenMetadata> {
+    raw_query(querier, token, TOKEN_METADATA_KEY.as_bytes())?
+        .ok_or_else(|| StdError::not_found("TokenMetadata"))
+}
+
+/// Total supply of `token`. Tokens with supply shards don't keep it under this key, so
+/// those need the smart query.
+pub fn query_total_supply_raw<CQ: CustomQuery>(
+    querier: &QuerierWrapper<CQ>,
+    token: &Addr,
+) -> StdResult<Uint128> {
+    Ok(raw_query(querier, token, TOTAL_SUPPLY_KEY.as_bytes())?.unwrap_or_default())
+}
+
+fn raw_query<CQ: CustomQuery, T: DeserializeOwned>(
+    querier: &QuerierWrapper<CQ>,
+    token: &Addr,
+    key: &[u8],
+) -> StdResult<Option<T>> {
+    querier
+        .query_wasm_raw(token, key.to_vec())?
+        .map(|value| from_json(value))
+        .transpose()
+}
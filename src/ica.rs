@@ -0,0 +1,126 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, to_json_vec, Binary, StdResult};
+
+use crate::msg::ExecuteMsg;
+
+/// Type URL of the wasm message an interchain account executes a token message with
+pub const MSG_EXECUTE_CONTRACT: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+/// `InterchainAccountPacketData` of ICS-27, the packet a controller chain sends over the
+/// channel of an interchain account
+#[cw_serde]
+pub struct IcaPacketData {
+    #[serde(rename = "type")]
+    pub packet_type: String,
+    /// The protobuf encoded `CosmosTx` the host executes
+    pub data: Binary,
+    pub memo: String,
+}
+
+impl IcaPacketData {
+    pub const TYPE_EXECUTE_TX: &'static str = "TYPE_EXECUTE_TX";
+
+    pub fn execute_tx(data: Binary, memo: impl Into<String>) -> Self {
+        IcaPacketData {
+            packet_type: Self::TYPE_EXECUTE_TX.to_string(),
+            data,
+            memo: memo.into(),
+        }
+    }
+}
+
+/// Token messages an interchain account sends to `token` on its host chain, executed in
+/// order and atomically by the host. Encoded for channels opened with the default
+/// `proto3` encoding.
+pub struct IcaCw20Tx {
+    pub interchain_account: String,
+    pub token: String,
+    pub msgs: Vec<ExecuteMsg>,
+    pub memo: String,
+}
+
+impl IcaCw20Tx {
+    pub fn new(interchain_account: impl Into<String>, token: impl Into<String>) -> Self {
+        IcaCw20Tx {
+            interchain_account: interchain_account.into(),
+            token: token.into(),
+            msgs: vec![],
+            memo: String::new(),
+        }
+    }
+
+    pub fn with_msg(mut self, msg: ExecuteMsg) -> Self {
+        self.msgs.push(msg);
+        self
+    }
+
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = memo.into();
+        self
+    }
+
+    /// The `CosmosTx` of the packet, a `MsgExecuteContract` for each message
+    pub fn cosmos_tx(&self) -> StdResult<Binary> {
+        let mut tx = proto::Writer::default();
+        for msg in &self.msgs {
+            let execute = proto::Writer::default()
+                .string(1, &self.interchain_account)
+                .string(2, &self.token)
+                .bytes(3, &to_json_vec(msg)?);
+            let any = proto::Writer::default()
+                .string(1, MSG_EXECUTE_CONTRACT)
+                .message(2, execute);
+            tx = tx.message(1, any);
+        }
+        Ok(tx.into_binary())
+    }
+
+    pub fn packet_data(&self) -> StdResult<IcaPacketData> {
+        Ok(IcaPacketData::execute_tx(self.cosmos_tx()?, &self.memo))
+    }
+
+    /// The JSON packet data, as sent in an `IbcMsg::SendPacket` or a `MsgSendTx`
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_json_binary(&self.packet_data()?)
+    }
+}
+
+/// Minimal protobuf encoding, just enough for a `CosmosTx` of wasm messages.
+mod proto {
+    use cosmwasm_std::Binary;
+
+    #[derive(Default)]
+    pub struct Writer(Vec<u8>);
+
+    impl Writer {
+        pub fn string(self, field: u64, value: &str) -> Self {
+            self.bytes(field, value.as_bytes())
+        }
+
+        pub fn message(self, field: u64, value: Writer) -> Self {
+            self.bytes(field, &value.0)
+        }
+
+        pub fn into_binary(self) -> Binary {
+            self.0.into()
+        }
+
+        pub fn bytes(mut self, field: u64, value: &[u8]) -> Self {
+            // proto3 omits empty length-delimited fields
+            if !value.is_empty() {
+                self.varint(field << 3 | 2);
+                self.varint(value.len() as u64);
+                self.0.extend_from_slice(value);
+            }
+            self
+        }
+
+        fn varint(&mut self, mut value: u64) {
+            while value >= 0x80 {
+                self.0.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            self.0.push(value as u8);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use cosmwasm_std::{
+    instantiate2_address, to_json_binary, Addr, Api, Binary, Checksum, Coin, QuerierWrapper,
+    StdError, StdResult, WasmMsg,
+};
+use sha2::{Digest, Sha256};
+
+use crate::msg::InstantiateMsg;
+
+/// Salt for the `nonce`-th token with `symbol` a factory creates. Hashed, so it fits the
+/// 64 byte limit of the chain whatever the symbol.
+pub fn token_salt(symbol: &str, nonce: u64) -> Binary {
+    let mut hasher = Sha256::new();
+    hasher.update(symbol.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    Binary::from(hasher.finalize().to_vec())
+}
+
+/// Checksum of the wasm stored as `code_id`, the one address predictions depend on
+pub fn code_checksum(querier: &QuerierWrapper, code_id: u64) -> StdResult<Checksum> {
+    Ok(querier.query_wasm_code_info(code_id)?.checksum)
+}
+
+/// Address `creator` gets when it instantiates the code with `checksum` using `salt`
+pub fn predict_token_address(
+    api: &dyn Api,
+    checksum: &Checksum,
+    creator: &Addr,
+    salt: &[u8],
+) -> StdResult<Addr> {
+    let creator = api.addr_canonicalize(creator.as_str())?;
+    let addr = instantiate2_address(checksum.as_slice(), &creator, salt)
+        .map_err(|err| StdError::generic_err(format!("Instantiate2: {err}")))?;
+    api.addr_humanize(&addr)
+}
+
+/// `WasmMsg::Instantiate2` of a cw20-base token, with the label defaulting to the
+/// symbol and no admin unless set
+pub struct TokenInstantiate2 {
+    pub code_id: u64,
+    pub msg: InstantiateMsg,
+    pub salt: Binary,
+    pub label: String,
+    pub admin: Option<String>,
+    pub funds: Vec<Coin>,
+}
+
+impl TokenInstantiate2 {
+    pub fn new(code_id: u64, msg: InstantiateMsg, salt: Binary) -> Self {
+        TokenInstantiate2 {
+            code_id,
+            label: msg.symbol.clone(),
+            msg,
+            salt,
+            admin: None,
+            funds: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_admin(mut self, admin: impl Into<String>) -> Self {
+        self.admin = Some(admin.into());
+        self
+    }
+
+    pub fn with_funds(mut self, funds: Vec<Coin>) -> Self {
+        self.funds = funds;
+        self
+    }
+
+    /// Address the message will instantiate the token at, when sent by `creator`
+    pub fn address(&self, api: &dyn Api, checksum: &Checksum, creator: &Addr) -> StdResult<Addr> {
+        predict_token_address(api, checksum, creator, &self.salt)
+    }
+
+    pub fn into_msg(self) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Instantiate2 {
+            admin: self.admin,
+            code_id: self.code_id,
+            label: self.label,
+            msg: to_json_binary(&self.msg)?,
+            funds: self.funds,
+            salt: self.salt,
+        })
+    }
+}
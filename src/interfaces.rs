@@ -0,0 +1,212 @@
+use cosmwasm_std::{Deps, Order, StdResult, Storage};
+use cw_storage_plus::Map;
+
+use crate::msg::{
+    BuildInfo, ContractInfoResponse, FeaturesResponse, SupportedInterface,
+    SupportedInterfacesResponse,
+};
+use crate::state::{
+    ADMIN, AUTO_BURN, BURN_SINK, EXCHANGE_RATE, FAUCET, HOLDER_COUNT, IMPORTER, MARKETING_INFO,
+    MAX_WALLET, MINTER, MINTING_RENOUNCED, MINT_TIMELOCK, MIN_TRANSFER_AMOUNT, MIRROR_DENOM,
+    REJECT_CONTRACT_TRANSFERS, REWARD_ASSET, SEND_ALLOWLIST_ENABLED, SUPPLY_LOG_CAPACITY,
+    SUPPLY_SHARD_COUNT, TOKEN_INFO_FROZEN, TRADING_ENABLED, TRANSFER_LIMIT,
+    TRANSFER_RESTRICTION_CONTRACT,
+};
+
+/// Same layout as cw22, so its `query_supported_interface` works on this token
+const SUPPORTED_INTERFACES: Map<&str, String> = Map::new("supported_interfaces");
+
+/// Version of the cw20 spec this token implements
+const CW20_VERSION: &str = "2.0.0";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Optional subsystems compiled into this build
+pub const EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "allowance-hooks")]
+    "allowance-hooks",
+    #[cfg(feature = "allowance-limits")]
+    "allowance-limits",
+    #[cfg(feature = "allowance-locks")]
+    "allowance-locks",
+    #[cfg(feature = "auto-burn")]
+    "auto-burn",
+    #[cfg(feature = "balance-proofs")]
+    "balance-proofs",
+    #[cfg(feature = "burn-sink")]
+    "burn-sink",
+    #[cfg(feature = "burn-stats")]
+    "burn-stats",
+    #[cfg(feature = "changelog")]
+    "changelog",
+    #[cfg(feature = "compliance")]
+    "compliance",
+    #[cfg(feature = "contract-guard")]
+    "contract-guard",
+    #[cfg(feature = "events")]
+    "events",
+    #[cfg(feature = "exchange-rate")]
+    "exchange-rate",
+    #[cfg(feature = "extended-metadata")]
+    "extended-metadata",
+    #[cfg(feature = "faucet")]
+    "faucet",
+    #[cfg(feature = "launch-guard")]
+    "launch-guard",
+    #[cfg(feature = "logo-info")]
+    "logo-info",
+    #[cfg(feature = "marketing-admins")]
+    "marketing-admins",
+    #[cfg(feature = "max-wallet")]
+    "max-wallet",
+    #[cfg(feature = "meta-tx")]
+    "meta-tx",
+    #[cfg(feature = "min-transfer")]
+    "min-transfer",
+    #[cfg(feature = "mint-timelock")]
+    "mint-timelock",
+    #[cfg(feature = "multi-token")]
+    "multi-token",
+    #[cfg(feature = "rebrand")]
+    "rebrand",
+    #[cfg(feature = "rescale-decimals")]
+    "rescale-decimals",
+    #[cfg(feature = "rescue")]
+    "rescue",
+    #[cfg(feature = "rewards")]
+    "rewards",
+    #[cfg(feature = "sampling")]
+    "sampling",
+    #[cfg(feature = "scheduled")]
+    "scheduled",
+    #[cfg(feature = "send-allowlist")]
+    "send-allowlist",
+    #[cfg(feature = "snapshots")]
+    "snapshots",
+    #[cfg(feature = "state-export")]
+    "state-export",
+    #[cfg(feature = "streams")]
+    "streams",
+    #[cfg(feature = "subscriptions")]
+    "subscriptions",
+    #[cfg(feature = "supply-hooks")]
+    "supply-hooks",
+    #[cfg(feature = "supply-log")]
+    "supply-log",
+    #[cfg(feature = "supply-shards")]
+    "supply-shards",
+    #[cfg(feature = "transfer-limits")]
+    "transfer-limits",
+    #[cfg(feature = "vesting")]
+    "vesting",
+    #[cfg(feature = "votes")]
+    "votes",
+];
+
+/// Records the interfaces this token supports, as configured now: the cw20 spec and
+/// its extensions, then every cw20-plus subsystem compiled in.
+pub fn register_interfaces(storage: &mut dyn Storage) -> StdResult<()> {
+    let previous = SUPPORTED_INTERFACES
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for interface in previous {
+        SUPPORTED_INTERFACES.remove(storage, &interface);
+    }
+
+    let mut interfaces = vec!["crates.io:cw20", "crates.io:cw20-allowances"];
+    if cfg!(feature = "mintable") && MINTER.exists(storage) {
+        interfaces.push("crates.io:cw20-mintable");
+    }
+    if cfg!(feature = "marketing") && MARKETING_INFO.exists(storage) {
+        interfaces.push("crates.io:cw20-marketing");
+    }
+    if cfg!(feature = "enumerable") {
+        interfaces.push("crates.io:cw20-enumerable");
+    }
+    for interface in interfaces {
+        SUPPORTED_INTERFACES.save(storage, interface, &CW20_VERSION.to_string())?;
+    }
+    for extension in EXTENSIONS {
+        let interface = format!("cw20-plus:{extension}");
+        SUPPORTED_INTERFACES.save(storage, &interface, &CONTRACT_VERSION.to_string())?;
+    }
+    Ok(())
+}
+
+pub fn query_supported_interfaces(deps: Deps) -> StdResult<SupportedInterfacesResponse> {
+    let interfaces = SUPPORTED_INTERFACES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(supported_interface, version)| SupportedInterface {
+                supported_interface,
+                version,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(SupportedInterfacesResponse { interfaces })
+}
+
+/// What this token has switched on, read from its configuration
+pub fn query_features(deps: Deps) -> StdResult<FeaturesResponse> {
+    let storage = deps.storage;
+    let minter = MINTER.may_load(storage)?;
+    Ok(FeaturesResponse {
+        mintable: cfg!(feature = "mintable") && minter.is_some(),
+        capped: minter.and_then(|m| m.cap).is_some(),
+        minting_renounced: MINTING_RENOUNCED.may_load(storage)?.unwrap_or_default(),
+        marketing: MARKETING_INFO.exists(storage),
+        admin: ADMIN.exists(storage),
+        native_mirror: MIRROR_DENOM.exists(storage),
+        auto_burn: AUTO_BURN.exists(storage),
+        burn_sink: BURN_SINK.exists(storage),
+        mint_timelock: MINT_TIMELOCK.exists(storage),
+        holder_sampling: HOLDER_COUNT.exists(storage),
+        supply_shards: SUPPLY_SHARD_COUNT.exists(storage),
+        supply_log: SUPPLY_LOG_CAPACITY.exists(storage),
+        send_allowlist: SEND_ALLOWLIST_ENABLED
+            .may_load(storage)?
+            .unwrap_or_default(),
+        transfer_restriction_contract: TRANSFER_RESTRICTION_CONTRACT.exists(storage),
+        transfer_limit: TRANSFER_LIMIT.exists(storage),
+        min_transfer_amount: MIN_TRANSFER_AMOUNT.exists(storage),
+        reject_contract_transfers: REJECT_CONTRACT_TRANSFERS
+            .may_load(storage)?
+            .unwrap_or_default(),
+        faucet: FAUCET.exists(storage),
+        max_wallet: MAX_WALLET.exists(storage),
+        trading_enabled: TRADING_ENABLED.may_load(storage)?.unwrap_or(true),
+        rewards: REWARD_ASSET.exists(storage),
+        exchange_rate: EXCHANGE_RATE.may_load(storage)?.is_some(),
+        token_info_updatable: cfg!(feature = "rebrand")
+            && ADMIN.exists(storage)
+            && !TOKEN_INFO_FROZEN.may_load(storage)?.unwrap_or_default(),
+        import_open: IMPORTER.exists(storage),
+    })
+}
+
+pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let stored = cw2::get_contract_version(deps.storage)?;
+    let classic = [
+        ("enumerable", cfg!(feature = "enumerable")),
+        ("marketing", cfg!(feature = "marketing")),
+        ("mintable", cfg!(feature = "mintable")),
+        ("tokenfactory", cfg!(feature = "tokenfactory")),
+    ];
+    let mut features: Vec<String> = classic
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| feature)
+        .chain(EXTENSIONS.iter().copied())
+        .map(String::from)
+        .collect();
+    features.sort();
+    Ok(ContractInfoResponse {
+        contract: stored.contract,
+        version: stored.version,
+        build: BuildInfo {
+            version: CONTRACT_VERSION.to_string(),
+            git_hash: option_env!("CW20_PLUS_GIT_HASH").map(String::from),
+            rustc: option_env!("CW20_PLUS_RUSTC_VERSION").map(String::from),
+            features,
+        },
+    })
+}
@@ -0,0 +1,399 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::helpers::Cw20ContractExt;
+use crate::msg::ExecuteMsg;
+use crate::test_utils::{Cw20TestSuite, Cw20TestSuiteBuilder};
+
+const ACCOUNTS: usize = 4;
+const INITIAL_BALANCE: u128 = 1_000_000;
+
+/// Runs `steps` random operations on a token from `builder` for every seed in `seeds`,
+/// checking the supply, balance and allowance invariants after each. A failure panics
+/// with the seed, to go back to it with [`check_invariants_with_seed`].
+#[track_caller]
+pub fn check_invariants(builder: &Cw20TestSuiteBuilder, seeds: Range<u64>, steps: usize) {
+    for seed in seeds {
+        check_invariants_with_seed(builder, seed, steps);
+    }
+}
+
+#[track_caller]
+pub fn check_invariants_with_seed(builder: &Cw20TestSuiteBuilder, seed: u64, steps: usize) {
+    let mut builder = builder.clone();
+    for i in 0..ACCOUNTS {
+        builder = builder.with_balance(&account_name(i), INITIAL_BALANCE);
+    }
+    let mut suite = builder.build();
+    let accounts: Vec<Addr> = (0..ACCOUNTS)
+        .map(|i| suite.addr(&account_name(i)))
+        .collect();
+    let mut rng = SplitMix64(seed);
+
+    for step in 0..steps {
+        let op = Op::random(&mut rng, &suite, &accounts);
+        let before = Snapshot::take(&suite, &accounts);
+        let sender = op.sender(&suite.minter);
+        let succeeded = suite.execute(&sender, &op.msg()).is_ok();
+        let after = Snapshot::take(&suite, &accounts);
+        if let Err(broken) = op.check(succeeded, &suite.minter, &before, &after) {
+            panic!("invariant broken at seed {seed}, step {step}, after {op}: {broken}");
+        }
+    }
+}
+
+fn account_name(i: usize) -> String {
+    format!("account{i}")
+}
+
+/// One of the core operations, between the accounts of a run
+#[derive(Clone, Debug)]
+pub enum Op {
+    Transfer {
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
+    Burn {
+        from: Addr,
+        amount: Uint128,
+    },
+    Mint {
+        to: Addr,
+        amount: Uint128,
+    },
+    IncreaseAllowance {
+        owner: Addr,
+        spender: Addr,
+        amount: Uint128,
+    },
+    DecreaseAllowance {
+        owner: Addr,
+        spender: Addr,
+        amount: Uint128,
+    },
+    TransferFrom {
+        spender: Addr,
+        owner: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
+    BurnFrom {
+        spender: Addr,
+        owner: Addr,
+        amount: Uint128,
+    },
+}
+
+impl Op {
+    fn random(rng: &mut SplitMix64, suite: &Cw20TestSuite, accounts: &[Addr]) -> Self {
+        let mut pick = || accounts[rng.below(accounts.len() as u64) as usize].clone();
+        let (a, b, c) = (pick(), pick(), pick());
+        let kind = rng.below(7);
+        // mostly amounts that fit what's there, sometimes zero or too much
+        let mut amount = |available: Uint128| {
+            let amount = match rng.below(10) {
+                0 => 0,
+                1 => available.u128() + 1 + rng.below(1_000) as u128,
+                _ => rng.below(available.u128().min(u64::MAX.into()) as u64 + 1) as u128,
+            };
+            Uint128::new(amount)
+        };
+        match kind {
+            0 => Op::Transfer {
+                amount: amount(suite.balance(&a)),
+                from: a,
+                to: b,
+            },
+            1 => Op::Burn {
+                amount: amount(suite.balance(&a)),
+                from: a,
+            },
+            2 => Op::Mint {
+                to: a,
+                amount: amount(Uint128::new(INITIAL_BALANCE)),
+            },
+            3 => Op::IncreaseAllowance {
+                owner: a,
+                spender: b,
+                amount: amount(Uint128::new(INITIAL_BALANCE)),
+            },
+            4 => Op::DecreaseAllowance {
+                amount: amount(suite.allowance(&a, &b)),
+                owner: a,
+                spender: b,
+            },
+            5 => Op::TransferFrom {
+                amount: amount(suite.allowance(&b, &a).min(suite.balance(&b))),
+                spender: a,
+                owner: b,
+                to: c,
+            },
+            _ => Op::BurnFrom {
+                amount: amount(suite.allowance(&b, &a).min(suite.balance(&b))),
+                spender: a,
+                owner: b,
+            },
+        }
+    }
+
+    fn sender(&self, minter: &Addr) -> Addr {
+        let sender = match self {
+            Op::Transfer { from, .. } | Op::Burn { from, .. } => from,
+            Op::Mint { .. } => minter,
+            Op::IncreaseAllowance { owner, .. } | Op::DecreaseAllowance { owner, .. } => owner,
+            Op::TransferFrom { spender, .. } | Op::BurnFrom { spender, .. } => spender,
+        };
+        sender.clone()
+    }
+
+    fn msg(&self) -> ExecuteMsg {
+        match self.clone() {
+            Op::Transfer { to, amount, .. } => ExecuteMsg::Transfer {
+                recipient: to.into(),
+                amount,
+                memo: None,
+            },
+            Op::Burn { amount, .. } => ExecuteMsg::Burn { amount, memo: None },
+            Op::Mint { to, amount } => ExecuteMsg::Mint {
+                recipient: to.into(),
+                amount,
+            },
+            Op::IncreaseAllowance {
+                spender, amount, ..
+            } => ExecuteMsg::IncreaseAllowance {
+                spender: spender.into(),
+                amount,
+                expires: None,
+            },
+            Op::DecreaseAllowance {
+                spender, amount, ..
+            } => ExecuteMsg::DecreaseAllowance {
+                spender: spender.into(),
+                amount,
+                expires: None,
+            },
+            Op::TransferFrom {
+                owner, to, amount, ..
+            } => ExecuteMsg::TransferFrom {
+                owner: owner.into(),
+                recipient: to.into(),
+                amount,
+                memo: None,
+            },
+            Op::BurnFrom { owner, amount, .. } => ExecuteMsg::BurnFrom {
+                owner: owner.into(),
+                amount,
+                memo: None,
+            },
+        }
+    }
+
+    /// The invariants are loose enough for forks taking fees or burning a slice of
+    /// transfers: they bound what moves rather than fix it.
+    fn check(
+        &self,
+        succeeded: bool,
+        minter: &Addr,
+        before: &Snapshot,
+        after: &Snapshot,
+    ) -> Result<(), String> {
+        if after.supply != after.balances.values().sum::<Uint128>() {
+            return Err(format!(
+                "supply {} isn't the sum of the balances {:?}",
+                after.supply, after.balances
+            ));
+        }
+        if !succeeded {
+            if before != after {
+                return Err("a failed operation changed the state".to_string());
+            }
+            return Ok(());
+        }
+        if after.supply > before.supply && !matches!(self, Op::Mint { .. }) {
+            return Err("the supply grew without a mint".to_string());
+        }
+        match self {
+            Op::Transfer { from, to, amount } => {
+                before.check_spent(after, from, to, *amount)?;
+            }
+            Op::Burn { from, amount } => {
+                if before.balance(from) - after.balance(from) != *amount
+                    || before.supply - after.supply != *amount
+                {
+                    return Err("the burn didn't take the amount off the balance".to_string());
+                }
+            }
+            Op::Mint { to, amount } => {
+                if after.supply - before.supply != *amount
+                    || after.balance(to) - before.balance(to) != *amount
+                {
+                    return Err(format!("the mint by {minter} didn't add the amount"));
+                }
+            }
+            Op::IncreaseAllowance {
+                owner,
+                spender,
+                amount,
+            } => {
+                let expected = before.allowance(owner, spender) + amount;
+                if after.allowance(owner, spender) != expected || before.balances != after.balances
+                {
+                    return Err("the allowance didn't grow by the amount".to_string());
+                }
+            }
+            Op::DecreaseAllowance {
+                owner,
+                spender,
+                amount,
+            } => {
+                let expected = before.allowance(owner, spender).saturating_sub(*amount);
+                if after.allowance(owner, spender) != expected || before.balances != after.balances
+                {
+                    return Err("the allowance didn't shrink by the amount".to_string());
+                }
+            }
+            Op::TransferFrom {
+                spender,
+                owner,
+                to,
+                amount,
+            } => {
+                before.check_allowance_spent(after, owner, spender, *amount)?;
+                before.check_spent(after, owner, to, *amount)?;
+            }
+            Op::BurnFrom {
+                spender,
+                owner,
+                amount,
+            } => {
+                before.check_allowance_spent(after, owner, spender, *amount)?;
+                if before.balance(owner) - after.balance(owner) != *amount {
+                    return Err("the burn didn't take the amount off the balance".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The balances of every holder, the allowances between the accounts of a run and the
+/// total supply
+#[derive(PartialEq, Debug)]
+struct Snapshot {
+    supply: Uint128,
+    balances: BTreeMap<Addr, Uint128>,
+    allowances: BTreeMap<(Addr, Addr), Uint128>,
+}
+
+impl Snapshot {
+    fn take(suite: &Cw20TestSuite, accounts: &[Addr]) -> Self {
+        let querier = suite.app.wrap();
+        let mut balances = BTreeMap::new();
+        let mut start_after = None;
+        loop {
+            let page = suite
+                .token
+                .all_accounts(&querier, start_after, Some(30))
+                .unwrap()
+                .accounts;
+            for holder in &page {
+                let holder = Addr::unchecked(holder);
+                balances.insert(holder.clone(), suite.balance(&holder));
+            }
+            match page.last() {
+                Some(last) => start_after = Some(last.clone()),
+                None => break,
+            }
+        }
+        let mut allowances = BTreeMap::new();
+        for owner in accounts {
+            for spender in accounts {
+                let allowance = suite.allowance(owner, spender);
+                allowances.insert((owner.clone(), spender.clone()), allowance);
+            }
+        }
+        Snapshot {
+            supply: suite.total_supply(),
+            balances,
+            allowances,
+        }
+    }
+
+    fn balance(&self, addr: &Addr) -> Uint128 {
+        self.balances.get(addr).copied().unwrap_or_default()
+    }
+
+    fn allowance(&self, owner: &Addr, spender: &Addr) -> Uint128 {
+        let key = (owner.clone(), spender.clone());
+        self.allowances.get(&key).copied().unwrap_or_default()
+    }
+
+    /// At least `amount` left `from`, and at most `amount` reached `to`
+    fn check_spent(
+        &self,
+        after: &Snapshot,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+    ) -> Result<(), String> {
+        if from == to {
+            return Ok(());
+        }
+        if after.balance(from) + amount > self.balance(from) {
+            return Err(format!("less than {amount} left {from}"));
+        }
+        if after.balance(to) < self.balance(to) || after.balance(to) - self.balance(to) > amount {
+            return Err(format!("{to} got more than {amount} or lost tokens"));
+        }
+        Ok(())
+    }
+
+    fn check_allowance_spent(
+        &self,
+        after: &Snapshot,
+        owner: &Addr,
+        spender: &Addr,
+        amount: Uint128,
+    ) -> Result<(), String> {
+        if self.allowance(owner, spender) < amount {
+            return Err(format!("{spender} spent more than its allowance"));
+        }
+        if after.allowance(owner, spender) + amount > self.allowance(owner, spender) {
+            return Err(format!(
+                "the allowance of {spender} shrank by less than {amount}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A small seeded generator, so a failing seed gives the same operations again
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`
+    fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        self.next() % bound
+    }
+}
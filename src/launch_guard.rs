@@ -0,0 +1,50 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::TradingStatusResponse;
+use crate::state::{LAUNCH_ALLOWLIST, TRADING_ENABLED};
+
+/// Holds all transfers back, except from the given addresses, until the admin enables trading
+pub fn setup_launch_guard(deps: &mut DepsMut, allowlist: Vec<String>) -> Result<(), ContractError> {
+    TRADING_ENABLED.save(deps.storage, &false)?;
+    for addr in allowlist {
+        let addr = deps.api.addr_validate(&addr)?;
+        LAUNCH_ALLOWLIST.save(deps.storage, &addr, &())?;
+    }
+    Ok(())
+}
+
+/// Whether `from` can transfer: trading is enabled, or it is on the launch allowlist
+pub fn can_trade(storage: &dyn Storage, from: &Addr) -> StdResult<bool> {
+    let enabled = TRADING_ENABLED.may_load(storage)?.unwrap_or(true);
+    Ok(enabled || LAUNCH_ALLOWLIST.has(storage, from))
+}
+
+/// Fails if trading is not enabled yet and `from` is not on the launch allowlist
+pub fn assert_trading_enabled(storage: &dyn Storage, from: &Addr) -> Result<(), ContractError> {
+    if !can_trade(storage, from)? {
+        return Err(ContractError::TradingNotEnabled {});
+    }
+    Ok(())
+}
+
+pub fn execute_enable_trading(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if TRADING_ENABLED.may_load(deps.storage)?.unwrap_or(true) {
+        return Err(ContractError::TradingAlreadyEnabled {});
+    }
+    TRADING_ENABLED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "enable_trading"))
+}
+
+pub fn query_trading_status(deps: Deps) -> StdResult<TradingStatusResponse> {
+    Ok(TradingStatusResponse {
+        enabled: TRADING_ENABLED.may_load(deps.storage)?.unwrap_or(true),
+    })
+}
@@ -14,12 +14,108 @@ For more information on this contract, please check out the
 [README](https://github.com/CosmWasm/cw-plus/blob/main/contracts/cw20-base/README.md).
 */
 
+pub mod admin;
+#[cfg(feature = "allowance-hooks")]
+pub mod allowance_hooks;
 pub mod allowances;
+pub mod amount;
+pub mod asset;
+#[cfg(feature = "auto-burn")]
+pub mod auto_burn;
+#[cfg(feature = "balance-proofs")]
+pub mod balance_proofs;
+pub mod balances;
+#[cfg(feature = "burn-sink")]
+pub mod burn_sink;
+#[cfg(feature = "burn-stats")]
+pub mod burn_stats;
+#[cfg(feature = "changelog")]
+pub mod changelog;
+pub mod coin;
+#[cfg(feature = "compliance")]
+pub mod compliance;
 pub mod contract;
+#[cfg(feature = "contract-guard")]
+pub mod contract_guard;
+#[cfg(feature = "cranks")]
+pub mod cranks;
 pub mod enumerable;
 mod error;
+#[cfg(feature = "events")]
+pub mod events;
+#[cfg(feature = "extended-metadata")]
+pub mod extended_metadata;
+#[cfg(feature = "faucet")]
+pub mod faucet;
+#[cfg(feature = "launch-guard")]
+pub mod launch_guard;
+#[cfg(feature = "logo-info")]
+pub mod logo_info;
+#[cfg(feature = "marketing-admins")]
+pub mod marketing_admins;
+#[cfg(feature = "max-wallet")]
+pub mod max_wallet;
+#[cfg(feature = "meta-tx")]
+pub mod meta_tx;
+pub mod migrations;
+#[cfg(feature = "min-transfer")]
+pub mod min_transfer;
+#[cfg(feature = "mint-timelock")]
+pub mod mint_timelock;
 pub mod msg;
+#[cfg(feature = "multi-token")]
+pub mod multi_token;
+#[cfg(all(feature = "query-client", not(target_arch = "wasm32")))]
+pub mod query_client;
+pub mod restrictions;
+#[cfg(feature = "rebrand")]
+pub mod rebrand;
+pub mod receiver;
+#[cfg(feature = "rescale-decimals")]
+pub mod rescale;
+#[cfg(feature = "rewards")]
+pub mod rewards;
+#[cfg(feature = "sampling")]
+pub mod sampling;
+#[cfg(feature = "scheduled")]
+pub mod scheduled;
+#[cfg(feature = "send-allowlist")]
+pub mod send_allowlist;
+#[cfg(feature = "snapshots")]
+pub mod snapshots;
 pub mod state;
+#[cfg(feature = "state-export")]
+pub mod state_export;
+pub mod storage;
+#[cfg(feature = "streams")]
+pub mod streams;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+pub mod supply;
+#[cfg(feature = "supply-hooks")]
+pub mod supply_hooks;
+#[cfg(feature = "supply-log")]
+pub mod supply_log;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "transfer-limits")]
+pub mod transfer_limits;
+#[cfg(feature = "vesting")]
+pub mod vesting;
+#[cfg(feature = "votes")]
+pub mod votes;
+#[cfg(feature = "exchange-rate")]
+pub mod exchange_rate;
 pub mod execute;
+mod extend;
+pub mod helpers;
+#[cfg(feature = "ica")]
+pub mod ica;
+#[cfg(feature = "instantiate2")]
+pub mod instantiate2;
+#[cfg(feature = "invariants")]
+pub mod invariants;
+pub mod interfaces;
+pub mod tokenfactory;
 
 pub use crate::error::ContractError;
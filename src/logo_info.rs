@@ -0,0 +1,28 @@
+use cosmwasm_std::{Deps, HexBinary, StdResult};
+use cw20::Logo;
+use sha2::{Digest, Sha256};
+
+use crate::contract::query_download_logo;
+use crate::msg::LogoInfoResponse;
+use crate::state::LOGO;
+
+/// Describes the logo without its data, so clients only download it when the hash changed
+pub fn query_logo_info(deps: Deps) -> StdResult<LogoInfoResponse> {
+    match LOGO.may_load(deps.storage)? {
+        None => Ok(LogoInfoResponse::default()),
+        Some(Logo::Url(url)) => Ok(LogoInfoResponse {
+            url: Some(url),
+            ..LogoInfoResponse::default()
+        }),
+        Some(Logo::Embedded(_)) => {
+            let logo = query_download_logo(deps)?;
+            let hash = Sha256::digest(logo.data.as_slice());
+            Ok(LogoInfoResponse {
+                url: None,
+                mime_type: Some(logo.mime_type),
+                size: Some(logo.data.len() as u64),
+                sha256: Some(HexBinary::from(hash.as_slice())),
+            })
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use cosmwasm_std::{Addr, Api, Deps, DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+use cw20::MarketingInfoResponse;
+
+use crate::error::ContractError;
+use crate::msg::MarketingAdminsResponse;
+use crate::state::{MARKETING_ADMINS, MARKETING_INFO, PENDING_MARKETING_ADMIN};
+
+/// Fails unless `sender` is the marketing admin set in the marketing info. Only it
+/// hands the role over and manages the additional marketing admins.
+fn assert_primary(
+    marketing_info: &MarketingInfoResponse,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    match &marketing_info.marketing {
+        Some(marketing) if marketing == sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Whether `sender` is one of the additional marketing admins
+pub fn is_marketing_admin(storage: &dyn Storage, sender: &Addr) -> bool {
+    MARKETING_ADMINS.has(storage, sender)
+}
+
+/// Proposes `nominee` as the next marketing admin. The role only moves once the
+/// nominee accepts, so a mistyped address can't lock the marketing info.
+pub fn nominate(
+    api: &dyn Api,
+    storage: &mut dyn Storage,
+    marketing_info: &MarketingInfoResponse,
+    sender: &Addr,
+    nominee: &str,
+) -> Result<Addr, ContractError> {
+    assert_primary(marketing_info, sender)?;
+    let nominee = api.addr_validate(nominee)?;
+    PENDING_MARKETING_ADMIN.save(storage, &nominee)?;
+    Ok(nominee)
+}
+
+/// Drops the nominee and the additional admins along with the marketing admin itself
+pub fn clear(
+    storage: &mut dyn Storage,
+    marketing_info: &MarketingInfoResponse,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    assert_primary(marketing_info, sender)?;
+    PENDING_MARKETING_ADMIN.remove(storage);
+    MARKETING_ADMINS.clear(storage);
+    Ok(())
+}
+
+pub fn execute_accept_marketing_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    match PENDING_MARKETING_ADMIN.may_load(deps.storage)? {
+        Some(nominee) if nominee == info.sender => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+
+    PENDING_MARKETING_ADMIN.remove(deps.storage);
+    MARKETING_ADMINS.remove(deps.storage, &info.sender);
+    marketing_info.marketing = Some(info.sender.clone());
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    let res = Response::new()
+        .add_attribute("action", "accept_marketing_admin")
+        .add_attribute("marketing", info.sender);
+    Ok(res)
+}
+
+pub fn execute_update_marketing_admins(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    assert_primary(&marketing_info, &info.sender)?;
+
+    for address in &add {
+        let addr = deps.api.addr_validate(address)?;
+        MARKETING_ADMINS.save(deps.storage, &addr, &())?;
+    }
+    for address in &remove {
+        let addr = deps.api.addr_validate(address)?;
+        MARKETING_ADMINS.remove(deps.storage, &addr);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_marketing_admins")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string());
+    Ok(res)
+}
+
+pub fn query_marketing_admins(deps: Deps) -> StdResult<MarketingAdminsResponse> {
+    let admins = MARKETING_ADMINS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(MarketingAdminsResponse {
+        pending: PENDING_MARKETING_ADMIN.may_load(deps.storage)?,
+        admins,
+    })
+}
@@ -0,0 +1,99 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+use crate::msg::{InstantiateMaxWallet, MaxWalletResponse};
+use crate::state::{BALANCES, MAX_WALLET, MAX_WALLET_EXEMPT};
+
+/// Caps every balance from now on, except for the exempt addresses
+pub fn setup_max_wallet(
+    deps: &mut DepsMut,
+    config: InstantiateMaxWallet,
+) -> Result<(), ContractError> {
+    MAX_WALLET.save(deps.storage, &config.amount)?;
+    for addr in config.exempt {
+        let addr = deps.api.addr_validate(&addr)?;
+        MAX_WALLET_EXEMPT.save(deps.storage, &addr, &())?;
+    }
+    Ok(())
+}
+
+/// Fails if crediting `amount` would push the balance of `recipient` above the max wallet.
+/// Exempt addresses can hold any amount.
+pub fn assert_within_max_wallet(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let max_wallet = match MAX_WALLET.may_load(storage)? {
+        Some(max_wallet) => max_wallet,
+        None => return Ok(()),
+    };
+    if MAX_WALLET_EXEMPT.has(storage, recipient) {
+        return Ok(());
+    }
+    let balance = BALANCES.may_load(storage, recipient)?.unwrap_or_default();
+    if balance.checked_add(amount).map_err(StdError::from)? > max_wallet {
+        return Err(ContractError::MaxWalletExceeded {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_max_wallet(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+    add_exempt: Vec<String>,
+    remove_exempt: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    match amount {
+        Some(amount) => MAX_WALLET.save(deps.storage, &amount)?,
+        None => MAX_WALLET.remove(deps.storage),
+    }
+    for addr in &add_exempt {
+        let addr = deps.api.addr_validate(addr)?;
+        MAX_WALLET_EXEMPT.save(deps.storage, &addr, &())?;
+    }
+    for addr in &remove_exempt {
+        let addr = deps.api.addr_validate(addr)?;
+        MAX_WALLET_EXEMPT.remove(deps.storage, &addr);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_max_wallet")
+        .add_attribute(
+            "amount",
+            amount.map_or_else(|| "None".to_string(), |amount| amount.to_string()),
+        )
+        .add_attribute("added", add_exempt.join(","))
+        .add_attribute("removed", remove_exempt.join(","));
+    Ok(res)
+}
+
+pub fn query_max_wallet(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MaxWalletResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let exempt = MAX_WALLET_EXEMPT
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(MaxWalletResponse {
+        amount: MAX_WALLET.may_load(deps.storage)?,
+        exempt,
+    })
+}
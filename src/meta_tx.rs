@@ -0,0 +1,235 @@
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::execute::execute_send::execute_send;
+use crate::execute::execute_transfer::execute_transfer;
+use crate::msg::{
+    AuthorizationStateResponse, SignedMsg, SignedPayload, SigningInfoResponse,
+    TransferAuthorization,
+};
+use crate::state::{SIGNING_KEYS, SIGNING_NONCES, USED_AUTHORIZATIONS};
+
+/// Binds signatures to this chain and contract, so they can't be replayed on another token
+pub fn domain_separator(env: &Env) -> Binary {
+    let hash = Sha256::new()
+        .chain_update(b"cw20-plus:execute-signed")
+        .chain_update(env.block.chain_id.as_bytes())
+        .chain_update([0u8])
+        .chain_update(env.contract.address.as_bytes())
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+/// Hash the owner signs for ExecuteSigned with `nonce` and `payload`
+pub fn signing_hash(env: &Env, nonce: u64, payload: &[u8]) -> Binary {
+    let hash = Sha256::new()
+        .chain_update(domain_separator(env).as_slice())
+        .chain_update(nonce.to_be_bytes())
+        .chain_update(payload)
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+/// Hash `authorization.from` signs for TransferWithAuthorization
+pub fn authorization_hash(env: &Env, authorization: &TransferAuthorization) -> StdResult<Binary> {
+    let hash = Sha256::new()
+        .chain_update(domain_separator(env).as_slice())
+        .chain_update(b"transfer-with-authorization")
+        .chain_update(to_json_vec(authorization)?)
+        .finalize();
+    Ok(Binary::from(hash.as_slice()))
+}
+
+/// Fails unless `signature` signs `hash` with the signing key of `owner`
+fn assert_signed_by(
+    deps: Deps,
+    owner: &Addr,
+    hash: &[u8],
+    signature: &[u8],
+) -> Result<(), ContractError> {
+    let pubkey = SIGNING_KEYS
+        .may_load(deps.storage, owner)?
+        .ok_or(ContractError::NoSigningKey {})?;
+    let verified = deps
+        .api
+        .secp256k1_verify(hash, signature, &pubkey)
+        .unwrap_or(false);
+    if !verified {
+        return Err(ContractError::InvalidSignature {});
+    }
+    Ok(())
+}
+
+/// Sets the secp256k1 key the sender signs ExecuteSigned payloads and transfer
+/// authorizations with, or removes it with None. The nonce carries over to the new key.
+pub fn execute_set_signing_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let res = Response::new()
+        .add_attribute("action", "set_signing_key")
+        .add_attribute("owner", &info.sender);
+    match pubkey {
+        Some(pubkey) => {
+            // compressed or uncompressed SEC1 encoding
+            let valid = matches!(
+                (pubkey.len(), pubkey.first()),
+                (33, Some(0x02 | 0x03)) | (65, Some(0x04))
+            );
+            if !valid {
+                return Err(ContractError::InvalidPubkey {});
+            }
+            SIGNING_KEYS.save(deps.storage, &info.sender, &pubkey)?;
+            Ok(res.add_attribute("pubkey", pubkey.to_base64()))
+        }
+        None => {
+            SIGNING_KEYS.remove(deps.storage, &info.sender);
+            Ok(res.add_attribute("pubkey", "None"))
+        }
+    }
+}
+
+/// Runs the Transfer or Send in `payload` as if its owner sent it, once the signature over
+/// it checks out. The relayer sending this pays the gas.
+pub fn execute_signed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payload: Binary,
+    nonce: u64,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let signed: SignedPayload = from_json(&payload)?;
+    let owner = deps.api.addr_validate(&signed.owner)?;
+    if !SIGNING_KEYS.has(deps.storage, &owner) {
+        return Err(ContractError::NoSigningKey {});
+    }
+    let expected = SIGNING_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidNonce { expected });
+    }
+    let hash = signing_hash(&env, nonce, &payload);
+    assert_signed_by(deps.as_ref(), &owner, &hash, &signature)?;
+    SIGNING_NONCES.save(deps.storage, &owner, &(nonce + 1))?;
+
+    let relayer = info.sender;
+    let info = MessageInfo {
+        sender: owner,
+        funds: vec![],
+    };
+    let res = match signed.msg {
+        SignedMsg::Transfer {
+            recipient,
+            amount,
+            memo,
+        } => execute_transfer(deps, env, info, recipient, amount, memo)?,
+        SignedMsg::Send {
+            contract,
+            amount,
+            msg,
+            memo,
+            safe,
+        } => execute_send(deps, env, info, contract, amount, msg, memo, safe)?,
+    };
+    Ok(res
+        .add_attribute("relayer", relayer)
+        .add_attribute("nonce", nonce.to_string()))
+}
+
+/// Runs the transfer `authorization.from` signed, once, within its validity window. The
+/// relayer sending this pays the gas.
+pub fn execute_transfer_with_authorization(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    authorization: TransferAuthorization,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let from = deps.api.addr_validate(&authorization.from)?;
+    let nonce = authorization_nonce(&authorization.nonce)?;
+    let now = env.block.time;
+    if now <= authorization.valid_after {
+        return Err(ContractError::AuthorizationNotYetValid {});
+    }
+    if now >= authorization.valid_before {
+        return Err(ContractError::AuthorizationExpired {});
+    }
+    if USED_AUTHORIZATIONS.has(deps.storage, (&from, nonce)) {
+        return Err(ContractError::AuthorizationUsed {});
+    }
+    let hash = authorization_hash(&env, &authorization)?;
+    assert_signed_by(deps.as_ref(), &from, &hash, &signature)?;
+    USED_AUTHORIZATIONS.save(deps.storage, (&from, nonce), &())?;
+
+    let relayer = info.sender;
+    let info = MessageInfo {
+        sender: from,
+        funds: vec![],
+    };
+    let res = execute_transfer(
+        deps,
+        env,
+        info,
+        authorization.to,
+        authorization.amount,
+        None,
+    )?;
+    Ok(res
+        .add_attribute("relayer", relayer)
+        .add_attribute("authorization", authorization.nonce.to_base64()))
+}
+
+pub fn execute_cancel_authorization(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    nonce: Binary,
+) -> Result<Response, ContractError> {
+    let key = authorization_nonce(&nonce)?;
+    if USED_AUTHORIZATIONS.has(deps.storage, (&info.sender, key)) {
+        return Err(ContractError::AuthorizationUsed {});
+    }
+    USED_AUTHORIZATIONS.save(deps.storage, (&info.sender, key), &())?;
+
+    let res = Response::new()
+        .add_attribute("action", "cancel_authorization")
+        .add_attribute("authorizer", info.sender)
+        .add_attribute("authorization", nonce.to_base64());
+    Ok(res)
+}
+
+fn authorization_nonce(nonce: &Binary) -> Result<&[u8], ContractError> {
+    if nonce.len() != 32 {
+        return Err(ContractError::InvalidAuthorizationNonce {});
+    }
+    Ok(nonce.as_slice())
+}
+
+pub fn query_authorization_state(
+    deps: Deps,
+    authorizer: String,
+    nonce: Binary,
+) -> StdResult<AuthorizationStateResponse> {
+    let authorizer = deps.api.addr_validate(&authorizer)?;
+    Ok(AuthorizationStateResponse {
+        used: USED_AUTHORIZATIONS.has(deps.storage, (&authorizer, nonce.as_slice())),
+    })
+}
+
+pub fn query_signing_info(deps: Deps, env: Env, address: String) -> StdResult<SigningInfoResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    Ok(SigningInfoResponse {
+        pubkey: SIGNING_KEYS.may_load(deps.storage, &addr)?,
+        nonce: SIGNING_NONCES
+            .may_load(deps.storage, &addr)?
+            .unwrap_or_default(),
+        domain_separator: domain_separator(&env),
+    })
+}
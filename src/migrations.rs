@@ -0,0 +1,226 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage,
+};
+use cw2::get_contract_version;
+use cw_storage_plus::Bound;
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::msg::MigrationStatusResponse;
+use crate::state::{
+    PendingMigration, TokenMetadata, ALLOWANCES, ALLOWANCES_SPENDER, LEGACY_TOKEN_INFO, MINTER,
+    PENDING_MIGRATION, TOKEN_METADATA, TOTAL_SUPPLY,
+};
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+
+/// A transform of the stored state, needed by tokens stored with a version below `before`
+pub struct MigrationStep {
+    pub before: &'static str,
+    pub name: &'static str,
+    run: StepRun,
+}
+
+enum StepRun {
+    /// Runs whole, in the migration itself
+    Once(fn(&mut dyn Storage, &Env) -> StdResult<()>),
+    /// Touches every account, so it runs from ProcessMigration, `limit` at a time after
+    /// the cursor it returned last. Until it's done, reads and writes of the state it
+    /// transforms must work with both layouts.
+    Chunked(fn(&mut dyn Storage, &Env, Option<Binary>, usize) -> StdResult<Chunk>),
+}
+
+/// What a chunked step got through in one call
+struct Chunk {
+    processed: usize,
+    /// Where to resume, None once the step is done
+    cursor: Option<Binary>,
+}
+
+/// Every step, oldest first. A migration runs the ones newer than the stored version, in
+/// this order. New state that needs transforming gets a step at the end.
+pub const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        before: "0.14.0",
+        name: "index_allowances_by_spender",
+        run: StepRun::Chunked(index_allowances_by_spender),
+    },
+    MigrationStep {
+        // the first cw20-plus version to split it; upstream cw20-base and the first
+        // cw20-plus release, 2.0.1, both store the legacy layout
+        before: "2.1.0",
+        name: "split_token_info",
+        run: StepRun::Once(split_token_info),
+    },
+];
+
+/// Version stored by `contract`, failing unless it's the same contract at this or an
+/// older version. Leaves the storage untouched, unlike cw2's ensure_from_older_version.
+pub fn stored_version(storage: &dyn Storage, contract: &str, to: &str) -> StdResult<Version> {
+    let stored = get_contract_version(storage)?;
+    if stored.contract != contract {
+        let msg = format!("Cannot migrate from {} to {}", stored.contract, contract);
+        return Err(StdError::generic_err(msg));
+    }
+    let version = parse_version(&stored.version)?;
+    if version > parse_version(to)? {
+        let msg = format!(
+            "Cannot migrate from newer version ({}) to older ({})",
+            stored.version, to
+        );
+        return Err(StdError::generic_err(msg));
+    }
+    Ok(version)
+}
+
+/// Steps a token stored with version `from` still needs, oldest first
+pub fn pending_steps(from: &Version) -> StdResult<Vec<&'static MigrationStep>> {
+    let mut steps = vec![];
+    for step in MIGRATIONS {
+        if *from < parse_version(step.before)? {
+            steps.push(step);
+        }
+    }
+    Ok(steps)
+}
+
+/// Runs the steps running once, and leaves the chunked ones to ProcessMigration. As the
+/// state stays usable in both layouts until a chunked step is done, the steps after it
+/// don't have to wait.
+pub fn run_steps(
+    storage: &mut dyn Storage,
+    env: &Env,
+    steps: &[&MigrationStep],
+) -> Result<(), ContractError> {
+    if PENDING_MIGRATION.exists(storage) {
+        return Err(ContractError::MigrationInProgress {});
+    }
+    let mut chunked = vec![];
+    for step in steps {
+        match step.run {
+            StepRun::Once(run) => run(storage, env)?,
+            StepRun::Chunked(_) => chunked.push(step.name.to_string()),
+        }
+    }
+    if !chunked.is_empty() {
+        let pending = PendingMigration {
+            steps: chunked,
+            cursor: None,
+        };
+        PENDING_MIGRATION.save(storage, &pending)?;
+    }
+    Ok(())
+}
+
+/// True while `name` is still left to ProcessMigration
+pub fn is_pending(storage: &dyn Storage, name: &str) -> StdResult<bool> {
+    Ok(PENDING_MIGRATION
+        .may_load(storage)?
+        .is_some_and(|pending| pending.steps.iter().any(|step| step == name)))
+}
+
+/// Runs the pending steps for up to `limit` accounts
+pub fn execute_process_migration(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut pending = PENDING_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMigrationInProgress {})?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+
+    let mut processed = 0;
+    while processed < limit && !pending.steps.is_empty() {
+        let step = MIGRATIONS
+            .iter()
+            .find(|step| step.name == pending.steps[0])
+            .ok_or_else(|| StdError::generic_err(format!("Unknown step {}", pending.steps[0])))?;
+        let StepRun::Chunked(run) = step.run else {
+            return Err(StdError::generic_err(format!("Step {} is not chunked", step.name)).into());
+        };
+        let chunk = run(deps.storage, &env, pending.cursor.take(), limit - processed)?;
+        processed += chunk.processed;
+        match chunk.cursor {
+            Some(cursor) => pending.cursor = Some(cursor),
+            None => {
+                pending.steps.remove(0);
+            }
+        }
+    }
+
+    let done = pending.steps.is_empty();
+    if done {
+        PENDING_MIGRATION.remove(deps.storage);
+    } else {
+        PENDING_MIGRATION.save(deps.storage, &pending)?;
+    }
+    let res = Response::new()
+        .add_attribute("action", "process_migration")
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("done", done.to_string());
+    Ok(res)
+}
+
+pub fn query_migration_status(deps: Deps) -> StdResult<MigrationStatusResponse> {
+    Ok(MigrationStatusResponse {
+        pending: PENDING_MIGRATION.may_load(deps.storage)?,
+    })
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|err| StdError::generic_err(format!("Semver: {err}")))
+}
+
+/// Builds the reverse map of allowances per spender, added in 0.14. Allowance updates
+/// save both maps meanwhile, and spender queries scan the owner map until it's done.
+fn index_allowances_by_spender(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    cursor: Option<Binary>,
+    limit: usize,
+) -> StdResult<Chunk> {
+    let after: Option<(Addr, Addr)> = cursor.map(|cursor| from_json(&cursor)).transpose()?;
+    let start = after
+        .as_ref()
+        .map(|(owner, spender)| Bound::exclusive((owner, spender)));
+    let data = ALLOWANCES
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let processed = data.len();
+    let mut last = None;
+    for ((owner, spender), allowance) in data {
+        ALLOWANCES_SPENDER.save(storage, (&spender, &owner), &allowance)?;
+        last = Some((owner, spender));
+    }
+    let cursor = match last {
+        Some(last) if processed == limit => Some(to_json_binary(&last)?),
+        _ => None,
+    };
+    Ok(Chunk { processed, cursor })
+}
+
+/// Splits the token info item of upstream cw20-base, rewritten on every mint and burn,
+/// into the metadata, supply and minter items
+fn split_token_info(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    if let Some(info) = LEGACY_TOKEN_INFO.may_load(storage)? {
+        let metadata = TokenMetadata {
+            name: info.name,
+            symbol: info.symbol,
+            decimals: info.decimals,
+        };
+        TOKEN_METADATA.save(storage, &metadata)?;
+        TOTAL_SUPPLY.save(storage, &info.total_supply, env.block.height)?;
+        if let Some(minter) = info.mint {
+            MINTER.save(storage, &minter)?;
+        }
+        LEGACY_TOKEN_INFO.remove(storage);
+    }
+    Ok(())
+}
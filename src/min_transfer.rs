@@ -0,0 +1,42 @@
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::MinTransferAmountResponse;
+use crate::state::MIN_TRANSFER_AMOUNT;
+
+/// Fails if a transfer of `amount` is below the minimum, so dust can't be spread over
+/// new accounts. Mints and burns don't go through here.
+pub fn assert_min_transfer(storage: &dyn Storage, amount: Uint128) -> Result<(), ContractError> {
+    match MIN_TRANSFER_AMOUNT.may_load(storage)? {
+        Some(min) if amount < min => Err(ContractError::TransferBelowMinimum { min }),
+        _ => Ok(()),
+    }
+}
+
+pub fn execute_update_min_transfer_amount(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    match amount {
+        Some(amount) => MIN_TRANSFER_AMOUNT.save(deps.storage, &amount)?,
+        None => MIN_TRANSFER_AMOUNT.remove(deps.storage),
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_min_transfer_amount")
+        .add_attribute(
+            "amount",
+            amount.map_or_else(|| "None".to_string(), |amount| amount.to_string()),
+        );
+    Ok(res)
+}
+
+pub fn query_min_transfer_amount(deps: Deps) -> StdResult<MinTransferAmountResponse> {
+    Ok(MinTransferAmountResponse {
+        amount: MIN_TRANSFER_AMOUNT.may_load(deps.storage)?,
+    })
+}
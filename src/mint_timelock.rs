@@ -0,0 +1,202 @@
+use cosmwasm_std::{
+    attr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    Timestamp, Uint128,
+};
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::execute::execute_mint::mint;
+use crate::msg::{PendingMintInfo, PendingMintsResponse};
+use crate::state::{
+    PendingMint, TransferBucket, DIRECT_MINT_BUCKETS, MINTER, MINT_TIMELOCK, PENDING_MINTS,
+    PENDING_MINT_COUNT,
+};
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+use crate::tokenfactory;
+
+/// Fails if `amount` is too large to be minted without going through the queue
+pub fn assert_below_threshold(storage: &dyn Storage, amount: Uint128) -> Result<(), ContractError> {
+    match MINT_TIMELOCK.may_load(storage)? {
+        Some(timelock) if amount > timelock.threshold => Err(ContractError::MintTimelocked {}),
+        _ => Ok(()),
+    }
+}
+
+/// Buckets the delay is split into, as for transfer limits
+const BUCKETS: u64 = 24;
+
+/// Counts a direct mint of `amount` against the threshold, which all direct mints within one
+/// delay share, so a large mint can't skip the queue by being split up
+pub fn record_direct_mint(
+    storage: &mut dyn Storage,
+    env: &Env,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let timelock = match MINT_TIMELOCK.may_load(storage)? {
+        Some(timelock) => timelock,
+        None => return Ok(()),
+    };
+    if timelock.delay == 0 {
+        return assert_below_threshold(storage, amount);
+    }
+    let length = timelock.delay.div_ceil(BUCKETS);
+    let mut buckets = DIRECT_MINT_BUCKETS.may_load(storage)?.unwrap_or_default();
+    buckets.retain(|bucket| env.block.time < bucket.start.plus_seconds(length * (BUCKETS + 1)));
+    let minted = buckets
+        .iter()
+        .map(|bucket| bucket.spent)
+        .sum::<Uint128>()
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    if minted > timelock.threshold {
+        return Err(ContractError::MintTimelocked {});
+    }
+    let start = Timestamp::from_seconds(env.block.time.seconds() / length * length);
+    match buckets.last_mut() {
+        Some(bucket) if bucket.start == start => bucket.spent += amount,
+        _ => buckets.push(TransferBucket {
+            start,
+            spent: amount,
+        }),
+    }
+    DIRECT_MINT_BUCKETS.save(storage, &buckets)?;
+    Ok(())
+}
+
+fn assert_minter(storage: &dyn Storage, info: &MessageInfo) -> Result<(), ContractError> {
+    match MINTER.may_load(storage)? {
+        Some(m) if m.minter == info.sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+pub fn execute_propose_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_minter(deps.storage, &info)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let delay = MINT_TIMELOCK
+        .may_load(deps.storage)?
+        .map(|timelock| timelock.delay)
+        .unwrap_or_default();
+
+    let id = PENDING_MINT_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    PENDING_MINT_COUNT.save(deps.storage, &id)?;
+    let pending = PendingMint {
+        recipient: rcpt_addr,
+        amount,
+        executable_at: env.block.time.plus_seconds(delay),
+    };
+    PENDING_MINTS.save(deps.storage, id, &pending)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "propose_mint"),
+        attr("mint_id", id.to_string()),
+        attr("to", recipient),
+        attr("amount", amount),
+        attr("executable_at", pending.executable_at.to_string()),
+    ]);
+    Ok(res)
+}
+
+pub fn execute_execute_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_MINTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoPendingMint {})?;
+    if env.block.time < pending.executable_at {
+        return Err(ContractError::TimelockNotElapsed {});
+    }
+    // the minter and the cap are checked against the state at execution time
+    mint(
+        deps.storage,
+        &env,
+        &info.sender,
+        &pending.recipient,
+        pending.amount,
+    )?;
+    PENDING_MINTS.remove(deps.storage, id);
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "mint"),
+            attr("mint_id", id.to_string()),
+            attr("to", &pending.recipient),
+            attr("amount", pending.amount),
+        ])
+        .add_messages(tokenfactory::mint_msgs(deps.storage, &env, pending.amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::mint_msgs(
+        deps.storage,
+        &info.sender,
+        &pending.recipient,
+        pending.amount,
+    )?);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::mint(
+        deps.storage,
+        &pending.recipient,
+        pending.amount,
+    )?);
+    Ok(res)
+}
+
+pub fn execute_cancel_mint(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // the admin can stop a compromised minter
+    assert_minter(deps.storage, &info).or_else(|_| assert_admin(deps.storage, &info.sender))?;
+    if !PENDING_MINTS.has(deps.storage, id) {
+        return Err(ContractError::NoPendingMint {});
+    }
+    PENDING_MINTS.remove(deps.storage, id);
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "cancel_mint"),
+        attr("mint_id", id.to_string()),
+        attr("by", info.sender),
+    ]);
+    Ok(res)
+}
+
+pub fn query_pending_mints(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingMintsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let mints = PENDING_MINTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(id, pending)| PendingMintInfo {
+                id,
+                recipient: pending.recipient,
+                amount: pending.amount,
+                executable_at: pending.executable_at,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(PendingMintsResponse { mints })
+}
@@ -1,10 +1,29 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{StdError, StdResult, Uint128};
-use cw20::{Cw20Coin, Logo, MinterResponse};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, HexBinary, StdError, StdResult, Timestamp,
+    Uint128, WasmMsg,
+};
+#[cfg(feature = "rewards")]
+use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20Coin, Expiration, Logo, MarketingInfoResponse, MinterResponse, TokenInfoResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub use cw20::Cw20ExecuteMsg as ExecuteMsg;
+#[cfg(feature = "allowance-limits")]
+use crate::state::AllowanceLimit;
+#[cfg(feature = "cranks")]
+use crate::state::CrankConfig;
+#[cfg(feature = "rescale-decimals")]
+use crate::state::DecimalRescale;
+#[cfg(feature = "faucet")]
+use crate::state::FaucetConfig;
+#[cfg(feature = "mint-timelock")]
+use crate::state::MintTimelock;
+use crate::state::PendingMigration;
+#[cfg(feature = "transfer-limits")]
+use crate::state::TransferLimit;
+#[cfg(feature = "vesting")]
+use crate::state::VestingSchedule;
 
 #[cw_serde]
 pub struct InstantiateMarketingInfo {
@@ -14,6 +33,62 @@ pub struct InstantiateMarketingInfo {
     pub logo: Option<Logo>,
 }
 
+#[cw_serde]
+pub struct InstantiateMirrorInfo {
+    /// Subdenom of the `factory/{contract}/{subdenom}` denom to create
+    pub subdenom: String,
+}
+
+#[cw_serde]
+pub struct InstantiateAutoBurn {
+    /// Share of every transfer burned from the total supply, in basis points
+    pub basis_points: u16,
+    /// Transfers from or to these addresses are not taxed (pools, treasury, ...)
+    pub exempt: Vec<String>,
+}
+
+#[cw_serde]
+pub struct InstantiateMaxWallet {
+    /// Most tokens a single account may hold
+    pub amount: Uint128,
+    /// Accounts that may hold more (pools, treasury, ...)
+    pub exempt: Vec<String>,
+}
+
+#[cw_serde]
+pub struct InstantiateExchangeRate {
+    /// Underlying amount per share to start at
+    pub rate: Decimal,
+    /// Account allowed to update the rate besides the admin, e.g. the yield strategy
+    pub updater: Option<String>,
+}
+
+/// Rules for the ticker symbol, at instantiation and on every rename
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum SymbolValidation {
+    /// 3-12 ASCII letters and dashes, as in cw20-base
+    #[default]
+    Strict,
+    /// 1-20 printable UTF-8 characters without whitespace, for wrapped and bridged
+    /// tickers such as "wstETH.axl"
+    Relaxed,
+}
+
+/// A native coin denom or a cw20 token
+#[cw_serde]
+pub enum AssetInfo {
+    Native { denom: String },
+    Cw20 { contract_addr: String },
+}
+
+/// An amount of a native coin or a cw20 token
+#[cw_serde]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct InstantiateMsg {
@@ -23,6 +98,93 @@ pub struct InstantiateMsg {
     pub initial_balances: Vec<Cw20Coin>,
     pub mint: Option<MinterResponse>,
     pub marketing: Option<InstantiateMarketingInfo>,
+    /// Only with the "tokenfactory" feature. Mirrors every mint/burn into a native denom.
+    pub mirror: Option<InstantiateMirrorInfo>,
+    /// Burns a slice of every transfer, making the token deflationary.
+    #[cfg(feature = "auto-burn")]
+    pub auto_burn: Option<InstantiateAutoBurn>,
+    /// Address transfers to which burn the tokens instead of crediting them, e.g. a
+    /// vanity dead address
+    #[cfg(feature = "burn-sink")]
+    pub burn_sink: Option<String>,
+    /// Maintains the weight tree needed by `SampleHolders`, at the cost of extra gas
+    /// on every balance change.
+    #[cfg(feature = "sampling")]
+    #[serde(default)]
+    pub holder_sampling: bool,
+    /// Account allowed to administer the token, e.g. to record snapshots
+    pub admin: Option<String>,
+    /// Forces large mints through ProposeMint and a waiting period
+    #[cfg(feature = "mint-timelock")]
+    pub mint_timelock: Option<MintTimelock>,
+    /// Commits the minter to only ever lowering the cap with UpdateCap
+    #[serde(default)]
+    pub cap_can_only_decrease: bool,
+    /// Spreads supply updates over this many storage keys, so mints and burns of
+    /// different accounts can execute in parallel
+    #[cfg(feature = "supply-shards")]
+    pub supply_shards: Option<u16>,
+    /// Keeps this many of the latest mints and burns, queryable with SupplyChangeLog
+    #[cfg(feature = "supply-log")]
+    pub supply_log_capacity: Option<u32>,
+    /// Only lets Send and SendFrom target these contracts, and whichever the admin adds
+    #[cfg(feature = "send-allowlist")]
+    pub send_allowlist: Option<Vec<String>>,
+    /// Contract asked with `ComplianceQueryMsg::CanTransfer` before every transfer
+    #[cfg(feature = "compliance")]
+    pub transfer_restriction_contract: Option<String>,
+    /// Caps what each account can transfer out within any span of the period
+    #[cfg(feature = "transfer-limits")]
+    pub transfer_limit: Option<TransferLimit>,
+    /// Rejects transfers and sends of less than this, against dust spam
+    #[cfg(feature = "min-transfer")]
+    pub min_transfer_amount: Option<Uint128>,
+    /// Rejects plain transfers to contracts, which then have to be sent tokens with Send
+    #[cfg(feature = "contract-guard")]
+    #[serde(default)]
+    pub reject_contract_transfers: bool,
+    /// Lets any account mint this much per period with FaucetMint. For testnets only.
+    #[cfg(feature = "faucet")]
+    pub faucet: Option<FaucetConfig>,
+    /// Rejects transfers and mints that would leave an account above this balance
+    #[cfg(feature = "max-wallet")]
+    pub max_wallet: Option<InstantiateMaxWallet>,
+    /// Starts with trading disabled: only these addresses (deployer, LP bootstrapper, ...)
+    /// can transfer until the admin calls EnableTrading
+    #[cfg(feature = "launch-guard")]
+    pub launch_allowlist: Option<Vec<String>>,
+    /// Lets anyone share this asset among the holders with DistributeRewards
+    #[cfg(feature = "rewards")]
+    pub rewards: Option<AssetInfo>,
+    /// Makes balances shares of an underlying amount. Balance, BalanceAtSnapshot, TokenInfo,
+    /// TotalSupply and TotalSupplyAt report the underlying amount; everything else,
+    /// message amounts included, stays in shares.
+    #[cfg(feature = "exchange-rate")]
+    pub exchange_rate: Option<InstantiateExchangeRate>,
+    /// Lets the sender of this message load the state exported from another deployment
+    /// with ImportState, until FinishImport
+    #[cfg(feature = "state-export")]
+    #[serde(default)]
+    pub import_state: bool,
+    /// Leaves out the flat attributes of transfers, mints, burns and allowance changes,
+    /// which are also described by the typed cw20-* events
+    #[cfg(feature = "events")]
+    #[serde(default)]
+    pub events_only: bool,
+    /// Sums repeated addresses in initial_balances instead of rejecting them
+    #[serde(default)]
+    pub merge_initial_balances: bool,
+    /// Which symbols are accepted, now and by UpdateTokenInfo
+    #[serde(default)]
+    pub symbol_validation: SymbolValidation,
+    /// Disables UpdateTokenInfo for good, so the name and symbol never change
+    #[cfg(feature = "rebrand")]
+    #[serde(default)]
+    pub freeze_token_info: bool,
+    /// Largest page of AllAllowances, AllSpenderAllowances and AllAccounts, 100 if unset
+    /// and at most 1000
+    #[cfg(feature = "enumerable")]
+    pub enumeration_limit: Option<u32>,
 }
 
 impl InstantiateMsg {
@@ -32,16 +194,8 @@ impl InstantiateMsg {
 
     pub fn validate(&self) -> StdResult<()> {
         // Check name, symbol, decimals
-        if !self.has_valid_name() {
-            return Err(StdError::generic_err(
-                "Name is not in the expected format (3-50 UTF-8 bytes)",
-            ));
-        }
-        if !self.has_valid_symbol() {
-            return Err(StdError::generic_err(
-                "Ticker symbol is not in expected format [a-zA-Z\\-]{3,12}",
-            ));
-        }
+        validate_name(&self.name)?;
+        validate_symbol(&self.symbol, self.symbol_validation)?;
         if self.decimals > 18 {
             return Err(StdError::generic_err("Decimals must not exceed 18"));
         }
@@ -49,46 +203,667 @@ impl InstantiateMsg {
     }
 
     pub fn has_valid_name(&self) -> bool {
-        let bytes = self.name.as_bytes();
-        if bytes.len() < 3 || bytes.len() > 50 {
-            return false;
-        }
-        true
+        is_valid_name(&self.name)
     }
 
     pub fn has_valid_symbol(&self) -> bool {
-        let bytes = self.symbol.as_bytes();
-        if bytes.len() < 3 || bytes.len() > 12 {
-            return false;
-        }
-        for byte in bytes.iter() {
-            if (*byte != 45) && (*byte < 65 || *byte > 90) && (*byte < 97 || *byte > 122) {
-                return false;
+        is_valid_symbol(&self.symbol, self.symbol_validation)
+    }
+}
+
+/// Checks a token name, at instantiation and when renaming
+pub fn validate_name(name: &str) -> StdResult<()> {
+    if !is_valid_name(name) {
+        return Err(StdError::generic_err(
+            "Name is not in the expected format (3-50 UTF-8 bytes)",
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a ticker symbol, at instantiation and when renaming
+pub fn validate_symbol(symbol: &str, validation: SymbolValidation) -> StdResult<()> {
+    if !is_valid_symbol(symbol, validation) {
+        return Err(StdError::generic_err(match validation {
+            SymbolValidation::Strict => "Ticker symbol is not in expected format [a-zA-Z\\-]{3,12}",
+            SymbolValidation::Relaxed => {
+                "Ticker symbol must be 1-20 printable characters without whitespace"
             }
+        }));
+    }
+    Ok(())
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    if bytes.len() < 3 || bytes.len() > 50 {
+        return false;
+    }
+    true
+}
+
+fn is_valid_symbol(symbol: &str, validation: SymbolValidation) -> bool {
+    if validation == SymbolValidation::Relaxed {
+        let chars = symbol.chars().count();
+        return (1..=20).contains(&chars)
+            && symbol
+                .chars()
+                .all(|c| !c.is_control() && !c.is_whitespace());
+    }
+    let bytes = symbol.as_bytes();
+    if bytes.len() < 3 || bytes.len() > 12 {
+        return false;
+    }
+    for byte in bytes.iter() {
+        if (*byte != 45) && (*byte < 65 || *byte > 90) && (*byte < 97 || *byte > 122) {
+            return false;
         }
-        true
+    }
+    true
+}
+
+/// The cw20 `Cw20ExecuteMsg` plus the extensions of this contract.
+/// In share mode, every amount in these messages is in shares.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Transfer is a base message to move tokens to another account without triggering actions
+    /// memo is emitted as an attribute on this and the other transfer messages,
+    /// e.g. to tell deposits to a shared exchange address apart.
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+        memo: Option<String>,
+    },
+    /// Transfers the sender's whole spendable balance, as it stands when the message
+    /// executes, so no dust is left behind
+    TransferAll { recipient: String },
+    /// Burn is a base message to destroy tokens forever.
+    /// memo is emitted as an attribute, e.g. to say which buyback a burn belongs to.
+    Burn {
+        amount: Uint128,
+        memo: Option<String>,
+    },
+    /// Burns the sender's whole spendable balance, as it stands when the message executes
+    BurnAll {},
+    /// Send is a base message to transfer tokens to a contract and trigger an action
+    /// on the receiving contract.
+    /// With safe, a receiver that fails makes the tokens go back to the sender instead of
+    /// failing the whole transaction.
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+        memo: Option<String>,
+        #[serde(default)]
+        safe: bool,
+    },
+    /// Only with "approval" extension. Allows spender to access an additional amount tokens
+    /// from the owner's (env.sender) account. If expires is Some(), overwrites current allowance
+    /// expiration with this one.
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Only with "approval" extension. Lowers the spender's access of tokens
+    /// from the owner's (env.sender) account by amount. If expires is Some(), overwrites current
+    /// allowance expiration with this one.
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Only with "approval" extension. Like IncreaseAllowance, but also notifies the spender
+    /// contract in the same transaction with a `Cw20ApprovalMsg` carrying msg, so it can act
+    /// on the new allowance right away.
+    IncreaseAllowanceAndSend {
+        spender_contract: String,
+        amount: Uint128,
+        msg: Binary,
+        expires: Option<Expiration>,
+    },
+    /// Called by a spender contract. If enabled, it gets a `Cw20AllowanceChangedMsg` whenever
+    /// an allowance it holds is increased, decreased or pruned.
+    #[cfg(feature = "allowance-hooks")]
+    SetAllowanceNotifications { enabled: bool },
+    /// Removes up to limit expired allowances granted by owner. Anyone can call it.
+    #[cfg(feature = "allowance-hooks")]
+    PruneExpiredAllowances { owner: String, limit: Option<u32> },
+    /// Only with "approval" extension. Transfers amount tokens from owner -> recipient
+    /// if `env.sender` has sufficient pre-approval.
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+        memo: Option<String>,
+    },
+    /// Only with "approval" extension. Transfers the whole remaining allowance of
+    /// `env.sender` from owner -> recipient, as it stands when the message executes
+    TransferFromAll { owner: String, recipient: String },
+    /// Only with "approval" extension. Sends amount tokens from owner -> contract
+    /// if `env.sender` has sufficient pre-approval. With with_owner, the contract gets a
+    /// `Cw20ReceiveFromMsg` naming the owner instead of the standard `Cw20ReceiveMsg`.
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+        memo: Option<String>,
+        #[serde(default)]
+        with_owner: bool,
+    },
+    /// Only with "approval" extension. Destroys tokens forever
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+        memo: Option<String>,
+    },
+    /// Only with the "mintable" extension. If authorized, creates amount new tokens
+    /// and adds to the recipient balance.
+    #[cfg(feature = "mintable")]
+    Mint { recipient: String, amount: Uint128 },
+    /// Only with the "mintable" extension. The current minter may set
+    /// a new minter. Setting the minter to None will remove the
+    /// token's minter forever.
+    #[cfg(feature = "mintable")]
+    UpdateMinter { new_minter: Option<String> },
+    /// Only for the minter. Adds or removes contracts notified with a `SupplyHookMsg`
+    /// after every mint and burn.
+    #[cfg(feature = "supply-hooks")]
+    UpdateSupplyHooks {
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    /// Only with the "marketing" extension. If authorized, updates marketing metadata.
+    /// Leaving out a field or setting it to null leaves it unchanged, `{"clear":{}}`
+    /// clears it. As in cw20, setting "" also clears the field.
+    #[cfg(feature = "marketing")]
+    UpdateMarketing {
+        /// A URL pointing to the project behind this token.
+        #[serde(default)]
+        project: FieldUpdate,
+        /// A longer description of the token and it's utility. Designed for tooltips or such
+        #[serde(default)]
+        description: FieldUpdate,
+        /// The address (if any) who can update this data structure. With the
+        /// "marketing-admins" feature, only the current marketing admin may change it,
+        /// and a new address is nominated until it calls AcceptMarketingAdmin.
+        #[serde(default)]
+        marketing: FieldUpdate,
+    },
+    /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
+    #[cfg(feature = "marketing")]
+    UploadLogo(Logo),
+    /// Only for the account nominated by UpdateMarketing. Takes over the marketing role.
+    #[cfg(feature = "marketing-admins")]
+    AcceptMarketingAdmin {},
+    /// Only for the marketing admin. Adds or removes accounts that may update the
+    /// marketing info and logo next to it.
+    #[cfg(feature = "marketing-admins")]
+    UpdateMarketingAdmins {
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    /// Only with an admin set, unless frozen at instantiation. Changes the name and/or
+    /// the symbol, validated as at instantiation. Decimals can't be changed.
+    #[cfg(feature = "rebrand")]
+    UpdateTokenInfo {
+        name: Option<String>,
+        symbol: Option<String>,
+    },
+    /// Only with an admin set. Sets a metadata entry, such as "website" or
+    /// "coingecko_id", replacing any previous value of the key.
+    #[cfg(feature = "extended-metadata")]
+    SetMetadata { key: String, value: String },
+    /// Only with an admin set. Removes a metadata entry.
+    #[cfg(feature = "extended-metadata")]
+    RemoveMetadata { key: String },
+    /// Only with an admin set. Adds a fungible sub-token, with a supply of zero, that
+    /// is minted with MintMulti and moved with TransferMulti.
+    #[cfg(feature = "multi-token")]
+    CreateToken {
+        token_id: String,
+        name: String,
+        symbol: String,
+        decimals: u8,
+    },
+    /// Only with an admin set. Mints amounts of one or more sub-tokens to recipient.
+    #[cfg(feature = "multi-token")]
+    MintMulti {
+        recipient: String,
+        amounts: Vec<MultiTokenAmount>,
+    },
+    /// Moves amounts of one or more sub-tokens from the sender to recipient
+    #[cfg(feature = "multi-token")]
+    TransferMulti {
+        recipient: String,
+        amounts: Vec<MultiTokenAmount>,
+    },
+    /// Only with "streams" extension. Escrows amount tokens from the sender that vest
+    /// linearly to the recipient between start and end.
+    #[cfg(feature = "streams")]
+    CreateStream {
+        recipient: String,
+        amount: Uint128,
+        start: Timestamp,
+        end: Timestamp,
+    },
+    /// Only with "streams" extension. Pays out the vested, not yet withdrawn part of a stream
+    /// to its recipient.
+    #[cfg(feature = "streams")]
+    WithdrawFromStream { id: u64 },
+    /// Only with "subscriptions" extension. Authorizes spender to pull up to amount_per_period
+    /// tokens from the sender's account every period (in seconds), until expires.
+    /// Replaces any existing subscription for this spender.
+    #[cfg(feature = "subscriptions")]
+    Subscribe {
+        spender: String,
+        amount_per_period: Uint128,
+        period: u64,
+        expires: Option<Expiration>,
+    },
+    /// Only with "subscriptions" extension. Revokes the spender's subscription.
+    #[cfg(feature = "subscriptions")]
+    CancelSubscription { spender: String },
+    /// Only with "subscriptions" extension. Pulls amount tokens from owner to recipient
+    /// (the spender if unset) within the current period's limit.
+    #[cfg(feature = "subscriptions")]
+    Charge {
+        owner: String,
+        amount: Uint128,
+        recipient: Option<String>,
+    },
+    /// Locks amount tokens from the sender now and delivers them to recipient
+    /// once execute_after has passed.
+    #[cfg(feature = "scheduled")]
+    ScheduleTransfer {
+        recipient: String,
+        amount: Uint128,
+        execute_after: Expiration,
+    },
+    /// Delivers up to limit scheduled transfers that have matured. Callable by anyone.
+    #[cfg(feature = "scheduled")]
+    ExecuteMatured { limit: Option<u32> },
+    /// Only with "approval" extension. Burns from several owners at once, deducting
+    /// each owner's allowance.
+    BurnFromBatch { owners: Vec<Cw20Coin> },
+    /// Makes every TransferFrom, SendFrom, BurnFrom and Charge against the sender fail,
+    /// whatever allowances it granted, until the lock is lifted.
+    #[cfg(feature = "allowance-locks")]
+    LockAllowances {},
+    /// Lifts the sender's allowance lock after a fixed delay.
+    #[cfg(feature = "allowance-locks")]
+    UnlockAllowances {},
+    /// Caps what the spender can take from the sender per period with TransferFrom,
+    /// SendFrom and BurnFrom, however large its allowance. None removes the cap.
+    #[cfg(feature = "allowance-limits")]
+    SetAllowanceLimit {
+        spender: String,
+        limit: Option<AllowanceLimit>,
+    },
+    /// Only for the admin. Nominates new_admin, who takes over with AcceptAdmin. None
+    /// removes the admin for good.
+    UpdateAdmin { new_admin: Option<String> },
+    /// Only for the account nominated by UpdateAdmin. Takes over the admin role.
+    AcceptAdmin {},
+    /// Only with an admin set. Names the balances as they stand at the end of this block,
+    /// so they can later be queried by label.
+    #[cfg(feature = "snapshots")]
+    RecordSnapshot { label: String },
+    /// Only with the "mintable" extension. Sets a new cap, or removes it with None.
+    /// Only lowering is allowed if the token was instantiated with cap_can_only_decrease.
+    #[cfg(feature = "mintable")]
+    UpdateCap { cap: Option<Uint128> },
+    /// Only with the "mintable" extension. Removes the minter for good, fixing the supply.
+    /// Unlike UpdateMinter with None, this is recorded and can never be undone.
+    #[cfg(feature = "mintable")]
+    RenounceMinting {},
+    /// Only with an admin set. Sends all the contract holds of a native coin or another
+    /// cw20 token, e.g. funds transferred to the contract by mistake, to recipient.
+    #[cfg(feature = "rescue")]
+    Rescue { asset: AssetInfo, recipient: String },
+    /// Only with the "mintable" extension. Mints to several recipients at once,
+    /// checking the cap against the total.
+    #[cfg(feature = "mintable")]
+    MintBatch { recipients: Vec<Cw20Coin> },
+    /// Only with an admin and the send allowlist enabled. Changes the contracts Send and
+    /// SendFrom may target.
+    #[cfg(feature = "send-allowlist")]
+    UpdateSendAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Only with an admin set. Sets the contract checking every transfer, or removes it
+    /// with None.
+    #[cfg(feature = "compliance")]
+    UpdateTransferRestrictionContract { contract: Option<String> },
+    /// Only with an admin set. Sets the per-account transfer limit, or removes it with
+    /// None. Transfers already counted stay in the window.
+    #[cfg(feature = "transfer-limits")]
+    UpdateTransferLimit { limit: Option<TransferLimit> },
+    /// Only with an admin set. Limits who can run the crank and how often, or opens it to
+    /// anyone at any time again with None.
+    #[cfg(feature = "cranks")]
+    UpdateCrankParams {
+        crank: Crank,
+        params: Option<CrankParams>,
+    },
+    /// Only with an admin set. Sets the smallest amount a transfer or send may move, or
+    /// removes it with None.
+    #[cfg(feature = "min-transfer")]
+    UpdateMinTransferAmount { amount: Option<Uint128> },
+    /// Only with an admin set. Turns the rejection of plain transfers to contracts on or
+    /// off. Send and TransferFrom can reach contracts either way.
+    #[cfg(feature = "contract-guard")]
+    UpdateRejectContractTransfers { reject: bool },
+    /// Only with a faucet set. Mints the sender what is left of the faucet amount in its
+    /// current window, within the cap.
+    #[cfg(feature = "faucet")]
+    FaucetMint {},
+    /// Only the minter. Sets the faucet, or turns it off with None.
+    #[cfg(feature = "faucet")]
+    UpdateFaucet { faucet: Option<FaucetConfig> },
+    /// Only with an admin set. Sets the address transfers to which burn the tokens, or
+    /// removes it with None.
+    #[cfg(feature = "burn-sink")]
+    UpdateBurnSink { address: Option<String> },
+    /// Only with an admin and auto-burn set. Changes the addresses whose transfers, from
+    /// or to them, are not auto-burned.
+    #[cfg(feature = "auto-burn")]
+    UpdateAutoBurnExempt {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Sets the secp256k1 public key the sender signs ExecuteSigned payloads and transfer
+    /// authorizations with, or removes it with None.
+    #[cfg(feature = "meta-tx")]
+    SetSigningKey { pubkey: Option<Binary> },
+    /// Runs a Transfer or Send signed by its owner, as if the owner sent it. `payload` is
+    /// a JSON `SignedPayload`, and `signature` signs `meta_tx::signing_hash` of it with the
+    /// owner's next nonce. Whoever relays it pays the gas.
+    #[cfg(feature = "meta-tx")]
+    ExecuteSigned {
+        payload: Binary,
+        nonce: u64,
+        signature: Binary,
+    },
+    /// Runs a transfer its owner authorized by signing `meta_tx::authorization_hash` of it.
+    /// Each authorization has its own random nonce, so any number of them can be relayed
+    /// in any order, within their validity window.
+    #[cfg(feature = "meta-tx")]
+    TransferWithAuthorization {
+        authorization: TransferAuthorization,
+        signature: Binary,
+    },
+    /// Voids the sender's transfer authorization with this nonce before it is used.
+    #[cfg(feature = "meta-tx")]
+    CancelAuthorization { nonce: Binary },
+    /// Only in share mode, by the rate updater or the admin. Sets the underlying amount
+    /// per share.
+    #[cfg(feature = "exchange-rate")]
+    UpdateExchangeRate { rate: Decimal },
+    /// Only with an admin and the launch guard set. Lets everyone transfer, for good.
+    #[cfg(feature = "launch-guard")]
+    EnableTrading {},
+    /// Only with an admin set. Sets the max wallet, or removes it with None, and changes
+    /// the accounts exempt from it.
+    #[cfg(feature = "max-wallet")]
+    UpdateMaxWallet {
+        amount: Option<Uint128>,
+        #[serde(default)]
+        add_exempt: Vec<String>,
+        #[serde(default)]
+        remove_exempt: Vec<String>,
+    },
+    /// Only with the "mintable" extension. Queues a mint that the minter can execute
+    /// once the configured timelock has passed.
+    #[cfg(feature = "mint-timelock")]
+    ProposeMint { recipient: String, amount: Uint128 },
+    /// Only with the "mintable" extension. Executes a queued mint after its timelock.
+    #[cfg(feature = "mint-timelock")]
+    ExecuteMint { id: u64 },
+    /// Drops a queued mint. Callable by the minter or the admin.
+    #[cfg(feature = "mint-timelock")]
+    CancelMint { id: u64 },
+    /// Only with the "mintable" extension. Like Mint, but the new tokens can only be
+    /// spent as they unlock along the linear schedule.
+    #[cfg(feature = "vesting")]
+    MintLocked {
+        recipient: String,
+        amount: Uint128,
+        schedule: VestingSchedule,
+    },
+    /// Only with rewards in a native denom. Shares the attached coins among all holders,
+    /// pro rata to their balance.
+    #[cfg(feature = "rewards")]
+    DistributeRewards {},
+    /// Only with rewards in a cw20 token. Shares the tokens sent among all holders, like
+    /// DistributeRewards.
+    #[cfg(feature = "rewards")]
+    Receive(Cw20ReceiveMsg),
+    /// Pays out the rewards the sender earned so far.
+    #[cfg(feature = "rewards")]
+    WithdrawRewards {},
+    /// Gives the voting power of the sender's balance to delegatee, which can be the
+    /// sender itself. Balances never delegated carry no voting power.
+    #[cfg(feature = "votes")]
+    Delegate { delegatee: String },
+    /// Only for the creator of a token instantiated with import_state. Credits a chunk of
+    /// exported balances and restores exported allowances.
+    #[cfg(feature = "state-export")]
+    ImportState {
+        balances: Vec<Cw20Coin>,
+        #[serde(default)]
+        allowances: Vec<ExportedAllowance>,
+    },
+    /// Only for the creator of a token instantiated with import_state. Ends the import.
+    #[cfg(feature = "state-export")]
+    FinishImport {},
+    /// Rescales up to limit more balances and allowances after a migration changed the
+    /// decimals. Anyone can call it, until RescaleStatus reports nothing left.
+    #[cfg(feature = "rescale-decimals")]
+    ContinueMigration { limit: Option<u32> },
+    /// Runs the next limit accounts of the migration steps left by the last migration.
+    /// Anyone can call it, until MigrationStatus reports nothing left.
+    ProcessMigration { limit: Option<u32> },
+    /// Only with a native mirror. Swaps amount of the sender's tokens for as many coins of
+    /// the mirrored denom.
+    #[cfg(feature = "tokenfactory")]
+    Unwrap { amount: Uint128 },
+    /// Only with a native mirror. Swaps the coins of the mirrored denom sent along back
+    /// for as many tokens.
+    #[cfg(feature = "tokenfactory")]
+    Wrap {},
+}
+
+/// `Cw20ReceiveMsg` extended with the owner whose tokens a spender sent with SendFrom.
+/// Receiving contracts get it as `{"receive_from": {...}}`.
+#[cw_serde]
+pub struct Cw20ReceiveFromMsg {
+    /// The spender who executed SendFrom
+    pub sender: String,
+    /// The account the tokens were taken from
+    pub owner: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+impl Cw20ReceiveFromMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::ReceiveFrom(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+/// Sent by IncreaseAllowanceAndSend to the spender contract, as
+/// `{"receive_approval": {...}}`.
+#[cw_serde]
+pub struct Cw20ApprovalMsg {
+    /// The account that granted the allowance
+    pub owner: String,
+    /// How much the allowance was increased by
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+impl Cw20ApprovalMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::ReceiveApproval(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+#[cfg(feature = "allowance-hooks")]
+#[cw_serde]
+pub enum AllowanceChange {
+    Increased,
+    Decreased,
+    /// Removed by PruneExpiredAllowances after it expired
+    Pruned,
+}
+
+/// Sent to spender contracts that opted in with SetAllowanceNotifications whenever an
+/// allowance they hold changes, as `{"allowance_changed": {...}}`.
+#[cfg(feature = "allowance-hooks")]
+#[cw_serde]
+pub struct Cw20AllowanceChangedMsg {
+    /// The account that granted the allowance
+    pub owner: String,
+    pub change: AllowanceChange,
+    /// How much the allowance changed by
+    pub amount: Uint128,
+    /// The allowance after the change
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cfg(feature = "allowance-hooks")]
+impl Cw20AllowanceChangedMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::AllowanceChanged(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+/// Sent to the supply hooks after every mint and burn, as `{"supply_hook": {...}}`, so
+/// they can track the supply without polling TokenInfo. In share mode, the amounts and
+/// the new supply are in shares.
+#[cfg(feature = "supply-hooks")]
+#[cw_serde]
+pub enum SupplyHookMsg {
+    Mint {
+        minter: String,
+        recipient: String,
+        amount: Uint128,
+        new_total_supply: Uint128,
+    },
+    Burn {
+        owner: String,
+        amount: Uint128,
+        new_total_supply: Uint128,
+    },
+}
+
+#[cfg(feature = "supply-hooks")]
+impl SupplyHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::SupplyHook(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
     }
 }
 
+// This is just a helper to properly serialize the above messages
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    ReceiveFrom(Cw20ReceiveFromMsg),
+    ReceiveApproval(Cw20ApprovalMsg),
+    #[cfg(feature = "allowance-hooks")]
+    AllowanceChanged(Cw20AllowanceChangedMsg),
+    #[cfg(feature = "supply-hooks")]
+    SupplyHook(SupplyHookMsg),
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     /// Returns the current balance of the given address, 0 if unset.
+    /// In share mode, this is the underlying amount the balance is worth.
     #[returns(cw20::BalanceResponse)]
     Balance { address: String },
+    /// Returns the storage key and value of the given address's balance, and the contract,
+    /// height and store they belong to, to verify an ICS-23 proof of it.
+    #[cfg(feature = "balance-proofs")]
+    #[returns(BalanceProofDataResponse)]
+    BalanceProofData { address: String },
     /// Returns metadata on the contract - name, decimals, supply, etc.
+    /// In share mode, the supply is the underlying amount it is worth.
     #[returns(cw20::TokenInfoResponse)]
     TokenInfo {},
     /// Only with "mintable" extension.
     /// Returns who can mint and the hard cap on maximum tokens after minting.
+    #[cfg(feature = "mintable")]
     #[returns(cw20::MinterResponse)]
     Minter {},
     /// Only with "allowance" extension.
     /// Returns how much spender can use from owner account, 0 if unset.
+    /// In share mode, allowances are in shares.
     #[returns(cw20::AllowanceResponse)]
     Allowance { owner: String, spender: String },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this owner has approved. Supports pagination.
+    #[cfg(feature = "enumerable")]
     #[returns(cw20::AllAllowancesResponse)]
     AllAllowances {
         owner: String,
@@ -97,30 +872,1079 @@ pub enum QueryMsg {
     },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this spender has been granted. Supports pagination.
+    #[cfg(feature = "enumerable")]
     #[returns(cw20::AllSpenderAllowancesResponse)]
     AllSpenderAllowances {
         spender: String,
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Only with "enumerable" extension (and "allowances")
+    /// Returns the allowances of this owner expiring before the given time or height,
+    /// lapsed ones included, or with `never` the ones that never expire. Supports
+    /// pagination.
+    #[cfg(feature = "enumerable")]
+    #[returns(cw20::AllAllowancesResponse)]
+    AllowancesExpiringBefore {
+        owner: String,
+        timestamp_or_height: Expiration,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Only with "enumerable" extension
     /// Returns all accounts that have balances. Supports pagination.
+    #[cfg(feature = "enumerable")]
     #[returns(cw20::AllAccountsResponse)]
     AllAccounts {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Only with "enumerable" extension
+    /// Sums the balances page by page and checks them against the total supply once
+    /// the last page is reached. Pass back last_address and running_total of the
+    /// previous page to continue.
+    /// Only with "enumerable" extension
+    /// AllAllowances with a cursor: pass the next_cursor of a page as cursor to get the
+    /// next one
+    #[cfg(feature = "enumerable")]
+    #[returns(Page<cw20::AllowanceInfo>)]
+    AllowancesPage {
+        owner: String,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Only with "enumerable" extension
+    /// AllSpenderAllowances with a cursor, like AllowancesPage
+    #[cfg(feature = "enumerable")]
+    #[returns(Page<cw20::SpenderAllowanceInfo>)]
+    SpenderAllowancesPage {
+        spender: String,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Only with "enumerable" extension
+    /// AllAccounts with a cursor, like AllowancesPage
+    #[cfg(feature = "enumerable")]
+    #[returns(Page<String>)]
+    AccountsPage {
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Only with "enumerable" extension
+    /// Number of allowances this owner has approved, counted entry by entry
+    #[cfg(feature = "enumerable")]
+    #[returns(CountResponse)]
+    AllowanceCount { owner: String },
+    /// Only with "enumerable" extension
+    /// Number of allowances this spender has been granted, counted entry by entry
+    #[cfg(feature = "enumerable")]
+    #[returns(CountResponse)]
+    SpenderAllowanceCount { spender: String },
+    /// Only with "enumerable" extension
+    /// Number of accounts that have balances, counted entry by entry
+    #[cfg(feature = "enumerable")]
+    #[returns(CountResponse)]
+    AccountCount {},
+    /// Only with "enumerable" extension
+    /// Largest page AllAllowances, AllSpenderAllowances and AllAccounts return
+    #[cfg(feature = "enumerable")]
+    #[returns(EnumerationLimitResponse)]
+    EnumerationLimit {},
+    #[cfg(feature = "enumerable")]
+    #[returns(VerifyInvariantsResponse)]
+    VerifyInvariants {
+        start_after: Option<String>,
+        running_total: Option<Uint128>,
+        limit: Option<u32>,
+    },
     /// Only with "marketing" extension
     /// Returns more metadata on the contract to display in the client:
     /// - description, logo, project url, etc.
+    #[cfg(feature = "marketing")]
     #[returns(cw20::MarketingInfoResponse)]
     MarketingInfo {},
     /// Only with "marketing" extension
     /// Downloads the embedded logo data (if stored on chain). Errors if no logo data is stored for this
     /// contract.
+    #[cfg(feature = "marketing")]
     #[returns(cw20::DownloadLogoResponse)]
     DownloadLogo {},
+    /// Returns the logo url, or the mime type, size and hash of the embedded logo, without
+    /// its data.
+    #[cfg(feature = "logo-info")]
+    #[returns(LogoInfoResponse)]
+    LogoInfo {},
+    /// Returns the token metadata entries in key order. Supports pagination.
+    #[cfg(feature = "extended-metadata")]
+    #[returns(MetadataResponse)]
+    Metadata {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns name, symbol, decimals and supply of a sub-token
+    #[cfg(feature = "multi-token")]
+    #[returns(cw20::TokenInfoResponse)]
+    MultiTokenInfo { token_id: String },
+    /// Returns the balance of the given address in a sub-token, 0 if unset.
+    #[cfg(feature = "multi-token")]
+    #[returns(cw20::BalanceResponse)]
+    MultiBalance { token_id: String, address: String },
+    /// Returns the ids of all sub-tokens. Supports pagination.
+    #[cfg(feature = "multi-token")]
+    #[returns(MultiTokensResponse)]
+    MultiTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "tokenfactory" feature
+    /// Returns the native denom mirroring this token's supply, if any.
+    #[returns(MirrorDenomResponse)]
+    MirrorDenom {},
+    /// Returns the auto-burn rate and the total amount burned by it so far.
+    #[cfg(feature = "auto-burn")]
+    #[returns(AutoBurnInfoResponse)]
+    AutoBurnInfo {},
+    /// Only with "burn-stats" extension
+    /// Returns the total amount of its own tokens an account has burned.
+    #[cfg(feature = "burn-stats")]
+    #[returns(BurnedByResponse)]
+    BurnedBy { address: String },
+    /// Only with "burn-sink" extension
+    /// Returns the address transfers to which burn the tokens, if any.
+    #[cfg(feature = "burn-sink")]
+    #[returns(BurnSinkResponse)]
+    BurnSink {},
+    /// Only with "streams" extension
+    /// Returns a single stream by id.
+    #[cfg(feature = "streams")]
+    #[returns(StreamResponse)]
+    Stream { id: u64 },
+    /// Only with "streams" extension
+    /// Returns all streams funded by this sender. Supports pagination.
+    #[cfg(feature = "streams")]
+    #[returns(StreamsResponse)]
+    StreamsBySender {
+        sender: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Only with "streams" extension
+    /// Returns all streams paying out to this recipient. Supports pagination.
+    #[cfg(feature = "streams")]
+    #[returns(StreamsResponse)]
+    StreamsByRecipient {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Only with "holder_sampling" enabled
+    /// Deterministically draws up to 100 holders from the seed, weighted by balance.
+    /// The contract's own balance is never drawn.
+    #[cfg(feature = "sampling")]
+    #[returns(SampleHoldersResponse)]
+    SampleHolders { seed: String, count: u32 },
+    /// Only with "subscriptions" extension
+    /// Returns the subscription owner granted to spender, if any.
+    #[cfg(feature = "subscriptions")]
+    #[returns(Option<SubscriptionResponse>)]
+    Subscription { owner: String, spender: String },
+    /// Returns how much the address can transfer right now, honoring every restriction
+    /// of this token, and which rule is the binding one.
+    #[returns(SpendableBalanceResponse)]
+    SpendableBalance { address: String },
+    /// Returns whether a transfer of amount from `from` to `to` would go through right
+    /// now: code 0 if so, otherwise the code and reason of the rule blocking it. With
+    /// `send`, checks a Send to the contract `to` rather than a Transfer.
+    #[returns(DetectTransferRestrictionResponse)]
+    DetectTransferRestriction {
+        from: String,
+        to: String,
+        amount: Uint128,
+        #[serde(default)]
+        send: bool,
+    },
+    /// Returns the named balance snapshots, ordered by label. Supports pagination.
+    #[cfg(feature = "snapshots")]
+    #[returns(SnapshotsResponse)]
+    Snapshots {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the balance of the given address as of a named snapshot.
+    /// In share mode, this is the underlying amount at the rate of the snapshot's height.
+    #[cfg(feature = "snapshots")]
+    #[returns(cw20::BalanceResponse)]
+    BalanceAtSnapshot { address: String, label: String },
+    /// Returns the total supply at the start of the given block height.
+    /// In share mode, this is the underlying amount at the rate of that height.
+    #[cfg(feature = "snapshots")]
+    #[returns(TotalSupplyResponse)]
+    TotalSupplyAt { height: u64 },
+    /// Returns whether the owner's allowances are locked, and when a requested unlock
+    /// takes effect.
+    #[cfg(feature = "allowance-locks")]
+    #[returns(AllowanceLockResponse)]
+    AllowanceLock { owner: String },
+    /// Returns whether the spender gets notified of changes to its allowances.
+    #[cfg(feature = "allowance-hooks")]
+    #[returns(AllowanceNotificationsResponse)]
+    AllowanceNotifications { spender: String },
+    /// Returns the spend cap the owner put on the spender, how much of it is left in the
+    /// current window, and when the window closes. All None without a cap.
+    #[cfg(feature = "allowance-limits")]
+    #[returns(AllowanceLimitResponse)]
+    AllowanceLimit { owner: String, spender: String },
+    /// Returns the current total supply, summed over all supply shards.
+    /// In share mode, this is the underlying amount the supply is worth.
+    #[returns(TotalSupplyResponse)]
+    TotalSupply {},
+    /// Returns whether Send is restricted, and the allowed contracts. Supports pagination.
+    #[cfg(feature = "send-allowlist")]
+    #[returns(SendAllowlistResponse)]
+    SendAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns how much more the address can transfer right now under the transfer limit,
+    /// and when the oldest transfer it counts leaves the rolling window. All None without
+    /// a limit.
+    #[cfg(feature = "transfer-limits")]
+    #[returns(TransferQuotaResponse)]
+    TransferQuota { address: String },
+    /// Returns who can run the crank and how often, when it last ran and when it can run
+    /// next. No restrictions and no next run if the admin set none.
+    #[cfg(feature = "cranks")]
+    #[returns(CrankStatusResponse)]
+    CrankStatus { crank: Crank },
+    /// Returns how many times the keeper ran the crank.
+    #[cfg(feature = "cranks")]
+    #[returns(KeeperRunsResponse)]
+    KeeperRuns { keeper: String, crank: Crank },
+    /// Returns the smallest amount a transfer or send may move, if any.
+    #[cfg(feature = "min-transfer")]
+    #[returns(MinTransferAmountResponse)]
+    MinTransferAmount {},
+    /// Returns how much more the address can mint from the faucet in its current window,
+    /// and when the window closes. All None without a faucet.
+    #[cfg(feature = "faucet")]
+    #[returns(FaucetQuotaResponse)]
+    FaucetQuota { address: String },
+    /// Returns the signing key and next nonce of the address, and the domain separator
+    /// its ExecuteSigned payloads are bound to.
+    #[cfg(feature = "meta-tx")]
+    #[returns(SigningInfoResponse)]
+    SigningInfo { address: String },
+    /// Returns whether the authorizer's transfer authorization with this nonce was used
+    /// or cancelled.
+    #[cfg(feature = "meta-tx")]
+    #[returns(AuthorizationStateResponse)]
+    AuthorizationState { authorizer: String, nonce: Binary },
+    /// Returns the max wallet, if any, and the exempt accounts. Supports pagination.
+    #[cfg(feature = "max-wallet")]
+    #[returns(MaxWalletResponse)]
+    MaxWallet {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether everyone can transfer, or only the launch allowlist.
+    #[cfg(feature = "launch-guard")]
+    #[returns(TradingStatusResponse)]
+    TradingStatus {},
+    /// Returns the accounts whose balance last changed at the given height or later, so
+    /// indexers can sync holders incrementally. Supports pagination.
+    #[cfg(feature = "changelog")]
+    #[returns(ChangedBalancesResponse)]
+    ChangedBalancesSince {
+        height: u64,
+        start_after: Option<BalanceChange>,
+        limit: Option<u32>,
+    },
+    /// Returns the contracts notified of every mint and burn.
+    #[cfg(feature = "supply-hooks")]
+    #[returns(SupplyHooksResponse)]
+    SupplyHooks {},
+    /// Returns the latest mints and burns kept in the supply log, oldest first, starting
+    /// after the given record id. Supports pagination.
+    #[cfg(feature = "supply-log")]
+    #[returns(SupplyChangeLogResponse)]
+    SupplyChangeLog {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the voting power delegated to the address at the start of the given height.
+    /// In share mode, voting power is counted in shares.
+    #[cfg(feature = "votes")]
+    #[returns(VotingPowerResponse)]
+    VotingPowerAt { address: String, height: u64 },
+    /// Returns the voting power of all delegatees together at the start of the given height.
+    #[cfg(feature = "votes")]
+    #[returns(VotingPowerResponse)]
+    TotalPowerAt { height: u64 },
+    /// DAO DAO voting module interface: VotingPowerAt, at the current height if none is
+    /// given. Lets a DAO use the token as its voting module directly.
+    #[cfg(feature = "votes")]
+    #[returns(VotingPowerAtHeightResponse)]
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// DAO DAO voting module interface: TotalPowerAt, at the current height if none is given.
+    #[cfg(feature = "votes")]
+    #[returns(TotalPowerAtHeightResponse)]
+    TotalPowerAtHeight { height: Option<u64> },
+    /// Returns the underlying amount per share, one outside share mode.
+    #[cfg(feature = "exchange-rate")]
+    #[returns(ExchangeRateResponse)]
+    ExchangeRate {},
+    /// Returns the balance of the given address in shares, the unit of message amounts.
+    #[cfg(feature = "exchange-rate")]
+    #[returns(cw20::BalanceResponse)]
+    Shares { address: String },
+    /// Returns the rewards the address can withdraw.
+    #[cfg(feature = "rewards")]
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { address: String },
+    /// Returns the cw22 interfaces the token supports, with their version: the cw20 spec
+    /// and extensions it implements, and the cw20-plus subsystems built in.
+    #[returns(SupportedInterfacesResponse)]
+    SupportedInterfaces {},
+    /// Returns which capabilities are switched on for this token, so clients don't have
+    /// to probe the queries of each.
+    #[returns(FeaturesResponse)]
+    Features {},
+    /// Returns the admin, if any, and the account nominated to take over.
+    #[returns(AdminResponse)]
+    Admin {},
+    /// Returns the cw2 name and version stored for this contract, and what the running
+    /// code was built from: git commit, compiler and cargo features.
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    /// Returns the migration steps still left to ProcessMigration, if any.
+    #[returns(MigrationStatusResponse)]
+    MigrationStatus {},
+    /// Returns one page of the token state, to re-deploy it elsewhere with ImportState.
+    /// Pass `next` of the previous page as `start_after`.
+    #[cfg(feature = "state-export")]
+    #[returns(ExportStateResponse)]
+    ExportState {
+        section: ExportSection,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the nominated marketing admin, if any, and the additional marketing admins.
+    #[cfg(feature = "marketing-admins")]
+    #[returns(MarketingAdminsResponse)]
+    MarketingAdmins {},
+    /// Returns whether the minter renounced minting, i.e. whether the supply is fixed.
+    #[cfg(feature = "mintable")]
+    #[returns(MintingRenouncedResponse)]
+    MintingRenounced {},
+    /// Returns the mints waiting for their timelock. Supports pagination.
+    #[cfg(feature = "mint-timelock")]
+    #[returns(PendingMintsResponse)]
+    PendingMints {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the pending scheduled transfers. Supports pagination.
+    #[cfg(feature = "scheduled")]
+    #[returns(ScheduledTransfersResponse)]
+    ScheduledTransfers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns how far the decimal rescale started by a migration got, if one is running.
+    #[cfg(feature = "rescale-decimals")]
+    #[returns(RescaleStatusResponse)]
+    RescaleStatus {},
+}
+
+#[cw_serde]
+pub struct MirrorDenomResponse {
+    pub denom: Option<String>,
+}
+
+#[cw_serde]
+pub struct AutoBurnInfoResponse {
+    /// Zero if auto-burn is not configured
+    pub basis_points: u16,
+    pub total_burned: Uint128,
+}
+
+/// One page of a cursor paginated query
+#[cfg(feature = "enumerable")]
+#[cw_serde]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Opaque position to pass as the cursor of the next page, None on the last page
+    pub next_cursor: Option<Binary>,
+    /// Whether there are entries after this page
+    pub is_truncated: bool,
+}
+
+#[cfg(feature = "enumerable")]
+#[cw_serde]
+pub struct CountResponse {
+    pub count: u64,
+}
+
+#[cfg(feature = "enumerable")]
+#[cw_serde]
+pub struct EnumerationLimitResponse {
+    pub max_limit: u32,
+}
+
+#[cfg(feature = "enumerable")]
+#[cw_serde]
+pub struct VerifyInvariantsResponse {
+    /// Sum of the balances up to and including last_address
+    pub running_total: Uint128,
+    /// None if there were no balances left to add
+    pub last_address: Option<String>,
+    pub total_supply: Uint128,
+    /// None until the last balance was added, then whether the balances add up to the
+    /// total supply
+    pub passed: Option<bool>,
+}
+
+#[cfg(feature = "rescale-decimals")]
+#[cw_serde]
+pub struct RescaleStatusResponse {
+    /// None once every stored amount is rescaled
+    pub rescale: Option<DecimalRescale>,
+}
+
+#[cw_serde]
+pub struct BurnedByResponse {
+    pub burned: Uint128,
+}
+
+#[cw_serde]
+pub struct BurnSinkResponse {
+    pub address: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct StreamResponse {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub withdrawn: Uint128,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Vested amount that can be withdrawn right now
+    pub withdrawable: Uint128,
+}
+
+#[cw_serde]
+pub struct StreamsResponse {
+    pub streams: Vec<StreamResponse>,
+}
+
+#[cw_serde]
+pub struct SampleHoldersResponse {
+    /// Drawn with replacement, so a large holder may appear more than once
+    pub holders: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SubscriptionResponse {
+    pub amount_per_period: Uint128,
+    pub period: u64,
+    pub expires: Expiration,
+    /// Amount the spender can still charge in the current period
+    pub available: Uint128,
+}
+
+/// The rule limiting how much an account can spend
+#[cw_serde]
+pub enum SpendConstraint {
+    /// Nothing but the balance itself
+    Balance,
+    /// Tokens from locked mints that have not vested yet
+    Vesting { locked: Uint128 },
+    /// What is left of the transfer limit in the rolling window
+    TransferLimit { remaining: Uint128 },
+    /// The launch guard holds transfers from the account back
+    TradingNotEnabled,
+    /// What the account could transfer is below the minimum transfer amount
+    BelowMinimum { min: Uint128 },
+}
+
+#[cw_serde]
+pub struct SpendableBalanceResponse {
+    pub spendable: Uint128,
+    pub constraint: SpendConstraint,
+}
+
+#[cw_serde]
+pub struct DetectTransferRestrictionResponse {
+    /// One of the codes in `restrictions`, 0 meaning the transfer is allowed
+    pub code: u8,
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct ScheduledTransferInfo {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub execute_after: Expiration,
+}
+
+#[cw_serde]
+pub struct ScheduledTransfersResponse {
+    pub transfers: Vec<ScheduledTransferInfo>,
+}
+
+#[cw_serde]
+pub struct SnapshotInfo {
+    pub label: String,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct SnapshotsResponse {
+    pub snapshots: Vec<SnapshotInfo>,
+}
+
+#[cw_serde]
+pub struct PendingMintInfo {
+    pub id: u64,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub executable_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct PendingMintsResponse {
+    pub mints: Vec<PendingMintInfo>,
+}
+
+#[cw_serde]
+pub struct BalanceProofDataResponse {
+    pub address: String,
+    /// The balance as stored, in shares in share mode
+    pub balance: Uint128,
+    pub contract: String,
+    pub chain_id: String,
+    /// Height of the state the key and value were read from
+    pub height: u64,
+    /// Name of the module store holding the key
+    pub store_name: String,
+    /// Key of the balance in the contract's storage, as used by raw queries
+    pub key: Binary,
+    /// Key of the balance in the module store, the one the proof is for
+    pub store_key: Binary,
+    /// The stored JSON of the balance. None if the address never held tokens, which
+    /// takes a non-membership proof.
+    pub value: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct AllowanceNotificationsResponse {
+    pub enabled: bool,
+}
+
+#[cw_serde]
+pub struct AllowanceLockResponse {
+    pub locked: bool,
+    pub unlocks_at: Option<Timestamp>,
+}
+
+#[cfg(feature = "allowance-limits")]
+#[cw_serde]
+pub struct AllowanceLimitResponse {
+    pub limit: Option<AllowanceLimit>,
+    pub remaining: Option<Uint128>,
+    pub window_end: Option<Timestamp>,
+}
+
+/// All fields are empty if the token has no logo
+#[cw_serde]
+#[derive(Default)]
+pub struct LogoInfoResponse {
+    /// Set if the logo is hosted off-chain
+    pub url: Option<String>,
+    /// Mime type of the embedded logo, as DownloadLogo reports it
+    pub mime_type: Option<String>,
+    /// Size of the embedded logo in bytes
+    pub size: Option<u64>,
+    /// SHA-256 of the embedded logo
+    pub sha256: Option<HexBinary>,
+}
+
+#[cw_serde]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct MetadataResponse {
+    pub entries: Vec<MetadataEntry>,
+}
+
+#[cw_serde]
+pub struct MultiTokenAmount {
+    pub token_id: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct MultiTokensResponse {
+    pub token_ids: Vec<String>,
+}
+
+#[cw_serde]
+pub struct AdminResponse {
+    pub admin: Option<Addr>,
+    pub pending: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct MarketingAdminsResponse {
+    pub pending: Option<Addr>,
+    pub admins: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct SupportedInterface {
+    /// e.g. "crates.io:cw20-mintable"
+    pub supported_interface: String,
+    pub version: String,
+}
+
+#[cw_serde]
+pub struct SupportedInterfacesResponse {
+    pub interfaces: Vec<SupportedInterface>,
+}
+
+#[cw_serde]
+pub struct MigrationStatusResponse {
+    /// None once every step ran
+    pub pending: Option<PendingMigration>,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    /// cw2 contract name, as stored at instantiation or the last migration
+    pub contract: String,
+    /// cw2 contract version, as stored at instantiation or the last migration
+    pub version: String,
+    pub build: BuildInfo,
+}
+
+/// Where the running code comes from. The commit and compiler are only known to builds
+/// with the "build-info" feature.
+#[cw_serde]
+pub struct BuildInfo {
+    /// Crate version of the running code
+    pub version: String,
+    pub git_hash: Option<String>,
+    pub rustc: Option<String>,
+    /// Cargo features compiled in
+    pub features: Vec<String>,
+}
+
+/// Subsystems not built into the contract always report false
+#[cw_serde]
+pub struct FeaturesResponse {
+    pub mintable: bool,
+    pub capped: bool,
+    pub minting_renounced: bool,
+    pub marketing: bool,
+    pub admin: bool,
+    pub native_mirror: bool,
+    pub auto_burn: bool,
+    pub burn_sink: bool,
+    pub mint_timelock: bool,
+    pub holder_sampling: bool,
+    pub supply_shards: bool,
+    pub supply_log: bool,
+    pub send_allowlist: bool,
+    pub transfer_restriction_contract: bool,
+    pub transfer_limit: bool,
+    pub min_transfer_amount: bool,
+    /// Whether plain transfers to contracts are rejected
+    pub reject_contract_transfers: bool,
+    pub faucet: bool,
+    pub max_wallet: bool,
+    /// False while the launch guard holds transfers back
+    pub trading_enabled: bool,
+    pub rewards: bool,
+    pub exchange_rate: bool,
+    /// Whether the admin can still rename the token
+    pub token_info_updatable: bool,
+    /// Whether the creator can still import exported state
+    pub import_open: bool,
+}
+
+#[cw_serde]
+pub enum ExportSection {
+    Balances,
+    Allowances,
+    /// Token info, minter and marketing info, to instantiate the copy with
+    Config,
+}
+
+#[cw_serde]
+pub struct ExportedAllowance {
+    pub owner: String,
+    pub spender: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct ExportedConfig {
+    pub token_info: TokenInfoResponse,
+    pub minter: Option<MinterResponse>,
+    pub marketing: MarketingInfoResponse,
+    /// To instantiate the copy with, so it accepts the same symbol
+    #[serde(default)]
+    pub symbol_validation: SymbolValidation,
+}
+
+/// Only the field of the requested section is filled
+#[cw_serde]
+pub struct ExportStateResponse {
+    pub balances: Vec<Cw20Coin>,
+    pub allowances: Vec<ExportedAllowance>,
+    pub config: Option<ExportedConfig>,
+    /// Cursor of the next page, None once the section is exhausted
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct MintingRenouncedResponse {
+    pub renounced: bool,
+}
+
+#[cw_serde]
+pub struct SendAllowlistResponse {
+    /// If false, Send can target any contract
+    pub enabled: bool,
+    pub contracts: Vec<String>,
+}
+
+#[cw_serde]
+pub struct BalanceChange {
+    pub height: u64,
+    pub address: String,
+}
+
+#[cw_serde]
+pub struct ChangedBalancesResponse {
+    pub changes: Vec<BalanceChange>,
+}
+
+#[cw_serde]
+pub enum SupplyAction {
+    Mint,
+    Burn,
+}
+
+#[cw_serde]
+pub struct SupplyChange {
+    /// Increases by one with every record, also across the ones dropped from the log
+    pub id: u64,
+    pub action: SupplyAction,
+    pub amount: Uint128,
+    /// Account that executed the mint or burn; the sender for burns taken on transfers
+    pub actor: Addr,
+    /// Account credited by the mint, or whose tokens were burned
+    pub account: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct SupplyHooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SupplyChangeLogResponse {
+    pub changes: Vec<SupplyChange>,
+}
+
+#[cw_serde]
+pub struct VotingPowerResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct VotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct TotalPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct ExchangeRateResponse {
+    pub rate: Decimal,
+    pub updater: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TradingStatusResponse {
+    pub enabled: bool,
+}
+
+#[cw_serde]
+pub struct MaxWalletResponse {
+    pub amount: Option<Uint128>,
+    pub exempt: Vec<String>,
+}
+
+#[cfg(feature = "transfer-limits")]
+#[cw_serde]
+pub struct TransferQuotaResponse {
+    pub limit: Option<TransferLimit>,
+    pub remaining: Option<Uint128>,
+    /// When the oldest transfer still counted leaves the window, freeing its amount.
+    /// None if nothing is counted.
+    pub next_release: Option<Timestamp>,
+}
+
+/// The messages anyone can call to move the contract's state along
+#[cfg(feature = "cranks")]
+#[cw_serde]
+#[derive(Copy)]
+pub enum Crank {
+    ExecuteMatured,
+    /// DistributeRewards, and rewards sent in a cw20 token
+    DistributeRewards,
+    PruneExpiredAllowances,
+    ContinueMigration,
+    ProcessMigration,
+}
+
+#[cfg(feature = "cranks")]
+impl Crank {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Crank::ExecuteMatured => "execute_matured",
+            Crank::DistributeRewards => "distribute_rewards",
+            Crank::PruneExpiredAllowances => "prune_expired_allowances",
+            Crank::ContinueMigration => "continue_migration",
+            Crank::ProcessMigration => "process_migration",
+        }
+    }
+}
+
+#[cfg(feature = "cranks")]
+#[cw_serde]
+pub struct CrankParams {
+    /// Least time between two runs, in seconds
+    pub min_interval: u64,
+    /// The only addresses that may run the crank, anyone if None
+    pub keepers: Option<Vec<String>>,
+}
+
+#[cfg(feature = "cranks")]
+#[cw_serde]
+pub struct CrankStatusResponse {
+    pub config: Option<CrankConfig>,
+    pub last_run: Option<Timestamp>,
+    pub next_run: Option<Timestamp>,
+}
+
+#[cfg(feature = "cranks")]
+#[cw_serde]
+pub struct KeeperRunsResponse {
+    pub runs: u64,
+}
+
+#[cfg(feature = "min-transfer")]
+#[cw_serde]
+pub struct MinTransferAmountResponse {
+    pub amount: Option<Uint128>,
+}
+
+#[cfg(feature = "faucet")]
+#[cw_serde]
+pub struct FaucetQuotaResponse {
+    pub faucet: Option<FaucetConfig>,
+    pub remaining: Option<Uint128>,
+    pub window_end: Option<Timestamp>,
+}
+
+/// What an owner signs for ExecuteSigned, serialized to JSON
+#[cfg(feature = "meta-tx")]
+#[cw_serde]
+pub struct SignedPayload {
+    pub owner: String,
+    pub msg: SignedMsg,
+}
+
+#[cfg(feature = "meta-tx")]
+#[cw_serde]
+pub enum SignedMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+        memo: Option<String>,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+        memo: Option<String>,
+        #[serde(default)]
+        safe: bool,
+    },
+}
+
+#[cfg(feature = "meta-tx")]
+#[cw_serde]
+pub struct SigningInfoResponse {
+    pub pubkey: Option<Binary>,
+    pub nonce: u64,
+    pub domain_separator: Binary,
+}
+
+/// A transfer `from` signs once, for anyone to relay
+#[cfg(feature = "meta-tx")]
+#[cw_serde]
+pub struct TransferAuthorization {
+    pub from: String,
+    pub to: String,
+    pub amount: Uint128,
+    /// Usable strictly after this time
+    pub valid_after: Timestamp,
+    /// Usable strictly before this time
+    pub valid_before: Timestamp,
+    /// 32 random bytes, used up by the transfer
+    pub nonce: Binary,
+}
+
+#[cfg(feature = "meta-tx")]
+#[cw_serde]
+pub struct AuthorizationStateResponse {
+    pub used: bool,
+}
+
+/// Query the token sends to its transfer restriction contract, which has to implement it
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ComplianceQueryMsg {
+    #[returns(CanTransferResponse)]
+    CanTransfer {
+        from: String,
+        to: String,
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct CanTransferResponse {
+    pub allowed: bool,
+    /// Why the transfer is refused, shown in the error
+    pub reason: Option<String>,
+}
+
+/// How UpdateMarketing changes one field. Plain strings and null keep working as in
+/// the cw20 `Option<String>`, so existing clients are unaffected.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(from = "FieldUpdateRepr", into = "FieldUpdateRepr")]
+pub enum FieldUpdate {
+    #[default]
+    Unchanged,
+    Clear,
+    Set(String),
+}
+
+impl FieldUpdate {
+    /// The new value, `None` to clear the field, or the current value if unchanged.
+    /// A blank value clears the field, as it does in cw20.
+    pub fn apply(self, current: Option<String>) -> Option<String> {
+        match self {
+            FieldUpdate::Unchanged => current,
+            FieldUpdate::Clear => None,
+            FieldUpdate::Set(value) if value.trim().is_empty() => None,
+            FieldUpdate::Set(value) => Some(value),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum FieldUpdateRepr {
+    Value(Option<String>),
+    Clear { clear: Empty },
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
-pub struct MigrateMsg {}
+#[serde(deny_unknown_fields)]
+struct Empty {}
+
+impl From<FieldUpdateRepr> for FieldUpdate {
+    fn from(repr: FieldUpdateRepr) -> Self {
+        match repr {
+            FieldUpdateRepr::Value(None) => FieldUpdate::Unchanged,
+            FieldUpdateRepr::Value(Some(value)) => FieldUpdate::Set(value),
+            FieldUpdateRepr::Clear { .. } => FieldUpdate::Clear,
+        }
+    }
+}
+
+impl From<FieldUpdate> for FieldUpdateRepr {
+    fn from(update: FieldUpdate) -> Self {
+        match update {
+            FieldUpdate::Unchanged => FieldUpdateRepr::Value(None),
+            FieldUpdate::Set(value) => FieldUpdateRepr::Value(Some(value)),
+            FieldUpdate::Clear => FieldUpdateRepr::Clear { clear: Empty {} },
+        }
+    }
+}
+
+impl JsonSchema for FieldUpdate {
+    fn schema_name() -> String {
+        "FieldUpdate".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        FieldUpdateRepr::json_schema(gen)
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Default)]
+pub struct MigrateMsg {
+    /// Only reports the migration steps that would run, without running them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Changes the decimals of a token launched with the wrong precision. Balances,
+    /// allowances and the other stored amounts are rescaled afterwards by
+    /// ContinueMigration, and every other message fails until it's done.
+    #[cfg(feature = "rescale-decimals")]
+    pub rescale_decimals: Option<RescaleDecimals>,
+    /// Changes the largest page of AllAllowances, AllSpenderAllowances and AllAccounts
+    #[cfg(feature = "enumerable")]
+    pub enumeration_limit: Option<u32>,
+}
+
+#[cfg(feature = "rescale-decimals")]
+#[cw_serde]
+pub struct RescaleDecimals {
+    pub new_decimals: u8,
+}
@@ -0,0 +1,150 @@
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult};
+use cw20::{BalanceResponse, TokenInfoResponse};
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+use crate::msg::{validate_name, validate_symbol, MultiTokenAmount, MultiTokensResponse};
+use crate::state::{TokenMetadata, MULTI_TOKEN_IDS};
+use crate::storage::Cw20Storage;
+
+pub const MAX_TOKEN_ID_LENGTH: usize = 32;
+
+/// Storage of a sub-token. Ids can't contain ':', so no two prefixes overlap.
+fn token_storage(token_id: &str) -> Cw20Storage {
+    Cw20Storage::new(&format!("multi_token:{token_id}:"))
+}
+
+fn existing_token(deps: Deps, token_id: &str) -> Result<Cw20Storage, ContractError> {
+    if !MULTI_TOKEN_IDS.has(deps.storage, token_id) {
+        return Err(ContractError::UnknownMultiToken {
+            token_id: token_id.to_string(),
+        });
+    }
+    Ok(token_storage(token_id))
+}
+
+pub fn execute_create_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    name: String,
+    symbol: String,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    let valid_id = !token_id.is_empty()
+        && token_id.len() <= MAX_TOKEN_ID_LENGTH
+        && token_id
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_');
+    if !valid_id {
+        return Err(ContractError::InvalidMultiTokenId {
+            max: MAX_TOKEN_ID_LENGTH,
+        });
+    }
+    if MULTI_TOKEN_IDS.has(deps.storage, &token_id) {
+        return Err(ContractError::MultiTokenExists {});
+    }
+    validate_name(&name)?;
+    validate_symbol(&symbol, Default::default())?;
+    if decimals > 18 {
+        return Err(StdError::generic_err("Decimals must not exceed 18").into());
+    }
+
+    let metadata = TokenMetadata {
+        name,
+        symbol,
+        decimals,
+    };
+    token_storage(&token_id).instantiate(deps.storage, &metadata, env.block.height)?;
+    MULTI_TOKEN_IDS.save(deps.storage, &token_id, &())?;
+
+    let res = Response::new()
+        .add_attribute("action", "create_token")
+        .add_attribute("token_id", token_id);
+    Ok(res)
+}
+
+pub fn execute_mint_multi(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amounts: Vec<MultiTokenAmount>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "mint_multi")
+        .add_attribute("to", recipient);
+    for MultiTokenAmount { token_id, amount } in amounts {
+        let token = existing_token(deps.as_ref(), &token_id)?;
+        token.mint(deps.storage, &rcpt_addr, amount, env.block.height)?;
+        res = res
+            .add_attribute("token_id", token_id)
+            .add_attribute("amount", amount);
+    }
+    Ok(res)
+}
+
+pub fn execute_transfer_multi(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amounts: Vec<MultiTokenAmount>,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "transfer_multi")
+        .add_attribute("from", &info.sender)
+        .add_attribute("to", recipient);
+    for MultiTokenAmount { token_id, amount } in amounts {
+        let token = existing_token(deps.as_ref(), &token_id)?;
+        token.transfer(
+            deps.storage,
+            &info.sender,
+            &rcpt_addr,
+            amount,
+            env.block.height,
+        )?;
+        res = res
+            .add_attribute("token_id", token_id)
+            .add_attribute("amount", amount);
+    }
+    Ok(res)
+}
+
+pub fn query_multi_token_info(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse> {
+    token_storage(&token_id).token_info(deps.storage)
+}
+
+pub fn query_multi_balance(
+    deps: Deps,
+    token_id: String,
+    address: String,
+) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = token_storage(&token_id).balance(deps.storage, &address)?;
+    Ok(BalanceResponse { balance })
+}
+
+pub fn query_multi_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MultiTokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let token_ids = MULTI_TOKEN_IDS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(MultiTokensResponse { token_ids })
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::future::Future;
+
+use cosmwasm_std::{from_json, to_json_vec, Binary, HexBinary, StdError};
+use cw20::{
+    AllAccountsResponse, AllowanceResponse, BalanceResponse, Cw20QueryMsg, TokenInfoResponse,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const SMART_QUERY_PATH: &str = "/cosmwasm.wasm.v1.Query/SmartContractState";
+
+/// Carries JSON-RPC requests to a CometBFT node, usually a POST to its RPC endpoint
+/// with whatever HTTP client the caller already uses
+pub trait RpcTransport {
+    type Error: fmt::Display + fmt::Debug;
+
+    /// Posts the JSON-RPC request `body` and returns the body of the response
+    fn post(&self, body: Vec<u8>) -> impl Future<Output = Result<Vec<u8>, Self::Error>>;
+}
+
+#[derive(Error, Debug)]
+pub enum QueryClientError<E> {
+    #[error("Transport error: {0}")]
+    Transport(E),
+
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+
+    #[error("Query failed with code {code}: {log}")]
+    Query { code: u32, log: String },
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("{0}")]
+    Std(#[from] StdError),
+}
+
+/// Queries a cw20 token on a live chain through the `abci_query` method of a node,
+/// for bots and indexers outside the chain
+pub struct Cw20QueryClient<T> {
+    transport: T,
+    contract: String,
+    height: Option<u64>,
+}
+
+impl<T: RpcTransport> Cw20QueryClient<T> {
+    pub fn new(transport: T, contract: impl Into<String>) -> Self {
+        Cw20QueryClient {
+            transport,
+            contract: contract.into(),
+            height: None,
+        }
+    }
+
+    /// Queries the state at `height` instead of the latest block
+    pub fn at_height(mut self, height: u64) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn contract(&self) -> &str {
+        &self.contract
+    }
+
+    pub async fn balance(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<BalanceResponse, QueryClientError<T::Error>> {
+        let msg = Cw20QueryMsg::Balance {
+            address: address.into(),
+        };
+        self.query(&msg).await
+    }
+
+    pub async fn token_info(&self) -> Result<TokenInfoResponse, QueryClientError<T::Error>> {
+        self.query(&Cw20QueryMsg::TokenInfo {}).await
+    }
+
+    pub async fn all_accounts(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<AllAccountsResponse, QueryClientError<T::Error>> {
+        self.query(&Cw20QueryMsg::AllAccounts { start_after, limit })
+            .await
+    }
+
+    pub async fn allowance(
+        &self,
+        owner: impl Into<String>,
+        spender: impl Into<String>,
+    ) -> Result<AllowanceResponse, QueryClientError<T::Error>> {
+        let msg = Cw20QueryMsg::Allowance {
+            owner: owner.into(),
+            spender: spender.into(),
+        };
+        self.query(&msg).await
+    }
+
+    /// Any smart query, for the messages of the extensions
+    pub async fn query<R: DeserializeOwned>(
+        &self,
+        msg: &impl Serialize,
+    ) -> Result<R, QueryClientError<T::Error>> {
+        let mut request = vec![];
+        encode_bytes_field(&mut request, 1, self.contract.as_bytes());
+        encode_bytes_field(&mut request, 2, &to_json_vec(msg)?);
+        let body = to_json_vec(&RpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "abci_query",
+            params: AbciQueryParams {
+                path: SMART_QUERY_PATH,
+                data: HexBinary::from(request).to_hex(),
+                height: self.height.unwrap_or_default().to_string(),
+                prove: false,
+            },
+        })?;
+
+        let body = self
+            .transport
+            .post(body)
+            .await
+            .map_err(QueryClientError::Transport)?;
+        let response: RpcResponse = from_json(body)?;
+        if let Some(err) = response.error {
+            return Err(QueryClientError::Rpc {
+                code: err.code,
+                message: err.data.unwrap_or(err.message),
+            });
+        }
+        let response = response
+            .result
+            .ok_or_else(|| QueryClientError::InvalidResponse("no result".to_string()))?
+            .response;
+        if response.code != 0 {
+            return Err(QueryClientError::Query {
+                code: response.code,
+                log: response.log,
+            });
+        }
+        let value = response.value.unwrap_or_default();
+        let data = decode_bytes_field(&value, 1).map_err(QueryClientError::InvalidResponse)?;
+        Ok(from_json(data)?)
+    }
+}
+
+#[derive(Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'a str,
+    id: u64,
+    method: &'a str,
+    params: AbciQueryParams<'a>,
+}
+
+#[derive(Serialize)]
+struct AbciQueryParams<'a> {
+    path: &'a str,
+    data: String,
+    height: String,
+    prove: bool,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<AbciQueryResult>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AbciQueryResult {
+    response: AbciQueryResponse,
+}
+
+#[derive(Deserialize)]
+struct AbciQueryResponse {
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    log: String,
+    value: Option<Binary>,
+}
+
+// the request and response of SmartContractState are protobuf messages of a string or
+// bytes field or two, too little to pull in a protobuf library for
+
+fn encode_bytes_field(out: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    encode_varint(out, field << 3 | 2);
+    encode_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn encode_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// The last value of a bytes `field` in a protobuf message, empty if it's not there
+fn decode_bytes_field(mut msg: &[u8], field: u64) -> Result<&[u8], String> {
+    let mut found: &[u8] = &[];
+    while !msg.is_empty() {
+        let key = decode_varint(&mut msg)?;
+        match key & 7 {
+            0 => {
+                decode_varint(&mut msg)?;
+            }
+            2 => {
+                let len = decode_varint(&mut msg)? as usize;
+                if len > msg.len() {
+                    return Err("truncated protobuf field".to_string());
+                }
+                let (value, rest) = msg.split_at(len);
+                if key >> 3 == field {
+                    found = value;
+                }
+                msg = rest;
+            }
+            wire_type => return Err(format!("unexpected protobuf wire type {wire_type}")),
+        }
+    }
+    Ok(found)
+}
+
+fn decode_varint(msg: &mut &[u8]) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = msg
+            .split_first()
+            .ok_or_else(|| "truncated protobuf varint".to_string())?;
+        *msg = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+    Err("protobuf varint too long".to_string())
+}
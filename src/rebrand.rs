@@ -0,0 +1,42 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::{validate_name, validate_symbol};
+use crate::state::{SYMBOL_VALIDATION, TOKEN_INFO_FROZEN, TOKEN_METADATA};
+
+/// Renames the token. Decimals stay as instantiated, as every balance is denominated in them.
+pub fn execute_update_token_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: Option<String>,
+    symbol: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if TOKEN_INFO_FROZEN
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::TokenInfoFrozen {});
+    }
+
+    let mut metadata = TOKEN_METADATA.load(deps.storage)?;
+    if let Some(name) = name {
+        validate_name(&name)?;
+        metadata.name = name;
+    }
+    if let Some(symbol) = symbol {
+        let validation = SYMBOL_VALIDATION
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        validate_symbol(&symbol, validation)?;
+        metadata.symbol = symbol;
+    }
+    TOKEN_METADATA.save(deps.storage, &metadata)?;
+
+    let res = Response::new()
+        .add_attribute("action", "update_token_info")
+        .add_attribute("name", metadata.name)
+        .add_attribute("symbol", metadata.symbol);
+    Ok(res)
+}
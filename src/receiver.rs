@@ -0,0 +1,55 @@
+use cosmwasm_std::{from_json, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
+use cw20::Cw20ReceiveMsg;
+use serde::de::DeserializeOwned;
+
+/// A Send of an accepted token to this contract, with its hook message decoded
+#[derive(Debug, PartialEq)]
+pub struct ReceivedCw20<T> {
+    /// The token contract, which called Receive
+    pub token: Addr,
+    /// Account whose tokens were sent
+    pub sender: Addr,
+    pub amount: Uint128,
+    pub msg: T,
+}
+
+/// Implemented by contracts taking tokens through Send, leaving the checks every one of
+/// them needs to `dispatch_receive`
+pub trait Cw20Receiver {
+    type Msg: DeserializeOwned;
+    type Error: From<StdError>;
+
+    /// Whether this contract takes tokens of `token`. Anyone can call Receive, so this is
+    /// what tells a real transfer from a forged one.
+    fn accepts(deps: Deps, env: &Env, token: &Addr) -> Result<bool, Self::Error>;
+
+    fn receive(
+        deps: DepsMut,
+        env: Env,
+        received: ReceivedCw20<Self::Msg>,
+    ) -> Result<Response, Self::Error>;
+}
+
+/// Handles `ExecuteMsg::Receive`: checks the calling token is accepted and carries no
+/// native funds, decodes the hook message and hands it to `R::receive`
+pub fn dispatch_receive<R: Cw20Receiver>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, R::Error> {
+    if !R::accepts(deps.as_ref(), &env, &info.sender)? {
+        let msg = format!("Tokens of {} are not accepted", info.sender);
+        return Err(StdError::generic_err(msg).into());
+    }
+    if !info.funds.is_empty() {
+        return Err(StdError::generic_err("Receive takes no native funds").into());
+    }
+    let received = ReceivedCw20 {
+        sender: deps.api.addr_validate(&wrapper.sender)?,
+        token: info.sender,
+        amount: wrapper.amount,
+        msg: from_json(&wrapper.msg)?,
+    };
+    R::receive(deps, env, received)
+}
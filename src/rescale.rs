@@ -0,0 +1,280 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::{Bound, PrimaryKey};
+
+use crate::balances::{add_balance, sub_balance};
+use crate::error::ContractError;
+use crate::msg::RescaleStatusResponse;
+use crate::state::{
+    streams, DecimalRescale, RescaleStage, ALLOWANCES, ALLOWANCES_SPENDER, ALLOWANCE_LIMITS,
+    BALANCES, DECIMAL_RESCALE, DIRECT_MINT_BUCKETS, FAUCET, MAX_WALLET, MINTER, MINT_TIMELOCK,
+    MIN_TRANSFER_AMOUNT, PENDING_MINTS, SCHEDULED_TRANSFERS, SUBSCRIPTIONS, TOKEN_METADATA,
+    TRANSFER_LIMIT, VESTING_LOCKS,
+};
+use crate::supply::{decrease_supply, increase_supply};
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+
+/// `amount` in `to` decimals instead of `from`. Scaling up is exact, scaling down rounds
+/// down, so no account ends up with more than it held.
+pub fn rescale_amount(amount: Uint128, from: u8, to: u8) -> StdResult<Uint128> {
+    if to >= from {
+        let factor = Uint128::new(10).checked_pow((to - from) as u32)?;
+        Ok(amount.checked_mul(factor)?)
+    } else {
+        let factor = Uint128::new(10).checked_pow((from - to) as u32)?;
+        Ok(amount.checked_div(factor)?)
+    }
+}
+
+/// Switches the token to `new_decimals` along with the cap and the other configured
+/// amounts. Balances, allowances and the amounts of pending operations follow with
+/// ContinueMigration. Spending windows in flight keep counting in the old decimals until
+/// they roll over.
+pub fn start_rescale(
+    storage: &mut dyn Storage,
+    new_decimals: u8,
+) -> Result<DecimalRescale, ContractError> {
+    if DECIMAL_RESCALE.exists(storage) {
+        return Err(ContractError::RescaleInProgress {});
+    }
+    let mut metadata = TOKEN_METADATA.load(storage)?;
+    if new_decimals > 18 || new_decimals == metadata.decimals {
+        return Err(ContractError::InvalidRescale {});
+    }
+    let from = metadata.decimals;
+    let scale = |amount| rescale_amount(amount, from, new_decimals);
+    if let Some(mut minter) = MINTER.may_load(storage)? {
+        minter.cap = minter.cap.map(scale).transpose()?;
+        MINTER.save(storage, &minter)?;
+    }
+    if let Some(max_wallet) = MAX_WALLET.may_load(storage)? {
+        MAX_WALLET.save(storage, &scale(max_wallet)?)?;
+    }
+    if let Some(min_amount) = MIN_TRANSFER_AMOUNT.may_load(storage)? {
+        MIN_TRANSFER_AMOUNT.save(storage, &scale(min_amount)?)?;
+    }
+    if let Some(mut limit) = TRANSFER_LIMIT.may_load(storage)? {
+        limit.amount = scale(limit.amount)?;
+        TRANSFER_LIMIT.save(storage, &limit)?;
+    }
+    if let Some(mut timelock) = MINT_TIMELOCK.may_load(storage)? {
+        timelock.threshold = scale(timelock.threshold)?;
+        MINT_TIMELOCK.save(storage, &timelock)?;
+    }
+    if let Some(mut buckets) = DIRECT_MINT_BUCKETS.may_load(storage)? {
+        for bucket in buckets.iter_mut() {
+            bucket.spent = scale(bucket.spent)?;
+        }
+        DIRECT_MINT_BUCKETS.save(storage, &buckets)?;
+    }
+    if let Some(mut faucet) = FAUCET.may_load(storage)? {
+        faucet.amount = scale(faucet.amount)?;
+        FAUCET.save(storage, &faucet)?;
+    }
+    let rescale = DecimalRescale {
+        from_decimals: from,
+        to_decimals: new_decimals,
+        stage: RescaleStage::Balances,
+        after: None,
+    };
+    metadata.decimals = new_decimals;
+    TOKEN_METADATA.save(storage, &metadata)?;
+    DECIMAL_RESCALE.save(storage, &rescale)?;
+    Ok(rescale)
+}
+
+/// Fails while balances are still in the old decimals
+pub fn assert_not_rescaling(storage: &dyn Storage) -> Result<(), ContractError> {
+    if DECIMAL_RESCALE.exists(storage) {
+        return Err(ContractError::RescaleInProgress {});
+    }
+    Ok(())
+}
+
+/// Rescales the next stored amounts, stage after stage, `limit` entries in all. Balances
+/// move through the usual balance and supply updates, so everything derived from them
+/// follows.
+pub fn execute_continue_migration(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut rescale = DECIMAL_RESCALE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRescaleInProgress {})?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut processed = 0;
+
+    let mut done = false;
+    while processed < limit {
+        let room = limit - processed;
+        let (count, last) = rescale_page(deps.storage, &env, &rescale, room)?;
+        processed += count;
+        if count == room {
+            rescale.after = last;
+            continue;
+        }
+        // the stage ran out of entries
+        match rescale.stage.next() {
+            Some(stage) => {
+                rescale.stage = stage;
+                rescale.after = None;
+            }
+            None => {
+                done = true;
+                break;
+            }
+        }
+    }
+
+    if done {
+        DECIMAL_RESCALE.remove(deps.storage);
+    } else {
+        DECIMAL_RESCALE.save(deps.storage, &rescale)?;
+    }
+    let res = Response::new()
+        .add_attribute("action", "continue_migration")
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("done", done.to_string());
+    Ok(res)
+}
+
+/// Bound starting right after the raw key `after`
+fn cursor<'a, K: PrimaryKey<'a>>(after: &Option<Binary>) -> Option<Bound<'a, K>> {
+    after.as_ref().map(|key| Bound::ExclusiveRaw(key.to_vec()))
+}
+
+/// Rescales up to `limit` entries of the current stage after the cursor. Returns how many
+/// there were and the key of the last one.
+fn rescale_page(
+    storage: &mut dyn Storage,
+    env: &Env,
+    rescale: &DecimalRescale,
+    limit: usize,
+) -> StdResult<(usize, Option<Binary>)> {
+    let (from, to) = (rescale.from_decimals, rescale.to_decimals);
+    let scale = |amount| rescale_amount(amount, from, to);
+    let mut last = None;
+    let mut count = 0;
+    match rescale.stage {
+        RescaleStage::Balances => {
+            let height = env.block.height;
+            let page = BALANCES
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+            for (addr, balance) in page {
+                let rescaled = scale(balance)?;
+                if rescaled > balance {
+                    add_balance(storage, &addr, rescaled - balance, height)?;
+                    increase_supply(storage, &addr, rescaled - balance, height)?;
+                } else if rescaled < balance {
+                    sub_balance(storage, &addr, balance - rescaled, height)?;
+                    decrease_supply(storage, &addr, balance - rescaled, height)?;
+                }
+                last = Some(addr.joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::Allowances => {
+            let page = ALLOWANCES
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for ((owner, spender), mut allowance) in page {
+                allowance.allowance = scale(allowance.allowance)?;
+                ALLOWANCES.save(storage, (&owner, &spender), &allowance)?;
+                ALLOWANCES_SPENDER.save(storage, (&spender, &owner), &allowance)?;
+                last = Some((&owner, &spender).joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::AllowanceLimits => {
+            let page = ALLOWANCE_LIMITS
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for ((owner, spender), mut allowance_limit) in page {
+                allowance_limit.amount = scale(allowance_limit.amount)?;
+                ALLOWANCE_LIMITS.save(storage, (&owner, &spender), &allowance_limit)?;
+                last = Some((&owner, &spender).joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::VestingLocks => {
+            let page = VESTING_LOCKS
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (addr, mut locks) in page {
+                for lock in locks.iter_mut() {
+                    lock.amount = scale(lock.amount)?;
+                }
+                VESTING_LOCKS.save(storage, &addr, &locks)?;
+                last = Some(addr.joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::Streams => {
+            let page = streams()
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (id, mut stream) in page {
+                stream.amount = scale(stream.amount)?;
+                stream.withdrawn = scale(stream.withdrawn)?;
+                streams().save(storage, id, &stream)?;
+                last = Some(id.joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::ScheduledTransfers => {
+            let page = SCHEDULED_TRANSFERS
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (id, mut scheduled) in page {
+                scheduled.amount = scale(scheduled.amount)?;
+                SCHEDULED_TRANSFERS.save(storage, id, &scheduled)?;
+                last = Some(id.joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::Subscriptions => {
+            let page = SUBSCRIPTIONS
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for ((owner, spender), mut subscription) in page {
+                subscription.amount_per_period = scale(subscription.amount_per_period)?;
+                subscription.charged = scale(subscription.charged)?;
+                SUBSCRIPTIONS.save(storage, (&owner, &spender), &subscription)?;
+                last = Some((&owner, &spender).joined_key());
+                count += 1;
+            }
+        }
+        RescaleStage::PendingMints => {
+            let page = PENDING_MINTS
+                .range(storage, cursor(&rescale.after), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (id, mut pending) in page {
+                pending.amount = scale(pending.amount)?;
+                PENDING_MINTS.save(storage, id, &pending)?;
+                last = Some(id.joined_key());
+                count += 1;
+            }
+        }
+    }
+    Ok((count, last.map(Binary::from)))
+}
+
+pub fn query_rescale_status(deps: Deps) -> StdResult<RescaleStatusResponse> {
+    Ok(RescaleStatusResponse {
+        rescale: DECIMAL_RESCALE.may_load(deps.storage)?,
+    })
+}
@@ -0,0 +1,220 @@
+use cosmwasm_std::{Addr, Deps, Env, StdResult, Uint128};
+
+#[cfg(feature = "compliance")]
+use crate::compliance;
+#[cfg(feature = "contract-guard")]
+use crate::contract_guard::assert_not_contract;
+use crate::error::ContractError;
+#[cfg(feature = "launch-guard")]
+use crate::launch_guard::{assert_trading_enabled, can_trade};
+#[cfg(feature = "max-wallet")]
+use crate::max_wallet::assert_within_max_wallet;
+#[cfg(feature = "min-transfer")]
+use crate::min_transfer::assert_min_transfer;
+use crate::msg::{DetectTransferRestrictionResponse, SpendConstraint, SpendableBalanceResponse};
+#[cfg(feature = "send-allowlist")]
+use crate::send_allowlist::assert_send_allowed;
+use crate::state::BALANCES;
+#[cfg(feature = "min-transfer")]
+use crate::state::MIN_TRANSFER_AMOUNT;
+#[cfg(feature = "transfer-limits")]
+use crate::transfer_limits;
+#[cfg(feature = "vesting")]
+use crate::vesting::locked_amount;
+
+/// How much `addr` can move out of its account right now, burns included, and the rule
+/// that caps it. Every restriction on all outgoing funds must be reflected here, the ones
+/// on transfers alone in `transferable_balance`.
+pub fn spendable_balance(
+    deps: Deps,
+    env: &Env,
+    addr: &Addr,
+) -> StdResult<SpendableBalanceResponse> {
+    let balance = BALANCES.may_load(deps.storage, addr)?.unwrap_or_default();
+    #[cfg(feature = "vesting")]
+    {
+        let locked = locked_amount(deps.storage, env, addr)?;
+        if !locked.is_zero() {
+            return Ok(SpendableBalanceResponse {
+                spendable: balance.saturating_sub(locked),
+                constraint: SpendConstraint::Vesting { locked },
+            });
+        }
+    }
+    #[cfg(not(feature = "vesting"))]
+    let _ = env;
+    Ok(SpendableBalanceResponse {
+        spendable: balance,
+        constraint: SpendConstraint::Balance,
+    })
+}
+
+/// How much `addr` can transfer right now, and the rule that caps it. Every restriction
+/// `assert_transferable` enforces on the sender must be reflected here.
+pub fn transferable_balance(
+    deps: Deps,
+    env: &Env,
+    addr: &Addr,
+) -> StdResult<SpendableBalanceResponse> {
+    #[cfg(feature = "launch-guard")]
+    if !can_trade(deps.storage, addr)? {
+        return Ok(SpendableBalanceResponse {
+            spendable: Uint128::zero(),
+            constraint: SpendConstraint::TradingNotEnabled,
+        });
+    }
+    let res = spendable_balance(deps, env, addr)?;
+    #[cfg(feature = "transfer-limits")]
+    let res = match transfer_limits::remaining_quota(deps.storage, env, addr)? {
+        Some(remaining) if remaining < res.spendable => SpendableBalanceResponse {
+            spendable: remaining,
+            constraint: SpendConstraint::TransferLimit { remaining },
+        },
+        _ => res,
+    };
+    #[cfg(feature = "min-transfer")]
+    let res = match MIN_TRANSFER_AMOUNT.may_load(deps.storage)? {
+        Some(min) if !res.spendable.is_zero() && res.spendable < min => SpendableBalanceResponse {
+            spendable: Uint128::zero(),
+            constraint: SpendConstraint::BelowMinimum { min },
+        },
+        _ => res,
+    };
+    Ok(res)
+}
+
+/// Fails if `addr` may not spend `amount` right now. A plain lack of balance is left
+/// to the balance update, so it keeps surfacing as an overflow.
+pub fn assert_spendable(
+    deps: Deps,
+    env: &Env,
+    addr: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let res = spendable_balance(deps, env, addr)?;
+    if amount <= res.spendable {
+        return Ok(());
+    }
+    match res.constraint {
+        SpendConstraint::Vesting { .. } => Err(ContractError::TokensLocked {}),
+        // the rules on transfers alone are checked by assert_transferable
+        _ => Ok(()),
+    }
+}
+
+/// Fails if a transfer of `amount` from `from` to `to` breaks any rule of this token.
+/// Like `assert_spendable`, a plain lack of balance is left to the balance update.
+pub fn assert_transferable(
+    deps: Deps,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    #[cfg(feature = "launch-guard")]
+    assert_trading_enabled(deps.storage, from)?;
+    #[cfg(feature = "min-transfer")]
+    assert_min_transfer(deps.storage, amount)?;
+    assert_receivable(deps, from, to, amount)?;
+    #[cfg(feature = "transfer-limits")]
+    transfer_limits::assert_within_limit(deps.storage, env, from, amount)?;
+    assert_spendable(deps, env, from, amount)
+}
+
+/// The checks of `assert_transferable` on the receiving side. Tokens paid out of escrow
+/// go through these again, their sending side having been checked on the way in.
+pub fn assert_receivable(
+    deps: Deps,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    #[cfg(feature = "compliance")]
+    compliance::assert_can_transfer(deps, from, to, amount)?;
+    #[cfg(feature = "max-wallet")]
+    if from != to {
+        assert_within_max_wallet(deps.storage, to, amount)?;
+    }
+    #[cfg(not(any(feature = "compliance", feature = "max-wallet")))]
+    let _ = (deps, from, to, amount);
+    Ok(())
+}
+
+/// Codes returned by `DetectTransferRestriction`, in the spirit of ERC-1404
+pub const TRANSFER_OK: u8 = 0;
+pub const INSUFFICIENT_BALANCE: u8 = 1;
+pub const TOKENS_LOCKED: u8 = 2;
+pub const RESTRICTED_BY_CONTRACT: u8 = 3;
+pub const TRANSFER_LIMIT_EXCEEDED: u8 = 4;
+pub const MAX_WALLET_EXCEEDED: u8 = 5;
+pub const TRADING_NOT_ENABLED: u8 = 6;
+pub const TRANSFER_BELOW_MINIMUM: u8 = 7;
+pub const CONTRACT_RECIPIENT: u8 = 8;
+pub const SEND_TARGET_NOT_ALLOWED: u8 = 9;
+
+/// The checks only one way of moving tokens to `to` makes: Transfer may not reach a
+/// contract under the contract guard, Send only the contracts on the send allowlist.
+fn assert_route(deps: Deps, to: &Addr, send: bool) -> Result<(), ContractError> {
+    #[cfg(feature = "contract-guard")]
+    if !send {
+        assert_not_contract(deps, to)?;
+    }
+    #[cfg(feature = "send-allowlist")]
+    if send {
+        assert_send_allowed(deps.storage, to)?;
+    }
+    #[cfg(not(any(feature = "contract-guard", feature = "send-allowlist")))]
+    let _ = (deps, to, send);
+    Ok(())
+}
+
+/// Runs the checks of the transfer path without executing it, so wallets can tell
+/// beforehand why a transfer would fail. With `send`, those of a Send instead.
+pub fn query_detect_transfer_restriction(
+    deps: Deps,
+    env: Env,
+    from: String,
+    to: String,
+    amount: Uint128,
+    send: bool,
+) -> StdResult<DetectTransferRestrictionResponse> {
+    let from = deps.api.addr_validate(&from)?;
+    let to = deps.api.addr_validate(&to)?;
+    let checked = assert_route(deps, &to, send)
+        .and_then(|()| assert_transferable(deps, &env, &from, &to, amount));
+    let (code, reason) = match checked {
+        Ok(()) => {
+            let balance = BALANCES.may_load(deps.storage, &from)?.unwrap_or_default();
+            if balance < amount {
+                (INSUFFICIENT_BALANCE, "Insufficient balance".to_string())
+            } else {
+                (TRANSFER_OK, "No restriction".to_string())
+            }
+        }
+        Err(ContractError::Std(err)) => return Err(err),
+        Err(err @ ContractError::TokensLocked {}) => (TOKENS_LOCKED, err.to_string()),
+        Err(err @ ContractError::TransferLimitExceeded {}) => {
+            (TRANSFER_LIMIT_EXCEEDED, err.to_string())
+        }
+        Err(err @ ContractError::MaxWalletExceeded {}) => (MAX_WALLET_EXCEEDED, err.to_string()),
+        Err(err @ ContractError::TradingNotEnabled {}) => (TRADING_NOT_ENABLED, err.to_string()),
+        Err(err @ ContractError::TransferBelowMinimum { .. }) => {
+            (TRANSFER_BELOW_MINIMUM, err.to_string())
+        }
+        Err(err @ ContractError::ContractRecipient {}) => (CONTRACT_RECIPIENT, err.to_string()),
+        Err(err @ ContractError::SendTargetNotAllowed {}) => {
+            (SEND_TARGET_NOT_ALLOWED, err.to_string())
+        }
+        Err(err) => (RESTRICTED_BY_CONTRACT, err.to_string()),
+    };
+    Ok(DetectTransferRestrictionResponse { code, reason })
+}
+
+pub fn query_spendable_balance(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<SpendableBalanceResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    transferable_balance(deps, &env, &addr)
+}
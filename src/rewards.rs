@@ -0,0 +1,178 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Uint128, Uint256,
+};
+use cw20::Cw20ReceiveMsg;
+
+use crate::error::ContractError;
+use crate::msg::{AssetInfo, PendingRewardsResponse};
+use crate::state::{
+    BALANCES, PENDING_REWARDS, REWARD_ASSET, REWARD_INDEX, REWARD_POINTS_PER_SHARE,
+};
+use crate::supply::total_supply;
+
+/// Points per share are scaled by this, so small distributions over a large supply still count
+const POINTS_MULTIPLIER: u128 = 1 << 64;
+
+pub fn setup_rewards(deps: &mut DepsMut, env: &Env, asset: AssetInfo) -> Result<(), ContractError> {
+    let asset = asset.validate(deps.api)?;
+    if asset == AssetInfo::cw20(&env.contract.address) {
+        return Err(ContractError::InvalidRewardAsset {});
+    }
+    REWARD_ASSET.save(deps.storage, &asset)?;
+    REWARD_POINTS_PER_SHARE.save(deps.storage, &Uint256::zero())?;
+    Ok(())
+}
+
+/// Rewards earned by `balance` over the given points per share
+fn earned(balance: Uint128, points_per_share: Uint256) -> StdResult<Uint128> {
+    let points = Uint256::from(balance).checked_mul(points_per_share)?;
+    Ok(Uint128::try_from(
+        points / Uint256::from(POINTS_MULTIPLIER),
+    )?)
+}
+
+/// Credits `addr` with what its current balance earned since it was last settled.
+/// Has to run before every change of its balance.
+pub fn settle(storage: &mut dyn Storage, addr: &Addr) -> StdResult<()> {
+    let points_per_share = match REWARD_POINTS_PER_SHARE.may_load(storage)? {
+        Some(points_per_share) => points_per_share,
+        None => return Ok(()),
+    };
+    let index = REWARD_INDEX.may_load(storage, addr)?.unwrap_or_default();
+    if index == points_per_share {
+        return Ok(());
+    }
+    let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+    let earned = earned(balance, points_per_share - index)?;
+    if !earned.is_zero() {
+        PENDING_REWARDS.update(storage, addr, |pending| -> StdResult<_> {
+            Ok(pending.unwrap_or_default().checked_add(earned)?)
+        })?;
+    }
+    REWARD_INDEX.save(storage, addr, &points_per_share)
+}
+
+/// Shares `amount` among all holders, pro rata to their current balance. The contract's
+/// own balance, tokens escrowed for streams and scheduled transfers among others, earns
+/// nothing, as the contract could never claim it.
+fn distribute(
+    storage: &mut dyn Storage,
+    contract: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let held = BALANCES.may_load(storage, contract)?.unwrap_or_default();
+    let supply = total_supply(storage)?
+        .checked_sub(held)
+        .map_err(StdError::from)?;
+    if supply.is_zero() {
+        return Err(ContractError::NoRewardHolders {});
+    }
+    let added = Uint256::from(amount)
+        .checked_mul(Uint256::from(POINTS_MULTIPLIER))
+        .map_err(StdError::from)?
+        / Uint256::from(supply);
+    let points_per_share = REWARD_POINTS_PER_SHARE
+        .update(storage, |points_per_share| -> StdResult<_> {
+            Ok(points_per_share.checked_add(added)?)
+        })?;
+    REWARD_INDEX.save(storage, contract, &points_per_share)?;
+    Ok(())
+}
+
+pub fn execute_distribute_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let denom = match REWARD_ASSET.may_load(deps.storage)? {
+        Some(AssetInfo::Native { denom }) => denom,
+        Some(AssetInfo::Cw20 { .. }) => return Err(ContractError::InvalidRewardAsset {}),
+        None => return Err(ContractError::RewardsDisabled {}),
+    };
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == denom => coin.amount,
+        _ => return Err(ContractError::InvalidRewardAsset {}),
+    };
+    distribute(deps.storage, &env.contract.address, amount)?;
+
+    let res = Response::new()
+        .add_attribute("action", "distribute_rewards")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+/// Distributes the cw20 reward tokens sent to this contract
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match REWARD_ASSET.may_load(deps.storage)? {
+        Some(AssetInfo::Cw20 { contract_addr }) if contract_addr == info.sender.as_str() => {}
+        Some(_) => return Err(ContractError::InvalidRewardAsset {}),
+        None => return Err(ContractError::RewardsDisabled {}),
+    }
+    distribute(deps.storage, &env.contract.address, wrapper.amount)?;
+
+    let res = Response::new()
+        .add_attribute("action", "distribute_rewards")
+        .add_attribute("from", wrapper.sender)
+        .add_attribute("amount", wrapper.amount);
+    Ok(res)
+}
+
+pub fn execute_withdraw_rewards(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let asset = REWARD_ASSET
+        .may_load(deps.storage)?
+        .ok_or(ContractError::RewardsDisabled {})?;
+    settle(deps.storage, &info.sender)?;
+    let amount = PENDING_REWARDS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    PENDING_REWARDS.remove(deps.storage, &info.sender);
+
+    let msg = asset.transfer_msg(&info.sender, amount)?;
+    let res = Response::new()
+        .add_message(msg)
+        .add_attribute("action", "withdraw_rewards")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+/// Fails if `asset` is the reward asset, which the contract holds on behalf of the holders
+pub fn assert_not_reward_asset(
+    storage: &dyn Storage,
+    asset: &AssetInfo,
+) -> Result<(), ContractError> {
+    if REWARD_ASSET.may_load(storage)?.as_ref() == Some(asset) {
+        return Err(ContractError::CannotRescueRewards {});
+    }
+    Ok(())
+}
+
+pub fn query_pending_rewards(deps: Deps, address: String) -> StdResult<PendingRewardsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let pending = PENDING_REWARDS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    let points_per_share = REWARD_POINTS_PER_SHARE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let index = REWARD_INDEX
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+    Ok(PendingRewardsResponse {
+        amount: pending + earned(balance, points_per_share - index)?,
+    })
+}
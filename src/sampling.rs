@@ -0,0 +1,145 @@
+use cosmwasm_std::{Addr, Deps, Env, StdError, StdResult, Storage, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::msg::SampleHoldersResponse;
+use crate::state::{HOLDERS, HOLDER_COUNT, HOLDER_INDEX, HOLDER_WEIGHTS};
+
+const MAX_SAMPLES: u32 = 100;
+
+/// Starts tracking holder weights. Must run before any balance is created.
+pub fn enable_sampling(storage: &mut dyn Storage) -> StdResult<()> {
+    HOLDER_COUNT.save(storage, &0)
+}
+
+/// Keeps the weight tree in sync with a balance increase. No-op unless sampling is enabled.
+pub fn increase_weight(storage: &mut dyn Storage, addr: &Addr, amount: Uint128) -> StdResult<()> {
+    let count = match HOLDER_COUNT.may_load(storage)? {
+        Some(count) => count,
+        None => return Ok(()),
+    };
+    let index = match HOLDER_INDEX.may_load(storage, addr)? {
+        Some(index) => index,
+        None => append_holder(storage, addr, count)?,
+    };
+    update_tree(storage, index, |weight| weight.checked_add(amount))
+}
+
+/// Keeps the weight tree in sync with a balance decrease. No-op unless sampling is enabled.
+pub fn decrease_weight(storage: &mut dyn Storage, addr: &Addr, amount: Uint128) -> StdResult<()> {
+    if HOLDER_COUNT.may_load(storage)?.is_none() {
+        return Ok(());
+    }
+    // an untracked address can only ever have a zero balance to decrease
+    match HOLDER_INDEX.may_load(storage, addr)? {
+        Some(index) => update_tree(storage, index, |weight| weight.checked_sub(amount)),
+        None => Ok(()),
+    }
+}
+
+/// Appends a holder to the Fenwick tree. The new node covers the already complete nodes
+/// directly below it, so its initial value is their sum.
+fn append_holder(storage: &mut dyn Storage, addr: &Addr, count: u64) -> StdResult<u64> {
+    let index = count + 1;
+    let mut weight = Uint128::zero();
+    let mut step = 1;
+    while step < lowbit(index) {
+        weight += HOLDER_WEIGHTS.load(storage, index - step)?;
+        step <<= 1;
+    }
+    HOLDER_WEIGHTS.save(storage, index, &weight)?;
+    HOLDER_INDEX.save(storage, addr, &index)?;
+    HOLDERS.save(storage, index, addr)?;
+    HOLDER_COUNT.save(storage, &index)?;
+    Ok(index)
+}
+
+fn update_tree<F>(storage: &mut dyn Storage, mut index: u64, action: F) -> StdResult<()>
+where
+    F: Fn(Uint128) -> Result<Uint128, cosmwasm_std::OverflowError>,
+{
+    let count = HOLDER_COUNT.load(storage)?;
+    while index <= count {
+        let weight = HOLDER_WEIGHTS.load(storage, index)?;
+        HOLDER_WEIGHTS.save(storage, index, &action(weight)?)?;
+        index += lowbit(index);
+    }
+    Ok(())
+}
+
+fn lowbit(index: u64) -> u64 {
+    index & index.wrapping_neg()
+}
+
+/// Finds the holder whose cumulative weight range contains `target`.
+fn find_holder(storage: &dyn Storage, count: u64, mut target: Uint128) -> StdResult<Addr> {
+    let mut pos = 0;
+    let mut step = if count == 0 {
+        0
+    } else {
+        1 << (63 - count.leading_zeros())
+    };
+    while step > 0 {
+        if pos + step <= count {
+            let weight = HOLDER_WEIGHTS.load(storage, pos + step)?;
+            if weight <= target {
+                pos += step;
+                target -= weight;
+            }
+        }
+        step >>= 1;
+    }
+    HOLDERS.load(storage, pos + 1)
+}
+
+/// Summed weight of the holders up to and including `index`
+fn prefix_weight(storage: &dyn Storage, mut index: u64) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    while index > 0 {
+        total += HOLDER_WEIGHTS.load(storage, index)?;
+        index -= lowbit(index);
+    }
+    Ok(total)
+}
+
+/// Deterministically draws `count` holders (with replacement), each with a probability
+/// proportional to its balance. The contract's own balance, tokens escrowed for streams
+/// and scheduled transfers among others, is left out, as it is in reward distributions.
+pub fn query_sample_holders(
+    deps: Deps,
+    env: Env,
+    seed: String,
+    count: u32,
+) -> StdResult<SampleHoldersResponse> {
+    let holders_count = HOLDER_COUNT
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("Holder sampling is not enabled"))?;
+    // the weight before the contract's range, and the range itself, to skip over
+    let (before, held) = match HOLDER_INDEX.may_load(deps.storage, &env.contract.address)? {
+        Some(index) => {
+            let before = prefix_weight(deps.storage, index - 1)?;
+            (before, prefix_weight(deps.storage, index)? - before)
+        }
+        None => (Uint128::zero(), Uint128::zero()),
+    };
+    let total = prefix_weight(deps.storage, holders_count)? - held;
+    if total.is_zero() {
+        return Ok(SampleHoldersResponse { holders: vec![] });
+    }
+
+    let holders = (0..count.min(MAX_SAMPLES))
+        .map(|i| {
+            let hash = Sha256::new()
+                .chain_update(seed.as_bytes())
+                .chain_update(i.to_be_bytes())
+                .finalize();
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(&hash[..16]);
+            let mut target = Uint128::new(u128::from_be_bytes(bytes) % total.u128());
+            if target >= before {
+                target += held;
+            }
+            find_holder(deps.storage, holders_count, target).map(String::from)
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(SampleHoldersResponse { holders })
+}
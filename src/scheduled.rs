@@ -0,0 +1,170 @@
+use cosmwasm_std::{
+    attr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128,
+};
+use cw20::Expiration;
+use cw_storage_plus::Bound;
+
+use crate::balances::{add_balance, escrow_balance, pay_out_escrow, sub_balance};
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::msg::{ScheduledTransferInfo, ScheduledTransfersResponse};
+use crate::restrictions::assert_receivable;
+use crate::state::{ScheduledTransfer, SCHEDULED_TRANSFERS, SCHEDULE_COUNT, SCHEDULE_QUEUE};
+
+// how many matured transfers a single ExecuteMatured call delivers
+const DEFAULT_BATCH: u32 = 10;
+const MAX_BATCH: u32 = 50;
+
+// queue prefixes, so height- and time-based schedules are each ordered by maturity
+const BY_HEIGHT: u8 = 0;
+const BY_TIME: u8 = 1;
+
+fn queue_key(execute_after: &Expiration) -> Result<(u8, u64), ContractError> {
+    match execute_after {
+        Expiration::AtHeight(height) => Ok((BY_HEIGHT, *height)),
+        Expiration::AtTime(time) => Ok((BY_TIME, time.nanos())),
+        Expiration::Never {} => Err(ContractError::InvalidExpiration {}),
+    }
+}
+
+pub fn execute_schedule_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    execute_after: Expiration,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let (kind, at) = queue_key(&execute_after)?;
+
+    // lock the tokens on the contract's own account until delivery
+    escrow_balance(&mut deps, &env, &info.sender, &rcpt_addr, amount)?;
+
+    let id = SCHEDULE_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    SCHEDULE_COUNT.save(deps.storage, &id)?;
+    let transfer = ScheduledTransfer {
+        sender: info.sender.clone(),
+        recipient: rcpt_addr,
+        amount,
+        execute_after,
+    };
+    SCHEDULED_TRANSFERS.save(deps.storage, id, &transfer)?;
+    SCHEDULE_QUEUE.save(deps.storage, (kind, at, id), &())?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "schedule_transfer"),
+        attr("schedule_id", id.to_string()),
+        attr("from", &info.sender),
+        attr("to", recipient),
+        attr("amount", amount),
+        attr("execute_after", execute_after.to_string()),
+    ]);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::transfer(
+        deps.storage,
+        &info.sender,
+        &env.contract.address,
+        amount,
+    )?);
+    Ok(res)
+}
+
+/// Ids of matured transfers in one queue, oldest first
+fn matured(storage: &dyn Storage, kind: u8, now: u64, limit: usize) -> StdResult<Vec<(u64, u64)>> {
+    SCHEDULE_QUEUE
+        .sub_prefix(kind)
+        .keys(
+            storage,
+            None,
+            Some(Bound::inclusive((now, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+pub fn execute_matured(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_BATCH).min(MAX_BATCH) as usize;
+
+    let mut due: Vec<_> = matured(deps.storage, BY_HEIGHT, env.block.height, limit)?
+        .into_iter()
+        .map(|(at, id)| (BY_HEIGHT, at, id))
+        .collect();
+    let remaining = limit - due.len();
+    due.extend(
+        matured(deps.storage, BY_TIME, env.block.time.nanos(), remaining)?
+            .into_iter()
+            .map(|(at, id)| (BY_TIME, at, id)),
+    );
+
+    let mut res = Response::new().add_attribute("action", "execute_matured");
+    for (kind, at, id) in due {
+        let transfer = SCHEDULED_TRANSFERS.load(deps.storage, id)?;
+        SCHEDULED_TRANSFERS.remove(deps.storage, id);
+        SCHEDULE_QUEUE.remove(deps.storage, (kind, at, id));
+
+        let (sender, recipient) = (&transfer.sender, &transfer.recipient);
+        // the recipient can't take the tokens anymore, or the compliance contract can't
+        // tell; they go back to the sender rather than holding up the queue
+        if assert_receivable(deps.as_ref(), sender, recipient, transfer.amount).is_err() {
+            let height = env.block.height;
+            sub_balance(deps.storage, &env.contract.address, transfer.amount, height)?;
+            add_balance(deps.storage, sender, transfer.amount, height)?;
+            #[cfg(feature = "events")]
+            {
+                let event =
+                    events::transfer(deps.storage, &env.contract.address, sender, transfer.amount)?;
+                res = res.add_event(event);
+            }
+            res = res.add_attributes(vec![
+                attr("schedule_id", id.to_string()),
+                attr("refunded_to", sender),
+                attr("amount", transfer.amount),
+            ]);
+            continue;
+        }
+        let effects = pay_out_escrow(&mut deps, &env, recipient, transfer.amount)?;
+        res = res
+            .add_attributes(vec![
+                attr("schedule_id", id.to_string()),
+                attr("to", recipient),
+                attr("amount", transfer.amount),
+            ])
+            .add_attributes(effects.attributes)
+            .add_submessages(effects.messages)
+            .add_events(effects.events);
+    }
+    Ok(res)
+}
+
+pub fn query_scheduled_transfers(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ScheduledTransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let transfers = SCHEDULED_TRANSFERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(id, transfer)| ScheduledTransferInfo {
+                id,
+                sender: transfer.sender,
+                recipient: transfer.recipient,
+                amount: transfer.amount,
+                execute_after: transfer.execute_after,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(ScheduledTransfersResponse { transfers })
+}
@@ -0,0 +1,87 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+use crate::msg::SendAllowlistResponse;
+use crate::state::{SEND_ALLOWLIST, SEND_ALLOWLIST_ENABLED};
+
+/// Restricts Send and SendFrom to the given contracts from now on.
+pub fn setup_send_allowlist(
+    deps: &mut DepsMut,
+    contracts: Vec<String>,
+) -> Result<(), ContractError> {
+    SEND_ALLOWLIST_ENABLED.save(deps.storage, &true)?;
+    for contract in contracts {
+        let contract = deps.api.addr_validate(&contract)?;
+        SEND_ALLOWLIST.save(deps.storage, &contract, &())?;
+    }
+    Ok(())
+}
+
+pub fn execute_update_send_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if !SEND_ALLOWLIST_ENABLED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::SendAllowlistDisabled {});
+    }
+
+    for contract in &add {
+        let contract = deps.api.addr_validate(contract)?;
+        SEND_ALLOWLIST.save(deps.storage, &contract, &())?;
+    }
+    for contract in &remove {
+        let contract = deps.api.addr_validate(contract)?;
+        SEND_ALLOWLIST.remove(deps.storage, &contract);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_send_allowlist")
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(","));
+    Ok(res)
+}
+
+/// Fails if the allowlist is enabled and `contract` is not on it
+pub fn assert_send_allowed(storage: &dyn Storage, contract: &Addr) -> Result<(), ContractError> {
+    let enabled = SEND_ALLOWLIST_ENABLED
+        .may_load(storage)?
+        .unwrap_or_default();
+    if enabled && !SEND_ALLOWLIST.has(storage, contract) {
+        return Err(ContractError::SendTargetNotAllowed {});
+    }
+    Ok(())
+}
+
+pub fn query_send_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SendAllowlistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let contracts = SEND_ALLOWLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(SendAllowlistResponse {
+        enabled: SEND_ALLOWLIST_ENABLED
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        contracts,
+    })
+}
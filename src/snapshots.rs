@@ -0,0 +1,65 @@
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+use cw20::BalanceResponse;
+use cw_storage_plus::Bound;
+
+use crate::admin::assert_admin;
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "exchange-rate")]
+use crate::exchange_rate::to_underlying_at;
+use crate::msg::{SnapshotInfo, SnapshotsResponse};
+use crate::state::{BALANCES, SNAPSHOTS};
+
+pub fn execute_record_snapshot(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    label: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    if SNAPSHOTS.has(deps.storage, &label) {
+        return Err(ContractError::SnapshotExists {});
+    }
+
+    // a checkpoint at the next height captures the balances at the end of this block
+    let height = env.block.height + 1;
+    BALANCES.add_checkpoint(deps.storage, height)?;
+    SNAPSHOTS.save(deps.storage, &label, &height)?;
+
+    let res = Response::new()
+        .add_attribute("action", "record_snapshot")
+        .add_attribute("label", label)
+        .add_attribute("height", height.to_string());
+    Ok(res)
+}
+
+pub fn query_snapshots(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SnapshotsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let snapshots = SNAPSHOTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(label, height)| SnapshotInfo { label, height }))
+        .collect::<StdResult<_>>()?;
+    Ok(SnapshotsResponse { snapshots })
+}
+
+pub fn query_balance_at_snapshot(
+    deps: Deps,
+    address: String,
+    label: String,
+) -> StdResult<BalanceResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let height = SNAPSHOTS.load(deps.storage, &label)?;
+    let balance = BALANCES
+        .may_load_at_height(deps.storage, &addr, height)?
+        .unwrap_or_default();
+    #[cfg(feature = "exchange-rate")]
+    let balance = to_underlying_at(deps.storage, balance, height)?;
+    Ok(BalanceResponse { balance })
+}
@@ -1,9 +1,21 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Decimal, Env, Timestamp, Uint128, Uint256};
+use cw_storage_plus::{
+    Deque, Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap, Strategy,
+};
 
-use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw20::{AllowanceResponse, Expiration, Logo, MarketingInfoResponse};
 
+use crate::msg::{AssetInfo, SupplyChange, SymbolValidation};
+
+#[cw_serde]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Layout before metadata, supply and minter were stored apart. Only read by `migrate`.
 #[cw_serde]
 pub struct TokenInfo {
     pub name: String,
@@ -18,19 +30,458 @@ pub struct MinterData {
     pub minter: Addr,
     /// cap is how many more tokens can be issued by the minter
     pub cap: Option<Uint128>,
+    /// If set, UpdateCap may only lower the cap
+    #[serde(default)]
+    pub cap_can_only_decrease: bool,
 }
 
-impl TokenInfo {
-    pub fn get_cap(&self) -> Option<Uint128> {
-        self.mint.as_ref().and_then(|v| v.cap)
-    }
+#[cw_serde]
+pub struct AutoBurnConfig {
+    /// Share of every transfer that is burned, in basis points
+    pub basis_points: u16,
 }
 
-pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+/// Namespaces of the core state, for other contracts reading it with raw queries (see
+/// `helpers`). They are part of the interface and never change.
+pub const TOKEN_METADATA_KEY: &str = "token_metadata";
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+pub const BALANCES_KEY: &str = "balance";
+pub const ALLOWANCES_KEY: &str = "allowance";
+
+pub const LEGACY_TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+pub const TOKEN_METADATA: Item<TokenMetadata> = Item::new(TOKEN_METADATA_KEY);
+/// Absent for strict symbol validation
+pub const SYMBOL_VALIDATION: Item<SymbolValidation> = Item::new("symbol_validation");
+/// Set at instantiation to keep name and symbol as they are for good
+pub const TOKEN_INFO_FROZEN: Item<bool> = Item::new("token_info_frozen");
+/// Written on every mint and burn, so kept apart from the metadata. Checkpointed at every
+/// change, so the supply can be looked up at past heights.
+pub const TOTAL_SUPPLY: SnapshotItem<Uint128> = SnapshotItem::new(
+    TOTAL_SUPPLY_KEY,
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
+/// Absent if the token has no minter
+pub const MINTER: Item<MinterData> = Item::new("minter");
+/// Set once the minter gave up minting for good
+pub const MINTING_RENOUNCED: Item<bool> = Item::new("minting_renounced");
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
-pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
-pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
+/// Nominated marketing admin, taking over once it accepts
+pub const PENDING_MARKETING_ADMIN: Item<Addr> = Item::new("pending_marketing_admin");
+/// Accounts besides the marketing admin allowed to update the marketing info and logo
+pub const MARKETING_ADMINS: Map<&Addr, ()> = Map::new("marketing_admins");
+/// Admin-managed token metadata, e.g. website or coingecko id, by key
+pub const EXTENDED_METADATA: Map<&str, String> = Map::new("extended_metadata");
+/// Ids of the sub-tokens of multi-token mode. Each keeps its state in a prefixed `Cw20Storage`.
+pub const MULTI_TOKEN_IDS: Map<&str, ()> = Map::new("multi_token_ids");
+/// Full TokenFactory denom mirroring the cw20 supply, if configured
+pub const MIRROR_DENOM: Item<String> = Item::new("mirror_denom");
+/// Balances only keep history for the heights recorded as named snapshots
+pub const BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    BALANCES_KEY,
+    "balance__checkpoints",
+    "balance__changelog",
+    Strategy::Selected,
+);
+/// Token administrator, if any
+pub const ADMIN: Item<Addr> = Item::new("admin");
+/// Account nominated to take over as admin, until it accepts
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+/// Whether Send and SendFrom may only target contracts in `SEND_ALLOWLIST`
+pub const SEND_ALLOWLIST_ENABLED: Item<bool> = Item::new("send_allowlist_enabled");
+pub const SEND_ALLOWLIST: Map<&Addr, ()> = Map::new("send_allowlist");
+/// Contracts notified with a `SupplyHookMsg` after every mint and burn
+pub const SUPPLY_HOOKS: Map<&Addr, ()> = Map::new("supply_hooks");
+/// Contract answering `CanTransfer` queries before every transfer, if any
+pub const TRANSFER_RESTRICTION_CONTRACT: Item<Addr> = Item::new("transfer_restriction_contract");
+#[cw_serde]
+pub struct TransferLimit {
+    /// Most an account can transfer out within any span of `period`
+    pub amount: Uint128,
+    /// Length of the rolling window in seconds
+    pub period: u64,
+}
+
+/// What an account transferred out in the bucket of the rolling window opening at `start`
+#[cw_serde]
+pub struct TransferBucket {
+    pub start: Timestamp,
+    pub spent: Uint128,
+}
+
+/// Limit on what each account can transfer per window, if any
+pub const TRANSFER_LIMIT: Item<TransferLimit> = Item::new("transfer_limit");
+/// Smallest amount a transfer or send may move
+pub const MIN_TRANSFER_AMOUNT: Item<Uint128> = Item::new("min_transfer_amount");
+/// Whether plain transfers to contracts are rejected in favour of Send
+pub const REJECT_CONTRACT_TRANSFERS: Item<bool> = Item::new("reject_contract_transfers");
+/// Buckets of each account still in the rolling window, oldest first
+pub const TRANSFER_BUCKETS: Map<&Addr, Vec<TransferBucket>> = Map::new("transfer_buckets");
+#[cw_serde]
+pub struct FaucetConfig {
+    /// Most an account can mint itself within one window
+    pub amount: Uint128,
+    /// Length of a window in seconds
+    pub period: u64,
+}
+
+/// What an account minted from the faucet since `start`
+#[cw_serde]
+pub struct FaucetWindow {
+    pub start: Timestamp,
+    pub minted: Uint128,
+}
+
+/// Lets anyone mint a little per window, if set. Meant for testnets only.
+pub const FAUCET: Item<FaucetConfig> = Item::new("faucet");
+pub const FAUCET_WINDOWS: Map<&Addr, FaucetWindow> = Map::new("faucet_windows");
+/// secp256k1 public key each account signs ExecuteSigned payloads and authorizations with
+pub const SIGNING_KEYS: Map<&Addr, Binary> = Map::new("signing_keys");
+/// Nonce the next ExecuteSigned of each account has to carry
+pub const SIGNING_NONCES: Map<&Addr, u64> = Map::new("signing_nonces");
+/// Nonces of transfer authorizations used or cancelled, by authorizer
+pub const USED_AUTHORIZATIONS: Map<(&Addr, &[u8]), ()> = Map::new("used_authorizations");
+/// Most a non-exempt account may hold, if any
+pub const MAX_WALLET: Item<Uint128> = Item::new("max_wallet");
+pub const MAX_WALLET_EXEMPT: Map<&Addr, ()> = Map::new("max_wallet_exempt");
+/// Only present with the launch guard. Until true, only `LAUNCH_ALLOWLIST` can transfer.
+pub const TRADING_ENABLED: Item<bool> = Item::new("trading_enabled");
+pub const LAUNCH_ALLOWLIST: Map<&Addr, ()> = Map::new("launch_allowlist");
+/// Asset shared among holders by DistributeRewards, if rewards are enabled
+pub const REWARD_ASSET: Item<AssetInfo> = Item::new("reward_asset");
+/// Rewards distributed per token held since the start, scaled by `POINTS_MULTIPLIER`
+pub const REWARD_POINTS_PER_SHARE: Item<Uint256> = Item::new("reward_points_per_share");
+/// Points per share each holder was last settled at
+pub const REWARD_INDEX: Map<&Addr, Uint256> = Map::new("reward_index");
+/// Rewards settled but not withdrawn yet
+pub const PENDING_REWARDS: Map<&Addr, Uint128> = Map::new("pending_rewards");
+/// Underlying amount per share, only present in share mode. Balances and the supply
+/// are stored in shares. Checkpointed at every change, so past balances and supply are
+/// valued at the rate of their height.
+pub const EXCHANGE_RATE: SnapshotItem<Decimal> = SnapshotItem::new(
+    "exchange_rate",
+    "exchange_rate__checkpoints",
+    "exchange_rate__changelog",
+    Strategy::EveryBlock,
+);
+/// Account besides the admin allowed to push exchange rate updates, e.g. a yield strategy
+pub const EXCHANGE_RATE_UPDATER: Item<Addr> = Item::new("exchange_rate_updater");
+/// Account each holder gave the voting power of its balance to
+pub const DELEGATES: Map<&Addr, Addr> = Map::new("delegates");
+/// Voting power of each delegatee, checkpointed at every change
+pub const VOTING_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "voting_power",
+    "voting_power__checkpoints",
+    "voting_power__changelog",
+    Strategy::EveryBlock,
+);
+pub const TOTAL_VOTING_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_voting_power",
+    "total_voting_power__checkpoints",
+    "total_voting_power__changelog",
+    Strategy::EveryBlock,
+);
+/// Whether responses with typed events drop their flat attributes
+pub const EVENTS_ONLY: Item<bool> = Item::new("events_only");
+/// Number of the last balance change, carried by its typed event
+pub const SEQUENCE: Item<u64> = Item::new("sequence");
+/// Creator still allowed to import state exported from another deployment
+pub const IMPORTER: Item<Addr> = Item::new("importer");
+/// Latest balance change of each account, (height, account)
+pub const BALANCE_CHANGES: Map<(u64, &Addr), ()> = Map::new("balance_changes");
+/// Height of the entry each account has in `BALANCE_CHANGES`
+pub const LAST_BALANCE_CHANGE: Map<&Addr, u64> = Map::new("last_balance_change");
+/// Named balance snapshots, label -> checkpoint height
+pub const SNAPSHOTS: Map<&str, u64> = Map::new("snapshots");
+pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new(ALLOWANCES_KEY);
+/// Spender contracts notified of every change to the allowances they hold
+pub const ALLOWANCE_NOTIFICATIONS: Map<&Addr, ()> = Map::new("allowance_notifications");
+/// Owners who blocked all spending of their allowances, with the time the block ends
+/// once they asked to lift it
+pub const ALLOWANCE_LOCKS: Map<&Addr, Option<Timestamp>> = Map::new("allowance_locks");
+#[cw_serde]
+pub struct AllowanceLimit {
+    /// Most the spender can take within one window, whatever its allowance
+    pub amount: Uint128,
+    /// Length of a window in seconds
+    pub period: u64,
+}
+
+/// What a spender took from an owner since `start`
+#[cw_serde]
+pub struct AllowanceWindow {
+    pub start: Timestamp,
+    pub spent: Uint128,
+}
+
+/// Caps owners put on what each spender takes per window, by (owner, spender). Kept
+/// when the allowance itself changes.
+pub const ALLOWANCE_LIMITS: Map<(&Addr, &Addr), AllowanceLimit> = Map::new("allowance_limits");
+pub const ALLOWANCE_WINDOWS: Map<(&Addr, &Addr), AllowanceWindow> = Map::new("allowance_windows");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
 pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> =
     Map::new("allowance_spender");
+
+pub const AUTO_BURN: Item<AutoBurnConfig> = Item::new("auto_burn");
+pub const AUTO_BURN_EXEMPT: Map<&Addr, ()> = Map::new("auto_burn_exempt");
+/// Cumulative amount burned by the auto-burn on transfers
+pub const AUTO_BURNED: Item<Uint128> = Item::new("auto_burned");
+/// Address transfers to which burn the tokens instead of crediting them
+pub const BURN_SINK: Item<Addr> = Item::new("burn_sink");
+
+/// Cumulative amount of its own tokens each account burned with Burn, BurnFrom,
+/// BurnFromBatch or a transfer to the burn sink
+pub const BURNED_BY: Map<&Addr, Uint128> = Map::new("burned_by");
+
+/// Decimal change started by a migration, applied to the stored amounts in chunks by
+/// ContinueMigration
+#[cw_serde]
+pub struct DecimalRescale {
+    pub from_decimals: u8,
+    pub to_decimals: u8,
+    /// Kind of entries being rescaled
+    pub stage: RescaleStage,
+    /// Key of the last entry of the stage that was rescaled
+    pub after: Option<Binary>,
+}
+
+/// Stored amounts ContinueMigration rescales, in this order
+#[cw_serde]
+pub enum RescaleStage {
+    Balances,
+    Allowances,
+    AllowanceLimits,
+    VestingLocks,
+    Streams,
+    ScheduledTransfers,
+    Subscriptions,
+    PendingMints,
+}
+
+impl RescaleStage {
+    /// Stage following this one, None after the last
+    pub fn next(&self) -> Option<RescaleStage> {
+        match self {
+            RescaleStage::Balances => Some(RescaleStage::Allowances),
+            RescaleStage::Allowances => Some(RescaleStage::AllowanceLimits),
+            RescaleStage::AllowanceLimits => Some(RescaleStage::VestingLocks),
+            RescaleStage::VestingLocks => Some(RescaleStage::Streams),
+            RescaleStage::Streams => Some(RescaleStage::ScheduledTransfers),
+            RescaleStage::ScheduledTransfers => Some(RescaleStage::Subscriptions),
+            RescaleStage::Subscriptions => Some(RescaleStage::PendingMints),
+            RescaleStage::PendingMints => None,
+        }
+    }
+}
+
+/// Rescale in progress, removed once every stored amount is rescaled
+pub const DECIMAL_RESCALE: Item<DecimalRescale> = Item::new("decimal_rescale");
+
+/// Migration steps left to ProcessMigration, oldest first
+#[cw_serde]
+pub struct PendingMigration {
+    pub steps: Vec<String>,
+    /// Where the first step stopped, in that step's own encoding
+    pub cursor: Option<Binary>,
+}
+
+/// Removed once every step ran
+pub const PENDING_MIGRATION: Item<PendingMigration> = Item::new("pending_migration");
+
+#[cw_serde]
+pub struct Stream {
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub withdrawn: Uint128,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Stream {
+    /// Amount vested linearly between start and end at the given time
+    pub fn vested(&self, now: Timestamp) -> Uint128 {
+        if now <= self.start {
+            Uint128::zero()
+        } else if now >= self.end {
+            self.amount
+        } else {
+            let elapsed = now.seconds() - self.start.seconds();
+            let duration = self.end.seconds() - self.start.seconds();
+            self.amount.multiply_ratio(elapsed, duration)
+        }
+    }
+}
+
+pub struct StreamIndexes<'a> {
+    pub sender: MultiIndex<'a, Addr, Stream, u64>,
+    pub recipient: MultiIndex<'a, Addr, Stream, u64>,
+}
+
+impl<'a> IndexList<Stream> for StreamIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Stream>> + '_> {
+        let v: Vec<&dyn Index<Stream>> = vec![&self.sender, &self.recipient];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn streams<'a>() -> IndexedMap<u64, Stream, StreamIndexes<'a>> {
+    let indexes = StreamIndexes {
+        sender: MultiIndex::new(|_pk, s| s.sender.clone(), "streams", "streams__sender"),
+        recipient: MultiIndex::new(
+            |_pk, s| s.recipient.clone(),
+            "streams",
+            "streams__recipient",
+        ),
+    };
+    IndexedMap::new("streams", indexes)
+}
+
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+
+/// Number of holders tracked for sampling, only present if sampling is enabled
+pub const HOLDER_COUNT: Item<u64> = Item::new("holder_count");
+/// 1-based position of each holder in the weight tree
+pub const HOLDER_INDEX: Map<&Addr, u64> = Map::new("holder_index");
+pub const HOLDERS: Map<u64, Addr> = Map::new("holders");
+/// Fenwick tree over holder balances, used for weighted sampling
+pub const HOLDER_WEIGHTS: Map<u64, Uint128> = Map::new("holder_weights");
+
+#[cw_serde]
+pub struct Subscription {
+    pub amount_per_period: Uint128,
+    /// Length of a period in seconds
+    pub period: u64,
+    pub expires: Expiration,
+    pub start: Timestamp,
+    /// Index of the period `charged` refers to
+    pub current_period: u64,
+    pub charged: Uint128,
+}
+
+impl Subscription {
+    pub fn period_at(&self, env: &Env) -> u64 {
+        env.block
+            .time
+            .seconds()
+            .saturating_sub(self.start.seconds())
+            / self.period
+    }
+}
+
+/// Pull-payment authorizations, keyed by (owner, spender)
+pub const SUBSCRIPTIONS: Map<(&Addr, &Addr), Subscription> = Map::new("subscription");
+
+#[cw_serde]
+pub struct ScheduledTransfer {
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub execute_after: Expiration,
+}
+
+pub const SCHEDULED_TRANSFERS: Map<u64, ScheduledTransfer> = Map::new("scheduled_transfer");
+/// Pending schedule ids ordered by maturity: (0 = height / 1 = time, height or nanos, id)
+pub const SCHEDULE_QUEUE: Map<(u8, u64, u64), ()> = Map::new("schedule_queue");
+pub const SCHEDULE_COUNT: Item<u64> = Item::new("schedule_count");
+
+/// Linear release: nothing unlocks before the cliff, everything after the end.
+#[cw_serde]
+pub struct VestingSchedule {
+    pub start: Timestamp,
+    pub cliff: Timestamp,
+    pub end: Timestamp,
+}
+
+impl VestingSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.start < self.end && self.start <= self.cliff && self.cliff <= self.end
+    }
+
+    /// Part of `amount` still locked at `now`
+    pub fn locked(&self, amount: Uint128, now: Timestamp) -> Uint128 {
+        if now < self.cliff {
+            amount
+        } else if now >= self.end {
+            Uint128::zero()
+        } else {
+            let elapsed = now.seconds() - self.start.seconds();
+            let duration = self.end.seconds() - self.start.seconds();
+            amount - amount.multiply_ratio(elapsed, duration)
+        }
+    }
+}
+
+#[cw_serde]
+pub struct VestingLock {
+    pub amount: Uint128,
+    pub schedule: VestingSchedule,
+}
+
+/// Locked mints per account
+pub const VESTING_LOCKS: Map<&Addr, Vec<VestingLock>> = Map::new("vesting_locks");
+
+#[cw_serde]
+pub struct MintTimelock {
+    /// Direct mints adding up to more than this within one delay have to be proposed and wait
+    pub threshold: Uint128,
+    /// Seconds between proposing a mint and being able to execute it
+    pub delay: u64,
+}
+
+#[cw_serde]
+pub struct PendingMint {
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub executable_at: Timestamp,
+}
+
+pub const MINT_TIMELOCK: Item<MintTimelock> = Item::new("mint_timelock");
+pub const PENDING_MINTS: Map<u64, PendingMint> = Map::new("pending_mints");
+pub const PENDING_MINT_COUNT: Item<u64> = Item::new("pending_mint_count");
+/// Direct mints within the last delay, by bucket, oldest first
+pub const DIRECT_MINT_BUCKETS: Item<Vec<TransferBucket>> = Item::new("direct_mint_buckets");
+
+/// Supply changes recorded in one shard
+#[cw_serde]
+#[derive(Default)]
+pub struct SupplyShard {
+    pub minted: Uint128,
+    pub burned: Uint128,
+}
+
+/// Number of supply shards, only present if sharding is enabled. `TOTAL_SUPPLY`
+/// then stays at the initial supply.
+pub const SUPPLY_SHARD_COUNT: Item<u16> = Item::new("supply_shard_count");
+pub const SUPPLY_SHARDS: SnapshotMap<u16, SupplyShard> = SnapshotMap::new(
+    "supply_shards",
+    "supply_shards__checkpoints",
+    "supply_shards__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Number of mints and burns the supply log keeps, only present if it is enabled
+pub const SUPPLY_LOG_CAPACITY: Item<u32> = Item::new("supply_log_capacity");
+
+/// Largest page of AllAllowances, AllSpenderAllowances and AllAccounts, if not the default
+pub const ENUMERATION_LIMIT: Item<u32> = Item::new("enumeration_limit");
+pub const SUPPLY_LOG: Deque<SupplyChange> = Deque::new("supply_log");
+/// Id of the next supply log record
+pub const SUPPLY_LOG_NEXT_ID: Item<u64> = Item::new("supply_log_next_id");
+
+/// Who may run a crank and how often, where the admin restricted it
+#[cw_serde]
+pub struct CrankConfig {
+    /// Least time between two runs, in seconds
+    pub min_interval: u64,
+    /// The only addresses that may run it, anyone if None
+    pub keepers: Option<Vec<Addr>>,
+}
+
+/// Restrictions on the cranks, by `Crank::as_str`. Cranks without one are open to all.
+pub const CRANK_CONFIGS: Map<&str, CrankConfig> = Map::new("crank_configs");
+pub const CRANK_LAST_RUN: Map<&str, Timestamp> = Map::new("crank_last_run");
+/// Runs of each crank by each keeper, for the operator to reward them by
+pub const KEEPER_RUNS: Map<(&Addr, &str), u64> = Map::new("keeper_runs");
@@ -0,0 +1,181 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128,
+};
+use cw20::{AllowanceResponse, Cw20Coin, TokenInfoResponse};
+use cw_storage_plus::Bound;
+
+use crate::contract::{query_marketing_info, query_minter};
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::execute::execute_mint::credit_mint;
+#[cfg(feature = "mint-timelock")]
+use crate::mint_timelock::record_direct_mint;
+use crate::msg::{ExportSection, ExportStateResponse, ExportedAllowance, ExportedConfig};
+use crate::state::{
+    ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, IMPORTER, MINTER, SYMBOL_VALIDATION, TOKEN_METADATA,
+};
+use crate::supply::total_supply;
+use crate::tokenfactory;
+
+/// Lets `creator` load balances and allowances exported from another deployment
+pub fn setup_import(deps: &mut DepsMut, creator: &Addr) -> StdResult<()> {
+    IMPORTER.save(deps.storage, creator)
+}
+
+fn assert_importer(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    match IMPORTER.may_load(deps.storage)? {
+        Some(importer) if importer == *sender => Ok(()),
+        Some(_) => Err(ContractError::Unauthorized {}),
+        None => Err(ContractError::ImportClosed {}),
+    }
+}
+
+/// Credits a chunk of exported balances as new supply and restores exported allowances.
+/// The cap, the mint timelock and the max wallet still hold for the imported supply.
+pub fn execute_import_state(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    balances: Vec<Cw20Coin>,
+    allowances: Vec<ExportedAllowance>,
+) -> Result<Response, ContractError> {
+    assert_importer(deps.as_ref(), &info.sender)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "import_state")
+        .add_attribute("balances", balances.len().to_string())
+        .add_attribute("allowances", allowances.len().to_string());
+    let mut imported = Uint128::zero();
+    for coin in balances {
+        let addr = deps.api.addr_validate(&coin.address)?;
+        credit_mint(deps.storage, &env, &info.sender, &addr, coin.amount)?;
+        #[cfg(feature = "events")]
+        {
+            res = res.add_event(events::mint(deps.storage, &addr, coin.amount)?);
+        }
+        imported = imported.checked_add(coin.amount).map_err(StdError::from)?;
+    }
+    #[cfg(feature = "mint-timelock")]
+    record_direct_mint(deps.storage, &env, imported)?;
+    if let Some(cap) = MINTER.may_load(deps.storage)?.and_then(|m| m.cap) {
+        if total_supply(deps.storage)? > cap {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    for allowance in allowances {
+        let owner = deps.api.addr_validate(&allowance.owner)?;
+        let spender = deps.api.addr_validate(&allowance.spender)?;
+        let value = AllowanceResponse {
+            allowance: allowance.allowance,
+            expires: allowance.expires,
+        };
+        ALLOWANCES.save(deps.storage, (&owner, &spender), &value)?;
+        ALLOWANCES_SPENDER.save(deps.storage, (&spender, &owner), &value)?;
+        #[cfg(feature = "events")]
+        {
+            res = res.add_event(events::allowance(&owner, &spender, &value));
+        }
+    }
+
+    res = res
+        .add_attribute("amount", imported)
+        .add_messages(tokenfactory::mint_msgs(deps.storage, &env, imported)?);
+    Ok(res)
+}
+
+/// Closes the import for good. Renouncing minting closes it too.
+pub fn execute_finish_import(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_importer(deps.as_ref(), &info.sender)?;
+    IMPORTER.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "finish_import"))
+}
+
+/// One page of `section`. Balances are raw, in shares if the exchange rate is on.
+pub fn query_export_state(
+    deps: Deps,
+    section: ExportSection,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ExportStateResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut res = ExportStateResponse {
+        balances: vec![],
+        allowances: vec![],
+        config: None,
+        next: None,
+    };
+    match section {
+        ExportSection::Balances => {
+            let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+            res.balances = BALANCES
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(address, amount)| Cw20Coin {
+                        address: address.into(),
+                        amount,
+                    })
+                })
+                .collect::<StdResult<_>>()?;
+            if res.balances.len() == limit {
+                res.next = res.balances.last().map(|c| c.address.clone());
+            }
+        }
+        ExportSection::Allowances => {
+            let start = match start_after {
+                Some(cursor) => {
+                    let (owner, spender) = cursor
+                        .split_once('/')
+                        .ok_or_else(|| StdError::generic_err("Invalid allowance cursor"))?;
+                    Some((Addr::unchecked(owner), Addr::unchecked(spender)))
+                }
+                None => None,
+            };
+            let start = start
+                .as_ref()
+                .map(|(owner, spender)| Bound::exclusive((owner, spender)));
+            res.allowances = ALLOWANCES
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|((owner, spender), allowance)| ExportedAllowance {
+                        owner: owner.into(),
+                        spender: spender.into(),
+                        allowance: allowance.allowance,
+                        expires: allowance.expires,
+                    })
+                })
+                .collect::<StdResult<_>>()?;
+            if res.allowances.len() == limit {
+                res.next = res
+                    .allowances
+                    .last()
+                    .map(|a| format!("{}/{}", a.owner, a.spender));
+            }
+        }
+        ExportSection::Config => {
+            let metadata = TOKEN_METADATA.load(deps.storage)?;
+            res.config = Some(ExportedConfig {
+                token_info: TokenInfoResponse {
+                    name: metadata.name,
+                    symbol: metadata.symbol,
+                    decimals: metadata.decimals,
+                    total_supply: total_supply(deps.storage)?,
+                },
+                minter: query_minter(deps)?,
+                marketing: query_marketing_info(deps)?,
+                symbol_validation: SYMBOL_VALIDATION
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    Ok(res)
+}
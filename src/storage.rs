@@ -0,0 +1,229 @@
+use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Storage, Uint128};
+use cw20::{AllowanceResponse, Expiration, TokenInfoResponse};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+
+use crate::error::ContractError;
+use crate::state::{
+    TokenMetadata, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_METADATA, TOTAL_SUPPLY,
+};
+
+/// The core cw20 state: metadata, supply, balances and allowances.
+///
+/// The contract itself uses [`Cw20Storage::DEFAULT`], laid out as in the `state` constants.
+/// A host contract embedding one or more tokens builds each with [`Cw20Storage::new`], so
+/// their keys don't collide with each other or with the host's own state.
+///
+/// The helpers only do the bookkeeping. Restrictions, events and the other optional
+/// subsystems of this contract keep their state under fixed keys and are not applied here.
+pub struct Cw20Storage {
+    pub token_info: Item<TokenMetadata>,
+    pub total_supply: SnapshotItem<Uint128>,
+    pub balances: SnapshotMap<&'static Addr, Uint128>,
+    pub allowances: Map<(&'static Addr, &'static Addr), AllowanceResponse>,
+    pub allowances_spender: Map<(&'static Addr, &'static Addr), AllowanceResponse>,
+}
+
+impl Cw20Storage {
+    pub const DEFAULT: Cw20Storage = Cw20Storage {
+        token_info: TOKEN_METADATA,
+        total_supply: TOTAL_SUPPLY,
+        balances: BALANCES,
+        allowances: ALLOWANCES,
+        allowances_spender: ALLOWANCES_SPENDER,
+    };
+
+    /// Same layout as [`Cw20Storage::DEFAULT`], with every namespace starting with `prefix`
+    pub fn new(prefix: &str) -> Self {
+        Cw20Storage {
+            token_info: Item::new_dyn(format!("{prefix}token_metadata")),
+            total_supply: SnapshotItem::new_dyn(
+                format!("{prefix}total_supply"),
+                format!("{prefix}total_supply__checkpoints"),
+                format!("{prefix}total_supply__changelog"),
+                Strategy::EveryBlock,
+            ),
+            balances: SnapshotMap::new_dyn(
+                format!("{prefix}balance"),
+                format!("{prefix}balance__checkpoints"),
+                format!("{prefix}balance__changelog"),
+                Strategy::Selected,
+            ),
+            allowances: Map::new_dyn(format!("{prefix}allowance")),
+            allowances_spender: Map::new_dyn(format!("{prefix}allowance_spender")),
+        }
+    }
+
+    /// Stores the metadata of a new token with a supply of zero
+    pub fn instantiate(
+        &self,
+        storage: &mut dyn Storage,
+        metadata: &TokenMetadata,
+        height: u64,
+    ) -> StdResult<()> {
+        self.token_info.save(storage, metadata)?;
+        self.total_supply.save(storage, &Uint128::zero(), height)
+    }
+
+    pub fn token_info(&self, storage: &dyn Storage) -> StdResult<TokenInfoResponse> {
+        let info = self.token_info.load(storage)?;
+        Ok(TokenInfoResponse {
+            name: info.name,
+            symbol: info.symbol,
+            decimals: info.decimals,
+            total_supply: self.total_supply.load(storage)?,
+        })
+    }
+
+    pub fn balance(&self, storage: &dyn Storage, addr: &Addr) -> StdResult<Uint128> {
+        Ok(self.balances.may_load(storage, addr)?.unwrap_or_default())
+    }
+
+    /// Credits `amount` new tokens to `recipient`
+    pub fn mint(
+        &self,
+        storage: &mut dyn Storage,
+        recipient: &Addr,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        self.total_supply
+            .update(storage, height, |supply| -> StdResult<_> {
+                Ok(supply.unwrap_or_default().checked_add(amount)?)
+            })?;
+        self.add_balance(storage, recipient, amount, height)
+    }
+
+    /// Destroys `amount` tokens of `owner`
+    pub fn burn(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        let balance = self.sub_balance(storage, owner, amount, height)?;
+        self.total_supply
+            .update(storage, height, |supply| -> StdResult<_> {
+                Ok(supply.unwrap_or_default().checked_sub(amount)?)
+            })?;
+        Ok(balance)
+    }
+
+    pub fn transfer(
+        &self,
+        storage: &mut dyn Storage,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<()> {
+        self.sub_balance(storage, from, amount, height)?;
+        self.add_balance(storage, to, amount, height)?;
+        Ok(())
+    }
+
+    pub fn allowance(
+        &self,
+        storage: &dyn Storage,
+        owner: &Addr,
+        spender: &Addr,
+    ) -> StdResult<AllowanceResponse> {
+        Ok(self
+            .allowances
+            .may_load(storage, (owner, spender))?
+            .unwrap_or_default())
+    }
+
+    /// Raises the allowance of `spender` over the tokens of `owner`, replacing its expiration
+    /// if one is given
+    pub fn increase_allowance(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        owner: &Addr,
+        spender: &Addr,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> Result<AllowanceResponse, ContractError> {
+        if owner == spender {
+            return Err(ContractError::CannotSetOwnAccount {});
+        }
+        let update_fn = |allow: Option<AllowanceResponse>| -> Result<_, ContractError> {
+            let mut val = allow.unwrap_or_default();
+            if let Some(exp) = expires {
+                if exp.is_expired(block) {
+                    return Err(ContractError::InvalidExpiration {});
+                }
+                val.expires = exp;
+            }
+            val.allowance += amount;
+            Ok(val)
+        };
+        let allowance = self
+            .allowances
+            .update(storage, (owner, spender), update_fn)?;
+        // saved rather than updated, as the spender index may still be built by a migration
+        self.allowances_spender
+            .save(storage, (spender, owner), &allowance)?;
+        Ok(allowance)
+    }
+
+    /// Lowers an unexpired allowance by `amount`, failing if it doesn't cover it
+    pub fn deduct_allowance(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        owner: &Addr,
+        spender: &Addr,
+        amount: Uint128,
+    ) -> Result<AllowanceResponse, ContractError> {
+        let update_fn = |current: Option<AllowanceResponse>| -> _ {
+            match current {
+                Some(mut a) => {
+                    if a.expires.is_expired(block) {
+                        Err(ContractError::Expired {})
+                    } else {
+                        a.allowance = a
+                            .allowance
+                            .checked_sub(amount)
+                            .map_err(StdError::overflow)?;
+                        Ok(a)
+                    }
+                }
+                None => Err(ContractError::NoAllowance {}),
+            }
+        };
+        let allowance = self
+            .allowances
+            .update(storage, (owner, spender), update_fn)?;
+        self.allowances_spender
+            .save(storage, (spender, owner), &allowance)?;
+        Ok(allowance)
+    }
+
+    fn add_balance(
+        &self,
+        storage: &mut dyn Storage,
+        addr: &Addr,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        self.balances
+            .update(storage, addr, height, |balance| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_add(amount)?)
+            })
+    }
+
+    fn sub_balance(
+        &self,
+        storage: &mut dyn Storage,
+        addr: &Addr,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        self.balances
+            .update(storage, addr, height, |balance| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_sub(amount)?)
+            })
+    }
+}
@@ -0,0 +1,154 @@
+use cosmwasm_std::{
+    attr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp, Uint128,
+};
+use cw_storage_plus::Bound;
+
+use crate::balances::{escrow_balance, release_escrow};
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::msg::{StreamResponse, StreamsResponse};
+use crate::state::{streams, Stream, STREAM_COUNT};
+
+pub fn execute_create_stream(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    start: Timestamp,
+    end: Timestamp,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    if end <= start {
+        return Err(ContractError::InvalidStreamSchedule {});
+    }
+
+    // escrow the tokens on the contract's own account
+    escrow_balance(&mut deps, &env, &info.sender, &rcpt_addr, amount)?;
+
+    let id = STREAM_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    STREAM_COUNT.save(deps.storage, &id)?;
+    let stream = Stream {
+        sender: info.sender.clone(),
+        recipient: rcpt_addr,
+        amount,
+        withdrawn: Uint128::zero(),
+        start,
+        end,
+    };
+    streams().save(deps.storage, id, &stream)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "create_stream"),
+        attr("stream_id", id.to_string()),
+        attr("from", &info.sender),
+        attr("to", recipient),
+        attr("amount", amount),
+    ]);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::transfer(
+        deps.storage,
+        &info.sender,
+        &env.contract.address,
+        amount,
+    )?);
+    Ok(res)
+}
+
+pub fn execute_withdraw_from_stream(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut stream = streams().load(deps.storage, id)?;
+    if stream.recipient != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = stream.vested(env.block.time) - stream.withdrawn;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    stream.withdrawn += amount;
+    streams().save(deps.storage, id, &stream)?;
+
+    let effects = release_escrow(&mut deps, &env, &stream.sender, &stream.recipient, amount)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "withdraw_from_stream"),
+            attr("stream_id", id.to_string()),
+            attr("to", info.sender),
+            attr("amount", amount),
+        ])
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
+    Ok(res)
+}
+
+fn stream_response(env: &Env, id: u64, stream: Stream) -> StreamResponse {
+    let withdrawable = stream.vested(env.block.time) - stream.withdrawn;
+    StreamResponse {
+        id,
+        sender: stream.sender,
+        recipient: stream.recipient,
+        amount: stream.amount,
+        withdrawn: stream.withdrawn,
+        start: stream.start,
+        end: stream.end,
+        withdrawable,
+    }
+}
+
+pub fn query_stream(deps: Deps, env: Env, id: u64) -> StdResult<StreamResponse> {
+    let stream = streams().load(deps.storage, id)?;
+    Ok(stream_response(&env, id, stream))
+}
+
+pub fn query_streams_by_sender(
+    deps: Deps,
+    env: Env,
+    sender: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let sender_addr = deps.api.addr_validate(&sender)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let streams = streams()
+        .idx
+        .sender
+        .prefix(sender_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, stream)| stream_response(&env, id, stream)))
+        .collect::<StdResult<_>>()?;
+    Ok(StreamsResponse { streams })
+}
+
+pub fn query_streams_by_recipient(
+    deps: Deps,
+    env: Env,
+    recipient: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let streams = streams()
+        .idx
+        .recipient
+        .prefix(recipient_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, stream)| stream_response(&env, id, stream)))
+        .collect::<StdResult<_>>()?;
+    Ok(StreamsResponse { streams })
+}
@@ -0,0 +1,154 @@
+use cosmwasm_std::{attr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+use cw20::Expiration;
+
+#[cfg(feature = "allowance-locks")]
+use crate::allowances::assert_allowances_unlocked;
+use crate::balances::transfer_balance;
+use crate::error::ContractError;
+use crate::msg::SubscriptionResponse;
+use crate::state::{Subscription, SUBSCRIPTIONS};
+
+pub fn execute_subscribe(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount_per_period: Uint128,
+    period: u64,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+    if period == 0 {
+        return Err(ContractError::InvalidSubscriptionPeriod {});
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let subscription = Subscription {
+        amount_per_period,
+        period,
+        expires,
+        start: env.block.time,
+        current_period: 0,
+        charged: Uint128::zero(),
+    };
+    SUBSCRIPTIONS.save(deps.storage, (&info.sender, &spender_addr), &subscription)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "subscribe"),
+        attr("owner", info.sender),
+        attr("spender", spender),
+        attr("amount_per_period", amount_per_period),
+        attr("period", period.to_string()),
+    ]);
+    Ok(res)
+}
+
+pub fn execute_cancel_subscription(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    spender: String,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    SUBSCRIPTIONS.remove(deps.storage, (&info.sender, &spender_addr));
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "cancel_subscription"),
+        attr("owner", info.sender),
+        attr("spender", spender),
+    ]);
+    Ok(res)
+}
+
+pub fn execute_charge(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    #[cfg(feature = "allowance-locks")]
+    assert_allowances_unlocked(deps.storage, &owner_addr, &env.block)?;
+
+    let key = (&owner_addr, &info.sender);
+    let mut subscription = SUBSCRIPTIONS
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NoSubscription {})?;
+    if subscription.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    // the cap refreshes at the start of every period
+    let current_period = subscription.period_at(&env);
+    if current_period != subscription.current_period {
+        subscription.current_period = current_period;
+        subscription.charged = Uint128::zero();
+    }
+    // an amount too large to add is over the cap as well
+    subscription.charged = subscription
+        .charged
+        .checked_add(amount)
+        .map_err(|_| ContractError::SubscriptionLimitExceeded {})?;
+    if subscription.charged > subscription.amount_per_period {
+        return Err(ContractError::SubscriptionLimitExceeded {});
+    }
+    SUBSCRIPTIONS.save(deps.storage, key, &subscription)?;
+
+    let effects = transfer_balance(&mut deps, &env, &owner_addr, &rcpt_addr, amount)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "charge"),
+            attr("from", owner),
+            attr("to", rcpt_addr),
+            attr("by", info.sender),
+            attr("amount", amount),
+        ])
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
+    Ok(res)
+}
+
+pub fn query_subscription(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    spender: String,
+) -> StdResult<Option<SubscriptionResponse>> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let subscription = SUBSCRIPTIONS.may_load(deps.storage, (&owner_addr, &spender_addr))?;
+
+    Ok(subscription.map(|s| {
+        let charged = if s.period_at(&env) == s.current_period {
+            s.charged
+        } else {
+            Uint128::zero()
+        };
+        let available = if s.expires.is_expired(&env.block) {
+            Uint128::zero()
+        } else {
+            s.amount_per_period - charged
+        };
+        SubscriptionResponse {
+            amount_per_period: s.amount_per_period,
+            period: s.period,
+            expires: s.expires,
+            available,
+        }
+    }))
+}
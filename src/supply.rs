@@ -0,0 +1,147 @@
+use cosmwasm_std::{Addr, Deps, Order, StdError, StdResult, Storage, Uint128};
+#[cfg(feature = "supply-shards")]
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+#[cfg(feature = "exchange-rate")]
+use crate::exchange_rate::to_underlying;
+#[cfg(all(feature = "exchange-rate", feature = "snapshots"))]
+use crate::exchange_rate::to_underlying_at;
+use crate::msg::TotalSupplyResponse;
+#[cfg(feature = "supply-shards")]
+use crate::state::SUPPLY_SHARD_COUNT;
+use crate::state::{SupplyShard, MINTER, SUPPLY_SHARDS, TOTAL_SUPPLY};
+
+/// Starts tracking supply changes in `count` shards instead of `TOTAL_SUPPLY`.
+#[cfg(feature = "supply-shards")]
+pub fn enable_shards(storage: &mut dyn Storage, count: u16) -> StdResult<()> {
+    if count == 0 {
+        return Err(StdError::generic_err("Supply shard count must not be zero"));
+    }
+    SUPPLY_SHARD_COUNT.save(storage, &count)
+}
+
+/// Shard recording supply changes caused by `addr`. Spreading accounts over shards keeps
+/// transactions of unrelated accounts from writing the same key.
+#[cfg(feature = "supply-shards")]
+fn shard_of(storage: &dyn Storage, addr: &Addr) -> StdResult<Option<u16>> {
+    let count = match SUPPLY_SHARD_COUNT.may_load(storage)? {
+        Some(count) => count,
+        None => return Ok(None),
+    };
+    let hash = Sha256::digest(addr.as_bytes());
+    Ok(Some(u16::from_be_bytes([hash[0], hash[1]]) % count))
+}
+
+#[cfg(not(feature = "supply-shards"))]
+fn shard_of(_storage: &dyn Storage, _addr: &Addr) -> StdResult<Option<u16>> {
+    Ok(None)
+}
+
+/// Records `amount` new tokens credited to `addr` at `height`
+pub fn increase_supply(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    match shard_of(storage, addr)? {
+        Some(shard) => {
+            SUPPLY_SHARDS.update(storage, shard, height, |s| -> StdResult<_> {
+                let mut s = s.unwrap_or_default();
+                s.minted = s.minted.checked_add(amount)?;
+                Ok(s)
+            })?;
+        }
+        None => {
+            TOTAL_SUPPLY.update(storage, height, |supply| -> StdResult<_> {
+                Ok(supply.unwrap_or_default().checked_add(amount)?)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Records `amount` tokens of `addr` destroyed at `height`
+pub fn decrease_supply(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    match shard_of(storage, addr)? {
+        // a shard may burn more than it minted; the balances guarantee the sum stays positive
+        Some(shard) => {
+            SUPPLY_SHARDS.update(storage, shard, height, |s| -> StdResult<_> {
+                let mut s = s.unwrap_or_default();
+                s.burned = s.burned.checked_add(amount)?;
+                Ok(s)
+            })?;
+        }
+        None => {
+            TOTAL_SUPPLY.update(storage, height, |supply| -> StdResult<_> {
+                Ok(supply.unwrap_or_default().checked_sub(amount)?)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// The supply at instantiation plus the changes recorded in every shard
+pub fn total_supply(storage: &dyn Storage) -> StdResult<Uint128> {
+    let base = TOTAL_SUPPLY.load(storage)?;
+    SUPPLY_SHARDS
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(base, |total, item| {
+            let (_, shard): (u16, SupplyShard) = item?;
+            Ok(total.checked_add(shard.minted)?.checked_sub(shard.burned)?)
+        })
+}
+
+/// Fails if `amount` new tokens would take the supply above the minter's cap
+pub fn assert_within_cap(storage: &dyn Storage, amount: Uint128) -> Result<(), ContractError> {
+    if let Some(limit) = MINTER.may_load(storage)?.and_then(|m| m.cap) {
+        let new_supply = total_supply(storage)?
+            .checked_add(amount)
+            .map_err(StdError::from)?;
+        if new_supply > limit {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    Ok(())
+}
+
+/// Total supply at the start of block `height`, i.e. after every change of the blocks before
+pub fn total_supply_at(storage: &dyn Storage, height: u64) -> StdResult<Uint128> {
+    let base = TOTAL_SUPPLY
+        .may_load_at_height(storage, height)?
+        .unwrap_or_default();
+    #[cfg(feature = "supply-shards")]
+    let base = match SUPPLY_SHARD_COUNT.may_load(storage)? {
+        Some(count) => (0..count).try_fold(base, |total, shard| -> StdResult<_> {
+            let shard = SUPPLY_SHARDS
+                .may_load_at_height(storage, shard, height)?
+                .unwrap_or_default();
+            Ok(total.checked_add(shard.minted)?.checked_sub(shard.burned)?)
+        })?,
+        None => base,
+    };
+    Ok(base)
+}
+
+pub fn query_total_supply(deps: Deps) -> StdResult<TotalSupplyResponse> {
+    let total_supply = total_supply(deps.storage)?;
+    #[cfg(feature = "exchange-rate")]
+    let total_supply = to_underlying(deps.storage, total_supply)?;
+    Ok(TotalSupplyResponse { total_supply })
+}
+
+/// Historical supply, like TotalSupply in the underlying amount in share mode, valued at
+/// the exchange rate of that height
+#[cfg(feature = "snapshots")]
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    let total_supply = total_supply_at(deps.storage, height)?;
+    #[cfg(feature = "exchange-rate")]
+    let total_supply = to_underlying_at(deps.storage, total_supply, height)?;
+    Ok(TotalSupplyResponse { total_supply })
+}
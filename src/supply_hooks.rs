@@ -0,0 +1,118 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg,
+    SubMsgResult, Uint128,
+};
+
+use crate::error::ContractError;
+use crate::msg::{SupplyHookMsg, SupplyHooksResponse};
+use crate::state::{MINTER, SUPPLY_HOOKS};
+use crate::supply::total_supply;
+
+/// Every hook costs a message on each mint and burn, so only a few can be registered
+pub const MAX_SUPPLY_HOOKS: usize = 10;
+
+pub const SUPPLY_HOOK_REPLY_ID: u64 = 3;
+
+pub fn execute_update_supply_hooks(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let minter = MINTER.may_load(deps.storage)?;
+    if minter.map(|m| m.minter) != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for contract in &remove {
+        let contract = deps.api.addr_validate(contract)?;
+        SUPPLY_HOOKS.remove(deps.storage, &contract);
+    }
+    for contract in &add {
+        let contract = deps.api.addr_validate(contract)?;
+        SUPPLY_HOOKS.save(deps.storage, &contract, &())?;
+    }
+    let count = SUPPLY_HOOKS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .count();
+    if count > MAX_SUPPLY_HOOKS {
+        return Err(ContractError::TooManySupplyHooks {
+            max: MAX_SUPPLY_HOOKS,
+        });
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_supply_hooks")
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(","));
+    Ok(res)
+}
+
+/// Notifications of a mint of `amount` to `recipient`, sent once the supply is updated
+pub fn mint_msgs(
+    storage: &dyn Storage,
+    minter: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    notify(storage, |new_total_supply| SupplyHookMsg::Mint {
+        minter: minter.to_string(),
+        recipient: recipient.to_string(),
+        amount,
+        new_total_supply,
+    })
+}
+
+/// Notifications of a burn of `amount` from `owner`, sent once the supply is updated
+pub fn burn_msgs(storage: &dyn Storage, owner: &Addr, amount: Uint128) -> StdResult<Vec<SubMsg>> {
+    notify(storage, |new_total_supply| SupplyHookMsg::Burn {
+        owner: owner.to_string(),
+        amount,
+        new_total_supply,
+    })
+}
+
+/// One notification per hook. A failing hook doesn't fail the mint or burn, so it can't
+/// hold up minting, burning or transfers that burn.
+fn notify(
+    storage: &dyn Storage,
+    msg: impl FnOnce(Uint128) -> SupplyHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let hooks = SUPPLY_HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if hooks.is_empty() {
+        return Ok(vec![]);
+    }
+    let msg = msg(total_supply(storage)?);
+    hooks
+        .into_iter()
+        .map(|hook| {
+            let payload = hook.as_bytes().to_vec();
+            let msg = msg.clone().into_cosmos_msg(hook)?;
+            Ok(SubMsg::reply_on_error(msg, SUPPLY_HOOK_REPLY_ID).with_payload(payload))
+        })
+        .collect()
+}
+
+/// Records a hook that failed to take its notification, leaving the supply change in place.
+pub fn reply_supply_hook(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+    };
+    let res = Response::new()
+        .add_attribute("action", "supply_hook_failed")
+        .add_attribute("hook", String::from_utf8_lossy(&msg.payload))
+        .add_attribute("error", error);
+    Ok(res)
+}
+
+pub fn query_supply_hooks(deps: Deps) -> StdResult<SupplyHooksResponse> {
+    let hooks = SUPPLY_HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(SupplyHooksResponse { hooks })
+}
@@ -0,0 +1,68 @@
+use cosmwasm_std::{Addr, BlockInfo, Deps, StdError, StdResult, Storage, Uint128};
+
+use crate::enumerable::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::msg::{SupplyAction, SupplyChange, SupplyChangeLogResponse};
+use crate::state::{SUPPLY_LOG, SUPPLY_LOG_CAPACITY, SUPPLY_LOG_NEXT_ID};
+
+/// Starts logging mints and burns, keeping the latest `capacity` of them.
+pub fn setup_supply_log(storage: &mut dyn Storage, capacity: u32) -> StdResult<()> {
+    if capacity == 0 {
+        return Err(StdError::generic_err(
+            "Supply log capacity must not be zero",
+        ));
+    }
+    SUPPLY_LOG_CAPACITY.save(storage, &capacity)
+}
+
+/// Appends a mint or burn to the log, if enabled, dropping the oldest records once it is
+/// full.
+pub fn record(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    action: SupplyAction,
+    actor: &Addr,
+    account: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let capacity = match SUPPLY_LOG_CAPACITY.may_load(storage)? {
+        Some(capacity) => capacity,
+        None => return Ok(()),
+    };
+    let id = SUPPLY_LOG_NEXT_ID.may_load(storage)?.unwrap_or_default();
+    SUPPLY_LOG_NEXT_ID.save(storage, &(id + 1))?;
+    let change = SupplyChange {
+        id,
+        action,
+        amount,
+        actor: actor.clone(),
+        account: account.clone(),
+        height: block.height,
+        time: block.time,
+    };
+    SUPPLY_LOG.push_back(storage, &change)?;
+    while SUPPLY_LOG.len(storage)? > capacity {
+        SUPPLY_LOG.pop_front(storage)?;
+    }
+    Ok(())
+}
+
+pub fn query_supply_change_log(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SupplyChangeLogResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let oldest = match SUPPLY_LOG.front(deps.storage)? {
+        Some(change) => change.id,
+        None => return Ok(SupplyChangeLogResponse { changes: vec![] }),
+    };
+    // ids are consecutive, so the cursor maps straight to a position in the log
+    let skip = start_after.map_or(0, |id| (id + 1).saturating_sub(oldest));
+
+    let changes = SUPPLY_LOG
+        .iter(deps.storage)?
+        .skip(skip as usize)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(SupplyChangeLogResponse { changes })
+}
@@ -0,0 +1,243 @@
+use cosmwasm_std::{Addr, Empty, StdError, Uint128};
+use cw20::{Cw20Coin, Cw20Contract, Expiration, MinterResponse};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::ContractError;
+
+pub fn cw20_base_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_migrate(migrate))
+}
+
+/// Sets up a [`Cw20TestSuite`]. Accounts are given by name and turned into addresses with
+/// the `addr_make` of the app.
+#[derive(Clone)]
+pub struct Cw20TestSuiteBuilder {
+    contract: fn() -> Box<dyn Contract<Empty>>,
+    msg: InstantiateMsg,
+    balances: Vec<(String, Uint128)>,
+    cap: Option<Uint128>,
+}
+
+impl Default for Cw20TestSuiteBuilder {
+    fn default() -> Self {
+        Cw20TestSuiteBuilder {
+            contract: cw20_base_contract,
+            msg: InstantiateMsg {
+                name: "Test Token".to_string(),
+                symbol: "TEST".to_string(),
+                decimals: 6,
+                ..InstantiateMsg::default()
+            },
+            balances: vec![],
+            cap: None,
+        }
+    }
+}
+
+impl Cw20TestSuiteBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs another contract taking the cw20-base messages, such as a fork of it
+    pub fn with_contract(mut self, contract: fn() -> Box<dyn Contract<Empty>>) -> Self {
+        self.contract = contract;
+        self
+    }
+
+    /// Starts from `msg`, for the settings of the extensions. Its balances are kept and
+    /// its minter, if any, replaces the `minter` account.
+    pub fn with_instantiate_msg(mut self, msg: InstantiateMsg) -> Self {
+        self.msg = msg;
+        self
+    }
+
+    pub fn with_token(mut self, name: &str, symbol: &str, decimals: u8) -> Self {
+        self.msg.name = name.to_string();
+        self.msg.symbol = symbol.to_string();
+        self.msg.decimals = decimals;
+        self
+    }
+
+    pub fn with_balance(mut self, account: &str, amount: impl Into<Uint128>) -> Self {
+        self.balances.push((account.to_string(), amount.into()));
+        self
+    }
+
+    pub fn with_cap(mut self, cap: impl Into<Uint128>) -> Self {
+        self.cap = Some(cap.into());
+        self
+    }
+
+    pub fn build(self) -> Cw20TestSuite {
+        let mut app = App::default();
+        let admin = app.api().addr_make("admin");
+        let minter = app.api().addr_make("minter");
+        let mut msg = self.msg;
+        for (account, amount) in self.balances {
+            msg.initial_balances.push(Cw20Coin {
+                address: app.api().addr_make(&account).to_string(),
+                amount,
+            });
+        }
+        let minter = match &msg.mint {
+            Some(mint) => Addr::unchecked(&mint.minter),
+            None => {
+                msg.mint = Some(MinterResponse {
+                    minter: minter.to_string(),
+                    cap: self.cap,
+                });
+                minter
+            }
+        };
+
+        let code_id = app.store_code((self.contract)());
+        let label = msg.symbol.clone();
+        let token = app
+            .instantiate_contract(code_id, admin.clone(), &msg, &[], label, Some(admin.into()))
+            .unwrap();
+        Cw20TestSuite {
+            app,
+            code_id,
+            token: Cw20Contract(token),
+            minter,
+        }
+    }
+}
+
+/// cw20-base running in a cw-multi-test [`App`], with shortcuts for the common messages
+/// and checks. The app is public for everything else.
+pub struct Cw20TestSuite {
+    pub app: App,
+    pub code_id: u64,
+    pub token: Cw20Contract,
+    pub minter: Addr,
+}
+
+impl Cw20TestSuite {
+    pub fn builder() -> Cw20TestSuiteBuilder {
+        Cw20TestSuiteBuilder::new()
+    }
+
+    /// Address of the account with that name
+    pub fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    /// Runs any message of the token as `sender`
+    pub fn execute(
+        &mut self,
+        sender: &Addr,
+        msg: &ExecuteMsg,
+    ) -> Result<AppResponse, ContractError> {
+        self.app
+            .execute_contract(sender.clone(), self.token.addr(), msg, &[])
+            .map_err(|err| {
+                err.downcast()
+                    .unwrap_or_else(|err| StdError::generic_err(err.to_string()).into())
+            })
+    }
+
+    pub fn mint(
+        &mut self,
+        recipient: &Addr,
+        amount: impl Into<Uint128>,
+    ) -> Result<AppResponse, ContractError> {
+        let msg = ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: amount.into(),
+        };
+        let minter = self.minter.clone();
+        self.execute(&minter, &msg)
+    }
+
+    pub fn transfer(
+        &mut self,
+        sender: &Addr,
+        recipient: &Addr,
+        amount: impl Into<Uint128>,
+    ) -> Result<AppResponse, ContractError> {
+        let msg = ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: amount.into(),
+            memo: None,
+        };
+        self.execute(sender, &msg)
+    }
+
+    pub fn increase_allowance(
+        &mut self,
+        owner: &Addr,
+        spender: &Addr,
+        amount: impl Into<Uint128>,
+        expires: Option<Expiration>,
+    ) -> Result<AppResponse, ContractError> {
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: amount.into(),
+            expires,
+        };
+        self.execute(owner, &msg)
+    }
+
+    pub fn transfer_from(
+        &mut self,
+        spender: &Addr,
+        owner: &Addr,
+        recipient: &Addr,
+        amount: impl Into<Uint128>,
+    ) -> Result<AppResponse, ContractError> {
+        let msg = ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: recipient.to_string(),
+            amount: amount.into(),
+            memo: None,
+        };
+        self.execute(spender, &msg)
+    }
+
+    pub fn balance(&self, account: &Addr) -> Uint128 {
+        self.token.balance(&self.app.wrap(), account).unwrap()
+    }
+
+    pub fn allowance(&self, owner: &Addr, spender: &Addr) -> Uint128 {
+        self.token
+            .allowance(&self.app.wrap(), owner, spender)
+            .unwrap()
+            .allowance
+    }
+
+    pub fn total_supply(&self) -> Uint128 {
+        self.token.meta(&self.app.wrap()).unwrap().total_supply
+    }
+
+    #[track_caller]
+    pub fn assert_balance(&self, account: &Addr, expected: impl Into<Uint128>) {
+        assert_eq!(
+            self.balance(account),
+            expected.into(),
+            "balance of {account}"
+        );
+    }
+
+    /// Checks each of the balances and that nobody else holds any of the token
+    #[track_caller]
+    pub fn assert_balances(&self, expected: &[(&Addr, u128)]) {
+        for (account, amount) in expected {
+            self.assert_balance(account, *amount);
+        }
+        let sum: u128 = expected.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(self.total_supply().u128(), sum, "total supply");
+    }
+
+    #[track_caller]
+    pub fn assert_allowance(&self, owner: &Addr, spender: &Addr, expected: impl Into<Uint128>) {
+        assert_eq!(
+            self.allowance(owner, spender),
+            expected.into(),
+            "allowance of {spender} over {owner}"
+        );
+    }
+}
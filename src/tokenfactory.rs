@@ -0,0 +1,199 @@
+#[cfg(feature = "tokenfactory")]
+use cosmwasm_std::{coins, BankMsg, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{CosmosMsg, Env, StdResult, Storage, Uint128};
+
+#[cfg(feature = "tokenfactory")]
+use crate::balances::{escrow_balance, release_escrow};
+use crate::error::ContractError;
+#[cfg(feature = "tokenfactory")]
+use crate::state::MIRROR_DENOM;
+
+#[cfg(feature = "tokenfactory")]
+const MSG_CREATE_DENOM: &str = "/osmosis.tokenfactory.v1beta1.MsgCreateDenom";
+#[cfg(feature = "tokenfactory")]
+const MSG_MINT: &str = "/osmosis.tokenfactory.v1beta1.MsgMint";
+#[cfg(feature = "tokenfactory")]
+const MSG_BURN: &str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+
+/// Creates the mirrored denom and mints the initial supply into it.
+/// Fails if the contract was compiled without the "tokenfactory" feature.
+#[cfg(feature = "tokenfactory")]
+pub fn setup_mirror(
+    storage: &mut dyn Storage,
+    env: &Env,
+    subdenom: &str,
+    initial_supply: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let denom = format!("factory/{}/{}", env.contract.address, subdenom);
+    MIRROR_DENOM.save(storage, &denom)?;
+
+    let create = proto::Writer::default()
+        .string(1, env.contract.address.as_str())
+        .string(2, subdenom)
+        .into_any(MSG_CREATE_DENOM);
+
+    let mut msgs = vec![create];
+    msgs.extend(supply_msgs(storage, env, MSG_MINT, initial_supply)?);
+    Ok(msgs)
+}
+
+#[cfg(not(feature = "tokenfactory"))]
+pub fn setup_mirror(
+    _storage: &mut dyn Storage,
+    _env: &Env,
+    _subdenom: &str,
+    _initial_supply: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    Err(ContractError::TokenFactoryDisabled {})
+}
+
+/// Messages minting `amount` of the mirrored denom, empty if no mirror is configured.
+pub fn mint_msgs(storage: &dyn Storage, env: &Env, amount: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    #[cfg(feature = "tokenfactory")]
+    return supply_msgs(storage, env, MSG_MINT, amount);
+    #[cfg(not(feature = "tokenfactory"))]
+    {
+        let _ = (storage, env, amount);
+        Ok(vec![])
+    }
+}
+
+/// Messages burning `amount` of the mirrored denom, empty if no mirror is configured.
+pub fn burn_msgs(storage: &dyn Storage, env: &Env, amount: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    #[cfg(feature = "tokenfactory")]
+    return supply_msgs(storage, env, MSG_BURN, amount);
+    #[cfg(not(feature = "tokenfactory"))]
+    {
+        let _ = (storage, env, amount);
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "tokenfactory")]
+fn supply_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    type_url: &str,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let denom = match MIRROR_DENOM.may_load(storage)? {
+        Some(denom) => denom,
+        None => return Ok(vec![]),
+    };
+    // the tokenfactory module rejects zero amounts
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let coin = proto::Writer::default()
+        .string(1, &denom)
+        .string(2, &amount.to_string());
+    // the mirrored coins are minted to (and burned from) the contract itself, so the
+    // native supply always equals the cw20 supply. The contract holds them for the cw20
+    // tokens in circulation, and hands them out for the tokens Unwrap locks up.
+    let msg = proto::Writer::default()
+        .string(1, env.contract.address.as_str())
+        .message(2, coin)
+        .into_any(type_url);
+    Ok(vec![msg])
+}
+
+/// Swaps `amount` of the sender's tokens for as many mirrored coins, checked like a
+/// transfer out of the sender's account. The tokens stay locked on the contract's account
+/// until Wrap takes the coins back.
+#[cfg(feature = "tokenfactory")]
+pub fn execute_unwrap(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let denom = MIRROR_DENOM
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMirror {})?;
+    escrow_balance(&mut deps, &env, &info.sender, &info.sender, amount)?;
+
+    let send = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: coins(amount.u128(), denom),
+    };
+    // the bank module rejects zero amounts
+    let res = Response::new()
+        .add_messages((!amount.is_zero()).then_some(send))
+        .add_attribute("action", "unwrap")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount);
+    Ok(res)
+}
+
+/// Swaps the mirrored coins sent along for as many tokens, released from the contract's
+/// account like a transfer from it, transfer-time burns included.
+#[cfg(feature = "tokenfactory")]
+pub fn execute_wrap(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let denom = MIRROR_DENOM
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMirror {})?;
+    let amount = match &info.funds[..] {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => coin.amount,
+        _ => return Err(ContractError::InvalidWrapFunds { denom }),
+    };
+    let contract = env.contract.address.clone();
+    let effects = release_escrow(&mut deps, &env, &contract, &info.sender, amount)?;
+
+    let res = Response::new()
+        .add_attribute("action", "wrap")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount)
+        .add_attributes(effects.attributes)
+        .add_submessages(effects.messages)
+        .add_events(effects.events);
+    Ok(res)
+}
+
+/// Minimal protobuf encoding, just enough for the tokenfactory messages.
+#[cfg(feature = "tokenfactory")]
+mod proto {
+    use cosmwasm_std::{AnyMsg, CosmosMsg};
+
+    #[derive(Default)]
+    pub struct Writer(Vec<u8>);
+
+    impl Writer {
+        pub fn string(self, field: u64, value: &str) -> Self {
+            self.bytes(field, value.as_bytes())
+        }
+
+        pub fn message(self, field: u64, value: Writer) -> Self {
+            self.bytes(field, &value.0)
+        }
+
+        pub fn into_any(self, type_url: &str) -> CosmosMsg {
+            CosmosMsg::Any(AnyMsg {
+                type_url: type_url.to_string(),
+                value: self.0.into(),
+            })
+        }
+
+        fn bytes(mut self, field: u64, value: &[u8]) -> Self {
+            // proto3 omits empty length-delimited fields
+            if !value.is_empty() {
+                self.varint(field << 3 | 2);
+                self.varint(value.len() as u64);
+                self.0.extend_from_slice(value);
+            }
+            self
+        }
+
+        fn varint(&mut self, mut value: u64) {
+            while value >= 0x80 {
+                self.0.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            self.0.push(value as u8);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Timestamp,
+    Uint128,
+};
+
+use crate::admin::assert_admin;
+use crate::error::ContractError;
+use crate::msg::TransferQuotaResponse;
+use crate::state::{TransferBucket, TransferLimit, TRANSFER_BUCKETS, TRANSFER_LIMIT};
+
+/// Buckets the window is split into. A transfer counts until its whole bucket has left
+/// the window, so for one period and up to a bucket more, and no span of one period ever
+/// sees more than the limit.
+const BUCKETS: u64 = 24;
+
+/// Sets the limit every account is held to from now on
+pub fn setup_transfer_limit(
+    storage: &mut dyn Storage,
+    limit: &TransferLimit,
+) -> Result<(), ContractError> {
+    if limit.period == 0 {
+        return Err(ContractError::InvalidTransferLimit {});
+    }
+    TRANSFER_LIMIT.save(storage, limit)?;
+    Ok(())
+}
+
+fn bucket_length(limit: &TransferLimit) -> u64 {
+    limit.period.div_ceil(BUCKETS)
+}
+
+/// When the transfers of `bucket` stop counting against the limit
+fn release_time(bucket: &TransferBucket, limit: &TransferLimit) -> Timestamp {
+    bucket
+        .start
+        .plus_seconds(bucket_length(limit) * (BUCKETS + 1))
+}
+
+/// Buckets of `addr` still in the window at the current time, oldest first
+fn live_buckets(
+    storage: &dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    limit: &TransferLimit,
+) -> StdResult<Vec<TransferBucket>> {
+    let mut buckets = TRANSFER_BUCKETS
+        .may_load(storage, addr)?
+        .unwrap_or_default();
+    buckets.retain(|bucket| env.block.time < release_time(bucket, limit));
+    Ok(buckets)
+}
+
+fn spent(buckets: &[TransferBucket]) -> Uint128 {
+    buckets.iter().map(|bucket| bucket.spent).sum()
+}
+
+/// What `addr` can still transfer in the window, None without a limit
+pub fn remaining_quota(
+    storage: &dyn Storage,
+    env: &Env,
+    addr: &Addr,
+) -> StdResult<Option<Uint128>> {
+    let limit = match TRANSFER_LIMIT.may_load(storage)? {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+    let buckets = live_buckets(storage, env, addr, &limit)?;
+    Ok(Some(limit.amount.saturating_sub(spent(&buckets))))
+}
+
+/// Fails if `addr` transferring `amount` now would exceed the limit of its window
+pub fn assert_within_limit(
+    storage: &dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let limit = match TRANSFER_LIMIT.may_load(storage)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let buckets = live_buckets(storage, env, addr, &limit)?;
+    let spent = spent(&buckets)
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    if spent > limit.amount {
+        return Err(ContractError::TransferLimitExceeded {});
+    }
+    Ok(())
+}
+
+/// Counts a transfer of `amount` out of `addr` against the window, in the current bucket
+pub fn record_transfer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let limit = match TRANSFER_LIMIT.may_load(storage)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let mut buckets = live_buckets(storage, env, addr, &limit)?;
+    let length = bucket_length(&limit);
+    let start = Timestamp::from_seconds(env.block.time.seconds() / length * length);
+    match buckets.last_mut() {
+        Some(bucket) if bucket.start == start => bucket.spent = bucket.spent.checked_add(amount)?,
+        _ => buckets.push(TransferBucket {
+            start,
+            spent: amount,
+        }),
+    }
+    TRANSFER_BUCKETS.save(storage, addr, &buckets)
+}
+
+/// Takes a transfer of `amount` out of `addr` that was undone back out of the window,
+/// from the newest buckets first
+pub fn release_transfer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let limit = match TRANSFER_LIMIT.may_load(storage)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let mut buckets = live_buckets(storage, env, addr, &limit)?;
+    let mut left = amount;
+    for bucket in buckets.iter_mut().rev() {
+        let released = left.min(bucket.spent);
+        bucket.spent -= released;
+        left -= released;
+    }
+    buckets.retain(|bucket| !bucket.spent.is_zero());
+    TRANSFER_BUCKETS.save(storage, addr, &buckets)
+}
+
+pub fn execute_update_transfer_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    limit: Option<TransferLimit>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    let mut res = Response::new().add_attribute("action", "update_transfer_limit");
+    match limit {
+        Some(limit) => {
+            setup_transfer_limit(deps.storage, &limit)?;
+            res = res
+                .add_attribute("amount", limit.amount)
+                .add_attribute("period", limit.period.to_string());
+        }
+        None => {
+            TRANSFER_LIMIT.remove(deps.storage);
+            res = res.add_attribute("amount", "None");
+        }
+    }
+    Ok(res)
+}
+
+pub fn query_transfer_quota(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<TransferQuotaResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = match TRANSFER_LIMIT.may_load(deps.storage)? {
+        Some(limit) => limit,
+        None => {
+            return Ok(TransferQuotaResponse {
+                limit: None,
+                remaining: None,
+                next_release: None,
+            })
+        }
+    };
+    let buckets = live_buckets(deps.storage, &env, &addr, &limit)?;
+    Ok(TransferQuotaResponse {
+        remaining: Some(limit.amount.saturating_sub(spent(&buckets))),
+        next_release: buckets.first().map(|bucket| release_time(bucket, &limit)),
+        limit: Some(limit),
+    })
+}
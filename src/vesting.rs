@@ -0,0 +1,69 @@
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+#[cfg(feature = "events")]
+use crate::events;
+use crate::execute::execute_mint::mint;
+#[cfg(feature = "mint-timelock")]
+use crate::mint_timelock::record_direct_mint;
+use crate::state::{VestingLock, VestingSchedule, VESTING_LOCKS};
+#[cfg(feature = "supply-hooks")]
+use crate::supply_hooks;
+use crate::tokenfactory;
+
+pub fn execute_mint_locked(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    schedule: VestingSchedule,
+) -> Result<Response, ContractError> {
+    if !schedule.is_valid() {
+        return Err(ContractError::InvalidVestingSchedule {});
+    }
+    #[cfg(feature = "mint-timelock")]
+    record_direct_mint(deps.storage, &env, amount)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    mint(deps.storage, &env, &info.sender, &rcpt_addr, amount)?;
+
+    // drop fully vested locks so the list only holds what still matters
+    let mut locks = VESTING_LOCKS
+        .may_load(deps.storage, &rcpt_addr)?
+        .unwrap_or_default();
+    locks.retain(|lock| !lock.schedule.locked(lock.amount, env.block.time).is_zero());
+    locks.push(VestingLock {
+        amount,
+        schedule: schedule.clone(),
+    });
+    VESTING_LOCKS.save(deps.storage, &rcpt_addr, &locks)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "mint_locked"),
+            attr("to", recipient),
+            attr("amount", amount),
+            attr("cliff", schedule.cliff.to_string()),
+            attr("end", schedule.end.to_string()),
+        ])
+        .add_messages(tokenfactory::mint_msgs(deps.storage, &env, amount)?);
+    #[cfg(feature = "supply-hooks")]
+    let res = res.add_submessages(supply_hooks::mint_msgs(
+        deps.storage,
+        &info.sender,
+        &rcpt_addr,
+        amount,
+    )?);
+    #[cfg(feature = "events")]
+    let res = res.add_event(events::mint(deps.storage, &rcpt_addr, amount)?);
+    Ok(res)
+}
+
+/// Total of `addr`'s locked mints that has not vested yet
+pub fn locked_amount(storage: &dyn Storage, env: &Env, addr: &Addr) -> StdResult<Uint128> {
+    let locks = VESTING_LOCKS.may_load(storage, addr)?.unwrap_or_default();
+    Ok(locks
+        .iter()
+        .map(|lock| lock.schedule.locked(lock.amount, env.block.time))
+        .sum())
+}
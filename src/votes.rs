@@ -0,0 +1,131 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse, VotingPowerResponse};
+use crate::state::{BALANCES, DELEGATES, TOTAL_VOTING_POWER, VOTING_POWER};
+
+fn add_power(
+    storage: &mut dyn Storage,
+    delegatee: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    VOTING_POWER.update(storage, delegatee, height, |power| -> StdResult<_> {
+        Ok(power.unwrap_or_default().checked_add(amount)?)
+    })?;
+    let total = TOTAL_VOTING_POWER.may_load(storage)?.unwrap_or_default();
+    TOTAL_VOTING_POWER.save(storage, &total.checked_add(amount)?, height)
+}
+
+fn sub_power(
+    storage: &mut dyn Storage,
+    delegatee: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    VOTING_POWER.update(storage, delegatee, height, |power| -> StdResult<_> {
+        Ok(power.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    let total = TOTAL_VOTING_POWER.may_load(storage)?.unwrap_or_default();
+    TOTAL_VOTING_POWER.save(storage, &total.checked_sub(amount)?, height)
+}
+
+/// Follows a balance increase of `addr` in the power of its delegatee, if any
+pub fn increase_power(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    match DELEGATES.may_load(storage, addr)? {
+        Some(delegatee) => add_power(storage, &delegatee, amount, height),
+        None => Ok(()),
+    }
+}
+
+/// Follows a balance decrease of `addr` in the power of its delegatee, if any
+pub fn decrease_power(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    match DELEGATES.may_load(storage, addr)? {
+        Some(delegatee) => sub_power(storage, &delegatee, amount, height),
+        None => Ok(()),
+    }
+}
+
+/// Gives the voting power of the sender's whole balance, now and in the future, to
+/// `delegatee`. Holders who never delegated have no voting power, not even their own.
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegatee: String,
+) -> Result<Response, ContractError> {
+    let delegatee_addr = deps.api.addr_validate(&delegatee)?;
+    let balance = BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let height = env.block.height;
+    if let Some(previous) = DELEGATES.may_load(deps.storage, &info.sender)? {
+        sub_power(deps.storage, &previous, balance, height)?;
+    }
+    DELEGATES.save(deps.storage, &info.sender, &delegatee_addr)?;
+    add_power(deps.storage, &delegatee_addr, balance, height)?;
+
+    let res = Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", delegatee);
+    Ok(res)
+}
+
+/// Voting power of `address` at the start of block `height`
+pub fn query_voting_power_at(
+    deps: Deps,
+    address: String,
+    height: u64,
+) -> StdResult<VotingPowerResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let power = VOTING_POWER
+        .may_load_at_height(deps.storage, &addr, height)?
+        .unwrap_or_default();
+    Ok(VotingPowerResponse { power, height })
+}
+
+/// Voting power of all delegatees together at the start of block `height`
+pub fn query_total_power_at(deps: Deps, height: u64) -> StdResult<VotingPowerResponse> {
+    let power = TOTAL_VOTING_POWER
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    Ok(VotingPowerResponse { power, height })
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<VotingPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let res = query_voting_power_at(deps, address, height)?;
+    Ok(VotingPowerAtHeightResponse {
+        power: res.power,
+        height,
+    })
+}
+
+pub fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<TotalPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let res = query_total_power_at(deps, height)?;
+    Ok(TotalPowerAtHeightResponse {
+        power: res.power,
+        height,
+    })
+}
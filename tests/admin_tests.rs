@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, Addr, Deps, DepsMut};
+
+    use cw20_base::contract::{execute, instantiate, query};
+    use cw20_base::msg::{AdminResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Governed".to_string(),
+            symbol: "GOV".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn admin(deps: Deps) -> AdminResponse {
+        from_json(query(deps, mock_env(), QueryMsg::Admin {}).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn admin_moves_once_the_nominee_accepts() {
+        let mut deps = mock_dependencies();
+        let old = deps.api.addr_make("old");
+        let new = deps.api.addr_make("new");
+        do_instantiate(deps.as_mut(), &old);
+
+        let msg = ExecuteMsg::UpdateAdmin {
+            new_admin: Some(new.to_string()),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&old, &[]), msg).unwrap();
+        assert_eq!(
+            admin(deps.as_ref()),
+            AdminResponse {
+                admin: Some(old.clone()),
+                pending: Some(new.clone()),
+            }
+        );
+
+        // only the nominee can accept
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&old, &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new, &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap();
+        assert_eq!(
+            admin(deps.as_ref()),
+            AdminResponse {
+                admin: Some(new),
+                pending: None,
+            }
+        );
+    }
+
+    #[test]
+    fn admin_can_be_renounced() {
+        let mut deps = mock_dependencies();
+        let old = deps.api.addr_make("old");
+        let new = deps.api.addr_make("new");
+        do_instantiate(deps.as_mut(), &old);
+
+        let info = message_info(&old, &[]);
+        let msg = ExecuteMsg::UpdateAdmin {
+            new_admin: Some(new.to_string()),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::UpdateAdmin { new_admin: None };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(
+            admin(deps.as_ref()),
+            AdminResponse {
+                admin: None,
+                pending: None,
+            }
+        );
+
+        // the nomination went with it, and nothing can bring an admin back
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new, &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+}
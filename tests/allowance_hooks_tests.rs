@@ -0,0 +1,195 @@
+#[cfg(all(test, feature = "allowance-hooks"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractInfoResponse, ContractResult, CosmosMsg, DepsMut,
+        Reply, SubMsg, SubMsgResult, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{AllowanceResponse, Cw20Coin, Expiration};
+
+    use cw20_base::allowance_hooks::ALLOWANCE_NOTIFICATION_REPLY_ID;
+    use cw20_base::contract::{execute, instantiate, query, reply};
+    use cw20_base::msg::{
+        AllowanceChange, AllowanceNotificationsResponse, Cw20AllowanceChangedMsg, ExecuteMsg,
+        InstantiateMsg, QueryMsg,
+    };
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, owner: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Approved".to_string(),
+            symbol: "APR".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn notifications(messages: &[SubMsg]) -> Vec<(String, Cw20AllowanceChangedMsg)> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Wrapper {
+            AllowanceChanged(Cw20AllowanceChangedMsg),
+        }
+        messages
+            .iter()
+            .map(|sub| {
+                assert_eq!(sub.id, ALLOWANCE_NOTIFICATION_REPLY_ID);
+                match &sub.msg {
+                    CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr, msg, ..
+                    }) => {
+                        let Wrapper::AllowanceChanged(changed) = from_json(msg).unwrap();
+                        (contract_addr.clone(), changed)
+                    }
+                    other => panic!("unexpected message {other:?}"),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn opted_in_spender_is_notified() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let vault = deps.api.addr_make("vault");
+        do_instantiate(deps.as_mut(), &owner);
+        let contract = vault.to_string();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { contract_addr } if *contract_addr == contract => {
+                let info =
+                    ContractInfoResponse::new(1, Addr::unchecked("creator"), None, false, None);
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&info).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::NoSuchContract {
+                addr: "unknown".to_string(),
+            }),
+        });
+
+        // accounts can't opt in
+        let msg = ExecuteMsg::SetAllowanceNotifications { enabled: true };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&owner, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotAContract {});
+
+        // nothing is sent before the spender opts in
+        let increase = ExecuteMsg::IncreaseAllowance {
+            spender: vault.to_string(),
+            amount: Uint128::new(100),
+            expires: None,
+        };
+        let info = message_info(&owner, &[]);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), increase.clone()).unwrap();
+        assert!(res.messages.is_empty());
+
+        execute(deps.as_mut(), mock_env(), message_info(&vault, &[]), msg).unwrap();
+        let query_msg = QueryMsg::AllowanceNotifications {
+            spender: vault.to_string(),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let res: AllowanceNotificationsResponse = from_json(res).unwrap();
+        assert!(res.enabled);
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), increase).unwrap();
+        assert_eq!(
+            notifications(&res.messages),
+            vec![(
+                vault.to_string(),
+                Cw20AllowanceChangedMsg {
+                    owner: owner.to_string(),
+                    change: AllowanceChange::Increased,
+                    amount: Uint128::new(100),
+                    allowance: Uint128::new(200),
+                    expires: Expiration::Never {},
+                }
+            )]
+        );
+
+        // decreasing below zero reports what was actually taken off
+        let msg = ExecuteMsg::DecreaseAllowance {
+            spender: vault.to_string(),
+            amount: Uint128::new(500),
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            notifications(&res.messages),
+            vec![(
+                vault.to_string(),
+                Cw20AllowanceChangedMsg {
+                    owner: owner.to_string(),
+                    change: AllowanceChange::Decreased,
+                    amount: Uint128::new(200),
+                    allowance: Uint128::zero(),
+                    expires: Expiration::Never {},
+                }
+            )]
+        );
+
+        // a failing spender leaves the change in place
+        let msg = Reply {
+            id: ALLOWANCE_NOTIFICATION_REPLY_ID,
+            payload: Default::default(),
+            gas_used: 0,
+            result: SubMsgResult::Err("vault paused".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn prune_removes_expired_allowances() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let stale = deps.api.addr_make("stale");
+        let live = deps.api.addr_make("live");
+        do_instantiate(deps.as_mut(), &owner);
+
+        let env = mock_env();
+        let info = message_info(&owner, &[]);
+        for (spender, expires) in [
+            (&stale, Expiration::AtHeight(env.block.height + 10)),
+            (&live, Expiration::Never {}),
+        ] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(100),
+                expires: Some(expires),
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let mut later = mock_env();
+        later.block.height += 10;
+        let msg = ExecuteMsg::PruneExpiredAllowances {
+            owner: owner.to_string(),
+            limit: None,
+        };
+        let anyone = message_info(&deps.api.addr_make("anyone"), &[]);
+        let res = execute(deps.as_mut(), later.clone(), anyone, msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "pruned" && attr.value == "1"));
+
+        let allowance = |spender: &Addr| -> AllowanceResponse {
+            let msg = QueryMsg::Allowance {
+                owner: owner.to_string(),
+                spender: spender.to_string(),
+            };
+            from_json(query(deps.as_ref(), later.clone(), msg).unwrap()).unwrap()
+        };
+        assert_eq!(allowance(&stale), AllowanceResponse::default());
+        assert_eq!(allowance(&live).allowance, Uint128::new(100));
+    }
+}
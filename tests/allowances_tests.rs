@@ -7,9 +7,15 @@ mod tests {
     use cw20::{Cw20Coin, TokenInfoResponse};
 
     use cw20_base::contract::{execute, instantiate, query_balance, query_token_info};
-    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::msg::{Cw20ApprovalMsg, Cw20ReceiveFromMsg, ExecuteMsg, InstantiateMsg};
 
     use cw20_base::allowances::query_allowance;
+    #[cfg(feature = "allowance-locks")]
+    use cw20_base::allowances::{query_allowance_lock, ALLOWANCE_UNLOCK_DELAY};
+    #[cfg(feature = "allowance-limits")]
+    use cw20_base::allowances::query_allowance_limit;
+    #[cfg(feature = "allowance-limits")]
+    use cw20_base::state::AllowanceLimit;
     use cw20::AllowanceResponse;
     use cw20::Expiration;
     use cw20_base::ContractError;
@@ -39,6 +45,7 @@ mod tests {
             }],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let info = message_info(&Addr::unchecked("creator"), &[]);
         let env = mock_env();
@@ -278,6 +285,7 @@ mod tests {
             owner: owner.clone(),
             recipient: rcpt.clone(),
             amount: transfer,
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -304,6 +312,7 @@ mod tests {
             owner: owner.clone(),
             recipient: rcpt.clone(),
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -327,12 +336,55 @@ mod tests {
             owner,
             recipient: rcpt,
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert_eq!(err, ContractError::Expired {});
     }
 
+    #[test]
+    fn transfer_from_all_drains_the_allowance() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let rcpt = deps.api.addr_make("addr0003").to_string();
+
+        let start = Uint128::new(999999);
+        do_instantiate(deps.as_mut(), &owner, start);
+
+        let allow1 = Uint128::new(77777);
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone(),
+            amount: allow1,
+            expires: Some(Expiration::AtHeight(mock_env().block.height + 1)),
+        };
+        let info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // pulls exactly what is left, without querying it first
+        let msg = ExecuteMsg::TransferFromAll {
+            owner: owner.clone(),
+            recipient: rcpt.clone(),
+        };
+        let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(res.attributes[0], attr("action", "transfer_from"));
+        assert_eq!(get_balance(deps.as_ref(), rcpt.clone()), allow1);
+        assert_eq!(
+            get_balance(deps.as_ref(), owner.clone()),
+            start.checked_sub(allow1).unwrap()
+        );
+        let allowance = query_allowance(deps.as_ref(), owner, spender).unwrap();
+        assert_eq!(allowance.allowance, Uint128::zero());
+
+        // an expired allowance can't be drained
+        let mut env = mock_env();
+        env.block.height += 1;
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Expired {});
+    }
+
     #[test]
     fn burn_from_respects_limits() {
         let mut deps = mock_dependencies_with_balance(&[]);
@@ -358,6 +410,7 @@ mod tests {
         let msg = ExecuteMsg::BurnFrom {
             owner: owner.clone(),
             amount: transfer,
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -382,6 +435,7 @@ mod tests {
         let msg = ExecuteMsg::BurnFrom {
             owner: owner.clone(),
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -405,12 +459,88 @@ mod tests {
         let msg = ExecuteMsg::BurnFrom {
             owner,
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert_eq!(err, ContractError::Expired {});
     }
 
+    #[test]
+    fn burn_from_batch_respects_limits() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let owner1 = deps.api.addr_make("addr0001").to_string();
+        let owner2 = deps.api.addr_make("addr0002").to_string();
+        let spender = deps.api.addr_make("addr0003").to_string();
+
+        let instantiate_msg = InstantiateMsg {
+            name: "Auto Gen".to_string(),
+            symbol: "AUTO".to_string(),
+            decimals: 3,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: owner1.clone(),
+                    amount: Uint128::new(1000),
+                },
+                Cw20Coin {
+                    address: owner2.clone(),
+                    amount: Uint128::new(1000),
+                },
+            ],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        for owner in [&owner1, &owner2] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.clone(),
+                amount: Uint128::new(300),
+                expires: None,
+            };
+            let info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        let batch = |amount1: u128, amount2: u128| ExecuteMsg::BurnFromBatch {
+            owners: vec![
+                Cw20Coin {
+                    address: owner1.clone(),
+                    amount: Uint128::new(amount1),
+                },
+                Cw20Coin {
+                    address: owner2.clone(),
+                    amount: Uint128::new(amount2),
+                },
+            ],
+        };
+
+        // the whole batch fails if one owner's allowance is not enough
+        let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, batch(301, 100)).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, batch(100, 300)).unwrap();
+        assert_eq!(res.attributes[0], attr("action", "burn_from_batch"));
+        assert_eq!(res.attributes.iter().filter(|a| a.key == "from").count(), 2);
+
+        assert_eq!(
+            get_balance(deps.as_ref(), owner1.clone()),
+            Uint128::new(900)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref(), owner2.clone()),
+            Uint128::new(700)
+        );
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(1600)
+        );
+        let allowance = query_allowance(deps.as_ref(), owner1, spender).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(200));
+    }
+
     #[test]
     fn send_from_respects_limits() {
         let mut deps = mock_dependencies_with_balance(&[]);
@@ -440,6 +570,8 @@ mod tests {
             amount: transfer,
             contract: contract.clone(),
             msg: send_msg.clone(),
+            memo: None,
+            with_owner: false,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -485,6 +617,8 @@ mod tests {
             amount: Uint128::new(33443),
             contract: contract.clone(),
             msg: send_msg.clone(),
+            memo: None,
+            with_owner: false,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
         let env = mock_env();
@@ -510,6 +644,8 @@ mod tests {
             amount: Uint128::new(33443),
             contract,
             msg: send_msg,
+            memo: None,
+            with_owner: false,
         };
         let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
 
@@ -517,6 +653,91 @@ mod tests {
         assert_eq!(err, ContractError::Expired {});
     }
 
+    #[test]
+    fn send_from_with_owner() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let contract = deps.api.addr_make("addr0003").to_string();
+        let send_msg = Binary::from(r#"{"some":123}"#.as_bytes());
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(999999));
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone(),
+            amount: Uint128::new(1000),
+            expires: None,
+        };
+        let info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::SendFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(300),
+            contract: contract.clone(),
+            msg: send_msg.clone(),
+            memo: None,
+            with_owner: true,
+        };
+        let info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // the receiver learns both who sent and whose tokens they were
+        let binary_msg = Cw20ReceiveFromMsg {
+            sender: spender,
+            owner,
+            amount: Uint128::new(300),
+            msg: send_msg,
+        }
+        .into_json_binary()
+        .unwrap();
+        let json = String::from_utf8(binary_msg.to_vec()).unwrap();
+        assert!(json.starts_with(r#"{"receive_from":{"#));
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract,
+                msg: binary_msg,
+                funds: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn increase_allowance_and_send() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let hook = Binary::from(r#"{"stake":{}}"#.as_bytes());
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(999999));
+
+        let msg = ExecuteMsg::IncreaseAllowanceAndSend {
+            spender_contract: spender.clone(),
+            amount: Uint128::new(500),
+            msg: hook.clone(),
+            expires: None,
+        };
+        let info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(500));
+        let binary_msg = Cw20ApprovalMsg {
+            owner,
+            amount: Uint128::new(500),
+            msg: hook,
+        }
+        .into_json_binary()
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: spender,
+                msg: binary_msg,
+                funds: vec![],
+            }))]
+        );
+    }
+
     #[test]
     fn no_past_expiration() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
@@ -633,4 +854,139 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    #[cfg(feature = "allowance-locks")]
+    fn locked_allowances_cannot_be_spent() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let rcpt = deps.api.addr_make("addr0003").to_string();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(999999));
+
+        let owner_info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+        let spender_info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone(),
+            amount: Uint128::new(1000),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::LockAllowances {};
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        let transfer_from = ExecuteMsg::TransferFrom {
+            owner: owner.clone(),
+            recipient: rcpt.clone(),
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            spender_info.clone(),
+            transfer_from.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::AllowancesLocked {});
+        let burn_from = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), spender_info.clone(), burn_from).unwrap_err();
+        assert_eq!(err, ContractError::AllowancesLocked {});
+
+        // the lock only ends after the delay
+        let msg = ExecuteMsg::UnlockAllowances {};
+        execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            spender_info.clone(),
+            transfer_from.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::AllowancesLocked {});
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(ALLOWANCE_UNLOCK_DELAY);
+        let lock = query_allowance_lock(deps.as_ref(), env.clone(), owner.clone()).unwrap();
+        assert!(!lock.locked);
+        execute(deps.as_mut(), env, spender_info, transfer_from).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), rcpt), Uint128::new(10));
+    }
+
+    #[test]
+    #[cfg(feature = "allowance-limits")]
+    fn allowance_limits_cap_spending_per_period() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let rcpt = deps.api.addr_make("addr0003").to_string();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(999999));
+
+        let owner_info = message_info(&Addr::unchecked(owner.as_str()), &[]);
+        let spender_info = message_info(&Addr::unchecked(spender.as_str()), &[]);
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone(),
+            amount: Uint128::new(50000),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        let day = 24 * 60 * 60;
+        let limit = |amount: u128, period: u64| ExecuteMsg::SetAllowanceLimit {
+            spender: spender.clone(),
+            limit: Some(AllowanceLimit {
+                amount: Uint128::new(amount),
+                period,
+            }),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), owner_info.clone(), limit(1000, 0)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAllowanceLimit {});
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), limit(1000, day)).unwrap();
+
+        let transfer_from = |amount: u128| ExecuteMsg::TransferFrom {
+            owner: owner.clone(),
+            recipient: rcpt.clone(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), spender_info.clone(), transfer_from(600)).unwrap();
+        let burn_from = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(300),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), spender_info.clone(), burn_from).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), spender_info.clone(), transfer_from(101))
+            .unwrap_err();
+        assert_eq!(err, ContractError::AllowanceLimitExceeded {});
+
+        let res = query_allowance_limit(deps.as_ref(), mock_env(), owner.clone(), spender.clone())
+            .unwrap();
+        assert_eq!(res.remaining, Some(Uint128::new(100)));
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(49100));
+
+        // the cap refills once the window closed
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(day);
+        execute(deps.as_mut(), env, spender_info.clone(), transfer_from(1000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), rcpt.clone()), Uint128::new(1600));
+
+        // without the cap only the allowance is left
+        let msg = ExecuteMsg::SetAllowanceLimit {
+            spender: spender.clone(),
+            limit: None,
+        };
+        execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+        let res = query_allowance_limit(deps.as_ref(), mock_env(), owner.clone(), spender.clone())
+            .unwrap();
+        assert_eq!(res.limit, None);
+        execute(deps.as_mut(), mock_env(), spender_info, transfer_from(5000)).unwrap();
+    }
 }
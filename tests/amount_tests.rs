@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Uint128;
+
+    use cw20_base::amount::{
+        format_amount, format_amount_rounded, parse_amount, parse_amount_rounded, Rounding,
+    };
+
+    #[test]
+    fn formats_whole_tokens() {
+        assert_eq!(format_amount(Uint128::new(12_500_000), 6), "12.5");
+        assert_eq!(format_amount(Uint128::new(12_000_000), 6), "12");
+        assert_eq!(format_amount(Uint128::new(5), 6), "0.000005");
+        assert_eq!(format_amount(Uint128::zero(), 6), "0");
+        assert_eq!(format_amount(Uint128::new(1234), 0), "1234");
+        assert_eq!(
+            format_amount(Uint128::MAX, 38),
+            "3.40282366920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn formats_rounded() {
+        let amount = Uint128::new(1_234_567);
+        let format = |precision, rounding| format_amount_rounded(amount, 6, precision, rounding);
+        assert_eq!(format(2, Rounding::Down).unwrap(), "1.23");
+        assert_eq!(format(2, Rounding::Up).unwrap(), "1.24");
+        assert_eq!(format(2, Rounding::HalfUp).unwrap(), "1.23");
+        assert_eq!(format(4, Rounding::HalfUp).unwrap(), "1.2346");
+        assert_eq!(format(0, Rounding::HalfUp).unwrap(), "1");
+        assert_eq!(format(8, Rounding::Down).unwrap(), "1.234567");
+
+        let amount = Uint128::new(1_995_000);
+        assert_eq!(
+            format_amount_rounded(amount, 6, 2, Rounding::HalfUp).unwrap(),
+            "2"
+        );
+        format_amount_rounded(Uint128::MAX, 6, 0, Rounding::Up).unwrap();
+    }
+
+    #[test]
+    fn parses_whole_tokens() {
+        assert_eq!(parse_amount("12.5", 6).unwrap(), Uint128::new(12_500_000));
+        assert_eq!(parse_amount("12", 6).unwrap(), Uint128::new(12_000_000));
+        assert_eq!(parse_amount("0.000005", 6).unwrap(), Uint128::new(5));
+        assert_eq!(
+            parse_amount("1.500000000", 6).unwrap(),
+            Uint128::new(1_500_000)
+        );
+        assert_eq!(parse_amount("1234", 0).unwrap(), Uint128::new(1234));
+
+        for invalid in ["", ".5", "12.", "1.2.3", "-1", "1e6", " 1", "1,5"] {
+            parse_amount(invalid, 6).unwrap_err();
+        }
+        // more decimals than the token has
+        parse_amount("0.0000005", 6).unwrap_err();
+        // overflow
+        parse_amount("340282366920938463463374607431768211455", 6).unwrap_err();
+        parse_amount("1", 39).unwrap_err();
+    }
+
+    #[test]
+    fn parses_rounded() {
+        let parse = |amount, rounding| parse_amount_rounded(amount, 6, rounding).unwrap().u128();
+        assert_eq!(parse("1.2345675", Rounding::Down), 1_234_567);
+        assert_eq!(parse("1.2345675", Rounding::Up), 1_234_568);
+        assert_eq!(parse("1.2345675", Rounding::HalfUp), 1_234_568);
+        assert_eq!(parse("1.2345674999", Rounding::HalfUp), 1_234_567);
+        assert_eq!(parse("1.2345670001", Rounding::Up), 1_234_568);
+        assert_eq!(parse("1.2345670000", Rounding::Up), 1_234_567);
+        assert_eq!(
+            parse_amount_rounded("7.9", 0, Rounding::HalfUp)
+                .unwrap()
+                .u128(),
+            8
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for amount in [0, 1, 10, 999_999, 1_000_000, 123_456_789_012] {
+            let amount = Uint128::new(amount);
+            assert_eq!(parse_amount(&format_amount(amount, 6), 6).unwrap(), amount);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_dependencies_with_balance};
+    use cosmwasm_std::{coins, from_json, Addr, BankMsg, CosmosMsg, StdError, Uint128, WasmMsg};
+    use cw20::Cw20ExecuteMsg;
+
+    use cw20_base::msg::{Asset, AssetInfo};
+
+    #[test]
+    fn validate_checks_the_asset() {
+        let deps = mock_dependencies();
+        let token = deps.api.addr_make("token");
+        let info = AssetInfo::Cw20 {
+            contract_addr: token.to_string(),
+        };
+        assert_eq!(info.validate(&deps.api).unwrap(), AssetInfo::cw20(&token));
+        let info = AssetInfo::Cw20 {
+            contract_addr: "not an address".to_string(),
+        };
+        info.validate(&deps.api).unwrap_err();
+        let err = AssetInfo::native("").validate(&deps.api).unwrap_err();
+        assert_eq!(err, StdError::generic_err("Denom must not be empty"));
+    }
+
+    #[test]
+    fn transfer_messages_and_display() {
+        let deps = mock_dependencies();
+        let token = deps.api.addr_make("token");
+        let alice = deps.api.addr_make("alice");
+
+        let atom = Asset::new(AssetInfo::native("uatom"), 7u128);
+        assert_eq!(atom.to_string(), "7uatom");
+        assert_eq!(
+            atom.transfer_msg(&alice).unwrap(),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(7, "uatom"),
+            })
+        );
+
+        let cw20 = Asset::new(AssetInfo::cw20(&token), 9u128);
+        assert_eq!(cw20.to_string(), format!("9{token}"));
+        match cw20.transfer_msg(&alice).unwrap() {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, token.to_string());
+                let msg: Cw20ExecuteMsg = from_json(msg).unwrap();
+                let amount = Uint128::new(9);
+                let recipient = alice.to_string();
+                assert_eq!(msg, Cw20ExecuteMsg::Transfer { recipient, amount });
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+
+    #[test]
+    fn native_balance() {
+        let deps = mock_dependencies_with_balance(&coins(42, "uatom"));
+        let contract = Addr::unchecked("cosmos2contract");
+        let querier = deps.as_ref().querier;
+        let balance = AssetInfo::native("uatom").query_balance(&querier, &contract);
+        assert_eq!(balance.unwrap(), Uint128::new(42));
+        let balance = AssetInfo::native("uosmo").query_balance(&querier, &contract);
+        assert_eq!(balance.unwrap(), Uint128::zero());
+    }
+}
@@ -0,0 +1,231 @@
+#[cfg(all(test, feature = "auto-burn"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{attr, Addr, Binary, DepsMut, Uint128};
+    use cw20::{Cw20Coin, Cw20ReceiveMsg};
+
+    use cw20_base::auto_burn::query_auto_burn_info;
+    use cw20_base::contract::{execute, instantiate, query_balance, query_token_info};
+    use cw20_base::msg::{ExecuteMsg, InstantiateAutoBurn, InstantiateMsg};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, owner: &Addr, basis_points: u16, exempt: Vec<String>) {
+        let msg = InstantiateMsg {
+            name: "Deflation".to_string(),
+            symbol: "DEFL".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(100_000),
+            }],
+            auto_burn: Some(InstantiateAutoBurn {
+                basis_points,
+                exempt,
+            }),
+            admin: Some(MockApi::default().addr_make("admin").to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn transfer_burns_slice() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let rcpt = deps.api.addr_make("rcpt");
+        do_instantiate(deps.as_mut(), &owner, 250, vec![]);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(10_000),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        assert!(res.attributes.contains(&attr("auto_burned", "250")));
+
+        let balance = |addr: &Addr| query_balance(deps.as_ref(), addr.to_string()).unwrap();
+        assert_eq!(balance(&owner).balance, Uint128::new(90_000));
+        assert_eq!(balance(&rcpt).balance, Uint128::new(9_750));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(99_750)
+        );
+
+        let info = query_auto_burn_info(deps.as_ref()).unwrap();
+        assert_eq!(info.basis_points, 250);
+        assert_eq!(info.total_burned, Uint128::new(250));
+    }
+
+    #[test]
+    fn send_reports_received_amount() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let contract = deps.api.addr_make("contract");
+        do_instantiate(deps.as_mut(), &owner, 100, vec![]);
+
+        let msg = ExecuteMsg::Send {
+            contract: contract.to_string(),
+            amount: Uint128::new(1_000),
+            msg: Binary::default(),
+            memo: None,
+            safe: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        let expected = Cw20ReceiveMsg {
+            sender: owner.to_string(),
+            amount: Uint128::new(990),
+            msg: Binary::default(),
+        }
+        .into_cosmos_msg(contract.to_string())
+        .unwrap();
+        assert_eq!(res.messages[0].msg, expected);
+    }
+
+    #[test]
+    fn exempt_addresses_are_not_taxed() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let pool = deps.api.addr_make("pool");
+        do_instantiate(deps.as_mut(), &owner, 500, vec![pool.to_string()]);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: pool.to_string(),
+            amount: Uint128::new(10_000),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        assert!(!res.attributes.iter().any(|a| a.key == "auto_burned"));
+        assert_eq!(
+            query_balance(deps.as_ref(), pool.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(10_000)
+        );
+        assert_eq!(
+            query_auto_burn_info(deps.as_ref()).unwrap().total_burned,
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn rejects_rate_above_100_percent() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Deflation".to_string(),
+            symbol: "DEFL".to_string(),
+            decimals: 6,
+            auto_burn: Some(InstantiateAutoBurn {
+                basis_points: 10_001,
+                exempt: vec![],
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBasisPoints {});
+    }
+
+    #[test]
+    fn admin_updates_exemptions() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let admin = deps.api.addr_make("admin");
+        let pool = deps.api.addr_make("pool");
+        do_instantiate(deps.as_mut(), &owner, 250, vec![]);
+
+        let update = |add: &[&Addr], remove: &[&Addr]| ExecuteMsg::UpdateAutoBurnExempt {
+            add: add.iter().map(|a| a.to_string()).collect(),
+            remove: remove.iter().map(|a| a.to_string()).collect(),
+        };
+        let owner_info = message_info(&owner, &[]);
+        let msg = update(&[&pool], &[]);
+        let err = execute(deps.as_mut(), mock_env(), owner_info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let info = message_info(&admin, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let transfer = ExecuteMsg::Transfer {
+            recipient: pool.to_string(),
+            amount: Uint128::new(10_000),
+            memo: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info.clone(),
+            transfer.clone(),
+        )
+        .unwrap();
+        let balance = query_balance(deps.as_ref(), pool.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(10_000));
+
+        execute(deps.as_mut(), mock_env(), info, update(&[], &[&pool])).unwrap();
+        execute(deps.as_mut(), mock_env(), owner_info, transfer).unwrap();
+        let balance = query_balance(deps.as_ref(), pool.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(19_750));
+    }
+
+    #[cfg(feature = "transfer-limits")]
+    #[test]
+    fn failed_safe_send_is_undone_whole() {
+        use cosmwasm_std::{Reply, SubMsgResult};
+        use cw20_base::contract::reply;
+        use cw20_base::state::TransferLimit;
+        use cw20_base::transfer_limits::query_transfer_quota;
+
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let receiver = deps.api.addr_make("receiver");
+        let msg = InstantiateMsg {
+            name: "Deflation".to_string(),
+            symbol: "DEFL".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(100_000),
+            }],
+            auto_burn: Some(InstantiateAutoBurn {
+                basis_points: 250,
+                exempt: vec![],
+            }),
+            transfer_limit: Some(TransferLimit {
+                amount: Uint128::new(50_000),
+                period: 3_600,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Send {
+            contract: receiver.to_string(),
+            amount: Uint128::new(10_000),
+            msg: Binary::default(),
+            memo: None,
+            safe: true,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        let sub_msg = res.messages.last().unwrap();
+        let reply_msg = Reply {
+            id: sub_msg.id,
+            payload: sub_msg.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Err("rejected".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert!(res.attributes.contains(&attr("auto_reminted", "250")));
+
+        let balance = |addr: &Addr| query_balance(deps.as_ref(), addr.to_string()).unwrap();
+        assert_eq!(balance(&owner).balance, Uint128::new(100_000));
+        assert_eq!(balance(&receiver).balance, Uint128::zero());
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(100_000)
+        );
+        let info = query_auto_burn_info(deps.as_ref()).unwrap();
+        assert_eq!(info.total_burned, Uint128::zero());
+        let quota = query_transfer_quota(deps.as_ref(), mock_env(), owner.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::new(50_000)));
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(all(test, feature = "balance-proofs"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, Addr, Api, Binary, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::balance_proofs::CONTRACT_STORE_PREFIX;
+    use cw20_base::contract::{instantiate, query};
+    use cw20_base::msg::{BalanceProofDataResponse, InstantiateMsg, QueryMsg};
+
+    #[test]
+    fn proof_data_locates_the_balance() {
+        let mut deps = mock_dependencies();
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Bridged".to_string(),
+            symbol: "BRG".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let mut env = mock_env();
+        env.contract.address = deps.api.addr_make("cw20");
+        let proof_data = |address: &Addr| -> BalanceProofDataResponse {
+            let msg = QueryMsg::BalanceProofData {
+                address: address.to_string(),
+            };
+            from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+        };
+        let res = proof_data(&holder);
+        assert_eq!(res.balance, Uint128::new(1_000));
+        assert_eq!(res.height, env.block.height);
+        assert_eq!(res.chain_id, env.block.chain_id);
+        assert_eq!(res.store_name, "wasm");
+        assert_eq!(res.value, Some(Binary::from(b"\"1000\"")));
+
+        // the length-prefixed namespace, then the address
+        let mut key = b"\x00\x07balance".to_vec();
+        key.extend_from_slice(holder.as_bytes());
+        assert_eq!(res.key, Binary::from(key.clone()));
+        let contract = deps
+            .api
+            .addr_canonicalize(env.contract.address.as_str())
+            .unwrap();
+        let mut store_key = vec![CONTRACT_STORE_PREFIX];
+        store_key.extend_from_slice(contract.as_slice());
+        store_key.extend_from_slice(&key);
+        assert_eq!(res.store_key, Binary::from(store_key));
+
+        let res = proof_data(&deps.api.addr_make("stranger"));
+        assert_eq!(res.balance, Uint128::zero());
+        assert_eq!(res.value, None);
+    }
+}
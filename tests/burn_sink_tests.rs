@@ -0,0 +1,92 @@
+#[cfg(all(test, feature = "burn-sink"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{attr, Addr, DepsMut, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::burn_sink::query_burn_sink;
+    use cw20_base::contract::{execute, instantiate, query_balance, query_token_info};
+    use cw20_base::interfaces::query_features;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr, sink: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Sinking".to_string(),
+            symbol: "SNK".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            burn_sink: Some(sink.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn transfers_to_the_sink_burn() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let dead = deps.api.addr_make("dead");
+        do_instantiate(deps.as_mut(), &admin, &holder, &dead);
+        assert!(query_features(deps.as_ref()).unwrap().burn_sink);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: dead.to_string(),
+            amount: Uint128::new(300),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        assert!(res.attributes.contains(&attr("burned", "300")));
+        let balance = query_balance(deps.as_ref(), dead.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::zero());
+        let balance = query_balance(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(700));
+        let info = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(info.total_supply, Uint128::new(700));
+        #[cfg(feature = "burn-stats")]
+        {
+            let burned =
+                cw20_base::burn_stats::query_burned_by(deps.as_ref(), holder.to_string()).unwrap();
+            assert_eq!(burned.burned, Uint128::new(300));
+        }
+    }
+
+    #[test]
+    fn admin_updates_the_sink() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let dead = deps.api.addr_make("dead");
+        do_instantiate(deps.as_mut(), &admin, &holder, &dead);
+
+        let msg = ExecuteMsg::UpdateBurnSink { address: None };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert_eq!(query_burn_sink(deps.as_ref()).unwrap().address, None);
+
+        // without a sink, the dead address is credited like any other
+        let msg = ExecuteMsg::Transfer {
+            recipient: dead.to_string(),
+            amount: Uint128::new(300),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        let balance = query_balance(deps.as_ref(), dead.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(300));
+        let info = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(info.total_supply, Uint128::new(1_000));
+    }
+}
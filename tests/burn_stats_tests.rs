@@ -0,0 +1,56 @@
+#[cfg(all(test, feature = "burn-stats"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{attr, Addr, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::burn_stats::query_burned_by;
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+
+    #[test]
+    fn burns_add_up_per_account() {
+        let mut deps = mock_dependencies();
+        let treasury = deps.api.addr_make("treasury");
+        let buyback = deps.api.addr_make("buyback");
+        let msg = InstantiateMsg {
+            name: "Deflating".to_string(),
+            symbol: "DFL".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: treasury.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let treasury_info = message_info(&treasury, &[]);
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+            memo: Some("buyback #1".to_string()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), treasury_info.clone(), msg).unwrap();
+        assert!(res.attributes.contains(&attr("memo", "buyback #1")));
+
+        // a burn through an allowance counts for the owner of the tokens
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: buyback.to_string(),
+            amount: Uint128::new(50),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), treasury_info, msg).unwrap();
+        let msg = ExecuteMsg::BurnFrom {
+            owner: treasury.to_string(),
+            amount: Uint128::new(50),
+            memo: Some("buyback #2".to_string()),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&buyback, &[]), msg).unwrap();
+
+        let burned = query_burned_by(deps.as_ref(), treasury.to_string()).unwrap();
+        assert_eq!(burned.burned, Uint128::new(150));
+        let burned = query_burned_by(deps.as_ref(), buyback.to_string()).unwrap();
+        assert_eq!(burned.burned, Uint128::zero());
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(all(test, feature = "changelog"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Env, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::changelog::query_changed_balances_since;
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{BalanceChange, ExecuteMsg, InstantiateMsg};
+
+    fn env_at(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    #[test]
+    fn changed_balances_since_height() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+        let msg = InstantiateMsg {
+            name: "Indexed".to_string(),
+            symbol: "IDX".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: alice.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: bob.to_string(),
+                    amount: Uint128::new(100),
+                },
+            ],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env_at(10), info, msg).unwrap();
+
+        let transfer = ExecuteMsg::Transfer {
+            recipient: carol.to_string(),
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_at(20),
+            message_info(&alice, &[]),
+            transfer.clone(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(30),
+            message_info(&alice, &[]),
+            transfer,
+        )
+        .unwrap();
+
+        let changes = query_changed_balances_since(deps.as_ref(), 15, None, None)
+            .unwrap()
+            .changes;
+        // alice and carol only show up with their latest change, bob didn't change
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.height == 30));
+        assert!(changes.contains(&BalanceChange {
+            height: 30,
+            address: carol.to_string(),
+        }));
+
+        // paging from the first entry gives the second one
+        let page = query_changed_balances_since(deps.as_ref(), 15, None, Some(1))
+            .unwrap()
+            .changes;
+        let rest = query_changed_balances_since(deps.as_ref(), 15, Some(page[0].clone()), None)
+            .unwrap()
+            .changes;
+        assert_eq!(rest, changes[1..]);
+
+        let all = query_changed_balances_since(deps.as_ref(), 0, None, None)
+            .unwrap()
+            .changes;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].address, bob.to_string());
+        assert_eq!(all[0].height, 10);
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{StdError, Uint128};
+    use cw20::{Cw20Coin, Cw20CoinVerified};
+
+    use cw20_base::coin::{Cw20CoinExt, Cw20CoinVerifiedExt};
+
+    #[test]
+    fn verify_validates_the_address() {
+        let deps = mock_dependencies();
+        let token = deps.api.addr_make("token");
+        let coin = Cw20Coin {
+            address: token.to_string(),
+            amount: Uint128::new(5),
+        };
+        let verified = coin.verify(&deps.api).unwrap();
+        assert_eq!(verified.address, token);
+        assert_eq!(verified.amount, Uint128::new(5));
+
+        let coin = Cw20Coin {
+            address: "not an address".to_string(),
+            amount: Uint128::new(5),
+        };
+        coin.verify(&deps.api).unwrap_err();
+    }
+
+    #[test]
+    fn arithmetic_needs_the_same_token() {
+        let deps = mock_dependencies();
+        let coin = |name: &str, amount: u128| Cw20CoinVerified {
+            address: deps.api.addr_make(name),
+            amount: Uint128::new(amount),
+        };
+
+        assert_eq!(
+            coin("a", 5).checked_add(&coin("a", 7)).unwrap(),
+            coin("a", 12)
+        );
+        assert_eq!(
+            coin("a", 7).checked_sub(&coin("a", 5)).unwrap(),
+            coin("a", 2)
+        );
+
+        let err = coin("a", 5).checked_add(&coin("b", 7)).unwrap_err();
+        assert!(matches!(err, StdError::GenericErr { .. }));
+        coin("a", 5).checked_sub(&coin("b", 1)).unwrap_err();
+        let err = coin("a", 5).checked_sub(&coin("a", 7)).unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+        coin("a", u128::MAX).checked_add(&coin("a", 1)).unwrap_err();
+    }
+
+    #[test]
+    fn formats_with_decimals() {
+        let deps = mock_dependencies();
+        let token = deps.api.addr_make("token");
+        let coin = Cw20CoinVerified {
+            address: token.clone(),
+            amount: Uint128::new(12_500_000),
+        };
+        assert_eq!(coin.to_string_with_decimals(6), format!("12.5 {token}"));
+        assert_eq!(coin.to_string_with_decimals(0), format!("12500000 {token}"));
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(all(test, feature = "compliance"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult, DepsMut, SystemResult, Uint128, WasmQuery,
+    };
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{CanTransferResponse, ComplianceQueryMsg, ExecuteMsg, InstantiateMsg};
+    use cw20_base::restrictions::{query_detect_transfer_restriction, RESTRICTED_BY_CONTRACT};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr, oracle: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Restricted".to_string(),
+            symbol: "RST".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            transfer_restriction_contract: Some(oracle.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn transfer(recipient: &Addr) -> ExecuteMsg {
+        ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(100),
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn oracle_decides_transfers() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let oracle = deps.api.addr_make("oracle");
+        let verified = deps.api.addr_make("verified");
+        let unverified = deps.api.addr_make("unverified");
+        do_instantiate(deps.as_mut(), &admin, &holder, &oracle);
+
+        let allowed = verified.to_string();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let ComplianceQueryMsg::CanTransfer { to, .. } = from_json(msg).unwrap();
+                let res = if to == allowed {
+                    CanTransferResponse {
+                        allowed: true,
+                        reason: None,
+                    }
+                } else {
+                    CanTransferResponse {
+                        allowed: false,
+                        reason: Some("recipient not verified".to_string()),
+                    }
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), transfer(&verified)).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&unverified),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TransferRestricted {
+                reason: "recipient not verified".to_string()
+            }
+        );
+        let balance = query_balance(deps.as_ref(), unverified.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::zero());
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            holder.to_string(),
+            unverified.to_string(),
+            Uint128::new(100),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, RESTRICTED_BY_CONTRACT);
+        assert_eq!(res.reason, "Transfer restricted: recipient not verified");
+
+        // the admin can lift the restriction
+        let msg = ExecuteMsg::UpdateTransferRestrictionContract { contract: None };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), info, transfer(&unverified)).unwrap();
+    }
+}
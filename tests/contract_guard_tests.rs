@@ -0,0 +1,99 @@
+#[cfg(all(test, feature = "contract-guard"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, ContractInfoResponse, ContractResult, DepsMut, SystemError,
+        SystemResult, Uint128, WasmQuery,
+    };
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::interfaces::query_features;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::restrictions::{
+        query_detect_transfer_restriction, CONTRACT_RECIPIENT, TRANSFER_OK,
+    };
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Guarded".to_string(),
+            symbol: "GRD".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            reject_contract_transfers: true,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn transfer(recipient: &Addr) -> ExecuteMsg {
+        ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(10),
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn contracts_only_receive_through_send() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let friend = deps.api.addr_make("friend");
+        let pool = deps.api.addr_make("pool");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+        assert!(
+            query_features(deps.as_ref())
+                .unwrap()
+                .reject_contract_transfers
+        );
+        let contract = pool.to_string();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { contract_addr } if *contract_addr == contract => {
+                let info =
+                    ContractInfoResponse::new(1, Addr::unchecked("creator"), None, false, None);
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&info).unwrap()))
+            }
+            WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(Binary::default())),
+            _ => SystemResult::Err(SystemError::NoSuchContract {
+                addr: "unknown".to_string(),
+            }),
+        });
+
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), transfer(&friend)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), transfer(&pool)).unwrap_err();
+        assert_eq!(err, ContractError::ContractRecipient {});
+        let detect = |send| {
+            let (from, to) = (holder.to_string(), pool.to_string());
+            let amount = Uint128::new(10);
+            query_detect_transfer_restriction(deps.as_ref(), mock_env(), from, to, amount, send)
+                .unwrap()
+                .code
+        };
+        assert_eq!(detect(false), CONTRACT_RECIPIENT);
+        assert_eq!(detect(true), TRANSFER_OK);
+
+        let send = ExecuteMsg::Send {
+            contract: pool.to_string(),
+            amount: Uint128::new(10),
+            msg: Binary::default(),
+            memo: None,
+            safe: false,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), send).unwrap();
+
+        // only the admin lifts the guard
+        let msg = ExecuteMsg::UpdateRejectContractTransfers { reject: false };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), info, transfer(&pool)).unwrap();
+    }
+}
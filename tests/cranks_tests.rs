@@ -0,0 +1,128 @@
+#[cfg(all(test, feature = "cranks"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{Addr, DepsMut, Env};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::cranks::{query_crank_status, query_keeper_runs};
+    use cw20_base::msg::{Crank, CrankParams, ExecuteMsg, InstantiateMsg};
+    use cw20_base::ContractError;
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.contract.address = MockApi::default().addr_make("cw20");
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Cranked".to_string(),
+            symbol: "CRNK".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, env_at(0), info, msg).unwrap();
+    }
+
+    #[cfg(feature = "scheduled")]
+    #[test]
+    fn keepers_run_cranks_at_the_set_pace() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let keeper = deps.api.addr_make("keeper");
+        let stranger = deps.api.addr_make("stranger");
+        do_instantiate(deps.as_mut(), &admin);
+        let crank = ExecuteMsg::ExecuteMatured { limit: None };
+
+        // open to anyone until the admin restricts it
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&stranger, &[]),
+            crank.clone(),
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::UpdateCrankParams {
+            crank: Crank::ExecuteMatured,
+            params: Some(CrankParams {
+                min_interval: 60,
+                keepers: Some(vec![keeper.to_string()]),
+            }),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&keeper, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env_at(0), message_info(&admin, &[]), msg).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env_at(100),
+            message_info(&stranger, &[]),
+            crank.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotAKeeper {});
+        execute(
+            deps.as_mut(),
+            env_at(100),
+            message_info(&keeper, &[]),
+            crank.clone(),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(130),
+            message_info(&keeper, &[]),
+            crank.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CrankRateLimited {
+                next_run: env_at(160).block.time
+            }
+        );
+        execute(
+            deps.as_mut(),
+            env_at(160),
+            message_info(&keeper, &[]),
+            crank.clone(),
+        )
+        .unwrap();
+
+        let status = query_crank_status(deps.as_ref(), Crank::ExecuteMatured).unwrap();
+        assert_eq!(status.last_run, Some(env_at(160).block.time));
+        assert_eq!(status.next_run, Some(env_at(220).block.time));
+        let runs = query_keeper_runs(deps.as_ref(), keeper.to_string(), Crank::ExecuteMatured)
+            .unwrap()
+            .runs;
+        assert_eq!(runs, 2);
+        let runs = query_keeper_runs(deps.as_ref(), stranger.to_string(), Crank::ExecuteMatured)
+            .unwrap()
+            .runs;
+        assert_eq!(runs, 1);
+
+        // lifting the restriction opens it up again
+        let msg = ExecuteMsg::UpdateCrankParams {
+            crank: Crank::ExecuteMatured,
+            params: None,
+        };
+        execute(deps.as_mut(), env_at(160), message_info(&admin, &[]), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(161),
+            message_info(&stranger, &[]),
+            crank,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,361 @@
+#[cfg(all(test, feature = "enumerable"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, OwnedDeps, Timestamp, Uint128};
+    use cw20::{Cw20Coin, Expiration};
+
+    use cw20_base::contract::{execute, instantiate, migrate};
+    use cw20_base::enumerable::{
+        query_account_count, query_accounts_page, query_all_accounts, query_allowance_count,
+        query_allowances_expiring_before, query_enumeration_limit, query_owner_allowances_page,
+        query_spender_allowance_count, query_spender_allowances, query_spender_allowances_page,
+        query_verify_invariants,
+    };
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+    use cw20_base::state::{PendingMigration, BALANCES, PENDING_MIGRATION};
+
+    #[test]
+    fn allowances_filtered_by_expiration() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let msg = InstantiateMsg {
+            name: "Allowed".to_string(),
+            symbol: "ALW".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let now = mock_env().block;
+        let grants = [
+            ("soon", Expiration::AtTime(now.time.plus_seconds(60))),
+            ("later", Expiration::AtTime(now.time.plus_seconds(3_600))),
+            ("height", Expiration::AtHeight(now.height + 10)),
+            ("never", Expiration::Never {}),
+        ];
+        let mut spenders = vec![];
+        for (name, expires) in grants {
+            let spender = deps.api.addr_make(name);
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(100),
+                expires: Some(expires),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+            spenders.push(spender);
+        }
+
+        let expiring = |before: Expiration, start_after: Option<String>, limit: Option<u32>| {
+            query_allowances_expiring_before(
+                deps.as_ref(),
+                owner.to_string(),
+                before,
+                start_after,
+                limit,
+            )
+            .unwrap()
+            .allowances
+            .into_iter()
+            .map(|info| info.spender)
+            .collect::<Vec<_>>()
+        };
+
+        let in_ten_minutes = Expiration::AtTime(now.time.plus_seconds(600));
+        assert_eq!(
+            expiring(in_ten_minutes, None, None),
+            [spenders[0].to_string()]
+        );
+        let in_a_day = Expiration::AtTime(now.time.plus_seconds(86_400));
+        let mut both = vec![spenders[0].to_string(), spenders[1].to_string()];
+        both.sort();
+        assert_eq!(expiring(in_a_day, None, None), both);
+        assert_eq!(
+            expiring(in_a_day, Some(both[0].clone()), Some(1)),
+            [both[1].clone()]
+        );
+        assert!(expiring(Expiration::AtTime(Timestamp::from_seconds(0)), None, None).is_empty());
+
+        let height = Expiration::AtHeight(now.height + 100);
+        assert_eq!(expiring(height, None, None), [spenders[2].to_string()]);
+        assert_eq!(
+            expiring(Expiration::Never {}, None, None),
+            [spenders[3].to_string()]
+        );
+    }
+
+    #[test]
+    fn invariants_verified_page_by_page() {
+        let mut deps = mock_dependencies();
+        let holders: Vec<Addr> = ["a", "b", "c"]
+            .iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+        let msg = InstantiateMsg {
+            name: "Audited".to_string(),
+            symbol: "AUD".to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(100),
+                })
+                .collect(),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let first = query_verify_invariants(deps.as_ref(), None, None, Some(2)).unwrap();
+        assert_eq!(first.running_total, Uint128::new(200));
+        assert_eq!(first.passed, None);
+        let last = query_verify_invariants(
+            deps.as_ref(),
+            first.last_address,
+            Some(first.running_total),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(last.running_total, Uint128::new(300));
+        assert_eq!(last.total_supply, Uint128::new(300));
+        assert_eq!(last.passed, Some(true));
+
+        // a balance changed behind the supply's back is caught
+        let height = mock_env().block.height;
+        BALANCES
+            .save(
+                deps.as_mut().storage,
+                &holders[0],
+                &Uint128::new(101),
+                height,
+            )
+            .unwrap();
+        let res = query_verify_invariants(deps.as_ref(), None, None, None).unwrap();
+        assert_eq!(res.passed, Some(false));
+    }
+
+    #[test]
+    fn enumeration_limit_is_configurable() {
+        let mut deps = mock_dependencies();
+        let accounts: Vec<_> = (0..8)
+            .map(|i| deps.api.addr_make(&format!("holder{i}")))
+            .collect();
+        let mut msg = InstantiateMsg {
+            name: "Listed".to_string(),
+            symbol: "LST".to_string(),
+            decimals: 6,
+            initial_balances: accounts
+                .iter()
+                .map(|addr| Cw20Coin {
+                    address: addr.to_string(),
+                    amount: Uint128::new(10),
+                })
+                .collect(),
+            enumeration_limit: Some(0),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        msg.enumeration_limit = Some(1_001);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        msg.enumeration_limit = Some(3);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(query_enumeration_limit(deps.as_ref()).unwrap().max_limit, 3);
+        assert_eq!(query_account_count(deps.as_ref()).unwrap().count, 8);
+        let mut listed = vec![];
+        let mut start_after = None;
+        loop {
+            let page = query_all_accounts(deps.as_ref(), start_after, Some(100))
+                .unwrap()
+                .accounts;
+            assert!(page.len() <= 3);
+            match page.last() {
+                Some(last) => start_after = Some(last.clone()),
+                None => break,
+            }
+            listed.extend(page);
+        }
+        let mut expected: Vec<_> = accounts.iter().map(|addr| addr.to_string()).collect();
+        expected.sort();
+        assert_eq!(listed, expected);
+
+        // raised by a migration
+        cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "1.1.0").unwrap();
+        let msg = MigrateMsg {
+            enumeration_limit: Some(1_000),
+            ..MigrateMsg::default()
+        };
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(
+            query_enumeration_limit(deps.as_ref()).unwrap().max_limit,
+            1_000
+        );
+        let page = query_all_accounts(deps.as_ref(), None, Some(1_000)).unwrap();
+        assert_eq!(page.accounts.len(), 8);
+    }
+
+    #[test]
+    fn allowances_counted_and_paged_by_spender() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Allowed".to_string(),
+            symbol: "ALW".to_string(),
+            decimals: 6,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // owners of different lengths, which the owner map doesn't keep in byte order
+        let spender = deps.api.addr_make("spender");
+        let owners = ["b", "aa", "ccc"];
+        for owner in owners {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(5),
+                expires: None,
+            };
+            let info = message_info(&Addr::unchecked(owner), &[]);
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+        let owner = deps.api.addr_make("owner");
+        for spender in [&spender, &deps.api.addr_make("other")] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(5),
+                expires: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        }
+        let count = query_spender_allowance_count(deps.as_ref(), spender.to_string()).unwrap();
+        assert_eq!(count.count, 4);
+        let count = query_allowance_count(deps.as_ref(), owner.to_string()).unwrap();
+        assert_eq!(count.count, 2);
+
+        let walk = |deps: &OwnedDeps<_, _, _>| {
+            let mut owners = vec![];
+            let mut start_after = None;
+            loop {
+                let page = query_spender_allowances(
+                    deps.as_ref(),
+                    spender.to_string(),
+                    start_after,
+                    Some(1),
+                )
+                .unwrap()
+                .allowances;
+                match page.last() {
+                    Some(last) => start_after = Some(last.owner.clone()),
+                    None => break,
+                }
+                owners.extend(page.into_iter().map(|allow| allow.owner));
+            }
+            owners
+        };
+        let expected = vec!["aa", "b", "ccc", owner.as_str()];
+        assert_eq!(walk(&deps), expected);
+
+        // the same pages from the owner map while the spender index is being built
+        let pending = PendingMigration {
+            steps: vec!["index_allowances_by_spender".to_string()],
+            cursor: None,
+        };
+        PENDING_MIGRATION.save(&mut deps.storage, &pending).unwrap();
+        assert_eq!(walk(&deps), expected);
+        let count = query_spender_allowance_count(deps.as_ref(), spender.to_string()).unwrap();
+        assert_eq!(count.count, 4);
+    }
+
+    #[test]
+    fn pages_resume_from_cursor() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let msg = InstantiateMsg {
+            name: "Paged".to_string(),
+            symbol: "PGD".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let spender = deps.api.addr_make("spender");
+        let mut spenders = vec![];
+        for i in 0..5 {
+            let recipient = deps.api.addr_make(&format!("spender{i}"));
+            for (owner, spender) in [(&owner, &recipient), (&recipient, &spender)] {
+                let msg = ExecuteMsg::IncreaseAllowance {
+                    spender: spender.to_string(),
+                    amount: Uint128::new(5),
+                    expires: None,
+                };
+                execute(deps.as_mut(), mock_env(), message_info(owner, &[]), msg).unwrap();
+            }
+            let msg = ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(10),
+                memo: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+            spenders.push(recipient.to_string());
+        }
+        spenders.sort();
+
+        let page =
+            query_owner_allowances_page(deps.as_ref(), owner.to_string(), None, Some(2)).unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert!(page.is_truncated);
+        let mut listed: Vec<_> = page.items.into_iter().map(|allow| allow.spender).collect();
+        let mut cursor = page.next_cursor;
+        while let Some(next) = cursor {
+            let page =
+                query_owner_allowances_page(deps.as_ref(), owner.to_string(), Some(next), Some(2))
+                    .unwrap();
+            assert_eq!(page.is_truncated, page.next_cursor.is_some());
+            listed.extend(page.items.into_iter().map(|allow| allow.spender));
+            cursor = page.next_cursor;
+        }
+        assert_eq!(listed, spenders);
+
+        // a page ending exactly on the last entry isn't truncated
+        let page = query_spender_allowances_page(deps.as_ref(), spender.to_string(), None, Some(5))
+            .unwrap();
+        assert_eq!(page.items.len(), 5);
+        assert!(!page.is_truncated);
+        assert_eq!(page.next_cursor, None);
+
+        // the same cursors work while the spender index is being built
+        let pending = PendingMigration {
+            steps: vec!["index_allowances_by_spender".to_string()],
+            cursor: None,
+        };
+        PENDING_MIGRATION.save(&mut deps.storage, &pending).unwrap();
+        let page = query_spender_allowances_page(deps.as_ref(), spender.to_string(), None, Some(3))
+            .unwrap();
+        let next = page.next_cursor.unwrap();
+        let rest =
+            query_spender_allowances_page(deps.as_ref(), spender.to_string(), Some(next), None)
+                .unwrap();
+        let owners: Vec<_> = page
+            .items
+            .iter()
+            .chain(&rest.items)
+            .map(|a| &a.owner)
+            .collect();
+        assert_eq!(owners, spenders.iter().collect::<Vec<_>>());
+
+        let page = query_accounts_page(deps.as_ref(), None, Some(4)).unwrap();
+        let rest = query_accounts_page(deps.as_ref(), page.next_cursor, Some(4)).unwrap();
+        assert_eq!(page.items.len() + rest.items.len(), 6);
+        assert!(!rest.is_truncated);
+    }
+}
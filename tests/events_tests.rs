@@ -0,0 +1,148 @@
+#[cfg(all(test, feature = "events"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Event, Uint128};
+    use cw20::{Cw20Coin, Expiration, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+
+    fn setup(deps: DepsMut, owner: &Addr, minter: &Addr, events_only: bool) {
+        let msg = InstantiateMsg {
+            name: "Evented".to_string(),
+            symbol: "EVT".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            events_only,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn handlers_emit_typed_events() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let spender = deps.api.addr_make("spender");
+        let minter = deps.api.addr_make("minter");
+        setup(deps.as_mut(), &owner, &minter, false);
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(300),
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        let allowance = Event::new("cw20-allowance")
+            .add_attribute("owner", &owner)
+            .add_attribute("spender", &spender)
+            .add_attribute("allowance", "300")
+            .add_attribute("expires", Expiration::Never {}.to_string());
+        assert_eq!(res.events, vec![allowance]);
+
+        // a spender's transfer reports the move and what is left of the allowance
+        let msg = ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: spender.to_string(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&spender, &[]), msg).unwrap();
+        let transfer = Event::new("cw20-transfer")
+            .add_attribute("seq", "1")
+            .add_attribute("from", &owner)
+            .add_attribute("to", &spender)
+            .add_attribute("amount", "100");
+        assert_eq!(res.events[0], transfer);
+        assert_eq!(res.events[1].ty, "cw20-allowance");
+        assert_eq!(res.events[1].attributes[2].value, "200");
+        // the legacy attributes are still there
+        assert_eq!(res.attributes[0].value, "transfer_from");
+
+        let msg = ExecuteMsg::Mint {
+            recipient: owner.to_string(),
+            amount: Uint128::new(50),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        let mint = Event::new("cw20-mint")
+            .add_attribute("seq", "2")
+            .add_attribute("to", &owner)
+            .add_attribute("amount", "50");
+        assert_eq!(res.events, vec![mint]);
+
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(20),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        let burn = Event::new("cw20-burn")
+            .add_attribute("seq", "3")
+            .add_attribute("from", &owner)
+            .add_attribute("amount", "20");
+        assert_eq!(res.events, vec![burn]);
+    }
+
+    #[test]
+    fn balance_changes_are_numbered_in_order() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let minter = deps.api.addr_make("minter");
+        setup(deps.as_mut(), &owner, &minter, false);
+
+        let holders: Vec<Cw20Coin> = (0..3)
+            .map(|i| Cw20Coin {
+                address: deps.api.addr_make(&format!("holder{i}")).to_string(),
+                amount: Uint128::new(10),
+            })
+            .collect();
+        let msg = ExecuteMsg::MintBatch {
+            recipients: holders,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        let sequence: Vec<_> = res
+            .events
+            .iter()
+            .map(|e| e.attributes[0].value.as_str())
+            .collect();
+        assert_eq!(sequence, ["1", "2", "3"]);
+
+        // the next action carries on from there
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        assert_eq!(res.events[0].attributes[0].value, "4");
+    }
+
+    #[test]
+    fn events_only_drops_the_attributes() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let recipient = deps.api.addr_make("recipient");
+        let minter = deps.api.addr_make("minter");
+        setup(deps.as_mut(), &owner, &minter, true);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        assert!(res.attributes.is_empty());
+        assert_eq!(res.events[0].ty, "cw20-transfer");
+
+        // handlers without typed events keep their attributes
+        let msg = ExecuteMsg::UpdateMinter { new_minter: None };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        assert_eq!(res.attributes[0].value, "update_minter");
+    }
+}
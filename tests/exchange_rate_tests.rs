@@ -0,0 +1,137 @@
+#[cfg(all(test, feature = "exchange-rate"))]
+mod tests {
+    use std::str::FromStr;
+
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Decimal, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate, query_balance, query_token_info};
+    use cw20_base::exchange_rate::{query_exchange_rate, query_shares};
+    use cw20_base::msg::{ExecuteMsg, InstantiateExchangeRate, InstantiateMsg};
+    use cw20_base::supply::query_total_supply;
+    use cw20_base::ContractError;
+
+    #[test]
+    fn queries_report_underlying_amounts() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let strategy = deps.api.addr_make("strategy");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Staked Token".to_string(),
+            symbol: "stTKN".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            exchange_rate: Some(InstantiateExchangeRate {
+                rate: Decimal::one(),
+                updater: Some(strategy.to_string()),
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // yield accrues
+        let rate = Decimal::from_str("1.05").unwrap();
+        let msg = ExecuteMsg::UpdateExchangeRate { rate };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&strategy, &[]), msg).unwrap();
+        assert_eq!(query_exchange_rate(deps.as_ref()).unwrap().rate, rate);
+
+        let balance = query_balance(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(1_050));
+        let shares = query_shares(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(shares.balance, Uint128::new(1_000));
+        let supply = query_total_supply(deps.as_ref()).unwrap();
+        assert_eq!(supply.total_supply, Uint128::new(1_050));
+        let info = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(info.total_supply, Uint128::new(1_050));
+
+        // the admin can update it as well, but not to zero
+        let msg = ExecuteMsg::UpdateExchangeRate {
+            rate: Decimal::zero(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidExchangeRate {});
+        let msg = ExecuteMsg::UpdateExchangeRate {
+            rate: Decimal::percent(98),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let balance = query_balance(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(980));
+    }
+
+    #[test]
+    #[cfg(feature = "snapshots")]
+    fn history_is_valued_at_the_rate_of_its_height() {
+        use cosmwasm_std::from_json;
+        use cw20::BalanceResponse;
+        use cw20_base::contract::query;
+        use cw20_base::msg::{QueryMsg, TotalSupplyResponse};
+
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Staked Token".to_string(),
+            symbol: "stTKN".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            exchange_rate: Some(InstantiateExchangeRate {
+                rate: Decimal::one(),
+                updater: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::RecordSnapshot {
+            label: "before".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.height += 100;
+        let msg = ExecuteMsg::UpdateExchangeRate {
+            rate: Decimal::from_str("1.05").unwrap(),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let supply_at = |height| {
+            let msg = QueryMsg::TotalSupplyAt { height };
+            let res: TotalSupplyResponse =
+                from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            res.total_supply
+        };
+        assert_eq!(supply_at(mock_env().block.height + 1), Uint128::new(1_000));
+        assert_eq!(supply_at(env.block.height + 1), Uint128::new(1_050));
+        let msg = QueryMsg::BalanceAtSnapshot {
+            address: holder.to_string(),
+            label: "before".to_string(),
+        };
+        let res: BalanceResponse =
+            from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.balance, Uint128::new(1_000));
+        // and today's supply matches today's balances
+        let supply = query_total_supply(deps.as_ref()).unwrap();
+        let balance = query_balance(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(supply.total_supply, balance.balance);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::{cw_serde, QueryResponses};
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, Addr, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin};
+
+    use cw20_base::contract::{execute, instantiate, query};
+    use cw20_base::msg::{self as base, InstantiateMsg};
+
+    #[cw_serde]
+    pub enum StakingExecuteMsg {
+        Bond {},
+        // shadows the one of cw20-base
+        Burn { amount: Uint128 },
+    }
+
+    #[cw_serde]
+    #[derive(QueryResponses)]
+    pub enum StakingQueryMsg {
+        #[returns(Uint128)]
+        Bonded {},
+    }
+
+    cw20_base::cw20_extend!(ExecuteMsg(StakingExecuteMsg), QueryMsg(StakingQueryMsg));
+
+    #[test]
+    fn messages_of_both_enums_parse() {
+        let msg: ExecuteMsg = from_json(r#"{"bond":{}}"#).unwrap();
+        assert_eq!(msg, ExecuteMsg::Custom(StakingExecuteMsg::Bond {}));
+        let msg: ExecuteMsg = from_json(r#"{"burn":{"amount":"5"}}"#).unwrap();
+        let amount = Uint128::new(5);
+        assert_eq!(msg, ExecuteMsg::Custom(StakingExecuteMsg::Burn { amount }));
+        let msg: QueryMsg = from_json(r#"{"bonded":{}}"#).unwrap();
+        assert_eq!(msg, QueryMsg::Custom(StakingQueryMsg::Bonded {}));
+        from_json::<ExecuteMsg>(r#"{"unbond":{}}"#).unwrap_err();
+
+        let schemas = QueryMsg::response_schemas().unwrap();
+        assert!(schemas.contains_key("bonded"));
+        assert!(schemas.contains_key("balance"));
+    }
+
+    #[test]
+    fn cw20_messages_route_to_the_base_handlers() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let msg = InstantiateMsg {
+            name: "Embedded".to_string(),
+            symbol: "EMB".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: alice.to_string(),
+                amount: Uint128::new(100),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let json = format!(r#"{{"transfer":{{"recipient":"{bob}","amount":"40"}}}}"#);
+        let msg = match from_json::<ExecuteMsg>(json).unwrap() {
+            ExecuteMsg::Cw20(msg) => msg,
+            ExecuteMsg::Custom(msg) => panic!("parsed as {msg:?}"),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap();
+
+        let json = format!(r#"{{"balance":{{"address":"{bob}"}}}}"#);
+        let msg = match from_json::<QueryMsg>(json).unwrap() {
+            QueryMsg::Cw20(msg) => msg,
+            QueryMsg::Custom(msg) => panic!("parsed as {msg:?}"),
+        };
+        assert!(matches!(msg, base::QueryMsg::Balance { .. }));
+        let res: BalanceResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.balance, Uint128::new(40));
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(all(test, feature = "extended-metadata"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::extended_metadata::query_metadata;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MetadataEntry};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Described".to_string(),
+            symbol: "DSC".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn set(key: &str, value: &str) -> ExecuteMsg {
+        ExecuteMsg::SetMetadata {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn entry(key: &str, value: &str) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn admin_manages_metadata() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        do_instantiate(deps.as_mut(), &admin);
+
+        let info = message_info(&admin, &[]);
+        for (key, value) in [
+            ("website", "https://example.com"),
+            ("coingecko_id", "described"),
+            ("tags", "defi,stable"),
+        ] {
+            execute(deps.as_mut(), mock_env(), info.clone(), set(key, value)).unwrap();
+        }
+        execute(deps.as_mut(), mock_env(), info.clone(), set("tags", "defi")).unwrap();
+
+        let page = query_metadata(deps.as_ref(), None, Some(2)).unwrap();
+        assert_eq!(
+            page.entries,
+            vec![entry("coingecko_id", "described"), entry("tags", "defi")]
+        );
+        let page = query_metadata(deps.as_ref(), Some("tags".to_string()), None).unwrap();
+        assert_eq!(page.entries, vec![entry("website", "https://example.com")]);
+
+        let msg = ExecuteMsg::RemoveMetadata {
+            key: "tags".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let all = query_metadata(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            all.entries,
+            vec![
+                entry("coingecko_id", "described"),
+                entry("website", "https://example.com")
+            ]
+        );
+    }
+
+    #[test]
+    fn only_admin_within_limits() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        do_instantiate(deps.as_mut(), &admin);
+
+        let info = message_info(&deps.api.addr_make("someone"), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, set("website", "x")).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), set("", "x")).unwrap_err();
+        assert_eq!(err, ContractError::InvalidMetadataKey { max: 64 });
+        let key = "k".repeat(65);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), set(&key, "x")).unwrap_err();
+        assert_eq!(err, ContractError::InvalidMetadataKey { max: 64 });
+        let value = "v".repeat(1025);
+        let err = execute(deps.as_mut(), mock_env(), info, set("website", &value)).unwrap_err();
+        assert_eq!(err, ContractError::MetadataValueTooLong { max: 1024 });
+
+        assert!(query_metadata(deps.as_ref(), None, None)
+            .unwrap()
+            .entries
+            .is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(all(test, feature = "faucet"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::MinterResponse;
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::faucet::query_faucet_quota;
+    use cw20_base::interfaces::query_features;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::state::FaucetConfig;
+    use cw20_base::ContractError;
+
+    const HOUR: u64 = 60 * 60;
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, minter: &Addr, cap: Option<u128>) {
+        let msg = InstantiateMsg {
+            name: "Testnet".to_string(),
+            symbol: "TEST".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: cap.map(Uint128::new),
+            }),
+            admin: Some(MockApi::default().addr_make("admin").to_string()),
+            faucet: Some(FaucetConfig {
+                amount: Uint128::new(100),
+                period: HOUR,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn anyone_mints_once_per_window() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        do_instantiate(deps.as_mut(), &minter, None);
+        assert!(query_features(deps.as_ref()).unwrap().faucet);
+
+        let info = message_info(&alice, &[]);
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            info.clone(),
+            ExecuteMsg::FaucetMint {},
+        )
+        .unwrap();
+        let balance = query_balance(deps.as_ref(), alice.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(100));
+
+        let err = execute(
+            deps.as_mut(),
+            env_at(10),
+            info.clone(),
+            ExecuteMsg::FaucetMint {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::FaucetLimitReached {});
+        let quota = query_faucet_quota(deps.as_ref(), env_at(10), alice.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::zero()));
+        assert_eq!(quota.window_end, Some(env_at(HOUR).block.time));
+
+        // other accounts have their own window
+        let quota = query_faucet_quota(deps.as_ref(), env_at(10), bob.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::new(100)));
+
+        // a new window opens once the first one closed
+        execute(deps.as_mut(), env_at(HOUR), info, ExecuteMsg::FaucetMint {}).unwrap();
+        let balance = query_balance(deps.as_ref(), alice.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(200));
+    }
+
+    #[test]
+    fn faucet_respects_the_cap() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        do_instantiate(deps.as_mut(), &minter, Some(150));
+
+        let msg = ExecuteMsg::FaucetMint {};
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&alice, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&bob, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+
+        // nothing left once minting is renounced
+        let info = message_info(&minter, &[]);
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            info,
+            ExecuteMsg::RenounceMinting {},
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), env_at(HOUR), message_info(&alice, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::MintingRenounced {});
+    }
+
+    #[test]
+    fn minter_updates_the_faucet() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let alice = deps.api.addr_make("alice");
+        do_instantiate(deps.as_mut(), &minter, None);
+
+        let msg = ExecuteMsg::UpdateFaucet {
+            faucet: Some(FaucetConfig {
+                amount: Uint128::zero(),
+                period: HOUR,
+            }),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&alice, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        // the admin can't turn on minting either
+        let admin = deps.api.addr_make("admin");
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidFaucet {});
+
+        let msg = ExecuteMsg::UpdateFaucet { faucet: None };
+        execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        let quota = query_faucet_quota(deps.as_ref(), mock_env(), alice.to_string()).unwrap();
+        assert_eq!(quota.remaining, None);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&alice, &[]),
+            ExecuteMsg::FaucetMint {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::FaucetDisabled {});
+    }
+}
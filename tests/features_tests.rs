@@ -0,0 +1,89 @@
+//! Builds the contract with each optional subsystem on its own and runs the core suite
+//! under the minimal profile. These compile the crate several times, so they are
+//! ignored by default: `cargo test --test features_tests -- --ignored`.
+use std::path::Path;
+use std::process::Command;
+
+const FEATURES: &[&str] = &[
+    "allowance-hooks",
+    "allowance-limits",
+    "allowance-locks",
+    "auto-burn",
+    "balance-proofs",
+    "burn-sink",
+    "burn-stats",
+    "changelog",
+    "classic",
+    "compliance",
+    "contract-guard",
+    "enumerable",
+    "events",
+    "exchange-rate",
+    "extended-metadata",
+    "faucet",
+    "launch-guard",
+    "logo-info",
+    "marketing",
+    "marketing-admins",
+    "max-wallet",
+    "meta-tx",
+    "min-transfer",
+    "mint-timelock",
+    "mintable",
+    "multi-token",
+    "rebrand",
+    "rescale-decimals",
+    "rescue",
+    "rewards",
+    "sampling",
+    "scheduled",
+    "send-allowlist",
+    "snapshots",
+    "state-export",
+    "streams",
+    "subscriptions",
+    "supply-hooks",
+    "supply-log",
+    "supply-shards",
+    "transfer-limits",
+    "vesting",
+    "votes",
+];
+
+fn cargo(args: &[&str]) {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let status = Command::new(env!("CARGO"))
+        .args(args)
+        .current_dir(manifest_dir)
+        // a separate target dir, so we don't wait on the lock held by this test run
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(manifest_dir).join("target/features"),
+        )
+        .status()
+        .expect("cargo could not be started");
+    assert!(status.success(), "cargo {} failed", args.join(" "));
+}
+
+#[test]
+#[ignore]
+fn each_feature_builds_alone() {
+    cargo(&["check", "--lib", "--no-default-features"]);
+    for feature in FEATURES {
+        cargo(&[
+            "check",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            feature,
+        ]);
+    }
+    cargo(&["check", "--lib", "--all-features"]);
+}
+
+#[test]
+#[ignore]
+fn minimal_profile_passes_core_suite() {
+    cargo(&["test", "--no-default-features"]);
+    cargo(&["test", "--no-default-features", "--features", "classic"]);
+}
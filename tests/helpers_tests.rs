@@ -0,0 +1,123 @@
+#[cfg(all(
+    test,
+    feature = "enumerable",
+    feature = "marketing",
+    feature = "mintable"
+))]
+mod tests {
+    use cosmwasm_std::{Addr, Empty, Uint128};
+    use cw20::{Cw20Coin, Cw20Contract, MinterResponse};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+    use cw20_base::contract::{execute, instantiate, query};
+    use cw20_base::helpers::{
+        query_allowance_raw, query_balance_raw, query_token_metadata_raw, query_total_supply_raw,
+        Cw20ContractExt,
+    };
+    use cw20_base::msg::{InstantiateMarketingInfo, InstantiateMsg};
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+
+    #[test]
+    fn helpers_cover_the_cw20_surface() {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+        let spender = app.api().addr_make("spender");
+        let code_id = app.store_code(cw20_contract());
+        let msg = InstantiateMsg {
+            name: "Helped".to_string(),
+            symbol: "HLP".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: owner.to_string(),
+                cap: None,
+            }),
+            marketing: Some(InstantiateMarketingInfo {
+                project: Some("helpers".to_string()),
+                description: None,
+                marketing: None,
+                logo: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let addr = app
+            .instantiate_contract(code_id, owner.clone(), &msg, &[], "HLP", None)
+            .unwrap();
+        let token = Cw20Contract(addr);
+        let run = |app: &mut App, sender: &Addr, msg| {
+            app.execute(sender.clone(), msg).unwrap();
+        };
+
+        let msg = token.increase_allowance(&spender, Uint128::new(300), None);
+        run(&mut app, &owner, msg.unwrap());
+        let msg = token.transfer_from(&owner, &spender, Uint128::new(100));
+        run(&mut app, &spender, msg.unwrap());
+        let msg = token.burn_from(&owner, Uint128::new(50));
+        run(&mut app, &spender, msg.unwrap());
+        let msg = token.decrease_allowance(&spender, Uint128::new(50), None);
+        run(&mut app, &owner, msg.unwrap());
+        run(
+            &mut app,
+            &owner,
+            token.mint(&owner, Uint128::new(10)).unwrap(),
+        );
+        run(&mut app, &spender, token.burn(Uint128::new(40)).unwrap());
+        let msg = token.transfer(&spender, Uint128::new(60));
+        run(&mut app, &owner, msg.unwrap());
+
+        let querier = app.wrap();
+        assert_eq!(token.balance(&querier, &owner).unwrap(), Uint128::new(800));
+        assert_eq!(
+            token.balance(&querier, &spender).unwrap(),
+            Uint128::new(120)
+        );
+        assert_eq!(
+            token.meta(&querier).unwrap().total_supply,
+            Uint128::new(920)
+        );
+        let allowances = token.all_allowances(&querier, &owner, None, None).unwrap();
+        assert_eq!(allowances.allowances.len(), 1);
+        assert_eq!(allowances.allowances[0].allowance, Uint128::new(100));
+        let allowances = token
+            .all_spender_allowances(&querier, &spender, None, None)
+            .unwrap();
+        assert_eq!(allowances.allowances[0].owner, owner.to_string());
+        let mut accounts = vec![owner.to_string(), spender.to_string()];
+        accounts.sort();
+        let res = token.all_accounts(&querier, None, None).unwrap();
+        assert_eq!(res.accounts, accounts);
+        let info = token.marketing_info(&querier).unwrap();
+        assert_eq!(info.project.as_deref(), Some("helpers"));
+        // no logo uploaded
+        token.download_logo(&querier).unwrap_err();
+
+        // raw queries read the same state
+        let addr = token.addr();
+        assert_eq!(
+            query_balance_raw(&querier, &addr, &owner).unwrap(),
+            Uint128::new(800)
+        );
+        let unknown = Addr::unchecked("unknown");
+        assert_eq!(
+            query_balance_raw(&querier, &addr, &unknown).unwrap(),
+            Uint128::zero()
+        );
+        let allowance = query_allowance_raw(&querier, &addr, &owner, &spender).unwrap();
+        assert_eq!(
+            allowance,
+            token.allowance(&querier, &owner, &spender).unwrap()
+        );
+        let metadata = query_token_metadata_raw(&querier, &addr).unwrap();
+        assert_eq!(metadata.symbol, "HLP");
+        assert_eq!(
+            query_total_supply_raw(&querier, &addr).unwrap(),
+            Uint128::new(920)
+        );
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(all(test, feature = "ica"))]
+mod tests {
+    use cosmwasm_std::{from_json, to_json_vec, Uint128};
+
+    use cw20_base::ica::{IcaCw20Tx, IcaPacketData, MSG_EXECUTE_CONTRACT};
+    use cw20_base::msg::ExecuteMsg;
+
+    /// A length-delimited protobuf field, for lengths under 16384
+    fn field(number: u8, value: &[u8]) -> Vec<u8> {
+        let mut out = vec![number << 3 | 2];
+        match value.len() {
+            len if len < 0x80 => out.push(len as u8),
+            len => out.extend([len as u8 | 0x80, (len >> 7) as u8]),
+        }
+        out.extend_from_slice(value);
+        out
+    }
+
+    #[test]
+    fn packet_data_wraps_execute_contract_msgs() {
+        let transfer = ExecuteMsg::Transfer {
+            recipient: "host1recipient".to_string(),
+            amount: Uint128::new(500),
+            memo: None,
+        };
+        let burn = ExecuteMsg::Burn {
+            amount: Uint128::new(7),
+            memo: None,
+        };
+        let tx = IcaCw20Tx::new("host1ica", "host1token")
+            .with_msg(transfer.clone())
+            .with_msg(burn.clone())
+            .with_memo("rebalance");
+
+        let any = |msg: &ExecuteMsg| {
+            let mut execute = field(1, b"host1ica");
+            execute.extend(field(2, b"host1token"));
+            execute.extend(field(3, &to_json_vec(msg).unwrap()));
+            let mut any = field(1, MSG_EXECUTE_CONTRACT.as_bytes());
+            any.extend(field(2, &execute));
+            field(1, &any)
+        };
+        let mut cosmos_tx = any(&transfer);
+        cosmos_tx.extend(any(&burn));
+        assert_eq!(tx.cosmos_tx().unwrap().as_slice(), cosmos_tx.as_slice());
+
+        let json = tx.into_binary().unwrap();
+        let packet: IcaPacketData = from_json(&json).unwrap();
+        assert_eq!(
+            packet,
+            IcaPacketData::execute_tx(cosmos_tx.into(), "rebalance")
+        );
+        let raw = String::from_utf8(json.to_vec()).unwrap();
+        assert!(raw.starts_with(r#"{"type":"TYPE_EXECUTE_TX","data":""#));
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(all(test, feature = "instantiate2"))]
+mod tests {
+    use cosmwasm_std::testing::MockApi;
+    use cosmwasm_std::{from_json, instantiate2_address, Api, Checksum, WasmMsg};
+
+    use cw20_base::instantiate2::{predict_token_address, token_salt, TokenInstantiate2};
+    use cw20_base::msg::InstantiateMsg;
+
+    #[test]
+    fn predicted_address_matches_the_message() {
+        let api = MockApi::default();
+        let factory = api.addr_make("factory");
+        let checksum = Checksum::generate(b"cw20-base wasm");
+        let msg = InstantiateMsg {
+            name: "Predicted".to_string(),
+            symbol: "PRD".to_string(),
+            decimals: 6,
+            ..InstantiateMsg::default()
+        };
+
+        let salt = token_salt("PRD", 0);
+        assert_eq!(salt.len(), 32);
+        assert_ne!(salt, token_salt("PRD", 1));
+        assert_ne!(salt, token_salt("PRD0", 0));
+
+        let token = TokenInstantiate2::new(7, msg, salt.clone()).with_admin(factory.to_string());
+        let addr = token.address(&api, &checksum, &factory).unwrap();
+        let canonical = api.addr_canonicalize(factory.as_str()).unwrap();
+        let expected = instantiate2_address(checksum.as_slice(), &canonical, &salt).unwrap();
+        assert_eq!(addr, api.addr_humanize(&expected).unwrap());
+        // another nonce, another address
+        let other = predict_token_address(&api, &checksum, &factory, &token_salt("PRD", 1));
+        assert_ne!(other.unwrap(), addr);
+
+        match token.into_msg().unwrap() {
+            WasmMsg::Instantiate2 {
+                admin,
+                code_id,
+                label,
+                msg,
+                funds,
+                salt: msg_salt,
+            } => {
+                assert_eq!(admin, Some(factory.to_string()));
+                assert_eq!((code_id, label.as_str()), (7, "PRD"));
+                assert!(funds.is_empty());
+                assert_eq!(msg_salt, salt);
+                let msg: InstantiateMsg = from_json(msg).unwrap();
+                assert_eq!(msg.name, "Predicted");
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+}
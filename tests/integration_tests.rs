@@ -1,3 +1,6 @@
+//! The classic cw20-base suite, covering the mintable, marketing and enumerable extensions
+#![cfg(all(feature = "enumerable", feature = "marketing", feature = "mintable"))]
+
 // use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 // use cosmwasm_std::{coins, Addr, Uint128};
 
@@ -9,7 +12,7 @@ use cw20_base::contract::{
     instantiate, execute, query, query_balance, query_download_logo, query_marketing_info,
     query_minter, query_token_info,
 };
-use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, MintingRenouncedResponse, QueryMsg};
 use cw20_base::ContractError;
 use cosmwasm_std::to_json_binary;
 use cosmwasm_std::Deps;
@@ -24,10 +27,12 @@ mod tests {
     use cosmwasm_std::testing::{
         mock_dependencies, mock_dependencies_with_balance, mock_env,
     };
-    use cosmwasm_std::{coins, from_json, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
+    use cosmwasm_std::{
+        coins, from_json, Addr, CosmosMsg, Reply, ReplyOn, StdError, SubMsg, SubMsgResult, WasmMsg,
+    };
 
     use super::*;
-    use cw20_base::msg::InstantiateMarketingInfo;
+    use cw20_base::msg::{FieldUpdate, InstantiateMarketingInfo};
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
         query_balance(deps, address.into()).unwrap().balance
@@ -74,6 +79,7 @@ mod tests {
             }],
             mint: mint.clone(),
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let info = message_info(&Addr::unchecked("creator"), &[]);
         let env = mock_env();
@@ -115,6 +121,7 @@ mod tests {
                 }],
                 mint: None,
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = message_info(&Addr::unchecked("creator"), &[]);
             let env = mock_env();
@@ -153,6 +160,7 @@ mod tests {
                     cap: Some(limit),
                 }),
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = message_info(&Addr::unchecked("creator"), &[]);
             let env = mock_env();
@@ -198,6 +206,7 @@ mod tests {
                     cap: Some(limit),
                 }),
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = message_info(&Addr::unchecked("creator"), &[]);
             let env = mock_env();
@@ -229,6 +238,7 @@ mod tests {
                         marketing: Some(marketing.to_string()),
                         logo: Some(Logo::Url("url".to_owned())),
                     }),
+                    ..InstantiateMsg::default()
                 };
 
                 let info = message_info(&Addr::unchecked("creator"), &[]);
@@ -268,6 +278,7 @@ mod tests {
                         marketing: Some("m".to_owned()),
                         logo: Some(Logo::Url("url".to_owned())),
                     }),
+                    ..InstantiateMsg::default()
                 };
 
                 let info = message_info(&Addr::unchecked("creator"), &[]);
@@ -349,6 +360,54 @@ mod tests {
         assert_eq!(err, ContractError::Unauthorized {});
     }
 
+    #[test]
+    fn mint_batch() {
+        let mut deps = mock_dependencies();
+
+        let genesis = deps.api.addr_make("genesis").to_string();
+        let minter = deps.api.addr_make("minter").to_string();
+        let alice = deps.api.addr_make("alice").to_string();
+        let bob = deps.api.addr_make("bob").to_string();
+        let limit = Uint128::new(1_000);
+        do_instantiate_with_minter(
+            deps.as_mut(),
+            &genesis,
+            Uint128::new(100),
+            &minter,
+            Some(limit),
+        );
+
+        let batch = |alice_amount: u128, bob_amount: u128| ExecuteMsg::MintBatch {
+            recipients: vec![
+                Cw20Coin {
+                    address: alice.clone(),
+                    amount: Uint128::new(alice_amount),
+                },
+                Cw20Coin {
+                    address: bob.clone(),
+                    amount: Uint128::new(bob_amount),
+                },
+            ],
+        };
+
+        // others cannot mint
+        let info = message_info(&Addr::unchecked("anyone else"), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, batch(1, 1)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // the cap applies to the sum
+        let info = message_info(&Addr::unchecked(minter.as_str()), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, batch(500, 401)).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+
+        let info = message_info(&Addr::unchecked(minter.as_str()), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, batch(500, 400)).unwrap();
+        assert_eq!(res.attributes.iter().filter(|a| a.key == "to").count(), 2);
+        assert_eq!(get_balance(deps.as_ref(), alice), Uint128::new(500));
+        assert_eq!(get_balance(deps.as_ref(), bob), Uint128::new(400));
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().total_supply, limit);
+    }
+
     #[test]
     fn minter_can_update_minter_but_not_cap() {
         let mut deps = mock_dependencies();
@@ -377,6 +436,79 @@ mod tests {
         assert!(mint.minter == new_minter)
     }
 
+    #[test]
+    fn minter_can_update_cap() {
+        let mut deps = mock_dependencies();
+
+        let genesis = deps.api.addr_make("genesis").to_string();
+        let minter = deps.api.addr_make("minter").to_string();
+        do_instantiate_with_minter(deps.as_mut(), &genesis, Uint128::new(1234), &minter, None);
+
+        let info = message_info(&Addr::unchecked(genesis.as_str()), &[]);
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(5000)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // the cap can be raised or removed, but not below the supply
+        let info = message_info(&Addr::unchecked(minter.as_str()), &[]);
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(1000)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::CapBelowSupply {});
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(5000)),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(6000)),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let mint = query_minter(deps.as_ref()).unwrap().unwrap();
+        assert_eq!(mint.cap, Some(Uint128::new(6000)));
+    }
+
+    #[test]
+    fn cap_can_only_decrease() {
+        let mut deps = mock_dependencies();
+
+        let genesis = deps.api.addr_make("genesis").to_string();
+        let minter = deps.api.addr_make("minter").to_string();
+        let instantiate_msg = InstantiateMsg {
+            name: "Auto Gen".to_string(),
+            symbol: "AUTO".to_string(),
+            decimals: 3,
+            initial_balances: vec![Cw20Coin {
+                address: genesis,
+                amount: Uint128::new(1234),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: Some(Uint128::new(5000)),
+            }),
+            cap_can_only_decrease: true,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        let info = message_info(&Addr::unchecked(minter.as_str()), &[]);
+        for cap in [Some(Uint128::new(5001)), None] {
+            let msg = ExecuteMsg::UpdateCap { cap };
+            let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+            assert_eq!(err, ContractError::CapCanOnlyDecrease {});
+        }
+
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(1234)),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let mint = query_minter(deps.as_ref()).unwrap().unwrap();
+        assert_eq!(mint.cap, Some(Uint128::new(1234)));
+    }
+
     #[test]
     fn others_cannot_update_minter() {
         let mut deps = mock_dependencies();
@@ -432,6 +564,47 @@ mod tests {
         assert_eq!(err, ContractError::Unauthorized {});
     }
 
+    #[test]
+    fn renounce_minting() {
+        let mut deps = mock_dependencies();
+
+        let genesis = deps.api.addr_make("genesis").to_string();
+        let minter = deps.api.addr_make("minter").to_string();
+
+        do_instantiate_with_minter(deps.as_mut(), &genesis, Uint128::new(1234), &minter, None);
+
+        // only the minter can renounce
+        let msg = ExecuteMsg::RenounceMinting {};
+        let info = message_info(&Addr::unchecked("not the minter"), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MintingRenounced {}).unwrap();
+        let renounced: MintingRenouncedResponse = from_json(res).unwrap();
+        assert!(!renounced.renounced);
+
+        let info = message_info(&Addr::unchecked(minter.as_str()), &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MintingRenounced {}).unwrap();
+        let renounced: MintingRenouncedResponse = from_json(res).unwrap();
+        assert!(renounced.renounced);
+        assert_eq!(query_minter(deps.as_ref()).unwrap(), None);
+
+        let msg = ExecuteMsg::Mint {
+            recipient: genesis,
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::UpdateMinter {
+            new_minter: Some(minter),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::MintingRenounced {});
+    }
+
     #[test]
     fn no_one_mints_if_minter_unset() {
         let mut deps = mock_dependencies();
@@ -478,6 +651,7 @@ mod tests {
             ],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let err =
             instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap_err();
@@ -500,6 +674,7 @@ mod tests {
             ],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let res = instantiate(deps.as_mut(), env, info, instantiate_msg).unwrap();
         assert_eq!(0, res.messages.len());
@@ -516,6 +691,39 @@ mod tests {
         assert_eq!(get_balance(deps.as_ref(), addr2), amount2);
     }
 
+    #[test]
+    fn instantiate_merges_duplicate_accounts() {
+        let mut deps = mock_dependencies();
+        let amount1 = Uint128::from(11223344u128);
+        let amount2 = Uint128::from(7890987u128);
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+
+        let instantiate_msg = InstantiateMsg {
+            name: "Bash Shell".to_string(),
+            symbol: "BASH".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: addr1.clone(),
+                    amount: amount1,
+                },
+                Cw20Coin {
+                    address: addr1.clone(),
+                    amount: amount2,
+                },
+            ],
+            merge_initial_balances: true,
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            amount1 + amount2
+        );
+        assert_eq!(get_balance(deps.as_ref(), addr1), amount1 + amount2);
+    }
+
     #[test]
     fn queries_work() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
@@ -566,6 +774,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: Uint128::zero(),
+            memo: None,
         };
         execute(deps.as_mut(), env, info, msg).unwrap();
 
@@ -575,6 +784,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: too_much,
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -585,6 +795,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr1.clone(),
             amount: transfer,
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -596,6 +807,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: transfer,
+            memo: None,
         };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 0);
@@ -609,6 +821,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transfer_memo() {
+        let mut deps = mock_dependencies();
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let addr2 = deps.api.addr_make("addr0002").to_string();
+        do_instantiate(deps.as_mut(), &addr1, Uint128::new(1000));
+
+        let info = message_info(&Addr::unchecked(addr1.as_str()), &[]);
+        let msg = ExecuteMsg::Transfer {
+            recipient: addr2.clone(),
+            amount: Uint128::new(10),
+            memo: Some("deposit 42".to_string()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "memo" && a.value == "deposit 42"));
+
+        // memos are length capped
+        let msg = ExecuteMsg::Transfer {
+            recipient: addr2.clone(),
+            amount: Uint128::new(10),
+            memo: Some("x".repeat(257)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::MemoTooLong {});
+        assert_eq!(get_balance(deps.as_ref(), addr2), Uint128::new(10));
+    }
+
     #[test]
     fn burn() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
@@ -624,6 +866,7 @@ mod tests {
         let env = mock_env();
         let msg = ExecuteMsg::Burn {
             amount: Uint128::zero(),
+            memo: None,
         };
         execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(
@@ -634,7 +877,10 @@ mod tests {
         // cannot burn more than we have
         let info = message_info(&Addr::unchecked(addr1.as_str()), &[]);
         let env = mock_env();
-        let msg = ExecuteMsg::Burn { amount: too_much };
+        let msg = ExecuteMsg::Burn {
+            amount: too_much,
+            memo: None,
+        };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
         assert_eq!(
@@ -645,7 +891,10 @@ mod tests {
         // valid burn reduces total supply
         let info = message_info(&Addr::unchecked(addr1.as_str()), &[]);
         let env = mock_env();
-        let msg = ExecuteMsg::Burn { amount: burn };
+        let msg = ExecuteMsg::Burn {
+            amount: burn,
+            memo: None,
+        };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 0);
 
@@ -657,6 +906,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transfer_all_and_burn_all() {
+        let mut deps = mock_dependencies();
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let addr2 = deps.api.addr_make("addr0002").to_string();
+        let amount1 = Uint128::new(12340000);
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+
+        // the whole balance moves, whatever it is by the time the message executes
+        let info = message_info(&Addr::unchecked(addr1.as_str()), &[]);
+        let msg = ExecuteMsg::TransferAll {
+            recipient: addr2.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "amount" && a.value == amount1.to_string()));
+        assert_eq!(get_balance(deps.as_ref(), addr1.clone()), Uint128::zero());
+        assert_eq!(get_balance(deps.as_ref(), addr2.clone()), amount1);
+
+        // an empty account moves nothing
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), addr2.clone()), amount1);
+
+        let info = message_info(&Addr::unchecked(addr2.as_str()), &[]);
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::BurnAll {}).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), addr2), Uint128::zero());
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::zero()
+        );
+    }
+
     #[test]
     fn send() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
@@ -676,6 +959,8 @@ mod tests {
             contract: contract.clone(),
             amount: Uint128::zero(),
             msg: send_msg.clone(),
+            memo: None,
+            safe: false,
         };
         execute(deps.as_mut(), env, info, msg).unwrap();
 
@@ -686,6 +971,8 @@ mod tests {
             contract: contract.clone(),
             amount: too_much,
             msg: send_msg.clone(),
+            memo: None,
+            safe: false,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -697,6 +984,8 @@ mod tests {
             contract: contract.clone(),
             amount: transfer,
             msg: send_msg.clone(),
+            memo: None,
+            safe: false,
         };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 1);
@@ -730,11 +1019,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn safe_send_refunds_failed_receiver() {
+        let mut deps = mock_dependencies();
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let contract = deps.api.addr_make("contract0001").to_string();
+        do_instantiate(deps.as_mut(), &addr1, Uint128::new(1000));
+
+        let info = message_info(&Addr::unchecked(addr1.as_str()), &[]);
+        let msg = ExecuteMsg::Send {
+            contract: contract.clone(),
+            amount: Uint128::new(300),
+            msg: Binary::from(r#"{"some":123}"#.as_bytes()),
+            memo: None,
+            safe: true,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let sub_msg = &res.messages[0];
+        assert_eq!(sub_msg.reply_on, ReplyOn::Error);
+        assert_eq!(get_balance(deps.as_ref(), &contract), Uint128::new(300));
+
+        // the receiver rejected the tokens
+        let reply = Reply {
+            id: sub_msg.id,
+            payload: sub_msg.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Err("rejected".to_string()),
+        };
+        contract::reply(deps.as_mut(), mock_env(), reply).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &contract), Uint128::zero());
+        assert_eq!(get_balance(deps.as_ref(), &addr1), Uint128::new(1000));
+    }
+
     mod migration {
         use super::*;
 
-        use cosmwasm_std::Empty;
+        use cosmwasm_std::{attr, Empty, Storage};
         use cw20::{AllAllowancesResponse, AllSpenderAllowancesResponse, SpenderAllowanceInfo};
+        use cw20::AllowanceResponse;
+        use cw20_base::migrations::query_migration_status;
+        use cw20_base::state::{MinterData, TokenInfo, ALLOWANCES, BALANCES, LEGACY_TOKEN_INFO};
         use cw_multi_test::{App, Contract, ContractWrapper, Executor};
         use cw_utils::Expiration;
 
@@ -770,6 +1094,7 @@ mod tests {
                         }],
                         mint: None,
                         marketing: None,
+                        ..InstantiateMsg::default()
                     },
                     &[],
                     "TOKEN",
@@ -812,7 +1137,7 @@ mod tests {
                 CosmosMsg::Wasm(WasmMsg::Migrate {
                     contract_addr: cw20_addr.to_string(),
                     new_code_id: cw20_id,
-                    msg: to_json_binary(&MigrateMsg {}).unwrap(),
+                    msg: to_json_binary(&MigrateMsg::default()).unwrap(),
                 }),
             )
             .unwrap();
@@ -851,6 +1176,233 @@ mod tests {
                 }]
             );
         }
+
+        #[test]
+        fn migrate_from_upstream_layout() {
+            let mut deps = mock_dependencies();
+            let holder = deps.api.addr_make("holder");
+            let minter = deps.api.addr_make("minter");
+            // state as written by cw20-base 1.1, whose minter data has no cap flags
+            cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "1.1.2").unwrap();
+            let token_info = format!(
+                r#"{{"name":"Token","symbol":"TOKEN","decimals":6,"total_supply":"100","mint":{{"minter":"{minter}","cap":null}}}}"#
+            );
+            deps.storage.set(b"token_info", token_info.as_bytes());
+            let balance_key = cw_storage_plus::Map::<&Addr, Uint128>::new("balance").key(&holder);
+            deps.storage.set(&balance_key, br#""100""#);
+
+            let res = contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+            assert_eq!(res.attributes[1], attr("from_version", "1.1.2"));
+            assert_eq!(res.attributes[3], attr("steps", "split_token_info"));
+            let version = cw2::get_contract_version(&deps.storage).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+            assert_eq!(
+                query_token_info(deps.as_ref()).unwrap().total_supply,
+                Uint128::new(100)
+            );
+            let msg = ExecuteMsg::Mint {
+                recipient: holder.to_string(),
+                amount: Uint128::new(50),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+            assert_eq!(get_balance(deps.as_ref(), &holder), Uint128::new(150));
+            assert_eq!(
+                query_token_info(deps.as_ref()).unwrap().total_supply,
+                Uint128::new(150)
+            );
+        }
+
+        #[test]
+        fn migrate_splits_token_info() {
+            let mut deps = mock_dependencies();
+            let minter = deps.api.addr_make("minter");
+            cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "1.1.0").unwrap();
+            let legacy = TokenInfo {
+                name: "Token".to_string(),
+                symbol: "TOKEN".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(100),
+                mint: Some(MinterData {
+                    minter: minter.clone(),
+                    cap: Some(Uint128::new(500)),
+                    cap_can_only_decrease: false,
+                }),
+            };
+            LEGACY_TOKEN_INFO.save(&mut deps.storage, &legacy).unwrap();
+
+            contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+            assert!(!LEGACY_TOKEN_INFO.exists(&deps.storage));
+            assert_eq!(
+                query_token_info(deps.as_ref()).unwrap(),
+                TokenInfoResponse {
+                    name: "Token".to_string(),
+                    symbol: "TOKEN".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::new(100),
+                }
+            );
+            assert_eq!(
+                query_minter(deps.as_ref()).unwrap(),
+                Some(MinterResponse {
+                    minter: minter.to_string(),
+                    cap: Some(Uint128::new(500)),
+                })
+            );
+        }
+
+        #[test]
+        fn migrate_splits_token_info_from_first_release() {
+            let mut deps = mock_dependencies();
+            let holder = deps.api.addr_make("holder");
+            let minter = deps.api.addr_make("minter");
+            cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "2.0.1").unwrap();
+            let legacy = TokenInfo {
+                name: "Token".to_string(),
+                symbol: "TOKEN".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(100),
+                mint: Some(MinterData {
+                    minter: minter.clone(),
+                    cap: None,
+                    cap_can_only_decrease: false,
+                }),
+            };
+            LEGACY_TOKEN_INFO.save(&mut deps.storage, &legacy).unwrap();
+
+            let res = contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+            assert_eq!(res.attributes[3], attr("steps", "split_token_info"));
+            assert!(!LEGACY_TOKEN_INFO.exists(&deps.storage));
+
+            let msg = ExecuteMsg::Mint {
+                recipient: holder.to_string(),
+                amount: Uint128::new(50),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+            assert_eq!(
+                query_token_info(deps.as_ref()).unwrap().total_supply,
+                Uint128::new(150)
+            );
+        }
+
+        #[test]
+        fn migrate_dry_run_reports_steps() {
+            let mut deps = mock_dependencies();
+            cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.13.0").unwrap();
+            let legacy = TokenInfo {
+                name: "Token".to_string(),
+                symbol: "TOKEN".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(100),
+                mint: None,
+            };
+            LEGACY_TOKEN_INFO.save(&mut deps.storage, &legacy).unwrap();
+
+            let msg: MigrateMsg = from_json(r#"{"dry_run":true}"#).unwrap();
+            let res = contract::migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            assert_eq!(
+                res.attributes[3],
+                attr("steps", "index_allowances_by_spender,split_token_info")
+            );
+            // nothing ran
+            assert!(LEGACY_TOKEN_INFO.exists(&deps.storage));
+            let version = cw2::get_contract_version(&deps.storage).unwrap();
+            assert_eq!(version.version, "0.13.0");
+
+            // nothing left to run once migrated
+            contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+            assert!(!LEGACY_TOKEN_INFO.exists(&deps.storage));
+            let msg: MigrateMsg = from_json(r#"{"dry_run":true}"#).unwrap();
+            let res = contract::migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            assert_eq!(res.attributes[3], attr("steps", ""));
+        }
+
+        #[test]
+        fn migrate_indexes_spenders_in_chunks() {
+            let mut deps = mock_dependencies();
+            let spender = deps.api.addr_make("spender");
+            let mut owners: Vec<Addr> = ["carl", "alice", "bob"]
+                .iter()
+                .map(|name| deps.api.addr_make(name))
+                .collect();
+            owners.sort();
+            cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.13.0").unwrap();
+            let legacy = TokenInfo {
+                name: "Token".to_string(),
+                symbol: "TOKEN".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(300),
+                mint: None,
+            };
+            LEGACY_TOKEN_INFO.save(&mut deps.storage, &legacy).unwrap();
+            for owner in &owners {
+                BALANCES
+                    .save(&mut deps.storage, owner, &Uint128::new(100), 1)
+                    .unwrap();
+                let allowance = AllowanceResponse {
+                    allowance: Uint128::new(50),
+                    expires: Expiration::Never {},
+                };
+                ALLOWANCES
+                    .save(&mut deps.storage, (owner, &spender), &allowance)
+                    .unwrap();
+            }
+            let spender_allowances = |deps: Deps, start_after: Option<&Addr>| {
+                let msg = QueryMsg::AllSpenderAllowances {
+                    spender: spender.to_string(),
+                    start_after: start_after.map(Addr::to_string),
+                    limit: None,
+                };
+                let res: AllSpenderAllowancesResponse =
+                    from_json(query(deps, mock_env(), msg).unwrap()).unwrap();
+                res.allowances
+                    .into_iter()
+                    .map(|info| (info.owner, info.allowance.u128()))
+                    .collect::<Vec<_>>()
+            };
+
+            contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+            // the token info is split right away, the spender index is left for later
+            assert!(!LEGACY_TOKEN_INFO.exists(&deps.storage));
+            let status = query_migration_status(deps.as_ref()).unwrap();
+            assert_eq!(
+                status.pending.unwrap().steps,
+                vec!["index_allowances_by_spender".to_string()]
+            );
+            let err =
+                contract::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+            assert_eq!(err, ContractError::MigrationInProgress {});
+
+            // allowances work before the index is built
+            let expected: Vec<_> = owners.iter().map(|owner| (owner.to_string(), 50)).collect();
+            assert_eq!(spender_allowances(deps.as_ref(), None), expected);
+            assert_eq!(
+                spender_allowances(deps.as_ref(), Some(&owners[0])),
+                expected[1..]
+            );
+            let msg = ExecuteMsg::TransferFrom {
+                owner: owners[2].to_string(),
+                recipient: spender.to_string(),
+                amount: Uint128::new(20),
+                memo: None,
+            };
+            let info = message_info(&spender, &[]);
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+            let msg = ExecuteMsg::ProcessMigration { limit: Some(2) };
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+            assert!(res.attributes.contains(&attr("done", "false")));
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+            assert!(res.attributes.contains(&attr("processed", "1")));
+            assert!(res.attributes.contains(&attr("done", "true")));
+            assert_eq!(query_migration_status(deps.as_ref()).unwrap().pending, None);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::NoMigrationInProgress {});
+
+            let mut expected = expected;
+            expected[2].1 = 30;
+            assert_eq!(spender_allowances(deps.as_ref(), None), expected);
+        }
     }
 
     mod marketing {
@@ -875,6 +1427,7 @@ mod tests {
                     marketing: Some(marketing.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -886,9 +1439,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: Some("New project".to_owned()),
-                    description: Some("Better description".to_owned()),
-                    marketing: Some(creator.to_string()),
+                    project: FieldUpdate::Set("New project".to_owned()),
+                    description: FieldUpdate::Set("Better description".to_owned()),
+                    marketing: FieldUpdate::Set(creator.to_string()),
                 },
             )
             .unwrap_err();
@@ -931,6 +1484,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -942,9 +1496,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: Some("New project".to_owned()),
-                    description: None,
-                    marketing: None,
+                    project: FieldUpdate::Set("New project".to_owned()),
+                    description: FieldUpdate::Unchanged,
+                    marketing: FieldUpdate::Unchanged,
                 },
             )
             .unwrap();
@@ -986,6 +1540,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -997,9 +1552,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: Some("".to_owned()),
-                    description: None,
-                    marketing: None,
+                    project: FieldUpdate::Clear,
+                    description: FieldUpdate::Unchanged,
+                    marketing: FieldUpdate::Unchanged,
                 },
             )
             .unwrap();
@@ -1041,6 +1596,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1052,9 +1608,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: Some("Better description".to_owned()),
-                    marketing: None,
+                    project: FieldUpdate::Unchanged,
+                    description: FieldUpdate::Set("Better description".to_owned()),
+                    marketing: FieldUpdate::Unchanged,
                 },
             )
             .unwrap();
@@ -1096,6 +1652,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1107,9 +1664,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: Some("".to_owned()),
-                    marketing: None,
+                    project: FieldUpdate::Unchanged,
+                    description: FieldUpdate::Clear,
+                    marketing: FieldUpdate::Unchanged,
                 },
             )
             .unwrap();
@@ -1133,6 +1690,8 @@ mod tests {
             );
         }
 
+        // with marketing-admins, the new address is only nominated
+        #[cfg(not(feature = "marketing-admins"))]
         #[test]
         fn update_marketing() {
             let mut deps = mock_dependencies();
@@ -1152,6 +1711,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1163,9 +1723,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some(marketing.to_string()),
+                    project: FieldUpdate::Unchanged,
+                    description: FieldUpdate::Unchanged,
+                    marketing: FieldUpdate::Set(marketing.to_string()),
                 },
             )
             .unwrap();
@@ -1207,6 +1767,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1218,9 +1779,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some("m".to_owned()),
+                    project: FieldUpdate::Unchanged,
+                    description: FieldUpdate::Unchanged,
+                    marketing: FieldUpdate::Set("m".to_owned()),
                 },
             )
             .unwrap_err();
@@ -1265,6 +1826,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1276,9 +1838,9 @@ mod tests {
                 mock_env(),
                 info,
                 ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some("".to_owned()),
+                    project: FieldUpdate::Unchanged,
+                    description: FieldUpdate::Unchanged,
+                    marketing: FieldUpdate::Clear,
                 },
             )
             .unwrap();
@@ -1320,6 +1882,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1371,6 +1934,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1424,6 +1988,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1478,6 +2043,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1530,6 +2096,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1589,6 +2156,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked(creator.as_str()), &[]);
@@ -1641,6 +2209,7 @@ mod tests {
                     marketing: Some(creator.to_string()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = message_info(&Addr::unchecked("creator"), &[]);
@@ -1675,5 +2244,123 @@ mod tests {
                 "Expected StdError::NotFound, received {err}",
             );
         }
+
+        #[test]
+        fn update_logo_svg_sanitized() {
+            let mut deps = mock_dependencies();
+
+            let creator = deps.api.addr_make("creator");
+
+            let instantiate_msg = InstantiateMsg {
+                name: "Cash Token".to_string(),
+                symbol: "CASH".to_string(),
+                decimals: 9,
+                marketing: Some(InstantiateMarketingInfo {
+                    project: Some("Project".to_owned()),
+                    description: Some("Description".to_owned()),
+                    marketing: Some(creator.to_string()),
+                    logo: Some(Logo::Url("url".to_owned())),
+                }),
+                ..InstantiateMsg::default()
+            };
+
+            let info = message_info(&creator, &[]);
+
+            instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg).unwrap();
+
+            let upload = |deps: DepsMut, svg: &str| {
+                let img = format!("<?xml version=\"1.0\"?>{svg}").into_bytes();
+                let msg = ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Svg(img.into())));
+                execute(deps, mock_env(), info.clone(), msg)
+            };
+
+            let cases = [
+                (
+                    r#"<svg><script>alert(1)</script></svg>"#,
+                    ContractError::SvgForbiddenElement {
+                        element: "script".to_owned(),
+                    },
+                ),
+                (
+                    r#"<svg><svg:Script/></svg>"#,
+                    ContractError::SvgForbiddenElement {
+                        element: "script".to_owned(),
+                    },
+                ),
+                (
+                    r#"<!DOCTYPE svg [<!ENTITY a "a">]><svg/>"#,
+                    ContractError::SvgForbiddenElement {
+                        element: "!DOCTYPE".to_owned(),
+                    },
+                ),
+                (
+                    r#"<svg onload="alert(1)"/>"#,
+                    ContractError::SvgEventHandler {
+                        attribute: "onload".to_owned(),
+                    },
+                ),
+                (
+                    r#"<svg><image xlink:href="https://tracker.example/p.png"/></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><rect style="fill: url( 'https://x.example/a')"/></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><style>@import "https://x.example/a.css";</style></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><a><animate attributeName="href" values="javascript:alert(1)"/></a></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><a><set attributeName="xlink:hr&#101;f" to="https://x.example"/></a></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><rect style="fill: u&#114;l(http://x.example/a)"/></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><style>.a { fill: u\72 l(http://x.example/a) }</style></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (
+                    r#"<svg><style>@\69mport "https://x.example/a.css";</style></svg>"#,
+                    ContractError::SvgExternalReference {},
+                ),
+                (r#"<svg><text>&ext;</text></svg>"#, ContractError::InvalidSvg {}),
+                (
+                    r#"<svg width="100000" height="64"/>"#,
+                    ContractError::SvgTooLarge { max: 4096 },
+                ),
+                (r#"<svg><rect x="1></svg>"#, ContractError::InvalidSvg {}),
+                (r#"<svg><rect hidden/></svg>"#, ContractError::InvalidSvg {}),
+                (r#"<png/>"#, ContractError::InvalidSvg {}),
+            ];
+            for (svg, expected) in cases {
+                assert_eq!(upload(deps.as_mut(), svg).unwrap_err(), expected, "{svg}");
+            }
+            assert_eq!(
+                query_marketing_info(deps.as_ref()).unwrap().logo,
+                Some(LogoInfo::Url("url".to_owned()))
+            );
+
+            let svg = concat!(
+                r#"<!-- exported --><svg xmlns="http://www.w3.org/2000/svg" "#,
+                r#"xmlns:xlink="http://www.w3.org/1999/xlink" width="64px" height='100%'>"#,
+                r#"<defs><linearGradient id="g"/><path id="p" d="M0 0"/></defs>"#,
+                r#"<style><![CDATA[.a { fill: url(#g) }]]></style>"#,
+                r##"<animate attributeName="opacity" values="0;1"/>"##,
+                r##"<use xlink:href="&#35;p" class="a"/><text>x &lt; y</text></svg>"##,
+            );
+            upload(deps.as_mut(), svg).unwrap();
+            assert_eq!(
+                query_marketing_info(deps.as_ref()).unwrap().logo,
+                Some(LogoInfo::Embedded)
+            );
+        }
     }
 }
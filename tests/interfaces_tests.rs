@@ -0,0 +1,141 @@
+#[cfg(all(test, feature = "mintable"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::MinterResponse;
+
+    use cw20_base::contract::{execute, instantiate, migrate};
+    use cw20_base::interfaces::{
+        query_contract_info, query_features, query_supported_interfaces, EXTENSIONS,
+    };
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+
+    #[test]
+    fn interfaces_follow_the_configuration() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let msg = InstantiateMsg {
+            name: "Introspect".to_string(),
+            symbol: "INTR".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query_supported_interfaces(deps.as_ref()).unwrap();
+        let names: Vec<_> = res
+            .interfaces
+            .iter()
+            .map(|i| i.supported_interface.as_str())
+            .collect();
+        assert!(names.contains(&"crates.io:cw20"));
+        assert!(names.contains(&"crates.io:cw20-mintable"));
+        // no marketing info was given
+        assert!(!names.contains(&"crates.io:cw20-marketing"));
+        assert_eq!(names.len(), 4 + EXTENSIONS.len());
+
+        // migrating keeps the list
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        let migrated = query_supported_interfaces(deps.as_ref()).unwrap();
+        assert_eq!(migrated, res);
+
+        // nor does it stay mintable once the minter gives it up
+        let info = message_info(&minter, &[]);
+        let msg = ExecuteMsg::UpdateMinter { new_minter: None };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query_supported_interfaces(deps.as_ref()).unwrap();
+        assert!(!res
+            .interfaces
+            .iter()
+            .any(|i| i.supported_interface == "crates.io:cw20-mintable"));
+        assert_eq!(res.interfaces.len(), 3 + EXTENSIONS.len());
+    }
+
+    #[test]
+    fn features_follow_the_configuration() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let msg = InstantiateMsg {
+            name: "Introspect".to_string(),
+            symbol: "INTR".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: Some(Uint128::new(1_000)),
+            }),
+            admin: Some(minter.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let features = query_features(deps.as_ref()).unwrap();
+        assert!(features.mintable);
+        assert!(features.capped);
+        assert!(features.admin);
+        assert!(features.trading_enabled);
+        assert!(!features.minting_renounced);
+        assert!(!features.marketing);
+        assert!(!features.auto_burn);
+        assert!(!features.max_wallet);
+        assert!(!features.rewards);
+        assert!(!features.exchange_rate);
+    }
+
+    #[test]
+    fn contract_info_describes_the_build() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Introspect".to_string(),
+            symbol: "INTR".to_string(),
+            decimals: 6,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query_contract_info(deps.as_ref()).unwrap();
+        assert_eq!(res.contract, "crates.io:cw20-base");
+        assert_eq!(res.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(res.build.version, env!("CARGO_PKG_VERSION"));
+        assert!(res.build.features.iter().any(|f| f == "mintable"));
+        for extension in EXTENSIONS {
+            assert!(res.build.features.contains(&extension.to_string()));
+        }
+        assert_eq!(res.build.rustc.is_some(), cfg!(feature = "build-info"));
+    }
+}
+
+#[cfg(all(test, not(feature = "mintable")))]
+mod bare {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::Addr;
+    use cw20::MinterResponse;
+
+    use cw20_base::contract::instantiate;
+    use cw20_base::msg::InstantiateMsg;
+    use cw20_base::ContractError;
+
+    #[test]
+    fn minter_needs_the_mintable_feature() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Bare".to_string(),
+            symbol: "BARE".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: deps.api.addr_make("minter").to_string(),
+                cap: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::MintableDisabled {});
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(all(test, feature = "invariants"))]
+mod tests {
+    use cosmwasm_std::{DepsMut, Empty, Env, MessageInfo, Response};
+    use cw_multi_test::{Contract, ContractWrapper};
+
+    use cw20_base::balances::add_balance;
+    use cw20_base::contract::{execute, instantiate, query};
+    use cw20_base::invariants::{check_invariants, check_invariants_with_seed};
+    use cw20_base::msg::ExecuteMsg;
+    use cw20_base::test_utils::Cw20TestSuite;
+    use cw20_base::ContractError;
+
+    #[test]
+    fn cw20_base_keeps_the_invariants() {
+        check_invariants(
+            &Cw20TestSuite::builder().with_cap(100_000_000u128),
+            0..8,
+            100,
+        );
+    }
+
+    /// A fork whose transfers credit the recipient with one token too many
+    fn leaky_execute(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        let recipient = match &msg {
+            ExecuteMsg::Transfer { recipient, .. } => Some(deps.api.addr_validate(recipient)?),
+            _ => None,
+        };
+        let height = env.block.height;
+        let res = execute(deps.branch(), env, info, msg)?;
+        if let Some(recipient) = recipient {
+            add_balance(deps.storage, &recipient, 1u128.into(), height)?;
+        }
+        Ok(res)
+    }
+
+    fn leaky_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(leaky_execute, instantiate, query))
+    }
+
+    #[test]
+    #[should_panic(expected = "supply")]
+    fn a_leaky_fork_is_caught() {
+        let builder = Cw20TestSuite::builder().with_contract(leaky_contract);
+        check_invariants_with_seed(&builder, 1, 50);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(all(test, feature = "launch-guard"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::launch_guard::query_trading_status;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::restrictions::{query_detect_transfer_restriction, TRADING_NOT_ENABLED};
+    use cw20_base::ContractError;
+
+    #[test]
+    fn only_allowlist_transfers_before_launch() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let deployer = deps.api.addr_make("deployer");
+        let pool = deps.api.addr_make("pool");
+        let sniper = deps.api.addr_make("sniper");
+        let msg = InstantiateMsg {
+            name: "Launch".to_string(),
+            symbol: "LNCH".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: deployer.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            launch_allowlist: Some(vec![deployer.to_string()]),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert!(!query_trading_status(deps.as_ref()).unwrap().enabled);
+
+        let transfer = |recipient: &Addr| ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        // the deployer seeds the pool, which cannot pass tokens on yet
+        let info = message_info(&deployer, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), transfer(&pool)).unwrap();
+        execute(deps.as_mut(), mock_env(), info, transfer(&sniper)).unwrap();
+        let info = message_info(&sniper, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), transfer(&pool)).unwrap_err();
+        assert_eq!(err, ContractError::TradingNotEnabled {});
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            sniper.to_string(),
+            pool.to_string(),
+            Uint128::new(100),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, TRADING_NOT_ENABLED);
+
+        // only the admin enables trading, and only once
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::EnableTrading {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let admin_info = message_info(&admin, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            ExecuteMsg::EnableTrading {},
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info,
+            ExecuteMsg::EnableTrading {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TradingAlreadyEnabled {});
+
+        assert!(query_trading_status(deps.as_ref()).unwrap().enabled);
+        execute(deps.as_mut(), mock_env(), info, transfer(&pool)).unwrap();
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(all(test, feature = "logo-info"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, HexBinary};
+    use cw20::{EmbeddedLogo, Logo};
+    use sha2::{Digest, Sha256};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::logo_info::query_logo_info;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMarketingInfo, InstantiateMsg, LogoInfoResponse};
+
+    const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    #[test]
+    fn describes_the_logo_without_its_data() {
+        let mut deps = mock_dependencies();
+        let marketing = deps.api.addr_make("marketing");
+        let msg = InstantiateMsg {
+            name: "Logo".to_string(),
+            symbol: "LOGO".to_string(),
+            decimals: 6,
+            marketing: Some(InstantiateMarketingInfo {
+                project: None,
+                description: None,
+                marketing: Some(marketing.to_string()),
+                logo: Some(Logo::Url("https://example.com/logo.png".to_string())),
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(
+            query_logo_info(deps.as_ref()).unwrap(),
+            LogoInfoResponse {
+                url: Some("https://example.com/logo.png".to_string()),
+                ..LogoInfoResponse::default()
+            }
+        );
+
+        let png = [&PNG_HEADER[..], &[7; 100][..]].concat();
+        let msg = ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(png.clone().into())));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            msg,
+        )
+        .unwrap();
+
+        let hash = Sha256::digest(&png);
+        assert_eq!(
+            query_logo_info(deps.as_ref()).unwrap(),
+            LogoInfoResponse {
+                url: None,
+                mime_type: Some("image/png".to_string()),
+                size: Some(108),
+                sha256: Some(HexBinary::from(hash.as_slice())),
+            }
+        );
+    }
+
+    #[test]
+    fn no_logo_is_empty() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Logo".to_string(),
+            symbol: "LOGO".to_string(),
+            decimals: 6,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(
+            query_logo_info(deps.as_ref()).unwrap(),
+            LogoInfoResponse::default()
+        );
+    }
+}
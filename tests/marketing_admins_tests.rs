@@ -0,0 +1,155 @@
+#[cfg(all(test, feature = "marketing-admins"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut};
+    use cw20::Logo;
+
+    use cw20_base::contract::{execute, instantiate, query_marketing_info};
+    use cw20_base::marketing_admins::query_marketing_admins;
+    use cw20_base::msg::{ExecuteMsg, FieldUpdate, InstantiateMarketingInfo, InstantiateMsg};
+    use cw20_base::ContractError;
+
+    fn setup(deps: DepsMut, marketing: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Marketed".to_string(),
+            symbol: "MKT".to_string(),
+            decimals: 6,
+            marketing: Some(InstantiateMarketingInfo {
+                project: Some("Project".to_string()),
+                description: None,
+                marketing: Some(marketing.to_string()),
+                logo: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn nominate(marketing: &str) -> ExecuteMsg {
+        ExecuteMsg::UpdateMarketing {
+            project: FieldUpdate::Unchanged,
+            description: FieldUpdate::Unchanged,
+            marketing: FieldUpdate::Set(marketing.to_string()),
+        }
+    }
+
+    #[test]
+    fn nominee_takes_over_once_it_accepts() {
+        let mut deps = mock_dependencies();
+        let marketing = deps.api.addr_make("marketing");
+        let nominee = deps.api.addr_make("nominee");
+        setup(deps.as_mut(), &marketing);
+
+        let msg = nominate(nominee.as_str());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            msg,
+        )
+        .unwrap();
+        // nothing changes until then, so a mistyped nominee can be replaced
+        let info = query_marketing_info(deps.as_ref()).unwrap();
+        assert_eq!(info.marketing, Some(marketing.clone()));
+        let admins = query_marketing_admins(deps.as_ref()).unwrap();
+        assert_eq!(admins.pending, Some(nominee.clone()));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            ExecuteMsg::AcceptMarketingAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&nominee, &[]),
+            ExecuteMsg::AcceptMarketingAdmin {},
+        )
+        .unwrap();
+
+        let info = query_marketing_info(deps.as_ref()).unwrap();
+        assert_eq!(info.marketing, Some(nominee.clone()));
+        assert_eq!(info.project, Some("Project".to_string()));
+        assert_eq!(query_marketing_admins(deps.as_ref()).unwrap().pending, None);
+
+        // the previous admin is out
+        let msg = ExecuteMsg::UploadLogo(Logo::Url("url".to_string()));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn additional_admins_update_the_metadata() {
+        let mut deps = mock_dependencies();
+        let marketing = deps.api.addr_make("marketing");
+        let designer = deps.api.addr_make("designer");
+        setup(deps.as_mut(), &marketing);
+
+        let msg = ExecuteMsg::UpdateMarketingAdmins {
+            add: vec![designer.to_string()],
+            remove: vec![],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&designer, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            query_marketing_admins(deps.as_ref()).unwrap().admins,
+            vec![designer.clone()]
+        );
+
+        let msg = ExecuteMsg::UploadLogo(Logo::Url("url".to_string()));
+        execute(deps.as_mut(), mock_env(), message_info(&designer, &[]), msg).unwrap();
+        let msg = ExecuteMsg::UpdateMarketing {
+            project: FieldUpdate::Unchanged,
+            description: FieldUpdate::Set("Description".to_string()),
+            marketing: FieldUpdate::Unchanged,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&designer, &[]), msg).unwrap();
+        assert_eq!(
+            query_marketing_info(deps.as_ref()).unwrap().description,
+            Some("Description".to_string())
+        );
+
+        // but only the marketing admin hands over the role
+        let msg = nominate(designer.as_str());
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&designer, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // clearing the marketing admin drops the others too
+        let msg = nominate("");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&marketing, &[]),
+            msg,
+        )
+        .unwrap();
+        assert!(query_marketing_admins(deps.as_ref())
+            .unwrap()
+            .admins
+            .is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(all(test, feature = "max-wallet"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Binary, DepsMut, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::max_wallet::query_max_wallet;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMaxWallet, InstantiateMsg};
+    use cw20_base::restrictions::{query_detect_transfer_restriction, MAX_WALLET_EXCEEDED};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, treasury: &Addr, pool: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Fair Launch".to_string(),
+            symbol: "FAIR".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: treasury.to_string(),
+                amount: Uint128::new(10_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: admin.to_string(),
+                cap: None,
+            }),
+            admin: Some(admin.to_string()),
+            max_wallet: Some(InstantiateMaxWallet {
+                amount: Uint128::new(1_000),
+                exempt: vec![treasury.to_string(), pool.to_string()],
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn balances_are_capped_unless_exempt() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let treasury = deps.api.addr_make("treasury");
+        let pool = deps.api.addr_make("pool");
+        let buyer = deps.api.addr_make("buyer");
+        do_instantiate(deps.as_mut(), &admin, &treasury, &pool);
+
+        let transfer = |recipient: &Addr, amount: u128| ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        let info = message_info(&treasury, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&buyer, 800),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&buyer, 201),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MaxWalletExceeded {});
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            treasury.to_string(),
+            buyer.to_string(),
+            Uint128::new(201),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, MAX_WALLET_EXCEEDED);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&buyer, 200),
+        )
+        .unwrap();
+
+        // sending to a contract is capped as well
+        let send = ExecuteMsg::Send {
+            contract: buyer.to_string(),
+            amount: Uint128::new(1),
+            msg: Binary::default(),
+            memo: None,
+            safe: false,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), send).unwrap_err();
+        assert_eq!(err, ContractError::MaxWalletExceeded {});
+
+        // and so is minting
+        let mint = ExecuteMsg::Mint {
+            recipient: buyer.to_string(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), mint).unwrap_err();
+        assert_eq!(err, ContractError::MaxWalletExceeded {});
+
+        // exempt accounts hold any amount
+        execute(deps.as_mut(), mock_env(), info, transfer(&pool, 5_000)).unwrap();
+        let balance = query_balance(deps.as_ref(), pool.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(5_000));
+    }
+
+    #[test]
+    fn admin_updates_the_max_wallet() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let treasury = deps.api.addr_make("treasury");
+        let pool = deps.api.addr_make("pool");
+        let buyer = deps.api.addr_make("buyer");
+        do_instantiate(deps.as_mut(), &admin, &treasury, &pool);
+
+        let msg = ExecuteMsg::UpdateMaxWallet {
+            amount: Some(Uint128::new(2_000)),
+            add_exempt: vec![],
+            remove_exempt: vec![pool.to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&treasury, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let res = query_max_wallet(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.amount, Some(Uint128::new(2_000)));
+        assert_eq!(res.exempt, vec![treasury.to_string()]);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: buyer.to_string(),
+            amount: Uint128::new(2_000),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&treasury, &[]), msg).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use cw20_base::msg::InstantiateMsg;
+use cw20_base::msg::{InstantiateMsg, SymbolValidation};
 
 #[cfg(test)]
 mod tests {
@@ -47,4 +47,72 @@ mod tests {
             assert!(!msg.has_valid_symbol());
         });
     }
+
+    #[test]
+    fn validate_instantiatemsg_relaxed_symbol() {
+        let mut msg = InstantiateMsg {
+            symbol: "wstETH.axl".to_string(),
+            ..InstantiateMsg::default()
+        };
+        assert!(!msg.has_valid_symbol());
+
+        msg.symbol_validation = SymbolValidation::Relaxed;
+        assert!(msg.has_valid_symbol());
+        msg.symbol = "$".to_string();
+        assert!(msg.has_valid_symbol());
+        // counted in characters, not bytes
+        msg.symbol = str::repeat("é", 20);
+        assert!(msg.has_valid_symbol());
+
+        msg.symbol = String::new();
+        assert!(!msg.has_valid_symbol());
+        msg.symbol = str::repeat("é", 21);
+        assert!(!msg.has_valid_symbol());
+        msg.symbol = "wst ETH".to_string();
+        assert!(!msg.has_valid_symbol());
+        msg.symbol = "ETH\n".to_string();
+        assert!(!msg.has_valid_symbol());
+    }
+
+    #[test]
+    #[cfg(feature = "marketing")]
+    fn update_marketing_fields_are_tri_state() {
+        use cosmwasm_std::{from_json, to_json_string};
+        use cw20_base::msg::{ExecuteMsg, FieldUpdate};
+
+        // cw20 clients send plain strings and nulls
+        let msg: ExecuteMsg = from_json(
+            r#"{"update_marketing":{"project":"https://example.com","description":null}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            ExecuteMsg::UpdateMarketing {
+                project: FieldUpdate::Set("https://example.com".to_string()),
+                description: FieldUpdate::Unchanged,
+                marketing: FieldUpdate::Unchanged,
+            }
+        );
+
+        let msg: ExecuteMsg =
+            from_json(r#"{"update_marketing":{"description":{"clear":{}}}}"#).unwrap();
+        assert_eq!(
+            msg,
+            ExecuteMsg::UpdateMarketing {
+                project: FieldUpdate::Unchanged,
+                description: FieldUpdate::Clear,
+                marketing: FieldUpdate::Unchanged,
+            }
+        );
+        assert_eq!(
+            to_json_string(&msg).unwrap(),
+            r#"{"update_marketing":{"project":null,"description":{"clear":{}},"marketing":null}}"#
+        );
+
+        // a blank value clears too, as in cw20
+        assert_eq!(
+            FieldUpdate::Set(" ".to_string()).apply(Some("old".to_string())),
+            None
+        );
+    }
 }
@@ -0,0 +1,294 @@
+#[cfg(all(test, feature = "meta-tx"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{to_json_binary, Addr, Binary, DepsMut, Env, Timestamp, Uint128};
+    use cw20::Cw20Coin;
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use k256::ecdsa::{Signature, SigningKey};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::meta_tx::{
+        authorization_hash, query_authorization_state, query_signing_info, signing_hash,
+    };
+    use cw20_base::msg::{
+        ExecuteMsg, InstantiateMsg, SignedMsg, SignedPayload, TransferAuthorization,
+    };
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, owners: &[&Addr]) {
+        let msg = InstantiateMsg {
+            name: "Relayed".to_string(),
+            symbol: "RLY".to_string(),
+            decimals: 6,
+            initial_balances: owners
+                .iter()
+                .map(|owner| Cw20Coin {
+                    address: owner.to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .collect(),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn pubkey(key: &SigningKey) -> Binary {
+        Binary::from(key.verifying_key().to_encoded_point(true).as_bytes())
+    }
+
+    fn transfer(owner: &Addr, recipient: &Addr, amount: u128) -> Binary {
+        to_json_binary(&SignedPayload {
+            owner: owner.to_string(),
+            msg: SignedMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+                memo: None,
+            },
+        })
+        .unwrap()
+    }
+
+    fn sign(key: &SigningKey, env: &Env, nonce: u64, payload: Binary) -> ExecuteMsg {
+        let signature: Signature = key
+            .sign_prehash(&signing_hash(env, nonce, &payload))
+            .unwrap();
+        ExecuteMsg::ExecuteSigned {
+            payload,
+            nonce,
+            signature: Binary::from(signature.to_bytes().as_slice()),
+        }
+    }
+
+    #[test]
+    fn relayer_executes_signed_transfer() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let relayer = deps.api.addr_make("relayer");
+        let recipient = deps.api.addr_make("recipient");
+        do_instantiate(deps.as_mut(), &[&owner]);
+        let key = signing_key(1);
+        let env = mock_env();
+
+        // nothing can be relayed before the owner sets a key
+        let msg = sign(&key, &env, 0, transfer(&owner, &recipient, 100));
+        let relay = message_info(&relayer, &[]);
+        let err = execute(deps.as_mut(), env.clone(), relay.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoSigningKey {});
+
+        let set_key = ExecuteMsg::SetSigningKey {
+            pubkey: Some(pubkey(&key)),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &[]),
+            set_key,
+        )
+        .unwrap();
+        let res = execute(deps.as_mut(), env.clone(), relay.clone(), msg.clone()).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "from" && attr.value == owner.as_str()));
+        let balance = query_balance(deps.as_ref(), recipient.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(100));
+
+        // the same signature can't run twice
+        let err = execute(deps.as_mut(), env.clone(), relay.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidNonce { expected: 1 });
+        let info = query_signing_info(deps.as_ref(), env.clone(), owner.to_string()).unwrap();
+        assert_eq!(info.nonce, 1);
+        assert_eq!(info.pubkey, Some(pubkey(&key)));
+
+        let msg = sign(&key, &env, 1, transfer(&owner, &recipient, 200));
+        execute(deps.as_mut(), env, relay, msg).unwrap();
+        let balance = query_balance(deps.as_ref(), owner.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(700));
+    }
+
+    #[test]
+    fn signatures_are_bound_to_owner_and_contract() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let relayer = deps.api.addr_make("relayer");
+        do_instantiate(deps.as_mut(), &[&owner]);
+        let key = signing_key(1);
+        let env = mock_env();
+        let set_key = ExecuteMsg::SetSigningKey {
+            pubkey: Some(pubkey(&key)),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &[]),
+            set_key,
+        )
+        .unwrap();
+        let relay = message_info(&relayer, &[]);
+
+        // signed by another key
+        let msg = sign(&signing_key(2), &env, 0, transfer(&owner, &relayer, 100));
+        let err = execute(deps.as_mut(), env.clone(), relay.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        // signed for another token
+        let mut other_token = mock_env();
+        other_token.contract.address = deps.api.addr_make("other_token");
+        let msg = sign(&key, &other_token, 0, transfer(&owner, &relayer, 100));
+        let err = execute(deps.as_mut(), env.clone(), relay.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        // a payload swapped under a valid signature
+        let msg = match sign(&key, &env, 0, transfer(&owner, &relayer, 100)) {
+            ExecuteMsg::ExecuteSigned {
+                nonce, signature, ..
+            } => ExecuteMsg::ExecuteSigned {
+                payload: transfer(&owner, &relayer, 900),
+                nonce,
+                signature,
+            },
+            _ => unreachable!(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), relay, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        let set_key = ExecuteMsg::SetSigningKey {
+            pubkey: Some(Binary::from(vec![0x05; 33])),
+        };
+        let err = execute(deps.as_mut(), env, message_info(&owner, &[]), set_key).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPubkey {});
+    }
+
+    fn authorize(
+        key: &SigningKey,
+        env: &Env,
+        from: &Addr,
+        to: &Addr,
+        amount: u128,
+        nonce: u8,
+    ) -> ExecuteMsg {
+        let authorization = TransferAuthorization {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: Uint128::new(amount),
+            valid_after: Timestamp::from_seconds(0),
+            valid_before: env.block.time.plus_seconds(60),
+            nonce: Binary::from([nonce; 32]),
+        };
+        let hash = authorization_hash(env, &authorization).unwrap();
+        let signature: Signature = key.sign_prehash(&hash).unwrap();
+        ExecuteMsg::TransferWithAuthorization {
+            authorization,
+            signature: Binary::from(signature.to_bytes().as_slice()),
+        }
+    }
+
+    #[test]
+    fn authorizations_run_once_in_any_order() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let processor = deps.api.addr_make("processor");
+        let merchant = deps.api.addr_make("merchant");
+        do_instantiate(deps.as_mut(), &[&alice, &bob]);
+        let env = mock_env();
+        for (owner, seed) in [(&alice, 1), (&bob, 2)] {
+            let set_key = ExecuteMsg::SetSigningKey {
+                pubkey: Some(pubkey(&signing_key(seed))),
+            };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(owner, &[]),
+                set_key,
+            )
+            .unwrap();
+        }
+
+        // random nonces don't have to be used in the order they were signed
+        let processor_info = message_info(&processor, &[]);
+        let first = authorize(&signing_key(1), &env, &alice, &merchant, 100, 7);
+        let second = authorize(&signing_key(1), &env, &alice, &merchant, 200, 3);
+        let bobs = authorize(&signing_key(2), &env, &bob, &merchant, 300, 7);
+        for msg in [second, bobs, first.clone()] {
+            execute(deps.as_mut(), env.clone(), processor_info.clone(), msg).unwrap();
+        }
+        let balance = query_balance(deps.as_ref(), merchant.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(600));
+
+        let err = execute(deps.as_mut(), env.clone(), processor_info.clone(), first).unwrap_err();
+        assert_eq!(err, ContractError::AuthorizationUsed {});
+        let state =
+            query_authorization_state(deps.as_ref(), alice.to_string(), Binary::from([7u8; 32]))
+                .unwrap();
+        assert!(state.used);
+
+        // a cancelled authorization can't be used anymore
+        let msg = authorize(&signing_key(1), &env, &alice, &merchant, 100, 9);
+        let cancel = ExecuteMsg::CancelAuthorization {
+            nonce: Binary::from([9u8; 32]),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            cancel,
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), env, processor_info, msg).unwrap_err();
+        assert_eq!(err, ContractError::AuthorizationUsed {});
+    }
+
+    #[test]
+    fn authorizations_have_a_validity_window() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let merchant = deps.api.addr_make("merchant");
+        do_instantiate(deps.as_mut(), &[&alice]);
+        let env = mock_env();
+        let set_key = ExecuteMsg::SetSigningKey {
+            pubkey: Some(pubkey(&signing_key(1))),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            set_key,
+        )
+        .unwrap();
+        let info = message_info(&merchant, &[]);
+
+        let msg = authorize(&signing_key(1), &env, &alice, &merchant, 100, 1);
+        let mut late = env.clone();
+        late.block.time = late.block.time.plus_seconds(60);
+        let err = execute(deps.as_mut(), late, info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::AuthorizationExpired {});
+
+        let msg = match authorize(&signing_key(1), &env, &alice, &merchant, 100, 1) {
+            ExecuteMsg::TransferWithAuthorization {
+                mut authorization,
+                signature,
+            } => {
+                authorization.valid_after = env.block.time;
+                ExecuteMsg::TransferWithAuthorization {
+                    authorization,
+                    signature,
+                }
+            }
+            _ => unreachable!(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::AuthorizationNotYetValid {});
+
+        let msg = ExecuteMsg::CancelAuthorization {
+            nonce: Binary::from([1u8; 16]),
+        };
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAuthorizationNonce {});
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(all(test, feature = "min-transfer"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::interfaces::query_features;
+    use cw20_base::min_transfer::query_min_transfer_amount;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::restrictions::{query_detect_transfer_restriction, TRANSFER_BELOW_MINIMUM};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Dustless".to_string(),
+            symbol: "DST".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: admin.to_string(),
+                cap: None,
+            }),
+            admin: Some(admin.to_string()),
+            min_transfer_amount: Some(Uint128::new(10)),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn transfer(recipient: &Addr, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn dust_transfers_are_rejected() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let spammed = deps.api.addr_make("spammed");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+        assert!(query_features(deps.as_ref()).unwrap().min_transfer_amount);
+
+        let info = message_info(&holder, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&spammed, 1),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TransferBelowMinimum {
+                min: Uint128::new(10)
+            }
+        );
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            holder.to_string(),
+            spammed.to_string(),
+            Uint128::new(1),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, TRANSFER_BELOW_MINIMUM);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            transfer(&spammed, 10),
+        )
+        .unwrap();
+
+        // mints and burns of any size still go through
+        let msg = ExecuteMsg::Mint {
+            recipient: holder.to_string(),
+            amount: Uint128::new(1),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let balance = query_balance(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(990));
+    }
+
+    #[test]
+    fn admin_updates_the_minimum() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let msg = ExecuteMsg::UpdateMinTransferAmount { amount: None };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert_eq!(
+            query_min_transfer_amount(deps.as_ref()).unwrap().amount,
+            None
+        );
+
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), mock_env(), info, transfer(&admin, 1)).unwrap();
+    }
+}
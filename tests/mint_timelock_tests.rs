@@ -0,0 +1,217 @@
+#[cfg(all(test, feature = "mint-timelock"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::mint_timelock::query_pending_mints;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::state::MintTimelock;
+    use cw20_base::ContractError;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, minter: &Addr, admin: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Timelock".to_string(),
+            symbol: "TIME".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            admin: Some(admin.to_string()),
+            mint_timelock: Some(MintTimelock {
+                threshold: Uint128::new(1_000),
+                delay: DAY,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn large_mints_wait_for_the_timelock() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let admin = deps.api.addr_make("admin");
+        let rcpt = deps.api.addr_make("rcpt");
+        do_instantiate(deps.as_mut(), &minter, &admin);
+
+        // small mints go through directly
+        let mint = |amount: u128| ExecuteMsg::Mint {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(amount),
+        };
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&minter, &[]),
+            mint(1_000),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&minter, &[]),
+            mint(1_001),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MintTimelocked {});
+
+        let msg = ExecuteMsg::ProposeMint {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(5_000),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap();
+        let pending = query_pending_mints(deps.as_ref(), None, None).unwrap();
+        assert_eq!(pending.mints.len(), 1);
+        assert_eq!(pending.mints[0].executable_at, env_at(DAY).block.time);
+
+        let msg = ExecuteMsg::ExecuteMint { id: 1 };
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY - 1),
+            message_info(&minter, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TimelockNotElapsed {});
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY),
+            message_info(&rcpt, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env_at(DAY),
+            message_info(&minter, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            query_balance(deps.as_ref(), rcpt.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(6_000)
+        );
+
+        let err = execute(deps.as_mut(), env_at(DAY), message_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingMint {});
+    }
+
+    #[test]
+    fn split_mints_share_the_threshold() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let admin = deps.api.addr_make("admin");
+        let rcpt = deps.api.addr_make("rcpt");
+        do_instantiate(deps.as_mut(), &minter, &admin);
+
+        let mint = |amount: u128| ExecuteMsg::Mint {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(amount),
+        };
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            message_info(&minter, &[]),
+            mint(600),
+        )
+        .unwrap();
+        // the second half of a split mint goes over the threshold within the delay
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY / 2),
+            message_info(&minter, &[]),
+            mint(600),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MintTimelocked {});
+        let batch = ExecuteMsg::MintBatch {
+            recipients: vec![
+                Cw20Coin {
+                    address: rcpt.to_string(),
+                    amount: Uint128::new(300),
+                },
+                Cw20Coin {
+                    address: admin.to_string(),
+                    amount: Uint128::new(300),
+                },
+            ],
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY / 2),
+            message_info(&minter, &[]),
+            batch,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MintTimelocked {});
+        execute(
+            deps.as_mut(),
+            env_at(DAY / 2),
+            message_info(&minter, &[]),
+            mint(400),
+        )
+        .unwrap();
+
+        // once the first mint has left the window there is room again
+        execute(
+            deps.as_mut(),
+            env_at(DAY + DAY / 24),
+            message_info(&minter, &[]),
+            mint(600),
+        )
+        .unwrap();
+        assert_eq!(
+            query_balance(deps.as_ref(), rcpt.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(1_600)
+        );
+    }
+
+    #[test]
+    fn admin_can_cancel_pending_mints() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let admin = deps.api.addr_make("admin");
+        let rcpt = deps.api.addr_make("rcpt");
+        do_instantiate(deps.as_mut(), &minter, &admin);
+
+        let msg = ExecuteMsg::ProposeMint {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(5_000),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap();
+
+        let cancel = ExecuteMsg::CancelMint { id: 1 };
+        let err = execute(
+            deps.as_mut(),
+            env_at(1),
+            message_info(&rcpt, &[]),
+            cancel.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env_at(1), message_info(&admin, &[]), cancel).unwrap();
+
+        let msg = ExecuteMsg::ExecuteMint { id: 1 };
+        let err = execute(deps.as_mut(), env_at(DAY), message_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingMint {});
+        let pending = query_pending_mints(deps.as_ref(), None, None).unwrap();
+        assert!(pending.mints.is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+#[cfg(all(test, feature = "multi-token"))]
+mod tests {
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{Addr, DepsMut, OwnedDeps, StdError, Uint128};
+    use cw20::{Cw20Coin, TokenInfoResponse};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MultiTokenAmount};
+    use cw20_base::multi_token::{query_multi_balance, query_multi_token_info, query_multi_tokens};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Game items".to_string(),
+            symbol: "ITEMS".to_string(),
+            decimals: 0,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(10),
+            }],
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn create(token_id: &str, symbol: &str) -> ExecuteMsg {
+        ExecuteMsg::CreateToken {
+            token_id: token_id.to_string(),
+            name: format!("{symbol} item"),
+            symbol: symbol.to_string(),
+            decimals: 0,
+        }
+    }
+
+    fn amounts(amounts: &[(&str, u128)]) -> Vec<MultiTokenAmount> {
+        amounts
+            .iter()
+            .map(|(token_id, amount)| MultiTokenAmount {
+                token_id: token_id.to_string(),
+                amount: Uint128::new(*amount),
+            })
+            .collect()
+    }
+
+    fn balance(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        token_id: &str,
+        addr: &Addr,
+    ) -> u128 {
+        query_multi_balance(deps.as_ref(), token_id.to_string(), addr.to_string())
+            .unwrap()
+            .balance
+            .u128()
+    }
+
+    #[test]
+    fn sub_tokens_mint_and_transfer() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        do_instantiate(deps.as_mut(), &admin, &alice);
+
+        let info = message_info(&admin, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create("sword", "SWORD"),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create("gold", "GOLD"),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create("gold", "GOLD"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MultiTokenExists {});
+
+        let msg = ExecuteMsg::MintMulti {
+            recipient: alice.to_string(),
+            amounts: amounts(&[("sword", 2), ("gold", 500)]),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::TransferMulti {
+            recipient: bob.to_string(),
+            amounts: amounts(&[("sword", 1), ("gold", 120)]),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap();
+        assert_eq!(res.attributes[0].value, "transfer_multi");
+
+        assert_eq!(balance(&deps, "sword", &alice), 1);
+        assert_eq!(balance(&deps, "sword", &bob), 1);
+        assert_eq!(balance(&deps, "gold", &alice), 380);
+        assert_eq!(balance(&deps, "gold", &bob), 120);
+        assert_eq!(
+            query_multi_token_info(deps.as_ref(), "gold".to_string()).unwrap(),
+            TokenInfoResponse {
+                name: "GOLD item".to_string(),
+                symbol: "GOLD".to_string(),
+                decimals: 0,
+                total_supply: Uint128::new(500),
+            }
+        );
+
+        // the main token is untouched
+        assert_eq!(
+            query_balance(deps.as_ref(), alice.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(10)
+        );
+
+        let ids = query_multi_tokens(deps.as_ref(), None, None).unwrap();
+        assert_eq!(ids.token_ids, vec!["gold".to_string(), "sword".to_string()]);
+        let ids = query_multi_tokens(deps.as_ref(), Some("gold".to_string()), None).unwrap();
+        assert_eq!(ids.token_ids, vec!["sword".to_string()]);
+    }
+
+    #[test]
+    fn transfer_multi_is_atomic() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        do_instantiate(deps.as_mut(), &admin, &alice);
+
+        let info = message_info(&admin, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create("sword", "SWORD"),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::MintMulti {
+            recipient: alice.to_string(),
+            amounts: amounts(&[("sword", 2)]),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // more than held
+        let msg = ExecuteMsg::TransferMulti {
+            recipient: bob.to_string(),
+            amounts: amounts(&[("sword", 3)]),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        // unknown ids fail the whole message
+        let msg = ExecuteMsg::TransferMulti {
+            recipient: bob.to_string(),
+            amounts: amounts(&[("shield", 1)]),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnknownMultiToken {
+                token_id: "shield".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn only_admin_creates_and_mints() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let alice = deps.api.addr_make("alice");
+        do_instantiate(deps.as_mut(), &admin, &alice);
+
+        let info = message_info(&alice, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create("sword", "SWORD"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let admin_info = message_info(&admin, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            create("sword", "SWORD"),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::MintMulti {
+            recipient: alice.to_string(),
+            amounts: amounts(&[("sword", 1)]),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        for token_id in ["", "has:colon", "has space", &"x".repeat(33)] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                admin_info.clone(),
+                create(token_id, "SWORD"),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::InvalidMultiTokenId { max: 32 });
+        }
+        let err = execute(deps.as_mut(), mock_env(), admin_info, create("bow", "B")).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::GenericErr { .. })
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(all(test, feature = "query-client", feature = "enumerable"))]
+mod tests {
+    use std::convert::Infallible;
+    use std::future::{ready, Future};
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, Addr, Binary, HexBinary, OwnedDeps, Uint128};
+    use cw20::{Cw20Coin, TokenInfoResponse};
+    use serde::Deserialize;
+
+    use cw20_base::contract::{instantiate, query};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use cw20_base::query_client::{Cw20QueryClient, QueryClientError, RpcTransport};
+
+    /// A node answering `abci_query` from a token in mock storage
+    struct MockNode {
+        deps: OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    }
+
+    #[derive(Deserialize)]
+    struct Request {
+        method: String,
+        params: Params,
+    }
+
+    #[derive(Deserialize)]
+    struct Params {
+        path: String,
+        data: HexBinary,
+        height: String,
+    }
+
+    impl RpcTransport for MockNode {
+        type Error = Infallible;
+
+        fn post(&self, body: Vec<u8>) -> impl Future<Output = Result<Vec<u8>, Infallible>> {
+            let request: Request = from_json(body).unwrap();
+            assert_eq!(request.method, "abci_query");
+            assert_eq!(
+                request.params.path,
+                "/cosmwasm.wasm.v1.Query/SmartContractState"
+            );
+            assert_eq!(request.params.height, "0");
+
+            let mut data = request.params.data.as_slice();
+            assert_eq!(read_field(&mut data, 0x0a), b"token");
+            let msg: QueryMsg = from_json(read_field(&mut data, 0x12)).unwrap();
+
+            let response = match query(self.deps.as_ref(), mock_env(), msg) {
+                Ok(data) => {
+                    let mut value = vec![0x0a, data.len() as u8];
+                    value.extend_from_slice(&data);
+                    format!(
+                        r#"{{"code":0,"log":"","info":"","index":"0","key":null,"value":"{}","proofOps":null,"height":"7","codespace":""}}"#,
+                        Binary::from(value).to_base64()
+                    )
+                }
+                Err(err) => format!(
+                    r#"{{"code":5,"log":"{err}","value":null,"height":"7","codespace":"wasm"}}"#
+                ),
+            };
+            let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":{{"response":{response}}}}}"#);
+            ready(Ok(body.into_bytes()))
+        }
+    }
+
+    /// Reads a protobuf bytes field with the given key
+    fn read_field<'a>(data: &mut &'a [u8], key: u8) -> &'a [u8] {
+        assert_eq!(data[0], key);
+        let (mut len, mut pos) = (0, 1);
+        while data[pos] >= 0x80 {
+            len |= ((data[pos] & 0x7f) as usize) << (7 * (pos - 1));
+            pos += 1;
+        }
+        len |= (data[pos] as usize) << (7 * (pos - 1));
+        let (field, rest) = data[pos + 1..].split_at(len);
+        *data = rest;
+        field
+    }
+
+    /// A node refusing every request
+    struct BrokenNode;
+
+    impl RpcTransport for BrokenNode {
+        type Error = Infallible;
+
+        fn post(&self, _body: Vec<u8>) -> impl Future<Output = Result<Vec<u8>, Infallible>> {
+            let body = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32603,"message":"Internal error","data":"height 9 is not available"}}"#;
+            ready(Ok(body.as_bytes().to_vec()))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        match pin!(future).poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock nodes answer right away"),
+        }
+    }
+
+    fn mock_node(owner: &Addr, spender: &Addr) -> MockNode {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Queried".to_string(),
+            symbol: "QRY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(300),
+            expires: None,
+        };
+        let info = message_info(owner, &[]);
+        cw20_base::contract::execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        MockNode { deps }
+    }
+
+    #[test]
+    fn queries_the_token() {
+        let api = MockApi::default();
+        let owner = api.addr_make("owner");
+        let spender = api.addr_make("spender");
+        let client = Cw20QueryClient::new(mock_node(&owner, &spender), "token");
+
+        let balance = block_on(client.balance(owner.as_str())).unwrap();
+        assert_eq!(balance.balance, Uint128::new(1_000));
+        let info = block_on(client.token_info()).unwrap();
+        assert_eq!(
+            info,
+            TokenInfoResponse {
+                name: "Queried".to_string(),
+                symbol: "QRY".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(1_000),
+            }
+        );
+        let accounts = block_on(client.all_accounts(None, None)).unwrap();
+        assert_eq!(accounts.accounts, vec![owner.to_string()]);
+        let allowance = block_on(client.allowance(owner.as_str(), spender.as_str())).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(300));
+    }
+
+    #[test]
+    fn surfaces_failures() {
+        let api = MockApi::default();
+        let owner = api.addr_make("owner");
+        let spender = api.addr_make("spender");
+        let client = Cw20QueryClient::new(mock_node(&owner, &spender), "token");
+
+        let err = block_on(client.balance("not an address")).unwrap_err();
+        assert!(matches!(err, QueryClientError::Query { code: 5, .. }));
+
+        let client = Cw20QueryClient::new(BrokenNode, "token").at_height(9);
+        let err = block_on(client.token_info()).unwrap_err();
+        match err {
+            QueryClientError::Rpc { code, message } => {
+                assert_eq!(code, -32603);
+                assert_eq!(message, "height 9 is not available");
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+}
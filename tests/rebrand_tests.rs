@@ -0,0 +1,137 @@
+#[cfg(all(test, feature = "rebrand"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, StdError};
+
+    use cw20_base::contract::{execute, instantiate, query_token_info};
+    use cw20_base::interfaces::query_features;
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, SymbolValidation};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, freeze_token_info: bool) {
+        let msg = InstantiateMsg {
+            name: "Old Name".to_string(),
+            symbol: "OLD".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            freeze_token_info,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn rename(name: Option<&str>, symbol: Option<&str>) -> ExecuteMsg {
+        ExecuteMsg::UpdateTokenInfo {
+            name: name.map(str::to_string),
+            symbol: symbol.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn admin_renames_the_token() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        do_instantiate(deps.as_mut(), &admin, false);
+        assert!(query_features(deps.as_ref()).unwrap().token_info_updatable);
+
+        let info = message_info(&deps.api.addr_make("someone"), &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            rename(Some("Hijacked"), None),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let info = message_info(&admin, &[]);
+        let msg = rename(Some("New Name"), Some("NEW"));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let token = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(token.name, "New Name");
+        assert_eq!(token.symbol, "NEW");
+        assert_eq!(token.decimals, 6);
+
+        // only the given fields change
+        let msg = rename(None, Some("NEWER"));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let token = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(token.name, "New Name");
+        assert_eq!(token.symbol, "NEWER");
+
+        // validated as at instantiation
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            rename(Some("N"), None),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::GenericErr { .. })
+        ));
+        let err = execute(deps.as_mut(), mock_env(), info, rename(None, Some("N3W"))).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::GenericErr { .. })
+        ));
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().symbol, "NEWER");
+    }
+
+    #[test]
+    fn frozen_token_info_stays() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        do_instantiate(deps.as_mut(), &admin, true);
+        assert!(!query_features(deps.as_ref()).unwrap().token_info_updatable);
+
+        let info = message_info(&admin, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            rename(Some("New Name"), None),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TokenInfoFrozen {});
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().name, "Old Name");
+    }
+
+    #[test]
+    fn renames_follow_the_symbol_validation() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let msg = InstantiateMsg {
+            name: "Wrapped Staked Ether".to_string(),
+            symbol: "wstETH.axl".to_string(),
+            decimals: 18,
+            admin: Some(admin.to_string()),
+            symbol_validation: SymbolValidation::Relaxed,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let info = message_info(&admin, &[]);
+        let msg = rename(None, Some("wstETH.eth"));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().symbol,
+            "wstETH.eth"
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            rename(None, Some("wst ETH")),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::GenericErr { .. })
+        ));
+    }
+}
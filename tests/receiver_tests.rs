@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        coins, to_json_binary, Addr, Deps, DepsMut, Env, Response, StdError, StdResult, Uint128,
+    };
+    use cw20::Cw20ReceiveMsg;
+    use cw_storage_plus::Item;
+
+    use cw20_base::receiver::{dispatch_receive, Cw20Receiver, ReceivedCw20};
+
+    const TOKEN: Item<Addr> = Item::new("token");
+    const DEPOSITED: Item<Uint128> = Item::new("deposited");
+
+    #[cw_serde]
+    enum HookMsg {
+        Deposit {},
+    }
+
+    struct Vault;
+
+    impl Cw20Receiver for Vault {
+        type Msg = HookMsg;
+        type Error = StdError;
+
+        fn accepts(deps: Deps, _env: &Env, token: &Addr) -> StdResult<bool> {
+            Ok(TOKEN.load(deps.storage)? == token)
+        }
+
+        fn receive(
+            deps: DepsMut,
+            _env: Env,
+            received: ReceivedCw20<HookMsg>,
+        ) -> StdResult<Response> {
+            match received.msg {
+                HookMsg::Deposit {} => DEPOSITED.save(deps.storage, &received.amount)?,
+            }
+            Ok(Response::new().add_attribute("depositor", received.sender))
+        }
+    }
+
+    #[test]
+    fn only_the_accepted_token_gets_through() {
+        let mut deps = mock_dependencies();
+        let token = deps.api.addr_make("token");
+        let forger = deps.api.addr_make("forger");
+        let alice = deps.api.addr_make("alice");
+        TOKEN.save(&mut deps.storage, &token).unwrap();
+        let wrapper = |msg| Cw20ReceiveMsg {
+            sender: alice.to_string(),
+            amount: Uint128::new(50),
+            msg,
+        };
+        let deposit = to_json_binary(&HookMsg::Deposit {}).unwrap();
+
+        let info = message_info(&forger, &[]);
+        let err =
+            dispatch_receive::<Vault>(deps.as_mut(), mock_env(), info, wrapper(deposit.clone()))
+                .unwrap_err();
+        assert!(err.to_string().contains("not accepted"));
+
+        let info = message_info(&token, &coins(1, "uatom"));
+        dispatch_receive::<Vault>(deps.as_mut(), mock_env(), info, wrapper(deposit.clone()))
+            .unwrap_err();
+
+        let info = message_info(&token, &[]);
+        let garbage = to_json_binary(&"withdraw").unwrap();
+        dispatch_receive::<Vault>(deps.as_mut(), mock_env(), info.clone(), wrapper(garbage))
+            .unwrap_err();
+        assert!(!DEPOSITED.exists(&deps.storage));
+
+        let res =
+            dispatch_receive::<Vault>(deps.as_mut(), mock_env(), info, wrapper(deposit)).unwrap();
+        assert_eq!(res.attributes[0].value, alice.to_string());
+        assert_eq!(DEPOSITED.load(&deps.storage).unwrap(), Uint128::new(50));
+    }
+}
@@ -0,0 +1,345 @@
+#[cfg(all(test, feature = "rescale-decimals"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{attr, Addr, DepsMut, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::allowances::query_allowance;
+    use cw20_base::contract::{
+        execute, instantiate, migrate, query_balance, query_minter, query_token_info,
+    };
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, RescaleDecimals};
+    use cw20_base::rescale::{query_rescale_status, rescale_amount};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, holders: &[(&Addr, u128)], minter: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Misprinted".to_string(),
+            symbol: "MSP".to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|(addr, amount)| Cw20Coin {
+                    address: addr.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: Some(Uint128::new(10_000_000)),
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn rescale_to(new_decimals: u8) -> MigrateMsg {
+        MigrateMsg {
+            rescale_decimals: Some(RescaleDecimals { new_decimals }),
+            ..MigrateMsg::default()
+        }
+    }
+
+    #[test]
+    fn amounts_round_down() {
+        let amount = Uint128::new(1_234_567);
+        assert_eq!(rescale_amount(amount, 6, 3).unwrap(), Uint128::new(1_234));
+        assert_eq!(
+            rescale_amount(amount, 6, 8).unwrap(),
+            Uint128::new(123_456_700)
+        );
+        assert_eq!(
+            rescale_amount(Uint128::new(999), 6, 3).unwrap(),
+            Uint128::zero()
+        );
+        rescale_amount(Uint128::MAX, 0, 18).unwrap_err();
+    }
+
+    #[test]
+    fn scale_down_in_chunks() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carl = deps.api.addr_make("carl");
+        let spender = deps.api.addr_make("spender");
+        let holders = [(&alice, 1_234_567), (&bob, 999), (&carl, 5)];
+        do_instantiate(deps.as_mut(), &holders, &alice);
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(1_500_000),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), rescale_to(3)).unwrap();
+        assert!(res.attributes.contains(&attr("rescale_decimals", "3")));
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().decimals, 3);
+        let cap = query_minter(deps.as_ref()).unwrap().unwrap().cap;
+        assert_eq!(cap, Some(Uint128::new(10_000)));
+        let status = query_rescale_status(deps.as_ref())
+            .unwrap()
+            .rescale
+            .unwrap();
+        assert_eq!((status.from_decimals, status.to_decimals), (6, 3));
+
+        // everything but ContinueMigration waits for the rescale
+        let msg = ExecuteMsg::Transfer {
+            recipient: bob.to_string(),
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let info = message_info(&alice, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::RescaleInProgress {});
+        // a second rescale can't start on top of it
+        let err = migrate(deps.as_mut(), mock_env(), rescale_to(0)).unwrap_err();
+        assert_eq!(err, ContractError::RescaleInProgress {});
+
+        let anyone = message_info(&deps.api.addr_make("anyone"), &[]);
+        let mut done = false;
+        let mut calls = 0;
+        while !done {
+            let msg = ExecuteMsg::ContinueMigration { limit: Some(2) };
+            let res = execute(deps.as_mut(), mock_env(), anyone.clone(), msg).unwrap();
+            done = res.attributes.contains(&attr("done", "true"));
+            calls += 1;
+        }
+        assert_eq!(calls, 3);
+        assert!(query_rescale_status(deps.as_ref())
+            .unwrap()
+            .rescale
+            .is_none());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            anyone,
+            ExecuteMsg::ContinueMigration { limit: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoRescaleInProgress {});
+
+        let balance = |addr: &Addr| query_balance(deps.as_ref(), addr.to_string()).unwrap();
+        assert_eq!(balance(&alice).balance, Uint128::new(1_234));
+        assert_eq!(balance(&bob).balance, Uint128::zero());
+        assert_eq!(balance(&carl).balance, Uint128::zero());
+        // the rounded off dust is burned
+        let token = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(token.total_supply, Uint128::new(1_234));
+        let allowance =
+            query_allowance(deps.as_ref(), alice.to_string(), spender.to_string()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(1_500));
+
+        // and transfers go through again
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn scale_up_and_invalid_targets() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        do_instantiate(deps.as_mut(), &[(&alice, 1_000)], &alice);
+
+        for new_decimals in [6, 19] {
+            let err = migrate(deps.as_mut(), mock_env(), rescale_to(new_decimals)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidRescale {});
+        }
+
+        migrate(deps.as_mut(), mock_env(), rescale_to(8)).unwrap();
+        let msg = ExecuteMsg::ContinueMigration { limit: None };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap();
+        assert!(res.attributes.contains(&attr("done", "true")));
+        let balance = query_balance(deps.as_ref(), alice.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(100_000));
+        let info = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(
+            (info.decimals, info.total_supply),
+            (8, Uint128::new(100_000))
+        );
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "allowance-limits",
+        feature = "faucet",
+        feature = "max-wallet",
+        feature = "min-transfer",
+        feature = "mint-timelock",
+        feature = "scheduled",
+        feature = "streams",
+        feature = "subscriptions",
+        feature = "transfer-limits",
+        feature = "vesting"
+    ))]
+    fn stored_amounts_follow_the_rescale() {
+        use cosmwasm_std::testing::MockApi;
+        use cosmwasm_std::Env;
+        use cw20::Expiration;
+        use cw20_base::msg::InstantiateMaxWallet;
+        use cw20_base::state::{
+            AllowanceLimit, FaucetConfig, MintTimelock, TransferLimit, VestingSchedule,
+        };
+
+        /// Every stored amount, in the order ContinueMigration rescales them
+        fn stored_amounts(deps: Deps) -> Vec<u128> {
+            use cosmwasm_std::{Deps, Order};
+            use cw20_base::state::{
+                streams, ALLOWANCE_LIMITS, DIRECT_MINT_BUCKETS, FAUCET, MAX_WALLET, MINT_TIMELOCK,
+                MIN_TRANSFER_AMOUNT, PENDING_MINTS, SCHEDULED_TRANSFERS, SUBSCRIPTIONS,
+                TRANSFER_LIMIT, VESTING_LOCKS,
+            };
+
+            let storage = deps.storage;
+            let mut amounts = vec![
+                MAX_WALLET.load(storage).unwrap(),
+                MIN_TRANSFER_AMOUNT.load(storage).unwrap(),
+                TRANSFER_LIMIT.load(storage).unwrap().amount,
+                MINT_TIMELOCK.load(storage).unwrap().threshold,
+                DIRECT_MINT_BUCKETS.load(storage).unwrap()[0].spent,
+                FAUCET.load(storage).unwrap().amount,
+            ];
+            for item in ALLOWANCE_LIMITS.range(storage, None, None, Order::Ascending) {
+                amounts.push(item.unwrap().1.amount);
+            }
+            for item in VESTING_LOCKS.range(storage, None, None, Order::Ascending) {
+                amounts.extend(item.unwrap().1.into_iter().map(|lock| lock.amount));
+            }
+            for item in streams().range(storage, None, None, Order::Ascending) {
+                let stream = item.unwrap().1;
+                amounts.extend([stream.amount, stream.withdrawn]);
+            }
+            for item in SCHEDULED_TRANSFERS.range(storage, None, None, Order::Ascending) {
+                amounts.push(item.unwrap().1.amount);
+            }
+            for item in SUBSCRIPTIONS.range(storage, None, None, Order::Ascending) {
+                let subscription = item.unwrap().1;
+                amounts.extend([subscription.amount_per_period, subscription.charged]);
+            }
+            for item in PENDING_MINTS.range(storage, None, None, Order::Ascending) {
+                amounts.push(item.unwrap().1.amount);
+            }
+            amounts.into_iter().map(Uint128::u128).collect()
+        }
+
+        fn contract_env(seconds: u64) -> Env {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env.contract.address = MockApi::default().addr_make("cw20");
+            env
+        }
+
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let spender = deps.api.addr_make("spender");
+        let msg = InstantiateMsg {
+            name: "Misprinted".to_string(),
+            symbol: "MSP".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: alice.to_string(),
+                amount: Uint128::new(20_000_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: alice.to_string(),
+                cap: None,
+            }),
+            mint_timelock: Some(MintTimelock {
+                threshold: Uint128::new(1_000_000),
+                delay: 86_400,
+            }),
+            max_wallet: Some(InstantiateMaxWallet {
+                amount: Uint128::new(50_000_000),
+                exempt: vec![],
+            }),
+            min_transfer_amount: Some(Uint128::new(1_000)),
+            transfer_limit: Some(TransferLimit {
+                amount: Uint128::new(5_000_000),
+                period: 86_400,
+            }),
+            faucet: Some(FaucetConfig {
+                amount: Uint128::new(100_000),
+                period: 86_400,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&alice, &[]);
+        instantiate(deps.as_mut(), contract_env(0), info.clone(), msg).unwrap();
+
+        let start = contract_env(0).block.time;
+        let msgs = vec![
+            ExecuteMsg::SetAllowanceLimit {
+                spender: spender.to_string(),
+                limit: Some(AllowanceLimit {
+                    amount: Uint128::new(300_000),
+                    period: 86_400,
+                }),
+            },
+            ExecuteMsg::MintLocked {
+                recipient: bob.to_string(),
+                amount: Uint128::new(400_000),
+                schedule: VestingSchedule {
+                    start,
+                    cliff: start,
+                    end: start.plus_seconds(1_000),
+                },
+            },
+            ExecuteMsg::CreateStream {
+                recipient: bob.to_string(),
+                amount: Uint128::new(2_000_000),
+                start,
+                end: start.plus_seconds(100),
+            },
+            ExecuteMsg::ScheduleTransfer {
+                recipient: bob.to_string(),
+                amount: Uint128::new(1_000_000),
+                execute_after: Expiration::AtHeight(1_000_000_000),
+            },
+            ExecuteMsg::Subscribe {
+                spender: spender.to_string(),
+                amount_per_period: Uint128::new(500_000),
+                period: 86_400,
+                expires: None,
+            },
+            ExecuteMsg::ProposeMint {
+                recipient: bob.to_string(),
+                amount: Uint128::new(3_000_000),
+            },
+        ];
+        for msg in msgs {
+            execute(deps.as_mut(), contract_env(0), info.clone(), msg).unwrap();
+        }
+        let msg = ExecuteMsg::WithdrawFromStream { id: 1 };
+        execute(
+            deps.as_mut(),
+            contract_env(50),
+            message_info(&bob, &[]),
+            msg,
+        )
+        .unwrap();
+        let before = stored_amounts(deps.as_ref());
+        assert_eq!(before.len(), 15);
+
+        let rescale = |deps: DepsMut, new_decimals: u8| {
+            let env = contract_env(60);
+            migrate(deps, env, rescale_to(new_decimals)).unwrap();
+        };
+        let finish = |deps: DepsMut| {
+            let msg = ExecuteMsg::ContinueMigration { limit: None };
+            let info = message_info(&alice, &[]);
+            let res = execute(deps, contract_env(60), info, msg).unwrap();
+            assert!(res.attributes.contains(&attr("done", "true")));
+        };
+
+        // down
+        rescale(deps.as_mut(), 3);
+        finish(deps.as_mut());
+        let scaled_down: Vec<u128> = before.iter().map(|amount| amount / 1_000).collect();
+        assert_eq!(stored_amounts(deps.as_ref()), scaled_down);
+
+        // and back up
+        rescale(deps.as_mut(), 6);
+        finish(deps.as_mut());
+        assert_eq!(stored_amounts(deps.as_ref()), before);
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(all(test, feature = "rescue"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, DepsMut, Env,
+        SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{BalanceResponse, Cw20ExecuteMsg};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{AssetInfo, ExecuteMsg, InstantiateMsg};
+    use cw20_base::ContractError;
+
+    fn contract_env() -> Env {
+        let mut env = mock_env();
+        env.contract.address = MockApi::default().addr_make("cw20");
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Rescue".to_string(),
+            symbol: "RESC".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, contract_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn admin_rescues_native_coins() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let rcpt = deps.api.addr_make("rcpt");
+        do_instantiate(deps.as_mut(), &admin);
+        deps.querier
+            .bank
+            .update_balance(contract_env().contract.address, coins(250, "uatom"));
+
+        let rescue = ExecuteMsg::Rescue {
+            asset: AssetInfo::Native {
+                denom: "uatom".to_string(),
+            },
+            recipient: rcpt.to_string(),
+        };
+        let info = message_info(&rcpt, &[]);
+        let err = execute(deps.as_mut(), contract_env(), info, rescue.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let info = message_info(&admin, &[]);
+        let res = execute(deps.as_mut(), contract_env(), info, rescue).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: rcpt.to_string(),
+                amount: coins(250, "uatom"),
+            })
+        );
+
+        // nothing held of this denom
+        let rescue = ExecuteMsg::Rescue {
+            asset: AssetInfo::Native {
+                denom: "uosmo".to_string(),
+            },
+            recipient: rcpt.to_string(),
+        };
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), contract_env(), info, rescue).unwrap_err();
+        assert_eq!(err, ContractError::NothingToRescue {});
+    }
+
+    #[test]
+    fn admin_rescues_foreign_cw20() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let rcpt = deps.api.addr_make("rcpt");
+        let foreign = deps.api.addr_make("foreign");
+        do_instantiate(deps.as_mut(), &admin);
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let _: cw20::Cw20QueryMsg = from_json(msg).unwrap();
+                let res = BalanceResponse {
+                    balance: Uint128::new(700),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        let rescue = ExecuteMsg::Rescue {
+            asset: AssetInfo::Cw20 {
+                contract_addr: foreign.to_string(),
+            },
+            recipient: rcpt.to_string(),
+        };
+        let info = message_info(&admin, &[]);
+        let res = execute(deps.as_mut(), contract_env(), info, rescue).unwrap();
+        let transfer = Cw20ExecuteMsg::Transfer {
+            recipient: rcpt.to_string(),
+            amount: Uint128::new(700),
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: foreign.to_string(),
+                msg: to_json_binary(&transfer).unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // the token's own escrowed balance is off limits
+        let rescue = ExecuteMsg::Rescue {
+            asset: AssetInfo::Cw20 {
+                contract_addr: contract_env().contract.address.to_string(),
+            },
+            recipient: rcpt.to_string(),
+        };
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), contract_env(), info, rescue).unwrap_err();
+        assert_eq!(err, ContractError::CannotRescueOwnToken {});
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::instantiate;
+    use cw20_base::msg::{InstantiateMsg, SpendConstraint};
+    use cw20_base::restrictions::{
+        query_detect_transfer_restriction, query_spendable_balance, INSUFFICIENT_BALANCE,
+        TRANSFER_OK,
+    };
+
+    #[test]
+    fn unrestricted_account_can_spend_its_balance() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let msg = InstantiateMsg {
+            name: "Restricted".to_string(),
+            symbol: "RSTR".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query_spendable_balance(deps.as_ref(), mock_env(), owner.to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::new(1_000));
+        assert_eq!(res.constraint, SpendConstraint::Balance);
+
+        let other = deps.api.addr_make("other").to_string();
+        let res = query_spendable_balance(deps.as_ref(), mock_env(), other.clone()).unwrap();
+        assert_eq!(res.spendable, Uint128::zero());
+
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            owner.to_string(),
+            other.clone(),
+            Uint128::new(1_000),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, TRANSFER_OK);
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            mock_env(),
+            owner.to_string(),
+            other,
+            Uint128::new(1_001),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, INSUFFICIENT_BALANCE);
+        assert_eq!(res.reason, "Insufficient balance");
+    }
+
+    #[cfg(all(feature = "launch-guard", feature = "min-transfer"))]
+    #[test]
+    fn spendable_balance_reflects_transfer_rules() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let small = deps.api.addr_make("small");
+        let msg = InstantiateMsg {
+            name: "Restricted".to_string(),
+            symbol: "RSTR".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: owner.to_string(),
+                    amount: Uint128::new(1_000),
+                },
+                Cw20Coin {
+                    address: small.to_string(),
+                    amount: Uint128::new(300),
+                },
+            ],
+            min_transfer_amount: Some(Uint128::new(500)),
+            launch_allowlist: Some(vec![small.to_string()]),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query_spendable_balance(deps.as_ref(), mock_env(), owner.to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::zero());
+        assert_eq!(res.constraint, SpendConstraint::TradingNotEnabled);
+
+        let res = query_spendable_balance(deps.as_ref(), mock_env(), small.to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::zero());
+        assert_eq!(
+            res.constraint,
+            SpendConstraint::BelowMinimum {
+                min: Uint128::new(500)
+            }
+        );
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(all(test, feature = "rewards"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        coins, to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, SubMsg, Uint128, WasmMsg,
+    };
+    use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{AssetInfo, ExecuteMsg, InstantiateMsg};
+    use cw20_base::rewards::query_pending_rewards;
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, alice: &Addr, bob: &Addr, rewards: AssetInfo) {
+        let msg = InstantiateMsg {
+            name: "Revenue Share".to_string(),
+            symbol: "REV".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: alice.to_string(),
+                    amount: Uint128::new(300),
+                },
+                Cw20Coin {
+                    address: bob.to_string(),
+                    amount: Uint128::new(100),
+                },
+            ],
+            rewards: Some(rewards),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn pending(deps: Deps, addr: &Addr) -> Uint128 {
+        query_pending_rewards(deps, addr.to_string())
+            .unwrap()
+            .amount
+    }
+
+    #[test]
+    fn native_rewards_follow_balances_over_time() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let payer = deps.api.addr_make("payer");
+        let rewards = AssetInfo::Native {
+            denom: "uusd".to_string(),
+        };
+        do_instantiate(deps.as_mut(), &alice, &bob, rewards);
+
+        let info = message_info(&payer, &coins(400, "uusd"));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DistributeRewards {},
+        )
+        .unwrap();
+        assert_eq!(pending(deps.as_ref(), &alice), Uint128::new(300));
+        assert_eq!(pending(deps.as_ref(), &bob), Uint128::new(100));
+
+        // later distributions follow the new balances
+        let msg = ExecuteMsg::Transfer {
+            recipient: bob.to_string(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap();
+        let info = message_info(&payer, &coins(400, "uusd"));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DistributeRewards {},
+        )
+        .unwrap();
+        assert_eq!(pending(deps.as_ref(), &alice), Uint128::new(500));
+        assert_eq!(pending(deps.as_ref(), &bob), Uint128::new(300));
+
+        // other coins are refused
+        let info = message_info(&payer, &coins(400, "uatom"));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DistributeRewards {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidRewardAsset {});
+
+        let info = message_info(&alice, &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::WithdrawRewards {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(500, "uusd"),
+            })]
+        );
+        assert_eq!(pending(deps.as_ref(), &alice), Uint128::zero());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::WithdrawRewards {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToWithdraw {});
+    }
+
+    #[test]
+    fn contract_balance_earns_nothing() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let payer = deps.api.addr_make("payer");
+        let rewards = AssetInfo::Native {
+            denom: "uusd".to_string(),
+        };
+        do_instantiate(deps.as_mut(), &alice, &bob, rewards);
+        let mut env = mock_env();
+        env.contract.address = deps.api.addr_make("cw20");
+
+        // tokens the contract holds, like those in escrow, cannot claim their share
+        let msg = ExecuteMsg::Transfer {
+            recipient: env.contract.address.to_string(),
+            amount: Uint128::new(200),
+            memo: None,
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&alice, &[]), msg).unwrap();
+        let info = message_info(&payer, &coins(200, "uusd"));
+        let msg = ExecuteMsg::DistributeRewards {};
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(pending(deps.as_ref(), &alice), Uint128::new(100));
+        assert_eq!(pending(deps.as_ref(), &bob), Uint128::new(100));
+        let contract = env.contract.address;
+        assert_eq!(pending(deps.as_ref(), &contract), Uint128::zero());
+    }
+
+    #[test]
+    fn cw20_rewards_come_through_receive() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let reward_token = deps.api.addr_make("reward_token");
+        let other_token = deps.api.addr_make("other_token");
+        let rewards = AssetInfo::Cw20 {
+            contract_addr: reward_token.to_string(),
+        };
+        do_instantiate(deps.as_mut(), &alice, &bob, rewards);
+
+        let receive = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: alice.to_string(),
+            amount: Uint128::new(400),
+            msg: Default::default(),
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&other_token, &[]),
+            receive.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidRewardAsset {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&reward_token, &[]),
+            receive,
+        )
+        .unwrap();
+        assert_eq!(pending(deps.as_ref(), &bob), Uint128::new(100));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&bob, &[]),
+            ExecuteMsg::WithdrawRewards {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: reward_token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: bob.to_string(),
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+    }
+
+    #[cfg(feature = "rescue")]
+    #[test]
+    fn reward_funds_cannot_be_rescued() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let rewards = AssetInfo::Native {
+            denom: "uusd".to_string(),
+        };
+        let msg = InstantiateMsg {
+            name: "Revenue Share".to_string(),
+            symbol: "REV".to_string(),
+            decimals: 6,
+            admin: Some(admin.to_string()),
+            rewards: Some(rewards.clone()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Rescue {
+            asset: rewards,
+            recipient: admin.to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotRescueRewards {});
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(all(test, feature = "sampling"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::sampling::query_sample_holders;
+
+    #[test]
+    fn samples_are_weighted_and_deterministic() {
+        let mut deps = mock_dependencies();
+        let whale = deps.api.addr_make("whale");
+        let shrimp = deps.api.addr_make("shrimp");
+        let empty = deps.api.addr_make("empty");
+        let msg = InstantiateMsg {
+            name: "Raffle".to_string(),
+            symbol: "RAFL".to_string(),
+            decimals: 0,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: whale.to_string(),
+                    amount: Uint128::new(999),
+                },
+                Cw20Coin {
+                    address: shrimp.to_string(),
+                    amount: Uint128::new(1),
+                },
+                Cw20Coin {
+                    address: empty.to_string(),
+                    amount: Uint128::zero(),
+                },
+            ],
+            holder_sampling: true,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sample =
+            query_sample_holders(deps.as_ref(), mock_env(), "seed".to_string(), 50).unwrap();
+        assert_eq!(sample.holders.len(), 50);
+        assert!(!sample.holders.contains(&empty.to_string()));
+        let whales = sample
+            .holders
+            .iter()
+            .filter(|h| **h == whale.to_string())
+            .count();
+        assert!(whales >= 45);
+
+        let again =
+            query_sample_holders(deps.as_ref(), mock_env(), "seed".to_string(), 50).unwrap();
+        assert_eq!(sample, again);
+    }
+
+    #[test]
+    fn weights_follow_transfers() {
+        let mut deps = mock_dependencies();
+        let holders: Vec<Addr> = (0..13)
+            .map(|i| deps.api.addr_make(&format!("holder{}", i)))
+            .collect();
+        let msg = InstantiateMsg {
+            name: "Raffle".to_string(),
+            symbol: "RAFL".to_string(),
+            decimals: 0,
+            initial_balances: holders
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| Cw20Coin {
+                    address: addr.to_string(),
+                    amount: Uint128::new(i as u128 + 1),
+                })
+                .collect(),
+            holder_sampling: true,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // move every balance to a single holder in the middle of the tree
+        let target = &holders[6];
+        for (i, holder) in holders.iter().enumerate() {
+            if holder == target {
+                continue;
+            }
+            let msg = ExecuteMsg::Transfer {
+                recipient: target.to_string(),
+                amount: Uint128::new(i as u128 + 1),
+                memo: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(holder, &[]), msg).unwrap();
+        }
+
+        let sample = query_sample_holders(deps.as_ref(), mock_env(), "x".to_string(), 20).unwrap();
+        assert!(sample.holders.iter().all(|h| *h == target.to_string()));
+    }
+
+    #[test]
+    fn sampling_must_be_enabled() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Plain".to_string(),
+            symbol: "PLN".to_string(),
+            decimals: 0,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        query_sample_holders(deps.as_ref(), mock_env(), "seed".to_string(), 1).unwrap_err();
+    }
+
+    #[test]
+    fn the_contract_itself_is_never_drawn() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        env.contract.address = deps.api.addr_make("cw20");
+        let before = deps.api.addr_make("before");
+        let after = deps.api.addr_make("after");
+        let msg = InstantiateMsg {
+            name: "Raffle".to_string(),
+            symbol: "RAFL".to_string(),
+            decimals: 0,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: before.to_string(),
+                    amount: Uint128::new(1),
+                },
+                Cw20Coin {
+                    address: env.contract.address.to_string(),
+                    amount: Uint128::new(1_000_000),
+                },
+                Cw20Coin {
+                    address: after.to_string(),
+                    amount: Uint128::new(1),
+                },
+            ],
+            holder_sampling: true,
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let sample = query_sample_holders(deps.as_ref(), env, "seed".to_string(), 50).unwrap();
+        assert!(sample.holders.contains(&before.to_string()));
+        assert!(sample.holders.contains(&after.to_string()));
+        assert_eq!(sample.holders.len(), 50);
+        assert!(sample
+            .holders
+            .iter()
+            .all(|h| *h == before.to_string() || *h == after.to_string()));
+    }
+}
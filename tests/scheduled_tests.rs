@@ -0,0 +1,225 @@
+#[cfg(all(test, feature = "scheduled"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::{Cw20Coin, Expiration};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::scheduled::query_scheduled_transfers;
+    use cw20_base::ContractError;
+
+    fn env_at(height: u64) -> Env {
+        let mut env = mock_env();
+        env.contract.address = MockApi::default().addr_make("cw20");
+        env.block.height += height;
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, owner: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Treasury".to_string(),
+            symbol: "TRSY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, env_at(0), info, msg).unwrap();
+    }
+
+    #[test]
+    fn matured_transfers_are_delivered_in_order() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let keeper = deps.api.addr_make("keeper");
+        do_instantiate(deps.as_mut(), &owner);
+
+        let start = env_at(0);
+        let schedules = [
+            (&alice, 100, Expiration::AtHeight(start.block.height + 20)),
+            (&bob, 200, Expiration::AtHeight(start.block.height + 10)),
+            (
+                &bob,
+                300,
+                Expiration::AtTime(start.block.time.plus_seconds(3600)),
+            ),
+        ];
+        for (recipient, amount, execute_after) in schedules {
+            let msg = ExecuteMsg::ScheduleTransfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+                execute_after,
+            };
+            execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap();
+        }
+        assert_eq!(
+            query_balance(deps.as_ref(), owner.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(400)
+        );
+
+        // nothing has matured yet
+        let msg = ExecuteMsg::ExecuteMatured { limit: None };
+        execute(deps.as_mut(), env_at(5), message_info(&keeper, &[]), msg).unwrap();
+        let pending = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert_eq!(pending.transfers.len(), 3);
+
+        // only the earliest schedule fits into the limit
+        let msg = ExecuteMsg::ExecuteMatured { limit: Some(1) };
+        execute(deps.as_mut(), env_at(30), message_info(&keeper, &[]), msg).unwrap();
+        let balance = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, addr: &Addr| {
+            query_balance(deps.as_ref(), addr.to_string())
+                .unwrap()
+                .balance
+        };
+        assert_eq!(balance(&deps, &bob), Uint128::new(200));
+        assert_eq!(balance(&deps, &alice), Uint128::zero());
+
+        let msg = ExecuteMsg::ExecuteMatured { limit: None };
+        execute(deps.as_mut(), env_at(30), message_info(&keeper, &[]), msg).unwrap();
+        assert_eq!(balance(&deps, &alice), Uint128::new(100));
+        assert_eq!(balance(&deps, &bob), Uint128::new(200));
+
+        let pending = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert_eq!(pending.transfers.len(), 1);
+        assert_eq!(pending.transfers[0].id, 3);
+    }
+
+    #[test]
+    fn schedule_requires_a_trigger_and_funds() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let alice = deps.api.addr_make("alice");
+        do_instantiate(deps.as_mut(), &owner);
+
+        let msg = ExecuteMsg::ScheduleTransfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(1),
+            execute_after: Expiration::Never {},
+        };
+        let err = execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiration {});
+
+        let msg = ExecuteMsg::ScheduleTransfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(1_001),
+            execute_after: Expiration::AtHeight(1),
+        };
+        let err = execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+    }
+
+    #[cfg(feature = "max-wallet")]
+    #[test]
+    fn undeliverable_transfer_is_refunded() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let owner = deps.api.addr_make("owner");
+        let alice = deps.api.addr_make("alice");
+        let keeper = deps.api.addr_make("keeper");
+        let msg = InstantiateMsg {
+            name: "Treasury".to_string(),
+            symbol: "TRSY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env_at(0), info, msg).unwrap();
+
+        let msg = ExecuteMsg::ScheduleTransfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(600),
+            execute_after: Expiration::AtHeight(env_at(10).block.height),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap();
+
+        // alice can't hold that much by the time it matures
+        let msg = ExecuteMsg::UpdateMaxWallet {
+            amount: Some(Uint128::new(500)),
+            add_exempt: vec![],
+            remove_exempt: vec![],
+        };
+        execute(deps.as_mut(), env_at(5), message_info(&admin, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteMatured { limit: None };
+        let res = execute(deps.as_mut(), env_at(10), message_info(&keeper, &[]), msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "refunded_to" && attr.value == owner.as_str()));
+        let balance = |addr: &Addr| {
+            query_balance(deps.as_ref(), addr.to_string())
+                .unwrap()
+                .balance
+        };
+        assert_eq!(balance(&alice), Uint128::zero());
+        assert_eq!(balance(&owner), Uint128::new(1_000));
+        let pending = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert!(pending.transfers.is_empty());
+    }
+
+    #[cfg(feature = "compliance")]
+    #[test]
+    fn failing_compliance_query_refunds_the_transfer() {
+        use cosmwasm_std::{to_json_binary, ContractResult, SystemResult};
+        use cw20_base::msg::CanTransferResponse;
+
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let alice = deps.api.addr_make("alice");
+        let oracle = deps.api.addr_make("oracle");
+        let msg = InstantiateMsg {
+            name: "Treasury".to_string(),
+            symbol: "TRSY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            transfer_restriction_contract: Some(oracle.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env_at(0), info, msg).unwrap();
+        deps.querier.update_wasm(|_| {
+            let res = CanTransferResponse {
+                allowed: true,
+                reason: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+        });
+
+        let msg = ExecuteMsg::ScheduleTransfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(600),
+            execute_after: Expiration::AtHeight(env_at(10).block.height),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap();
+
+        // the compliance contract breaks before the transfer matures
+        deps.querier
+            .update_wasm(|_| SystemResult::Ok(ContractResult::Err("paused".to_string())));
+        let msg = ExecuteMsg::ExecuteMatured { limit: None };
+        let res = execute(deps.as_mut(), env_at(10), message_info(&alice, &[]), msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "refunded_to" && attr.value == owner.as_str()));
+        let balance = query_balance(deps.as_ref(), owner.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(1_000));
+        let pending = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert!(pending.transfers.is_empty());
+    }
+}
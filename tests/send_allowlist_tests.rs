@@ -0,0 +1,124 @@
+#[cfg(all(test, feature = "send-allowlist"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Binary, DepsMut, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::restrictions::{
+        query_detect_transfer_restriction, SEND_TARGET_NOT_ALLOWED, TRANSFER_OK,
+    };
+    use cw20_base::send_allowlist::query_send_allowlist;
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr, allowed: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Regulated".to_string(),
+            symbol: "REG".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            send_allowlist: Some(vec![allowed.to_string()]),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn send(contract: &Addr) -> ExecuteMsg {
+        ExecuteMsg::Send {
+            contract: contract.to_string(),
+            amount: Uint128::new(10),
+            msg: Binary::default(),
+            memo: None,
+            safe: false,
+        }
+    }
+
+    #[test]
+    fn send_only_reaches_allowed_contracts() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let approved = deps.api.addr_make("approved");
+        let defi = deps.api.addr_make("defi");
+        do_instantiate(deps.as_mut(), &admin, &holder, &approved);
+
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), send(&approved)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), send(&defi)).unwrap_err();
+        assert_eq!(err, ContractError::SendTargetNotAllowed {});
+        let detect = |send| {
+            let (from, to) = (holder.to_string(), defi.to_string());
+            let amount = Uint128::new(10);
+            query_detect_transfer_restriction(deps.as_ref(), mock_env(), from, to, amount, send)
+                .unwrap()
+                .code
+        };
+        assert_eq!(detect(true), SEND_TARGET_NOT_ALLOWED);
+        assert_eq!(detect(false), TRANSFER_OK);
+
+        // plain transfers are not restricted
+        let msg = ExecuteMsg::Transfer {
+            recipient: defi.to_string(),
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // neither is SendFrom a way around it
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: admin.to_string(),
+            amount: Uint128::new(100),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = ExecuteMsg::SendFrom {
+            owner: holder.to_string(),
+            contract: defi.to_string(),
+            amount: Uint128::new(10),
+            msg: Binary::default(),
+            memo: None,
+            with_owner: false,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::SendTargetNotAllowed {});
+    }
+
+    #[test]
+    fn admin_manages_allowlist() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let approved = deps.api.addr_make("approved");
+        let defi = deps.api.addr_make("defi");
+        do_instantiate(deps.as_mut(), &admin, &holder, &approved);
+
+        let update = ExecuteMsg::UpdateSendAllowlist {
+            add: vec![defi.to_string()],
+            remove: vec![approved.to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&holder, &[]),
+            update.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), update).unwrap();
+
+        let res = query_send_allowlist(deps.as_ref(), None, None).unwrap();
+        assert!(res.enabled);
+        assert_eq!(res.contracts, vec![defi.to_string()]);
+
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), send(&defi)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info, send(&approved)).unwrap_err();
+        assert_eq!(err, ContractError::SendTargetNotAllowed {});
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(all(test, feature = "snapshots"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, SnapshotInfo};
+    use cw20_base::snapshots::{query_balance_at_snapshot, query_snapshots};
+    use cw20_base::supply::query_total_supply_at;
+    use cw20_base::ContractError;
+
+    fn env_at(blocks: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height += blocks;
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Airdrop".to_string(),
+            symbol: "DROP".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            admin: Some(admin.to_string()),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, env_at(0), info, msg).unwrap();
+    }
+
+    #[test]
+    fn snapshot_freezes_balances_at_end_of_block() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let transfer = |amount: u128| ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        let msg = ExecuteMsg::RecordSnapshot {
+            label: "genesis".to_string(),
+        };
+        execute(deps.as_mut(), env_at(1), message_info(&admin, &[]), msg).unwrap();
+        // still part of the snapshot block
+        execute(
+            deps.as_mut(),
+            env_at(1),
+            message_info(&holder, &[]),
+            transfer(100),
+        )
+        .unwrap();
+        // after the snapshot
+        execute(
+            deps.as_mut(),
+            env_at(2),
+            message_info(&holder, &[]),
+            transfer(200),
+        )
+        .unwrap();
+
+        let balance_at = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, addr: &Addr| {
+            query_balance_at_snapshot(deps.as_ref(), addr.to_string(), "genesis".to_string())
+                .unwrap()
+                .balance
+        };
+        assert_eq!(balance_at(&deps, &holder), Uint128::new(900));
+        assert_eq!(balance_at(&deps, &other), Uint128::new(100));
+
+        let snapshots = query_snapshots(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            snapshots.snapshots,
+            vec![SnapshotInfo {
+                label: "genesis".to_string(),
+                height: env_at(2).block.height,
+            }]
+        );
+        query_balance_at_snapshot(deps.as_ref(), holder.to_string(), "unknown".to_string())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn only_admin_records_unique_labels() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let msg = ExecuteMsg::RecordSnapshot {
+            label: "genesis".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(1),
+            message_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            env_at(1),
+            message_info(&admin, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), env_at(5), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::SnapshotExists {});
+    }
+
+    #[test]
+    fn total_supply_at_past_heights() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let burn = |amount: u128| ExecuteMsg::Burn {
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_at(2),
+            message_info(&holder, &[]),
+            burn(100),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(2),
+            message_info(&holder, &[]),
+            burn(50),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(5),
+            message_info(&holder, &[]),
+            burn(250),
+        )
+        .unwrap();
+
+        let supply_at = |blocks: u64| {
+            query_total_supply_at(deps.as_ref(), env_at(blocks).block.height)
+                .unwrap()
+                .total_supply
+        };
+        // a height reflects the changes of the blocks before it
+        assert_eq!(supply_at(1), Uint128::new(1_000));
+        assert_eq!(supply_at(2), Uint128::new(1_000));
+        assert_eq!(supply_at(3), Uint128::new(850));
+        assert_eq!(supply_at(5), Uint128::new(850));
+        assert_eq!(supply_at(6), Uint128::new(600));
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(all(test, feature = "state-export"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::{Cw20Coin, Expiration, MinterResponse};
+
+    use cw20_base::allowances::query_allowance;
+    use cw20_base::contract::{execute, instantiate, query_balance, query_token_info};
+    use cw20_base::interfaces::query_features;
+    use cw20_base::msg::{ExecuteMsg, ExportSection, InstantiateMsg};
+    use cw20_base::state_export::query_export_state;
+    use cw20_base::ContractError;
+
+    fn token(initial_balances: Vec<Cw20Coin>, import_state: bool) -> InstantiateMsg {
+        InstantiateMsg {
+            name: "Forked".to_string(),
+            symbol: "FORK".to_string(),
+            decimals: 6,
+            initial_balances,
+            import_state,
+            ..InstantiateMsg::default()
+        }
+    }
+
+    #[test]
+    fn exported_state_imports_into_a_copy() {
+        let mut source = mock_dependencies();
+        let holders: Vec<Addr> = (0..5)
+            .map(|i| source.api.addr_make(&format!("holder{i}")))
+            .collect();
+        let balances = holders
+            .iter()
+            .enumerate()
+            .map(|(i, holder)| Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(100 * (i as u128 + 1)),
+            })
+            .collect();
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(source.as_mut(), mock_env(), info, token(balances, false)).unwrap();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: holders[1].to_string(),
+            amount: Uint128::new(30),
+            expires: Some(Expiration::AtHeight(1_000_000)),
+        };
+        execute(
+            source.as_mut(),
+            mock_env(),
+            message_info(&holders[0], &[]),
+            msg,
+        )
+        .unwrap();
+
+        // the copy is instantiated with nothing but the config
+        let config = query_export_state(source.as_ref(), ExportSection::Config, None, None)
+            .unwrap()
+            .config
+            .unwrap();
+        assert_eq!(config.token_info.total_supply, Uint128::new(1_500));
+        let mut copy = mock_dependencies();
+        let creator = copy.api.addr_make("creator");
+        let creator_info = message_info(&creator, &[]);
+        instantiate(
+            copy.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            token(vec![], true),
+        )
+        .unwrap();
+
+        // balances come over two pages at a time
+        let mut start_after = None;
+        loop {
+            let page = query_export_state(
+                source.as_ref(),
+                ExportSection::Balances,
+                start_after,
+                Some(2),
+            )
+            .unwrap();
+            let msg = ExecuteMsg::ImportState {
+                balances: page.balances,
+                allowances: vec![],
+            };
+            execute(copy.as_mut(), mock_env(), creator_info.clone(), msg).unwrap();
+            start_after = page.next;
+            if start_after.is_none() {
+                break;
+            }
+        }
+        let page =
+            query_export_state(source.as_ref(), ExportSection::Allowances, None, None).unwrap();
+        assert_eq!(page.allowances.len(), 1);
+        let msg = ExecuteMsg::ImportState {
+            balances: vec![],
+            allowances: page.allowances,
+        };
+        execute(copy.as_mut(), mock_env(), creator_info.clone(), msg).unwrap();
+
+        for holder in &holders {
+            assert_eq!(
+                query_balance(copy.as_ref(), holder.to_string()).unwrap(),
+                query_balance(source.as_ref(), holder.to_string()).unwrap()
+            );
+        }
+        assert_eq!(
+            query_token_info(copy.as_ref()).unwrap().total_supply,
+            Uint128::new(1_500)
+        );
+        let allowance = query_allowance(
+            copy.as_ref(),
+            holders[0].to_string(),
+            holders[1].to_string(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(30));
+
+        // only the creator imports, and only until it finishes
+        let msg = ExecuteMsg::FinishImport {};
+        let err = execute(
+            copy.as_mut(),
+            mock_env(),
+            message_info(&holders[0], &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(copy.as_mut(), mock_env(), creator_info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::ImportState {
+            balances: vec![Cw20Coin {
+                address: creator.to_string(),
+                amount: Uint128::new(1),
+            }],
+            allowances: vec![],
+        };
+        let err = execute(copy.as_mut(), mock_env(), creator_info, msg).unwrap_err();
+        assert_eq!(err, ContractError::ImportClosed {});
+    }
+
+    #[test]
+    fn import_respects_the_cap() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            mint: Some(MinterResponse {
+                minter: creator.to_string(),
+                cap: Some(Uint128::new(100)),
+            }),
+            ..token(vec![], true)
+        };
+        let info = message_info(&creator, &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::ImportState {
+            balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(101),
+            }],
+            allowances: vec![],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+    }
+
+    #[test]
+    fn renouncing_minting_closes_the_import() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            mint: Some(MinterResponse {
+                minter: creator.to_string(),
+                cap: Some(Uint128::new(100)),
+            }),
+            ..token(vec![], true)
+        };
+        let info = message_info(&creator, &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert!(query_features(deps.as_ref()).unwrap().import_open);
+
+        let msg = ExecuteMsg::RenounceMinting {};
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert!(!query_features(deps.as_ref()).unwrap().import_open);
+
+        // the cap went with the minter, but nothing can be imported anymore
+        let msg = ExecuteMsg::ImportState {
+            balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            allowances: vec![],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::ImportClosed {});
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::Uint128;
+    use cw20::{AllowanceResponse, Cw20Coin, Expiration, TokenInfoResponse};
+
+    use cw20_base::contract::{instantiate, query_balance};
+    use cw20_base::msg::InstantiateMsg;
+    use cw20_base::state::TokenMetadata;
+    use cw20_base::storage::Cw20Storage;
+    use cw20_base::ContractError;
+
+    fn metadata(symbol: &str) -> TokenMetadata {
+        TokenMetadata {
+            name: format!("{symbol} token"),
+            symbol: symbol.to_string(),
+            decimals: 6,
+        }
+    }
+
+    #[test]
+    fn prefixed_tokens_share_storage() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let height = env.block.height;
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        let first = Cw20Storage::new("first_");
+        let second = Cw20Storage::new("second_");
+        first
+            .instantiate(&mut deps.storage, &metadata("ONE"), height)
+            .unwrap();
+        second
+            .instantiate(&mut deps.storage, &metadata("TWO"), height)
+            .unwrap();
+
+        first
+            .mint(&mut deps.storage, &alice, Uint128::new(100), height)
+            .unwrap();
+        second
+            .mint(&mut deps.storage, &alice, Uint128::new(7), height)
+            .unwrap();
+        first
+            .transfer(&mut deps.storage, &alice, &bob, Uint128::new(40), height)
+            .unwrap();
+        second
+            .burn(&mut deps.storage, &alice, Uint128::new(2), height)
+            .unwrap();
+
+        assert_eq!(
+            first.balance(&deps.storage, &alice).unwrap(),
+            Uint128::new(60)
+        );
+        assert_eq!(
+            first.balance(&deps.storage, &bob).unwrap(),
+            Uint128::new(40)
+        );
+        assert_eq!(
+            second.balance(&deps.storage, &alice).unwrap(),
+            Uint128::new(5)
+        );
+        assert_eq!(
+            second.balance(&deps.storage, &bob).unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            first.token_info(&deps.storage).unwrap(),
+            TokenInfoResponse {
+                name: "ONE token".to_string(),
+                symbol: "ONE".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(100),
+            }
+        );
+        assert_eq!(
+            second.token_info(&deps.storage).unwrap().total_supply,
+            Uint128::new(5)
+        );
+
+        // balances can't go negative
+        let err = second
+            .transfer(&mut deps.storage, &bob, &alice, Uint128::new(1), height)
+            .unwrap_err();
+        assert!(err.to_string().contains("Overflow"));
+
+        // nothing leaked into the default layout
+        assert_eq!(
+            Cw20Storage::DEFAULT.balance(&deps.storage, &alice).unwrap(),
+            Uint128::zero()
+        );
+        Cw20Storage::DEFAULT.token_info(&deps.storage).unwrap_err();
+    }
+
+    #[test]
+    fn prefixed_allowances() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("owner");
+        let spender = deps.api.addr_make("spender");
+        let first = Cw20Storage::new("first_");
+        let second = Cw20Storage::new("second_");
+
+        let expires = Expiration::AtHeight(env.block.height + 10);
+        first
+            .increase_allowance(
+                &mut deps.storage,
+                &env.block,
+                &owner,
+                &spender,
+                Uint128::new(50),
+                Some(expires),
+            )
+            .unwrap();
+        let allowance = first
+            .deduct_allowance(
+                &mut deps.storage,
+                &env.block,
+                &owner,
+                &spender,
+                Uint128::new(20),
+            )
+            .unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(30),
+                expires,
+            }
+        );
+        assert_eq!(
+            first.allowance(&deps.storage, &owner, &spender).unwrap(),
+            allowance
+        );
+        assert_eq!(
+            first
+                .allowances_spender
+                .load(&deps.storage, (&spender, &owner))
+                .unwrap(),
+            allowance
+        );
+
+        let err = second
+            .deduct_allowance(
+                &mut deps.storage,
+                &env.block,
+                &owner,
+                &spender,
+                Uint128::new(1),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::NoAllowance {});
+        let err = first
+            .increase_allowance(
+                &mut deps.storage,
+                &env.block,
+                &owner,
+                &owner,
+                Uint128::new(1),
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::CannotSetOwnAccount {});
+
+        let mut later = env.block.clone();
+        later.height += 10;
+        let err = first
+            .deduct_allowance(&mut deps.storage, &later, &owner, &spender, Uint128::new(1))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Expired {});
+    }
+
+    #[test]
+    fn contract_uses_default_layout() {
+        let mut deps = mock_dependencies();
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Default".to_string(),
+            symbol: "DFLT".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(123),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let storage = Cw20Storage::DEFAULT;
+        assert_eq!(
+            storage.balance(&deps.storage, &holder).unwrap(),
+            query_balance(deps.as_ref(), holder.to_string())
+                .unwrap()
+                .balance
+        );
+        assert_eq!(
+            storage.token_info(&deps.storage).unwrap().total_supply,
+            Uint128::new(123)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(all(test, feature = "streams"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::streams::{query_stream, query_streams_by_recipient, query_streams_by_sender};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, owner: &Addr, amount: Uint128) {
+        let msg = InstantiateMsg {
+            name: "Payroll".to_string(),
+            symbol: "PAY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount,
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.contract.address = MockApi::default().addr_make("cw20");
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    #[test]
+    fn stream_vests_linearly() {
+        let mut deps = mock_dependencies();
+        let employer = deps.api.addr_make("employer");
+        let employee = deps.api.addr_make("employee");
+        do_instantiate(deps.as_mut(), &employer, Uint128::new(5_000));
+
+        let start = mock_env().block.time;
+        let msg = ExecuteMsg::CreateStream {
+            recipient: employee.to_string(),
+            amount: Uint128::new(1_000),
+            start,
+            end: start.plus_seconds(100),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&employer, &[]), msg).unwrap();
+
+        // tokens are escrowed by the contract
+        let balance = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, addr: &Addr| {
+            query_balance(deps.as_ref(), addr.to_string())
+                .unwrap()
+                .balance
+        };
+        let contract = env_at(0).contract.address;
+        assert_eq!(balance(&deps, &employer), Uint128::new(4_000));
+        assert_eq!(balance(&deps, &contract), Uint128::new(1_000));
+
+        // only the recipient can withdraw
+        let msg = ExecuteMsg::WithdrawFromStream { id: 1 };
+        let err = execute(
+            deps.as_mut(),
+            env_at(25),
+            message_info(&employer, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // a quarter is vested after a quarter of the time
+        assert_eq!(
+            query_stream(deps.as_ref(), env_at(25), 1)
+                .unwrap()
+                .withdrawable,
+            Uint128::new(250)
+        );
+        execute(
+            deps.as_mut(),
+            env_at(25),
+            message_info(&employee, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(balance(&deps, &employee), Uint128::new(250));
+
+        // nothing new in the same block
+        let err = execute(
+            deps.as_mut(),
+            env_at(25),
+            message_info(&employee, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToWithdraw {});
+
+        // everything after the end
+        execute(
+            deps.as_mut(),
+            env_at(500),
+            message_info(&employee, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(balance(&deps, &employee), Uint128::new(1_000));
+        assert_eq!(balance(&deps, &contract), Uint128::zero());
+        let stream = query_stream(deps.as_ref(), env_at(500), 1).unwrap();
+        assert_eq!(stream.withdrawn, Uint128::new(1_000));
+        assert_eq!(stream.withdrawable, Uint128::zero());
+    }
+
+    #[test]
+    fn streams_are_listed_by_sender_and_recipient() {
+        let mut deps = mock_dependencies();
+        let employer = deps.api.addr_make("employer");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        do_instantiate(deps.as_mut(), &employer, Uint128::new(5_000));
+
+        let start = mock_env().block.time;
+        for rcpt in [&alice, &bob, &alice] {
+            let msg = ExecuteMsg::CreateStream {
+                recipient: rcpt.to_string(),
+                amount: Uint128::new(100),
+                start,
+                end: start.plus_seconds(10),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&employer, &[]), msg).unwrap();
+        }
+
+        let ids = |streams: Vec<cw20_base::msg::StreamResponse>| -> Vec<u64> {
+            streams.into_iter().map(|s| s.id).collect()
+        };
+        let by_sender =
+            query_streams_by_sender(deps.as_ref(), mock_env(), employer.to_string(), None, None)
+                .unwrap();
+        assert_eq!(ids(by_sender.streams), vec![1, 2, 3]);
+        let page = query_streams_by_sender(
+            deps.as_ref(),
+            mock_env(),
+            employer.to_string(),
+            Some(1),
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(ids(page.streams), vec![2]);
+
+        let by_alice =
+            query_streams_by_recipient(deps.as_ref(), mock_env(), alice.to_string(), None, None)
+                .unwrap();
+        assert_eq!(ids(by_alice.streams), vec![1, 3]);
+    }
+
+    #[test]
+    fn rejects_invalid_schedule() {
+        let mut deps = mock_dependencies();
+        let employer = deps.api.addr_make("employer");
+        do_instantiate(deps.as_mut(), &employer, Uint128::new(5_000));
+
+        let start = mock_env().block.time;
+        let msg = ExecuteMsg::CreateStream {
+            recipient: deps.api.addr_make("employee").to_string(),
+            amount: Uint128::new(100),
+            start,
+            end: start,
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&employer, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidStreamSchedule {});
+    }
+
+    #[cfg(feature = "launch-guard")]
+    #[test]
+    fn stream_is_held_back_by_the_launch_guard() {
+        let mut deps = mock_dependencies();
+        let employer = deps.api.addr_make("employer");
+        let employee = deps.api.addr_make("employee");
+        let msg = InstantiateMsg {
+            name: "Payroll".to_string(),
+            symbol: "PAY".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: employer.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            launch_allowlist: Some(vec![]),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // already fully vested, so it would pay out right away
+        let start = mock_env().block.time;
+        let msg = ExecuteMsg::CreateStream {
+            recipient: employee.to_string(),
+            amount: Uint128::new(1_000),
+            start,
+            end: start.plus_seconds(1),
+        };
+        let err =
+            execute(deps.as_mut(), env_at(10), message_info(&employer, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::TradingNotEnabled {});
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(all(test, feature = "subscriptions"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::{Cw20Coin, Expiration};
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+    use cw20_base::subscriptions::query_subscription;
+    use cw20_base::ContractError;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn do_instantiate(deps: DepsMut, owner: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Subscription".to_string(),
+            symbol: "SUBS".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(10_000),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    #[test]
+    fn charge_is_capped_per_period() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let service = deps.api.addr_make("service");
+        do_instantiate(deps.as_mut(), &owner);
+
+        let msg = ExecuteMsg::Subscribe {
+            spender: service.to_string(),
+            amount_per_period: Uint128::new(100),
+            period: DAY,
+            expires: None,
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap();
+
+        let charge = |amount: u128| ExecuteMsg::Charge {
+            owner: owner.to_string(),
+            amount: Uint128::new(amount),
+            recipient: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            message_info(&service, &[]),
+            charge(60),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(20),
+            message_info(&service, &[]),
+            charge(50),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SubscriptionLimitExceeded {});
+        let err = execute(
+            deps.as_mut(),
+            env_at(20),
+            message_info(&service, &[]),
+            charge(u128::MAX),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SubscriptionLimitExceeded {});
+
+        let sub = query_subscription(
+            deps.as_ref(),
+            env_at(20),
+            owner.to_string(),
+            service.to_string(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(sub.available, Uint128::new(40));
+
+        // the cap refreshes in the next period
+        execute(
+            deps.as_mut(),
+            env_at(DAY + 1),
+            message_info(&service, &[]),
+            charge(100),
+        )
+        .unwrap();
+        assert_eq!(
+            query_balance(deps.as_ref(), service.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(160)
+        );
+    }
+
+    #[test]
+    fn cancelled_or_expired_subscriptions_cannot_be_charged() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let service = deps.api.addr_make("service");
+        do_instantiate(deps.as_mut(), &owner);
+
+        let msg = ExecuteMsg::Subscribe {
+            spender: service.to_string(),
+            amount_per_period: Uint128::new(100),
+            period: DAY,
+            expires: Some(Expiration::AtTime(env_at(DAY).block.time)),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&owner, &[]), msg).unwrap();
+
+        let charge = ExecuteMsg::Charge {
+            owner: owner.to_string(),
+            amount: Uint128::new(1),
+            recipient: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY),
+            message_info(&service, &[]),
+            charge.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Expired {});
+
+        let msg = ExecuteMsg::CancelSubscription {
+            spender: service.to_string(),
+        };
+        execute(deps.as_mut(), env_at(1), message_info(&owner, &[]), msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(2),
+            message_info(&service, &[]),
+            charge,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoSubscription {});
+    }
+}
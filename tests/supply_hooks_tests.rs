@@ -0,0 +1,240 @@
+#[cfg(all(test, feature = "supply-hooks"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        attr, from_json, Addr, Binary, CosmosMsg, DepsMut, Reply, ReplyOn, SubMsgResult, Uint128,
+        WasmMsg,
+    };
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate, query, reply};
+    use cw20_base::msg::{
+        ExecuteMsg, InstantiateMsg, QueryMsg, SupplyHookMsg, SupplyHooksResponse,
+    };
+    use cw20_base::supply_hooks::{MAX_SUPPLY_HOOKS, SUPPLY_HOOK_REPLY_ID};
+    use cw20_base::ContractError;
+
+    fn do_instantiate(deps: DepsMut, minter: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Tracked".to_string(),
+            symbol: "TRK".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn update_hooks(add: Vec<&Addr>, remove: Vec<&Addr>) -> ExecuteMsg {
+        ExecuteMsg::UpdateSupplyHooks {
+            add: add.into_iter().map(Addr::to_string).collect(),
+            remove: remove.into_iter().map(Addr::to_string).collect(),
+        }
+    }
+
+    fn hook_msgs(messages: &[cosmwasm_std::SubMsg]) -> Vec<(String, SupplyHookMsg)> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Wrapper {
+            SupplyHook(SupplyHookMsg),
+        }
+        messages
+            .iter()
+            .map(|sub| match &sub.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    let Wrapper::SupplyHook(hook) = from_json(msg).unwrap();
+                    (contract_addr.clone(), hook)
+                }
+                other => panic!("unexpected message {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hooks_are_notified_of_mints_and_burns() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        let tracker = deps.api.addr_make("tracker");
+        do_instantiate(deps.as_mut(), &minter, &holder);
+
+        // nothing is sent without hooks
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        assert!(res.messages.is_empty());
+
+        let info = message_info(&minter, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_hooks(vec![&tracker], vec![]),
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SupplyHooks {}).unwrap();
+        let hooks: SupplyHooksResponse = from_json(res).unwrap();
+        assert_eq!(hooks.hooks, vec![tracker.to_string()]);
+
+        let msg = ExecuteMsg::Mint {
+            recipient: holder.to_string(),
+            amount: Uint128::new(500),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            hook_msgs(&res.messages),
+            vec![(
+                tracker.to_string(),
+                SupplyHookMsg::Mint {
+                    minter: minter.to_string(),
+                    recipient: holder.to_string(),
+                    amount: Uint128::new(500),
+                    new_total_supply: Uint128::new(1_400),
+                }
+            )]
+        );
+
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(400),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        assert_eq!(
+            hook_msgs(&res.messages),
+            vec![(
+                tracker.to_string(),
+                SupplyHookMsg::Burn {
+                    owner: holder.to_string(),
+                    amount: Uint128::new(400),
+                    new_total_supply: Uint128::new(1_000),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn failing_hooks_dont_block_supply_changes() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        let tracker = deps.api.addr_make("tracker");
+        do_instantiate(deps.as_mut(), &minter, &holder);
+
+        let info = message_info(&minter, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_hooks(vec![&tracker], vec![]),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::Mint {
+            recipient: holder.to_string(),
+            amount: Uint128::new(500),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let sub = &res.messages[0];
+        assert_eq!(
+            (sub.id, &sub.reply_on),
+            (SUPPLY_HOOK_REPLY_ID, &ReplyOn::Error)
+        );
+
+        // a failing hook leaves the mint in place
+        let msg = Reply {
+            id: SUPPLY_HOOK_REPLY_ID,
+            payload: sub.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Err("tracker paused".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert!(res.attributes.contains(&attr("hook", tracker.as_str())));
+        assert_eq!(sub.payload, Binary::from(tracker.as_bytes()));
+
+        // the hooks go with the minter
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RenounceMinting {},
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SupplyHooks {}).unwrap();
+        let hooks: SupplyHooksResponse = from_json(res).unwrap();
+        assert!(hooks.hooks.is_empty());
+    }
+
+    #[test]
+    fn only_minter_updates_hooks() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        let tracker = deps.api.addr_make("tracker");
+        do_instantiate(deps.as_mut(), &minter, &holder);
+
+        let info = message_info(&holder, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            update_hooks(vec![&tracker], vec![]),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let info = message_info(&minter, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            update_hooks(vec![&tracker], vec![]),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            update_hooks(vec![], vec![&tracker]),
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SupplyHooks {}).unwrap();
+        let hooks: SupplyHooksResponse = from_json(res).unwrap();
+        assert!(hooks.hooks.is_empty());
+    }
+
+    #[test]
+    fn hook_count_is_capped() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        do_instantiate(deps.as_mut(), &minter, &holder);
+
+        let hooks: Vec<_> = (0..=MAX_SUPPLY_HOOKS)
+            .map(|i| deps.api.addr_make(&format!("tracker{i}")))
+            .collect();
+        let info = message_info(&minter, &[]);
+        let msg = update_hooks(hooks[..MAX_SUPPLY_HOOKS].iter().collect(), vec![]);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = update_hooks(vec![&hooks[MAX_SUPPLY_HOOKS]], vec![]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TooManySupplyHooks {
+                max: MAX_SUPPLY_HOOKS
+            }
+        );
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(all(test, feature = "supply-log"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, SupplyAction, SupplyChange};
+    use cw20_base::supply_log::query_supply_change_log;
+
+    #[test]
+    fn keeps_the_latest_mints_and_burns() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Audited".to_string(),
+            symbol: "AUD".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            supply_log_capacity: Some(3),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        for amount in 1..=3 {
+            let msg = ExecuteMsg::Mint {
+                recipient: holder.to_string(),
+                amount: Uint128::new(amount),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        }
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+
+        // the first mint fell out of the log
+        let changes = query_supply_change_log(deps.as_ref(), None, None)
+            .unwrap()
+            .changes;
+        let ids: Vec<u64> = changes.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+        let env = mock_env();
+        assert_eq!(
+            changes[2],
+            SupplyChange {
+                id: 3,
+                action: SupplyAction::Burn,
+                amount: Uint128::new(10),
+                actor: holder.clone(),
+                account: holder.clone(),
+                height: env.block.height,
+                time: env.block.time,
+            }
+        );
+        assert_eq!(changes[0].actor, minter);
+        assert_eq!(changes[0].amount, Uint128::new(2));
+
+        let page = query_supply_change_log(deps.as_ref(), Some(1), Some(1))
+            .unwrap()
+            .changes;
+        assert_eq!(page, changes[1..2]);
+        // a cursor older than the log starts at its oldest record
+        let page = query_supply_change_log(deps.as_ref(), Some(0), None)
+            .unwrap()
+            .changes;
+        assert_eq!(page, changes);
+        let page = query_supply_change_log(deps.as_ref(), Some(3), None)
+            .unwrap()
+            .changes;
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn capacity_must_not_be_zero() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Audited".to_string(),
+            symbol: "AUD".to_string(),
+            decimals: 6,
+            supply_log_capacity: Some(0),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    }
+}
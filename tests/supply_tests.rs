@@ -0,0 +1,147 @@
+#[cfg(all(test, feature = "supply-shards", feature = "auto-burn"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Order, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{execute, instantiate, query_token_info};
+    use cw20_base::msg::{ExecuteMsg, InstantiateAutoBurn, InstantiateMsg};
+    use cw20_base::state::SUPPLY_SHARDS;
+    use cw20_base::supply::query_total_supply;
+    use cw20_base::ContractError;
+
+    #[test]
+    fn sharded_supply_sums_all_changes() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "Sharded".to_string(),
+            symbol: "SHRD".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: Some(Uint128::new(2_000)),
+            }),
+            auto_burn: Some(InstantiateAutoBurn {
+                basis_points: 1_000,
+                exempt: vec![],
+            }),
+            supply_shards: Some(8),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let recipients: Vec<Addr> = (0..10)
+            .map(|i| deps.api.addr_make(&format!("rcpt{}", i)))
+            .collect();
+        for rcpt in &recipients {
+            let msg = ExecuteMsg::Mint {
+                recipient: rcpt.to_string(),
+                amount: Uint128::new(100),
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap();
+        }
+        let shards = SUPPLY_SHARDS
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert!(shards > 1);
+
+        // the cap is checked against the summed supply
+        let msg = ExecuteMsg::Mint {
+            recipient: holder.to_string(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(50),
+            memo: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&recipients[0], &[]),
+            msg,
+        )
+        .unwrap();
+        // 10% of the transfer is burned
+        let msg = ExecuteMsg::Transfer {
+            recipient: recipients[1].to_string(),
+            amount: Uint128::new(500),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+
+        let total = query_total_supply(deps.as_ref()).unwrap().total_supply;
+        assert_eq!(total, Uint128::new(2_000 - 50 - 50));
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().total_supply, total);
+    }
+
+    #[cfg(feature = "snapshots")]
+    #[test]
+    fn sharded_supply_keeps_history() {
+        use cw20_base::supply::query_total_supply_at;
+
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let msg = InstantiateMsg {
+            name: "Sharded".to_string(),
+            symbol: "SHRD".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            supply_shards: Some(4),
+            ..InstantiateMsg::default()
+        };
+        let env = mock_env();
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let mut later = env.clone();
+        later.block.height += 10;
+        for i in 0..6 {
+            let msg = ExecuteMsg::Mint {
+                recipient: deps.api.addr_make(&format!("rcpt{}", i)).to_string(),
+                amount: Uint128::new(100),
+            };
+            execute(
+                deps.as_mut(),
+                later.clone(),
+                message_info(&minter, &[]),
+                msg,
+            )
+            .unwrap();
+        }
+
+        let supply_at = |height: u64| {
+            query_total_supply_at(deps.as_ref(), height)
+                .unwrap()
+                .total_supply
+        };
+        assert_eq!(supply_at(later.block.height), Uint128::zero());
+        assert_eq!(supply_at(later.block.height + 1), Uint128::new(600));
+    }
+
+    #[test]
+    fn shard_count_must_not_be_zero() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Sharded".to_string(),
+            symbol: "SHRD".to_string(),
+            decimals: 6,
+            supply_shards: Some(0),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use cosmwasm_std::testing::MockApi;
+    use cosmwasm_std::Uint128;
+    use cw20::MinterResponse;
+
+    use cw20_base::msg::InstantiateMsg;
+    use cw20_base::test_utils::{Cw20TestSuite, Cw20TestSuiteBuilder};
+    use cw20_base::ContractError;
+
+    #[test]
+    fn suite_runs_the_token() {
+        let mut suite = Cw20TestSuite::builder()
+            .with_token("Suite", "STE", 6)
+            .with_balance("alice", 1_000u128)
+            .with_cap(5_000u128)
+            .build();
+        let alice = suite.addr("alice");
+        let bob = suite.addr("bob");
+        let carol = suite.addr("carol");
+        suite.assert_balances(&[(&alice, 1_000)]);
+        assert_eq!(suite.token.meta(&suite.app.wrap()).unwrap().symbol, "STE");
+
+        suite.mint(&bob, 500u128).unwrap();
+        suite.transfer(&alice, &bob, 100u128).unwrap();
+        suite.assert_balances(&[(&alice, 900), (&bob, 600)]);
+
+        suite
+            .increase_allowance(&bob, &carol, 250u128, None)
+            .unwrap();
+        suite.assert_allowance(&bob, &carol, 250u128);
+        suite.transfer_from(&carol, &bob, &carol, 200u128).unwrap();
+        suite.assert_allowance(&bob, &carol, 50u128);
+        suite.assert_balances(&[(&alice, 900), (&bob, 400), (&carol, 200)]);
+
+        // errors come back as the contract's own
+        let err = suite.mint(&bob, 4_000u128).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+        let err = suite.transfer(&carol, &alice, 201u128).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+    }
+
+    #[test]
+    fn suite_keeps_the_given_minter() {
+        let owner = MockApi::default().addr_make("owner");
+        let mut suite = Cw20TestSuiteBuilder::new()
+            .with_instantiate_msg(InstantiateMsg {
+                name: "Owned".to_string(),
+                symbol: "OWN".to_string(),
+                decimals: 0,
+                mint: Some(MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                ..InstantiateMsg::default()
+            })
+            .build();
+        assert_eq!(suite.minter, owner);
+        suite.mint(&owner, 7u128).unwrap();
+        suite.assert_balance(&owner, Uint128::new(7));
+    }
+}
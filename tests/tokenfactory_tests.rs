@@ -0,0 +1,196 @@
+#[cfg(all(test, feature = "mintable"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::{Cw20Coin, MinterResponse};
+
+    use cw20_base::contract::{instantiate, query_mirror_denom};
+    use cw20_base::msg::{InstantiateMirrorInfo, InstantiateMsg};
+
+    fn mirrored_msg(owner: &Addr, minter: &Addr) -> InstantiateMsg {
+        InstantiateMsg {
+            name: "Mirrored".to_string(),
+            symbol: "MIRR".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            mirror: Some(InstantiateMirrorInfo {
+                subdenom: "mirr".to_string(),
+            }),
+            ..InstantiateMsg::default()
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "tokenfactory"))]
+    fn mirror_requires_feature() {
+        use cw20_base::ContractError;
+
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let minter = deps.api.addr_make("minter");
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            mirrored_msg(&owner, &minter),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TokenFactoryDisabled {});
+        assert_eq!(query_mirror_denom(deps.as_ref()).unwrap().denom, None);
+    }
+
+    #[test]
+    #[cfg(feature = "tokenfactory")]
+    fn mint_and_burn_are_mirrored() {
+        use cosmwasm_std::{CosmosMsg, SubMsg};
+        use cw20_base::contract::execute;
+        use cw20_base::msg::ExecuteMsg;
+
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let minter = deps.api.addr_make("minter");
+        let env = mock_env();
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+
+        let res = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            mirrored_msg(&owner, &minter),
+        )
+        .unwrap();
+        let type_urls: Vec<_> = res
+            .messages
+            .iter()
+            .map(|m| match &m.msg {
+                CosmosMsg::Any(any) => any.type_url.as_str(),
+                other => panic!("unexpected message {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            type_urls,
+            vec![
+                "/osmosis.tokenfactory.v1beta1.MsgCreateDenom",
+                "/osmosis.tokenfactory.v1beta1.MsgMint",
+            ]
+        );
+        assert_eq!(
+            query_mirror_denom(deps.as_ref()).unwrap().denom,
+            Some(format!("factory/{}/mirr", env.contract.address))
+        );
+
+        let msg = ExecuteMsg::Mint {
+            recipient: owner.to_string(),
+            amount: Uint128::new(500),
+        };
+        let res = execute(deps.as_mut(), env.clone(), message_info(&minter, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let SubMsg {
+            msg: CosmosMsg::Any(mint),
+            ..
+        } = &res.messages[0]
+        else {
+            panic!("expected an Any message");
+        };
+        assert_eq!(mint.type_url, "/osmosis.tokenfactory.v1beta1.MsgMint");
+        let denom = format!("factory/{}/mirr", env.contract.address);
+        assert!(mint
+            .value
+            .windows(denom.len())
+            .any(|w| w == denom.as_bytes()));
+        assert!(mint.value.windows(3).any(|w| w == b"500"));
+
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(200),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), env, message_info(&owner, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let CosmosMsg::Any(burn) = &res.messages[0].msg else {
+            panic!("expected an Any message");
+        };
+        assert_eq!(burn.type_url, "/osmosis.tokenfactory.v1beta1.MsgBurn");
+    }
+
+    #[test]
+    #[cfg(feature = "tokenfactory")]
+    fn unwrap_and_wrap_swap_tokens_for_mirrored_coins() {
+        use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, Deps};
+        use cw20_base::contract::{execute, query_balance, query_token_info};
+        use cw20_base::msg::ExecuteMsg;
+        use cw20_base::ContractError;
+
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let minter = deps.api.addr_make("minter");
+        let mut env = mock_env();
+        env.contract.address = deps.api.addr_make("cw20");
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            mirrored_msg(&owner, &minter),
+        )
+        .unwrap();
+        let denom = format!("factory/{}/mirr", env.contract.address);
+
+        let msg = ExecuteMsg::Unwrap {
+            amount: Uint128::new(300),
+        };
+        let res = execute(deps.as_mut(), env.clone(), message_info(&owner, &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: owner.to_string(),
+                amount: coins(300, &denom),
+            })
+        );
+        let balance =
+            |deps: Deps, addr: &Addr| query_balance(deps, addr.to_string()).unwrap().balance;
+        assert_eq!(balance(deps.as_ref(), &owner), Uint128::new(700));
+        // the unwrapped tokens still count, as the coins standing for them
+        let total = query_token_info(deps.as_ref()).unwrap().total_supply;
+        assert_eq!(total, Uint128::new(1000));
+
+        // only the mirrored coins can be wrapped
+        let funds = [coin(100, "uatom")];
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &funds),
+            ExecuteMsg::Wrap {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidWrapFunds {
+                denom: denom.clone()
+            }
+        );
+
+        let funds = coins(100, &denom);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &funds),
+            ExecuteMsg::Wrap {},
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(balance(deps.as_ref(), &owner), Uint128::new(800));
+        assert_eq!(
+            balance(deps.as_ref(), &env.contract.address),
+            Uint128::new(200)
+        );
+    }
+}
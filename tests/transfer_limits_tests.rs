@@ -0,0 +1,208 @@
+#[cfg(all(test, feature = "transfer-limits"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, SpendConstraint};
+    use cw20_base::restrictions::{
+        query_detect_transfer_restriction, query_spendable_balance, TRANSFER_LIMIT_EXCEEDED,
+    };
+    use cw20_base::state::TransferLimit;
+    use cw20_base::transfer_limits::query_transfer_quota;
+    use cw20_base::ContractError;
+
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, admin: &Addr, holder: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Limited".to_string(),
+            symbol: "LIM".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::new(10_000),
+            }],
+            admin: Some(admin.to_string()),
+            transfer_limit: Some(TransferLimit {
+                amount: Uint128::new(1_000),
+                period: DAY,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn transfers_are_capped_per_window() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let spender = deps.api.addr_make("spender");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let transfer = |amount: u128| ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), env_at(0), info.clone(), transfer(600)).unwrap();
+
+        // spending an allowance counts against the owner's window
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(1_000),
+            expires: None,
+        };
+        execute(deps.as_mut(), env_at(0), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::TransferFrom {
+            owner: holder.to_string(),
+            recipient: other.to_string(),
+            amount: Uint128::new(300),
+            memo: None,
+        };
+        execute(deps.as_mut(), env_at(10), message_info(&spender, &[]), msg).unwrap();
+
+        let quota = query_transfer_quota(deps.as_ref(), env_at(20), holder.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::new(100)));
+        // released a day after, give or take the bucket of the window they went into
+        let release = quota.next_release.unwrap();
+        assert!(release > env_at(DAY).block.time && release <= env_at(DAY + HOUR).block.time);
+
+        let err = execute(deps.as_mut(), env_at(20), info.clone(), transfer(101)).unwrap_err();
+        assert_eq!(err, ContractError::TransferLimitExceeded {});
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            env_at(20),
+            holder.to_string(),
+            other.to_string(),
+            Uint128::new(101),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, TRANSFER_LIMIT_EXCEEDED);
+        execute(deps.as_mut(), env_at(20), info.clone(), transfer(100)).unwrap();
+
+        // the quota is back once they left the window
+        let later = env_at(DAY + HOUR);
+        execute(deps.as_mut(), later.clone(), info, transfer(1_000)).unwrap();
+        let quota = query_transfer_quota(deps.as_ref(), later.clone(), holder.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::zero()));
+
+        // other accounts have their own window
+        let quota = query_transfer_quota(deps.as_ref(), later, other.to_string()).unwrap();
+        assert_eq!(quota.remaining, Some(Uint128::new(1_000)));
+        assert_eq!(quota.next_release, None);
+    }
+
+    #[test]
+    fn window_rolls_instead_of_resetting() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let transfer = |amount: u128| ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        let info = message_info(&holder, &[]);
+        execute(deps.as_mut(), env_at(0), info.clone(), transfer(100)).unwrap();
+        execute(deps.as_mut(), env_at(DAY - 60), info.clone(), transfer(900)).unwrap();
+
+        // past the end of a window opened by the first transfer, only the first one is
+        // released, not the whole limit
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY + HOUR),
+            info.clone(),
+            transfer(101),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TransferLimitExceeded {});
+        execute(deps.as_mut(), env_at(DAY + HOUR), info, transfer(100)).unwrap();
+    }
+
+    #[test]
+    fn admin_updates_the_limit() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let msg = ExecuteMsg::UpdateTransferLimit {
+            limit: Some(TransferLimit {
+                amount: Uint128::new(5_000),
+                period: 0,
+            }),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidTransferLimit {});
+
+        let msg = ExecuteMsg::UpdateTransferLimit { limit: None };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let quota = query_transfer_quota(deps.as_ref(), mock_env(), holder.to_string()).unwrap();
+        assert_eq!(quota.remaining, None);
+        let msg = ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(5_000),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn transfer_all_stays_within_the_limit() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &admin, &holder);
+
+        let info = message_info(&holder, &[]);
+        let msg = ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(600),
+            memo: None,
+        };
+        execute(deps.as_mut(), env_at(0), info.clone(), msg).unwrap();
+
+        let res = query_spendable_balance(deps.as_ref(), env_at(10), holder.to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::new(400));
+        assert_eq!(
+            res.constraint,
+            SpendConstraint::TransferLimit {
+                remaining: Uint128::new(400)
+            }
+        );
+
+        let msg = ExecuteMsg::TransferAll {
+            recipient: other.to_string(),
+        };
+        execute(deps.as_mut(), env_at(10), info, msg).unwrap();
+        let balance = query_balance(deps.as_ref(), other.to_string()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(1_000));
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(all(test, feature = "vesting"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, DepsMut, Env, Uint128};
+    use cw20::MinterResponse;
+
+    use cw20_base::contract::{execute, instantiate, query_balance};
+    use cw20_base::msg::{ExecuteMsg, InstantiateMsg, SpendConstraint};
+    use cw20_base::restrictions::{
+        query_detect_transfer_restriction, query_spendable_balance, TOKENS_LOCKED,
+    };
+    use cw20_base::state::VestingSchedule;
+    use cw20_base::ContractError;
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn do_instantiate(deps: DepsMut, minter: &Addr) {
+        let msg = InstantiateMsg {
+            name: "Investor".to_string(),
+            symbol: "INV".to_string(),
+            decimals: 6,
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    fn schedule() -> VestingSchedule {
+        VestingSchedule {
+            start: env_at(0).block.time,
+            cliff: env_at(100).block.time,
+            end: env_at(1_000).block.time,
+        }
+    }
+
+    #[test]
+    fn locked_tokens_unlock_linearly_after_the_cliff() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let investor = deps.api.addr_make("investor");
+        let other = deps.api.addr_make("other");
+        do_instantiate(deps.as_mut(), &minter);
+
+        let msg = ExecuteMsg::MintLocked {
+            recipient: investor.to_string(),
+            amount: Uint128::new(1_000),
+            schedule: schedule(),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap();
+        assert_eq!(
+            query_balance(deps.as_ref(), investor.to_string())
+                .unwrap()
+                .balance,
+            Uint128::new(1_000)
+        );
+
+        // before the cliff nothing can move, not even by burning
+        let transfer = |amount: u128| ExecuteMsg::Transfer {
+            recipient: other.to_string(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(50),
+            message_info(&investor, &[]),
+            transfer(1),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TokensLocked {});
+        let burn = ExecuteMsg::Burn {
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(50),
+            message_info(&investor, &[]),
+            burn,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TokensLocked {});
+
+        // 40% vested
+        let res =
+            query_spendable_balance(deps.as_ref(), env_at(400), investor.to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::new(400));
+        assert_eq!(
+            res.constraint,
+            SpendConstraint::Vesting {
+                locked: Uint128::new(600)
+            }
+        );
+        execute(
+            deps.as_mut(),
+            env_at(400),
+            message_info(&investor, &[]),
+            transfer(400),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(400),
+            message_info(&investor, &[]),
+            transfer(1),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TokensLocked {});
+        let res = query_detect_transfer_restriction(
+            deps.as_ref(),
+            env_at(400),
+            investor.to_string(),
+            other.to_string(),
+            Uint128::new(1),
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.code, TOKENS_LOCKED);
+        assert_eq!(res.reason, err.to_string());
+
+        // fully vested
+        execute(
+            deps.as_mut(),
+            env_at(1_000),
+            message_info(&investor, &[]),
+            transfer(600),
+        )
+        .unwrap();
+        let res =
+            query_spendable_balance(deps.as_ref(), env_at(1_000), investor.to_string()).unwrap();
+        assert_eq!(res.constraint, SpendConstraint::Balance);
+    }
+
+    #[test]
+    fn only_locked_part_is_restricted() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let investor = deps.api.addr_make("investor");
+        do_instantiate(deps.as_mut(), &minter);
+
+        let msg = ExecuteMsg::Mint {
+            recipient: investor.to_string(),
+            amount: Uint128::new(50),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap();
+        let msg = ExecuteMsg::MintLocked {
+            recipient: investor.to_string(),
+            amount: Uint128::new(1_000),
+            schedule: schedule(),
+        };
+        execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(50),
+            memo: None,
+        };
+        execute(deps.as_mut(), env_at(10), message_info(&investor, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn mint_locked_checks_minter_and_schedule() {
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter");
+        let investor = deps.api.addr_make("investor");
+        do_instantiate(deps.as_mut(), &minter);
+
+        let msg = ExecuteMsg::MintLocked {
+            recipient: investor.to_string(),
+            amount: Uint128::new(1),
+            schedule: schedule(),
+        };
+        let err = execute(deps.as_mut(), env_at(0), message_info(&investor, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let mut bad = schedule();
+        bad.cliff = env_at(2_000).block.time;
+        let msg = ExecuteMsg::MintLocked {
+            recipient: investor.to_string(),
+            amount: Uint128::new(1),
+            schedule: bad,
+        };
+        let err = execute(deps.as_mut(), env_at(0), message_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidVestingSchedule {});
+    }
+}
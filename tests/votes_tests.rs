@@ -0,0 +1,148 @@
+#[cfg(all(test, feature = "votes"))]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, Addr, Deps, Env, Uint128};
+    use cw20::Cw20Coin;
+
+    use cw20_base::contract::{execute, instantiate, query};
+    use cw20_base::msg::{
+        ExecuteMsg, InstantiateMsg, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    };
+    use cw20_base::votes::{query_total_power_at, query_voting_power_at};
+
+    fn env_at(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    fn power(deps: Deps, addr: &Addr, height: u64) -> u128 {
+        query_voting_power_at(deps, addr.to_string(), height)
+            .unwrap()
+            .power
+            .u128()
+    }
+
+    fn total(deps: Deps, height: u64) -> u128 {
+        query_total_power_at(deps, height).unwrap().power.u128()
+    }
+
+    #[test]
+    fn voting_power_is_checkpointed() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+        let msg = InstantiateMsg {
+            name: "Governance".to_string(),
+            symbol: "GOV".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: alice.to_string(),
+                    amount: Uint128::new(300),
+                },
+                Cw20Coin {
+                    address: bob.to_string(),
+                    amount: Uint128::new(100),
+                },
+            ],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env_at(10), info, msg).unwrap();
+
+        // alice votes herself, bob gives his power to carol
+        let delegate = |delegatee: &Addr| ExecuteMsg::Delegate {
+            delegatee: delegatee.to_string(),
+        };
+        let alice_info = message_info(&alice, &[]);
+        execute(
+            deps.as_mut(),
+            env_at(20),
+            alice_info.clone(),
+            delegate(&alice),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(20),
+            message_info(&bob, &[]),
+            delegate(&carol),
+        )
+        .unwrap();
+        assert_eq!(power(deps.as_ref(), &alice, 20), 0);
+        assert_eq!(power(deps.as_ref(), &alice, 21), 300);
+        assert_eq!(power(deps.as_ref(), &carol, 21), 100);
+        assert_eq!(power(deps.as_ref(), &bob, 21), 0);
+        assert_eq!(total(deps.as_ref(), 21), 400);
+
+        // transfers move power between delegatees
+        let msg = ExecuteMsg::Transfer {
+            recipient: bob.to_string(),
+            amount: Uint128::new(50),
+            memo: None,
+        };
+        execute(deps.as_mut(), env_at(30), alice_info.clone(), msg).unwrap();
+        assert_eq!(power(deps.as_ref(), &alice, 31), 250);
+        assert_eq!(power(deps.as_ref(), &carol, 31), 150);
+        assert_eq!(total(deps.as_ref(), 31), 400);
+
+        // burns take power away as well
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(50),
+            memo: None,
+        };
+        execute(deps.as_mut(), env_at(40), alice_info.clone(), msg).unwrap();
+        assert_eq!(power(deps.as_ref(), &alice, 41), 200);
+        assert_eq!(total(deps.as_ref(), 41), 350);
+
+        // redelegating moves the whole balance
+        execute(deps.as_mut(), env_at(50), alice_info, delegate(&carol)).unwrap();
+        assert_eq!(power(deps.as_ref(), &alice, 51), 0);
+        assert_eq!(power(deps.as_ref(), &carol, 51), 350);
+        assert_eq!(total(deps.as_ref(), 51), 350);
+
+        // history stays available
+        assert_eq!(power(deps.as_ref(), &alice, 31), 250);
+        assert_eq!(total(deps.as_ref(), 15), 0);
+    }
+
+    #[test]
+    fn dao_dao_voting_module_queries() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let msg = InstantiateMsg {
+            name: "Governance".to_string(),
+            symbol: "GOV".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: alice.to_string(),
+                amount: Uint128::new(300),
+            }],
+            ..InstantiateMsg::default()
+        };
+        let info = message_info(&Addr::unchecked("creator"), &[]);
+        instantiate(deps.as_mut(), env_at(10), info, msg).unwrap();
+        let msg = ExecuteMsg::Delegate {
+            delegatee: alice.to_string(),
+        };
+        execute(deps.as_mut(), env_at(20), message_info(&alice, &[]), msg).unwrap();
+
+        // queried the way a DAO DAO proposal module does
+        let msg = from_json(format!(
+            r#"{{"voting_power_at_height":{{"address":"{alice}","height":null}}}}"#
+        ))
+        .unwrap();
+        let res: VotingPowerAtHeightResponse =
+            from_json(query(deps.as_ref(), env_at(21), msg).unwrap()).unwrap();
+        assert_eq!(res.power, Uint128::new(300));
+        assert_eq!(res.height, 21);
+
+        let msg = from_json(r#"{"total_power_at_height":{"height":20}}"#).unwrap();
+        let res: TotalPowerAtHeightResponse =
+            from_json(query(deps.as_ref(), env_at(30), msg).unwrap()).unwrap();
+        assert_eq!(res.power, Uint128::zero());
+        assert_eq!(res.height, 20);
+    }
+}